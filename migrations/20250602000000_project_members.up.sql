@@ -0,0 +1,18 @@
+-- Add up migration script here
+CREATE TABLE project_members (
+    project_member_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    project_id INTEGER NOT NULL,
+    user_id INTEGER NOT NULL,
+    role INTEGER NOT NULL CHECK (role BETWEEN 0 AND 2),
+    UNIQUE (project_id, user_id),
+    FOREIGN KEY (project_id) REFERENCES projects (project_id) ON DELETE CASCADE,
+    FOREIGN KEY (user_id) REFERENCES users (user_id) ON DELETE CASCADE
+);
+
+-- これまでは全ユーザーが全プロジェクトを編集できたため、
+-- 既存のタスクに割り当てられているユーザーをそのプロジェクトのメンバーとして登録する。
+-- オーナーは管理者がCLI（project add-member --role owner）で指定する
+INSERT INTO project_members (project_id, user_id, role)
+SELECT DISTINCT tasks.project_id, user_assign.user_id, 1
+FROM user_assign
+JOIN tasks ON tasks.task_id = user_assign.task_id;
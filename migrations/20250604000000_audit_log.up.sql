@@ -0,0 +1,17 @@
+-- Add up migration script here
+-- 操作したユーザーが削除されても履歴は残すため、actor_user_idに外部キーは設定しない
+CREATE TABLE audit_log (
+    audit_log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor_user_id INTEGER,
+    entity_type TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('create', 'update', 'delete')),
+    before_json TEXT,
+    after_json TEXT,
+    request_id TEXT,
+    created_at INTEGER NOT NULL
+);
+
+CREATE INDEX idx_audit_log_entity ON audit_log (entity_type, entity_id);
+CREATE INDEX idx_audit_log_actor_user_id ON audit_log (actor_user_id);
+CREATE INDEX idx_audit_log_created_at ON audit_log (created_at);
@@ -0,0 +1,13 @@
+-- Add up migration script here
+CREATE TABLE task_history (
+    task_history_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER NOT NULL,
+    field_name TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    changed_by INTEGER,
+    changed_at INTEGER NOT NULL,
+    FOREIGN KEY (task_id) REFERENCES tasks (task_id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_task_history_task_id ON task_history (task_id, changed_at);
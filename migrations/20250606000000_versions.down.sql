@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE comments DROP COLUMN version;
+ALTER TABLE user_assign DROP COLUMN version;
+ALTER TABLE tasks DROP COLUMN version;
+ALTER TABLE projects DROP COLUMN version;
+ALTER TABLE users DROP COLUMN version;
@@ -0,0 +1,65 @@
+-- Add down migration script here
+-- deleted_atを削除するとゴミ箱の中身が通常のデータに戻ってしまうため、先に完全に削除する。
+-- ゴミ箱に移動したプロジェクトやタスクの下にあるタスクも合わせて削除する
+CREATE TEMP TABLE trashed_tasks AS
+WITH RECURSIVE trashed(task_id) AS (
+    SELECT task_id
+    FROM tasks
+    WHERE deleted_at IS NOT NULL
+        OR project_id IN (SELECT project_id FROM projects WHERE deleted_at IS NOT NULL)
+    UNION
+    SELECT tasks.task_id
+    FROM tasks
+    INNER JOIN trashed ON tasks.parent_id = trashed.task_id
+)
+SELECT task_id FROM trashed;
+
+DELETE FROM comments
+WHERE deleted_at IS NOT NULL OR task_id IN (SELECT task_id FROM trashed_tasks);
+DELETE FROM user_assign
+WHERE deleted_at IS NOT NULL OR task_id IN (SELECT task_id FROM trashed_tasks);
+DELETE FROM tasks WHERE task_id IN (SELECT task_id FROM trashed_tasks);
+DELETE FROM projects WHERE deleted_at IS NOT NULL;
+DROP TABLE trashed_tasks;
+
+-- 元の制約では復元と完全削除を記録できないため、それぞれ更新と削除として残す
+CREATE TABLE audit_log_old (
+    audit_log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor_user_id INTEGER,
+    entity_type TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('create', 'update', 'delete')),
+    before_json TEXT,
+    after_json TEXT,
+    request_id TEXT,
+    created_at INTEGER NOT NULL
+);
+
+INSERT INTO audit_log_old
+SELECT
+    audit_log_id,
+    actor_user_id,
+    entity_type,
+    entity_id,
+    CASE action WHEN 'restore' THEN 'update' WHEN 'purge' THEN 'delete' ELSE action END,
+    before_json,
+    after_json,
+    request_id,
+    created_at
+FROM audit_log;
+DROP TABLE audit_log;
+ALTER TABLE audit_log_old RENAME TO audit_log;
+
+CREATE INDEX idx_audit_log_entity ON audit_log (entity_type, entity_id);
+CREATE INDEX idx_audit_log_actor_user_id ON audit_log (actor_user_id);
+CREATE INDEX idx_audit_log_created_at ON audit_log (created_at);
+
+DROP INDEX IF EXISTS idx_comments_deleted_at;
+DROP INDEX IF EXISTS idx_user_assign_deleted_at;
+DROP INDEX IF EXISTS idx_tasks_deleted_at;
+DROP INDEX IF EXISTS idx_projects_deleted_at;
+
+ALTER TABLE comments DROP COLUMN deleted_at;
+ALTER TABLE user_assign DROP COLUMN deleted_at;
+ALTER TABLE tasks DROP COLUMN deleted_at;
+ALTER TABLE projects DROP COLUMN deleted_at;
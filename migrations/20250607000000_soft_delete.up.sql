@@ -0,0 +1,32 @@
+-- Add up migration script here
+-- ゴミ箱に移動した日時。NULLの行のみ通常のクエリの対象にする
+ALTER TABLE projects ADD COLUMN deleted_at INTEGER;
+ALTER TABLE tasks ADD COLUMN deleted_at INTEGER;
+ALTER TABLE user_assign ADD COLUMN deleted_at INTEGER;
+ALTER TABLE comments ADD COLUMN deleted_at INTEGER;
+
+CREATE INDEX idx_projects_deleted_at ON projects (deleted_at);
+CREATE INDEX idx_tasks_deleted_at ON tasks (deleted_at);
+CREATE INDEX idx_user_assign_deleted_at ON user_assign (deleted_at);
+CREATE INDEX idx_comments_deleted_at ON comments (deleted_at);
+
+-- 復元と完全削除を監査ログに記録できるようにする。SQLiteではCHECK制約を変更できないため作り直す
+CREATE TABLE audit_log_new (
+    audit_log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    actor_user_id INTEGER,
+    entity_type TEXT NOT NULL,
+    entity_id INTEGER NOT NULL,
+    action TEXT NOT NULL CHECK (action IN ('create', 'update', 'delete', 'restore', 'purge')),
+    before_json TEXT,
+    after_json TEXT,
+    request_id TEXT,
+    created_at INTEGER NOT NULL
+);
+
+INSERT INTO audit_log_new SELECT * FROM audit_log;
+DROP TABLE audit_log;
+ALTER TABLE audit_log_new RENAME TO audit_log;
+
+CREATE INDEX idx_audit_log_entity ON audit_log (entity_type, entity_id);
+CREATE INDEX idx_audit_log_actor_user_id ON audit_log (actor_user_id);
+CREATE INDEX idx_audit_log_created_at ON audit_log (created_at);
@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- 管理者はすべてのプロジェクトにアクセスでき、管理用のエンドポイントを利用できる
+ALTER TABLE users ADD COLUMN is_admin INTEGER NOT NULL DEFAULT 0 CHECK (is_admin IN (0, 1));
@@ -0,0 +1,248 @@
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+#[derive(Parser, Debug, Default)]
+#[command(name = "menahel", version, about = "MENAHEL API server")]
+pub struct Cli {
+    #[command(flatten)]
+    pub config: ConfigArgs,
+
+    // 省略した場合はサーバーを起動する
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    #[command(subcommand, about = "Manage the database schema")]
+    Migrate(MigrateCommand),
+
+    #[command(subcommand, about = "Manage users")]
+    User(UserCommand),
+
+    #[command(subcommand, about = "Manage projects")]
+    Project(ProjectCommand),
+
+    #[command(subcommand, about = "Manage tasks")]
+    Task(TaskCommand),
+
+    #[command(subcommand, about = "Inspect the database")]
+    Db(DbCommand),
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum MigrateCommand {
+    #[command(about = "Show the applied schema version and pending migrations")]
+    Status,
+
+    #[command(about = "Apply all pending migrations")]
+    Up,
+
+    #[command(about = "Roll back migrations using the .down.sql files")]
+    Down {
+        #[arg(
+            long,
+            value_name = "VERSION",
+            help = "Roll back every migration newer than VERSION (0 rolls back all). \
+                    Defaults to rolling back the latest migration only"
+        )]
+        target: Option<i64>,
+    },
+}
+
+// パスワードを省略した場合は標準入力から読み込む
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum UserCommand {
+    #[command(about = "Create a user")]
+    Create {
+        #[arg(long)]
+        username: String,
+
+        #[arg(long)]
+        email: String,
+
+        #[arg(long, help = "Password (read from stdin if omitted)")]
+        password: Option<String>,
+    },
+
+    #[command(about = "Set a user's password and revoke their sessions")]
+    SetPassword {
+        username: String,
+
+        #[arg(long, help = "Password (read from stdin if omitted)")]
+        password: Option<String>,
+    },
+
+    #[command(about = "List users")]
+    List,
+
+    #[command(about = "Grant or revoke administrator privileges")]
+    SetAdmin {
+        username: String,
+
+        #[arg(
+            long,
+            help = "Revoke administrator privileges instead of granting them"
+        )]
+        revoke: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum ProjectCommand {
+    #[command(about = "List projects")]
+    List,
+
+    #[command(about = "Add a user to a project, or change the role of an existing member")]
+    AddMember {
+        project_id: i64,
+
+        username: String,
+
+        #[arg(
+            long,
+            default_value = "member",
+            value_parser = ["owner", "member", "viewer"],
+            help = "Project role"
+        )]
+        role: String,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum TaskCommand {
+    #[command(about = "Move a task under another parent task")]
+    Reparent {
+        task_id: i64,
+
+        #[arg(
+            long,
+            value_name = "TASK_ID",
+            required_unless_present = "root",
+            conflicts_with = "root",
+            help = "New parent task"
+        )]
+        parent: Option<i64>,
+
+        #[arg(long, help = "Make the task a top-level (major) task")]
+        root: bool,
+    },
+}
+
+#[derive(Subcommand, Debug, PartialEq, Eq)]
+pub enum DbCommand {
+    #[command(
+        about = "Check database integrity, foreign keys, pending migrations and data consistency"
+    )]
+    Check {
+        #[arg(long, help = "Fix the data issues that can be repaired automatically")]
+        fix: bool,
+
+        #[arg(long, requires = "fix", help = "Show the fixes without applying them")]
+        dry_run: bool,
+    },
+}
+
+// 設定ファイルと環境変数より優先される
+#[derive(Args, Debug, Default, Clone)]
+pub struct ConfigArgs {
+    #[arg(
+        short,
+        long,
+        global = true,
+        value_name = "FILE",
+        help = "Path to the configuration file (also MENAHEL_CONFIG)"
+    )]
+    pub config: Option<PathBuf>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "HOST:PORT",
+        help = "Address to bind the server to"
+    )]
+    pub bind_address: Option<String>,
+
+    #[arg(long, global = true, value_name = "URL", help = "Database URL")]
+    pub database_url: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Maximum number of database connections"
+    )]
+    pub max_connections: Option<u32>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "BOOL",
+        help = "Apply pending migrations at startup"
+    )]
+    pub auto_migrate: Option<bool>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "Log destination: a file path, stdout or stderr"
+    )]
+    pub log_destination: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "LEVEL",
+        help = "Log level: off, error, warn, info, debug or trace"
+    )]
+    pub log_level: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "FORMAT",
+        help = "Log format: json or text"
+    )]
+    pub log_format: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "BYTES",
+        help = "Rotate the log file when it exceeds this size (0 disables rotation)"
+    )]
+    pub log_max_size_bytes: Option<u64>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Number of rotated log files to keep"
+    )]
+    pub log_max_files: Option<u32>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "LANG",
+        help = "Default language for messages"
+    )]
+    pub default_language: Option<String>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        help = "Maximum page size for paginated lists"
+    )]
+    pub max_page_size: Option<i32>,
+
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        help = "Directory of message catalogues (<lang>.json, .json5 or .yaml)"
+    )]
+    pub message_catalog_dir: Option<String>,
+}
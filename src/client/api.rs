@@ -1,14 +1,17 @@
 use once_cell::sync::Lazy;
 use std::collections::HashMap;
 
-use crate::models::TaskWithUser;
+use crate::models::TaskTreeNode;
+use crate::models::TaskTreeResponse;
 use crate::models::Project;
 use crate::models::ProjectResponse;
 use crate::models::ErrorResponse;
 use crate::client::repository::TaskTree;
+use crate::constants::REQUEST_ID_HEADER;
 
 use reqwest::StatusCode;
 use anyhow::Result;
+use uuid::Uuid;
 
 static API_URL: Lazy<String> = Lazy::new(|| {
     let url = std::env::var("MENAHEL_API_URL")
@@ -16,11 +19,39 @@ static API_URL: Lazy<String> = Lazy::new(|| {
     url
 });
 
+// セッショントークンまたはAPIトークン。未設定なら認証ヘッダーを付けない
+static API_TOKEN: Lazy<Option<String>> = Lazy::new(api_token_from_env);
+
+static CLIENT: Lazy<reqwest::Client> = Lazy::new(reqwest::Client::new);
+
+pub(crate) fn api_token_from_env() -> Option<String> {
+    std::env::var("MENAHEL_API_TOKEN")
+        .ok()
+        .filter(|token| !token.is_empty())
+}
+
+pub(crate) fn build_get(url: &str, request_id: &str, token: Option<&str>) -> reqwest::RequestBuilder {
+    let request = CLIENT.get(url).header(REQUEST_ID_HEADER, request_id);
+    match token {
+        Some(token) => request.bearer_auth(token),
+        None => request,
+    }
+}
+
+// サーバーのログと突き合わせるため、リクエストごとにIDを採番して送信する
+async fn get(url: &str) -> Result<(String, reqwest::Response)> {
+    let request_id = Uuid::new_v4().to_string();
+    tracing::debug!(request_id = %request_id, "GET {}", url);
+    let response = build_get(url, &request_id, API_TOKEN.as_deref()).send().await
+        .map_err(|e| anyhow::anyhow!("{} (request_id: {})", e, request_id))?;
+    Ok((request_id, response))
+}
+
 pub async fn get_project(project_name: &str) -> Result<Project> {
     let url = format!("{}/projects?target=name&name={}", API_URL.as_str(), project_name);
-    let response = reqwest::get(url).await;
+    let response = get(&url).await;
 
-    let response = match response {
+    let (request_id, response) = match response {
         Ok(response) => response,
         Err(e) => {
             return Err(anyhow::anyhow!("Failed to get project: {:?}", e.to_string()));
@@ -39,10 +70,10 @@ pub async fn get_project(project_name: &str) -> Result<Project> {
             let error_response: Result<ErrorResponse, reqwest::Error> = response.json().await;
             match error_response {
                 Ok(error_response) => {
-                    return Err(anyhow::anyhow!(error_response.message));
+                    return Err(anyhow::anyhow!("{} (request_id: {})", error_response.message, request_id));
                 }
                 Err(e) => {
-                    return Err(anyhow::anyhow!(e.to_string()));
+                    return Err(anyhow::anyhow!("{} (request_id: {})", e, request_id));
                 }
             }
         }
@@ -52,80 +83,39 @@ pub async fn get_project(project_name: &str) -> Result<Project> {
 }
 
 pub async fn get_task_tree(project_id: i64) -> Result<HashMap<i64, TaskTree>> {
-    let level0_tasks_url = format!("{}/tasks?target=filter&project_id={}&level=0&with_user=true", API_URL.as_str(), project_id);
-    let level1_tasks_url = format!("{}/tasks?target=filter&project_id={}&level=1&with_user=true", API_URL.as_str(), project_id);
+    // プロジェクトのタスクをすべてのレベルまで1回のリクエストで取得する
+    let url = format!("{}/projects/{}/tree", API_URL.as_str(), project_id);
 
-    // レベル0タスクの取得
-    let level0_tasks_response = reqwest::get(&level0_tasks_url).await?;
-    let level0_tasks = match level0_tasks_response.status() {
+    let (request_id, response) = get(&url).await?;
+    let task_tree_response = match response.status() {
         StatusCode::OK => {
-            level0_tasks_response.json::<Vec<TaskWithUser>>().await?
+            response.json::<TaskTreeResponse>().await?
         }
         _ => {
-            let error_response: Result<ErrorResponse, reqwest::Error> = level0_tasks_response.json().await;
-            match error_response {
-                Ok(error_response) => {
-                    return Err(anyhow::anyhow!("Failed to get level 0 tasks: {}", error_response.message));
-                }
-                Err(e) => {
-                    return Err(anyhow::anyhow!("Failed to get level 0 tasks: {}", e.to_string()));
-                }
-            }
-        }
-    };
-
-    // レベル1タスクの取得
-    let level1_tasks_response = reqwest::get(&level1_tasks_url).await?;
-    let level1_tasks = match level1_tasks_response.status() {
-        StatusCode::OK => {
-            level1_tasks_response.json::<Vec<TaskWithUser>>().await?
-        }
-        _ => {
-            let error_response: Result<ErrorResponse, reqwest::Error> = level1_tasks_response.json().await;
+            let error_response: Result<ErrorResponse, reqwest::Error> = response.json().await;
             match error_response {
                 Ok(error_response) => {
-                    return Err(anyhow::anyhow!("Failed to get level 1 tasks: {}", error_response.message));
+                    return Err(anyhow::anyhow!("Failed to get task tree: {} (request_id: {})", error_response.message, request_id));
                 }
                 Err(e) => {
-                    return Err(anyhow::anyhow!("Failed to get level 1 tasks: {}", e.to_string()));
+                    return Err(anyhow::anyhow!("Failed to get task tree: {} (request_id: {})", e, request_id));
                 }
             }
         }
     };
 
-    let mut task_map: HashMap<i64, TaskTree> = HashMap::new();
-
-    // レベル0タスクを初期化
-    for task in level0_tasks {
-        let task_tree = TaskTree {
-            own: task.clone(),
-            children: Some(HashMap::new()),
-        };
-        task_map.insert(task.task_id, task_tree);
-    }
-
-    // レベル1タスクを親タスクに追加
-    for task in level1_tasks {
-        let parent_id = match task.parent_id {
-            Some(id) => id,
-            None => {
-                // 親IDがない場合はスキップ（ログ出力を追加することも可能）
-                continue;
-            }
-        };
-        
-        if let Some(parent_task) = task_map.get_mut(&parent_id) {
-            if let Some(children) = parent_task.children.as_mut() {
-                children.insert(task.task_id, TaskTree {
-                    own: task,
-                    children: Some(HashMap::new()), // 将来の拡張性のため空のHashMapを設定
-                });
-            }
-        } else {
-            // 親タスクが見つからない場合（データの不整合の可能性）
-            return Err(anyhow::anyhow!("Parent task with ID {} not found for task {}", parent_id, task.task_id));
-        }
-    }
+    Ok(to_task_tree_map(task_tree_response.results))
+}
 
-    Ok(task_map)
-}
\ No newline at end of file
+fn to_task_tree_map(nodes: Vec<TaskTreeNode>) -> HashMap<i64, TaskTree> {
+    nodes
+        .into_iter()
+        .map(|node| {
+            let task_tree = TaskTree {
+                own: node.task,
+                children: Some(to_task_tree_map(node.children)),
+            };
+            (task_tree.own.task_id, task_tree)
+        })
+        .collect()
+}
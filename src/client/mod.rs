@@ -6,4 +6,6 @@ pub mod component;
 pub mod ui;
 pub mod key_map;
 pub mod command_map;
-pub mod repository;
\ No newline at end of file
+pub mod repository;
+#[cfg(test)]
+mod tests;
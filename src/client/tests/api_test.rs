@@ -0,0 +1,60 @@
+use crate::client::api::{api_token_from_env, build_get};
+use crate::constants::REQUEST_ID_HEADER;
+use reqwest::header::AUTHORIZATION;
+
+#[cfg(test)]
+mod api_test {
+    use super::*;
+
+    #[test]
+    fn test_build_get_sends_bearer_token() {
+        let request = build_get(
+            "http://localhost:3000/projects/1/tree",
+            "request-id",
+            Some("mnh_token"),
+        )
+        .build()
+        .unwrap();
+
+        assert_eq!(
+            request.headers().get(AUTHORIZATION).unwrap(),
+            "Bearer mnh_token"
+        );
+        assert_eq!(
+            request.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "request-id"
+        );
+    }
+
+    #[test]
+    fn test_build_get_without_token() {
+        let request = build_get("http://localhost:3000/projects/1/tree", "request-id", None)
+            .build()
+            .unwrap();
+
+        assert!(request.headers().get(AUTHORIZATION).is_none());
+        assert_eq!(
+            request.headers().get(REQUEST_ID_HEADER).unwrap(),
+            "request-id"
+        );
+    }
+
+    #[test]
+    fn test_api_token_from_env() {
+        // MENAHEL_API_TOKENを読み書きするテストはこの1つだけ
+        unsafe {
+            std::env::set_var("MENAHEL_API_TOKEN", "mnh_token");
+        }
+        assert_eq!(api_token_from_env().as_deref(), Some("mnh_token"));
+
+        unsafe {
+            std::env::set_var("MENAHEL_API_TOKEN", "");
+        }
+        assert_eq!(api_token_from_env(), None);
+
+        unsafe {
+            std::env::remove_var("MENAHEL_API_TOKEN");
+        }
+        assert_eq!(api_token_from_env(), None);
+    }
+}
@@ -0,0 +1,349 @@
+use crate::cli::{DbCommand, ProjectCommand, TaskCommand, UserCommand};
+use crate::enums::{ProjectRole, TaskLevel};
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::models::{ProjectMember, TaskFilter, User};
+use crate::repository::database_repo::DatabaseRepository;
+use crate::repository::integrity_repo::IntegrityRepository;
+use crate::repository::migration_repo::{MigrationRepository, current_version};
+use crate::repository::password::hash_password;
+use crate::repository::project_member_repo::ProjectMemberRepository;
+use crate::repository::project_repo::ProjectRepository;
+use crate::repository::session_repo::SessionRepository;
+use crate::repository::task_repo::TaskRepository;
+use crate::repository::user_repo::UserRepository;
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use std::io::{BufRead, Write};
+
+// 引数で指定されなかった場合は入力から1行読み込む
+fn read_password(
+    password: &Option<String>,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> anyhow::Result<String> {
+    if let Some(password) = password {
+        return Ok(password.clone());
+    }
+
+    write!(out, "Password: ")?;
+    out.flush()?;
+    let mut line = String::new();
+    input.read_line(&mut line).map_err(|e| {
+        anyhow::anyhow!(get_error_message(
+            ErrorKey::AdminCommandPasswordReadFailed,
+            e.to_string()
+        ))
+    })?;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+pub async fn run_user_command(
+    command: &UserCommand,
+    pool: &SqlitePool,
+    input: &mut impl BufRead,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let user_repo = UserRepository::new(pool.clone());
+
+    match command {
+        UserCommand::Create {
+            username,
+            email,
+            password,
+        } => {
+            let password = read_password(password, input, out)?;
+            let user = User::new(username.clone(), email.clone(), hash_password(&password)?)?;
+            let user = user_repo.create_user(user).await?;
+            writeln!(
+                out,
+                "Created user {} ({})",
+                user.user_id.unwrap_or_default(),
+                user.username
+            )?;
+        }
+        UserCommand::SetPassword { username, password } => {
+            let mut user = user_repo.get_user_with_password_by_name(username).await?;
+            let password = read_password(password, input, out)?;
+            user.password_hash = hash_password(&password)?;
+            let user = user_repo.update_user(user).await?.value;
+
+            // 古いパスワードで発行されたセッションは無効にする
+            let session_repo = SessionRepository::new(pool.clone());
+            let revoked = session_repo
+                .revoke_sessions_by_user_id(user.user_id.unwrap_or_default())
+                .await?;
+            writeln!(
+                out,
+                "Updated password for {} (revoked {} sessions)",
+                user.username, revoked
+            )?;
+        }
+        UserCommand::List => {
+            let users = user_repo.get_all_users().await?;
+            writeln!(out, "{:<8} {:<24} EMAIL", "ID", "USERNAME")?;
+            for user in users.iter() {
+                writeln!(
+                    out,
+                    "{:<8} {:<24} {}",
+                    user.user_id.unwrap_or_default(),
+                    user.username,
+                    user.email
+                )?;
+            }
+        }
+        UserCommand::SetAdmin { username, revoke } => {
+            let user = user_repo.get_user_by_name(username).await?;
+            user_repo
+                .set_admin(user.user_id.unwrap_or_default(), !revoke)
+                .await?;
+            match revoke {
+                true => writeln!(out, "Revoked administrator privileges from {}", username)?,
+                false => writeln!(out, "Granted administrator privileges to {}", username)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn run_project_command(
+    command: &ProjectCommand,
+    pool: &SqlitePool,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let project_repo = ProjectRepository::new(pool.clone());
+
+    match command {
+        ProjectCommand::List => {
+            let projects = project_repo.get_all_projects().await?;
+            writeln!(out, "{:<8} NAME", "ID")?;
+            for project in projects.iter() {
+                writeln!(
+                    out,
+                    "{:<8} {}",
+                    project.project_id.unwrap_or_default(),
+                    project.name
+                )?;
+            }
+        }
+        ProjectCommand::AddMember {
+            project_id,
+            username,
+            role,
+        } => {
+            let project_role = ProjectRole::from_string(role)?;
+            let user = UserRepository::new(pool.clone())
+                .get_user_by_name(username)
+                .await?;
+            let user_id = user.user_id.unwrap_or_default();
+
+            // すでにメンバーの場合はロールだけを変更する
+            let project_member_repo = ProjectMemberRepository::new(pool.clone());
+            match project_member_repo
+                .get_project_role(*project_id, user_id)
+                .await?
+            {
+                Some(_) => {
+                    project_member_repo
+                        .update_project_member_role(*project_id, user_id, project_role.to_int())
+                        .await?;
+                    writeln!(
+                        out,
+                        "Changed the role of {} in project {} to {}",
+                        username, project_id, role
+                    )?;
+                }
+                None => {
+                    project_member_repo
+                        .create_project_member(ProjectMember::new(
+                            *project_id,
+                            user_id,
+                            project_role,
+                        ))
+                        .await?;
+                    writeln!(
+                        out,
+                        "Added {} to project {} as {}",
+                        username, project_id, role
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub async fn run_task_command(
+    command: &TaskCommand,
+    pool: &SqlitePool,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let task_repo = TaskRepository::new(pool.clone());
+
+    match command {
+        TaskCommand::Reparent {
+            task_id,
+            parent,
+            root: _,
+        } => {
+            let mut task = task_repo.get_task_by_id(*task_id).await?;
+
+            // 親タスクの1つ下のレベルに移動する
+            let level = match parent {
+                Some(parent_id) => {
+                    let parent_task = task_repo.get_task_by_id(*parent_id).await?;
+                    if parent_task.project_id != task.project_id {
+                        anyhow::bail!(get_error_message(
+                            ErrorKey::AdminCommandTaskParentProjectMismatch,
+                            [
+                                ("project_id", json!(task.project_id)),
+                                ("parent_project_id", json!(parent_task.project_id))
+                            ],
+                        ));
+                    }
+                    parent_task.level + 1
+                }
+                None => TaskLevel::Major.to_int(),
+            };
+
+            // サブタスクのレベルが親子関係と合わなくなるため
+            if level != task.level {
+                let mut filter = TaskFilter::new();
+                filter.set_parent_id(*task_id);
+                let subtasks = task_repo
+                    .get_tasks_by_filter(Some(&filter), None, None)
+                    .await?;
+                if !subtasks.is_empty() {
+                    anyhow::bail!(get_error_message(
+                        ErrorKey::AdminCommandTaskHasSubtasks,
+                        [("id", json!(task_id)), ("subtasks", json!(subtasks.len()))],
+                    ));
+                }
+            }
+
+            task.parent_id = *parent;
+            task.level = level;
+            let task = task_repo.update_task(task).await?.value;
+            match task.parent_id {
+                Some(parent_id) => writeln!(
+                    out,
+                    "Moved task {} under task {} (level {})",
+                    task_id, parent_id, task.level
+                )?,
+                None => writeln!(out, "Moved task {} to the top level", task_id)?,
+            }
+        }
+    }
+
+    Ok(())
+}
+
+// 問題が見つかった場合はエラーを返す
+pub async fn run_db_command(
+    command: &DbCommand,
+    pool: &SqlitePool,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    match command {
+        DbCommand::Check { fix, dry_run } => {
+            let database_repo = DatabaseRepository::new(pool.clone());
+            let mut problems = 0;
+
+            let integrity = database_repo.check_integrity().await?;
+            match integrity.is_empty() {
+                true => writeln!(out, "integrity_check: ok")?,
+                false => writeln!(out, "integrity_check: {} problems", integrity.len())?,
+            }
+            for message in integrity.iter() {
+                writeln!(out, "  {}", message)?;
+            }
+            problems += integrity.len();
+
+            let violations = database_repo.check_foreign_keys().await?;
+            match violations.is_empty() {
+                true => writeln!(out, "foreign_key_check: ok")?,
+                false => writeln!(out, "foreign_key_check: {} violations", violations.len())?,
+            }
+            for violation in violations.iter() {
+                writeln!(
+                    out,
+                    "  {} (rowid {}) references missing {}",
+                    violation.table,
+                    violation
+                        .rowid
+                        .map(|rowid| rowid.to_string())
+                        .unwrap_or_default(),
+                    violation.parent
+                )?;
+            }
+            problems += violations.len();
+
+            let migration_repo = MigrationRepository::new(pool.clone());
+            let status = migration_repo.get_migration_status().await?;
+            let pending = status.iter().filter(|migration| !migration.applied).count();
+            let modified = status
+                .iter()
+                .filter(|migration| migration.checksum_mismatch)
+                .count();
+            match (pending, modified) {
+                (0, 0) => writeln!(
+                    out,
+                    "migrations: ok (version {})",
+                    current_version(&status).unwrap_or_default()
+                )?,
+                _ => writeln!(
+                    out,
+                    "migrations: {} pending, {} modified",
+                    pending, modified
+                )?,
+            }
+            problems += pending + modified;
+
+            let integrity_repo = IntegrityRepository::new(pool.clone());
+            let report = match fix {
+                true => integrity_repo.repair_integrity_issues(*dry_run).await?,
+                false => integrity_repo.check_integrity_issues().await?,
+            };
+            match (report.issues.is_empty(), *fix) {
+                (true, _) => writeln!(out, "data_check: ok")?,
+                (false, false) => writeln!(
+                    out,
+                    "data_check: {} issues, {} fixable",
+                    report.issues.len(),
+                    report.fixable
+                )?,
+                (false, true) => writeln!(
+                    out,
+                    "data_check: {} issues, {} fixed{}",
+                    report.issues.len(),
+                    report.fixed,
+                    if report.dry_run { " (dry run)" } else { "" }
+                )?,
+            }
+            for issue in report.issues.iter() {
+                let fix = match (&issue.fix, issue.fixed, report.dry_run && *fix) {
+                    (None, _, _) => " -> fix manually".to_string(),
+                    (Some(fix), true, _) => format!(" -> {} (fixed)", fix),
+                    (Some(fix), false, true) => format!(" -> {} (dry run)", fix),
+                    (Some(fix), false, false) => format!(" -> {}", fix),
+                };
+                writeln!(
+                    out,
+                    "  {} {} {}: {}{}",
+                    issue.check, issue.entity_type, issue.id, issue.detail, fix
+                )?;
+            }
+            problems += report.remaining() as usize;
+
+            if problems > 0 {
+                anyhow::bail!(get_error_message(
+                    ErrorKey::AdminCommandDbCheckFailed,
+                    [("problems", json!(problems))],
+                ));
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::cli::MigrateCommand;
+use crate::repository::migration_repo::{MigrationRepository, current_version};
+use sqlx::sqlite::SqlitePool;
+use std::io::Write;
+
+pub async fn run_migrate_command(
+    command: &MigrateCommand,
+    pool: &SqlitePool,
+    out: &mut impl Write,
+) -> anyhow::Result<()> {
+    let migration_repo = MigrationRepository::new(pool.clone());
+
+    match command {
+        MigrateCommand::Status => {
+            let status = migration_repo.get_migration_status().await?;
+            match current_version(&status) {
+                Some(version) => writeln!(out, "Current schema version: {}", version)?,
+                None => writeln!(out, "Current schema version: none")?,
+            }
+            writeln!(out, "{:<16} {:<9} DESCRIPTION", "VERSION", "STATUS")?;
+            for migration in status.iter() {
+                let state = match (migration.applied, migration.checksum_mismatch) {
+                    (true, true) => "modified",
+                    (true, false) => "applied",
+                    (false, _) => "pending",
+                };
+                writeln!(
+                    out,
+                    "{:<16} {:<9} {}",
+                    migration.version, state, migration.description
+                )?;
+            }
+        }
+        MigrateCommand::Up => {
+            let applied = migration_repo.run_pending_migrations().await?;
+            if applied.is_empty() {
+                writeln!(out, "No pending migrations")?;
+            }
+            for version in applied.iter() {
+                writeln!(out, "Applied {}", version)?;
+            }
+        }
+        MigrateCommand::Down { target } => {
+            let reverted = migration_repo.revert_migrations(*target).await?;
+            if reverted.is_empty() {
+                writeln!(out, "No migrations to roll back")?;
+            }
+            for version in reverted.iter() {
+                writeln!(out, "Reverted {}", version)?;
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod admin;
+pub mod migrate;
+
+#[cfg(test)]
+mod tests;
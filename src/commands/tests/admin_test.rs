@@ -0,0 +1,345 @@
+use crate::cli::{DbCommand, ProjectCommand, TaskCommand, UserCommand};
+use crate::commands::admin::{
+    run_db_command, run_project_command, run_task_command, run_user_command,
+};
+use crate::enums::ProjectRole;
+use crate::repository::password::verify_password;
+use crate::repository::project_member_repo::ProjectMemberRepository;
+use crate::repository::session_repo::SessionRepository;
+use crate::repository::task_repo::TaskRepository;
+use crate::repository::user_repo::UserRepository;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod admin_command_test {
+    use super::*;
+
+    #[sqlx::test]
+    async fn test_user_create_and_list(pool: SqlitePool) {
+        // パスワードを省略した場合は入力から読み込む
+        let command = UserCommand::Create {
+            username: "AdminUser".to_string(),
+            email: "admin@example.com".to_string(),
+            password: None,
+        };
+        let mut input = "secret-password\n".as_bytes();
+        let mut out = Vec::new();
+        run_user_command(&command, &pool, &mut input, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("Password: Created user "));
+
+        let user = UserRepository::new(pool.clone())
+            .get_user_with_password_by_name("AdminUser")
+            .await
+            .unwrap();
+        assert!(verify_password("secret-password", &user.password_hash).unwrap());
+
+        let mut out = Vec::new();
+        run_user_command(&UserCommand::List, &pool, &mut "".as_bytes(), &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.contains("AdminUser"));
+        assert!(output.contains("admin@example.com"));
+
+        // リポジトリの検証がそのまま適用される
+        let command = UserCommand::Create {
+            username: "AdminUser".to_string(),
+            email: "other@example.com".to_string(),
+            password: Some("secret-password".to_string()),
+        };
+        let result = run_user_command(&command, &pool, &mut "".as_bytes(), &mut Vec::new()).await;
+        assert!(result.is_err());
+
+        let command = UserCommand::Create {
+            username: "EmptyPassword".to_string(),
+            email: "empty@example.com".to_string(),
+            password: None,
+        };
+        let result = run_user_command(&command, &pool, &mut "\n".as_bytes(), &mut Vec::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("sessions")))]
+    async fn test_user_set_password(pool: SqlitePool) {
+        let command = UserCommand::SetPassword {
+            username: "TestUser0".to_string(),
+            password: Some("new-password".to_string()),
+        };
+        let mut out = Vec::new();
+        run_user_command(&command, &pool, &mut "".as_bytes(), &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(
+            output,
+            "Updated password for TestUser0 (revoked 3 sessions)\n"
+        );
+
+        let user = UserRepository::new(pool.clone())
+            .get_user_with_password_by_name("TestUser0")
+            .await
+            .unwrap();
+        assert!(verify_password("new-password", &user.password_hash).unwrap());
+
+        let session_repo = SessionRepository::new(pool.clone());
+        let result = session_repo
+            .get_active_session_by_token_hash("active_token_hash_0")
+            .await;
+        assert!(result.is_err());
+
+        let command = UserCommand::SetPassword {
+            username: "UnknownUser".to_string(),
+            password: Some("new-password".to_string()),
+        };
+        let result = run_user_command(&command, &pool, &mut "".as_bytes(), &mut Vec::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_project_list(pool: SqlitePool) {
+        let mut out = Vec::new();
+        run_project_command(&ProjectCommand::List, &pool, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(output.lines().count(), 3);
+        assert!(output.contains("Test Project 0"));
+        assert!(output.contains("Test Project 1"));
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_user_set_admin(pool: SqlitePool) {
+        let user_repo = UserRepository::new(pool.clone());
+
+        let command = UserCommand::SetAdmin {
+            username: "Test User 0".to_string(),
+            revoke: false,
+        };
+        let mut out = Vec::new();
+        run_user_command(&command, &pool, &mut "".as_bytes(), &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Granted administrator privileges to Test User 0\n"
+        );
+        assert!(user_repo.is_admin(1).await.unwrap());
+        assert!(!user_repo.is_admin(2).await.unwrap());
+
+        let command = UserCommand::SetAdmin {
+            username: "Test User 0".to_string(),
+            revoke: true,
+        };
+        run_user_command(&command, &pool, &mut "".as_bytes(), &mut Vec::new())
+            .await
+            .unwrap();
+        assert!(!user_repo.is_admin(1).await.unwrap());
+
+        let command = UserCommand::SetAdmin {
+            username: "UnknownUser".to_string(),
+            revoke: false,
+        };
+        let result = run_user_command(&command, &pool, &mut "".as_bytes(), &mut Vec::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_project_add_member(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool.clone());
+
+        let command = ProjectCommand::AddMember {
+            project_id: 1,
+            username: "Test User 0".to_string(),
+            role: "owner".to_string(),
+        };
+        let mut out = Vec::new();
+        run_project_command(&command, &pool, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Added Test User 0 to project 1 as owner\n"
+        );
+        assert_eq!(
+            project_member_repo.get_project_role(1, 1).await.unwrap(),
+            Some(ProjectRole::Owner)
+        );
+
+        // すでにメンバーの場合はロールを変更する
+        let command = ProjectCommand::AddMember {
+            project_id: 1,
+            username: "Test User 1".to_string(),
+            role: "viewer".to_string(),
+        };
+        run_project_command(&command, &pool, &mut Vec::new())
+            .await
+            .unwrap();
+        let command = ProjectCommand::AddMember {
+            project_id: 1,
+            username: "Test User 1".to_string(),
+            role: "owner".to_string(),
+        };
+        let mut out = Vec::new();
+        run_project_command(&command, &pool, &mut out)
+            .await
+            .unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Changed the role of Test User 1 in project 1 to owner\n"
+        );
+        assert_eq!(
+            project_member_repo.get_project_role(1, 2).await.unwrap(),
+            Some(ProjectRole::Owner)
+        );
+
+        let command = ProjectCommand::AddMember {
+            project_id: 100,
+            username: "Test User 0".to_string(),
+            role: "member".to_string(),
+        };
+        let result = run_project_command(&command, &pool, &mut Vec::new()).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_task_reparent(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone());
+
+        let command = TaskCommand::Reparent {
+            task_id: 13,
+            parent: Some(4),
+            root: false,
+        };
+        let mut out = Vec::new();
+        run_task_command(&command, &pool, &mut out).await.unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Moved task 13 under task 4 (level 1)\n"
+        );
+        let task = task_repo.get_task_by_id(13).await.unwrap();
+        assert_eq!(task.parent_id, Some(4));
+        assert_eq!(task.level, 1);
+
+        let command = TaskCommand::Reparent {
+            task_id: 13,
+            parent: None,
+            root: true,
+        };
+        run_task_command(&command, &pool, &mut Vec::new())
+            .await
+            .unwrap();
+        let task = task_repo.get_task_by_id(13).await.unwrap();
+        assert_eq!(task.parent_id, None);
+        assert_eq!(task.level, 0);
+
+        // 別のプロジェクトのタスク、サブタスクを持つタスク、レベルの上限を超える移動は失敗する
+        for (task_id, parent) in [(14, Some(1)), (5, None), (14, Some(15))] {
+            let command = TaskCommand::Reparent {
+                task_id,
+                parent,
+                root: parent.is_none(),
+            };
+            let result = run_task_command(&command, &pool, &mut Vec::new()).await;
+            assert!(
+                result.is_err(),
+                "task_id: {}, parent: {:?}",
+                task_id,
+                parent
+            );
+        }
+        let task = task_repo.get_task_by_id(14).await.unwrap();
+        assert_eq!(task.parent_id, Some(5));
+        assert_eq!(task.level, 2);
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_db_check(pool: SqlitePool) {
+        let mut out = Vec::new();
+        run_db_command(
+            &DbCommand::Check {
+                fix: false,
+                dry_run: false,
+            },
+            &pool,
+            &mut out,
+        )
+        .await
+        .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("integrity_check: ok"));
+        assert!(output.contains("foreign_key_check: ok"));
+        assert!(output.contains("migrations: ok"));
+        assert!(output.contains("data_check: ok"));
+
+        // 外部キー制約は接続ごとの設定のため同じ接続で更新する
+        let mut conn = pool.acquire().await.unwrap();
+        sqlx::query("PRAGMA foreign_keys = OFF")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET project_id = 999 WHERE task_id = 13")
+            .execute(&mut *conn)
+            .await
+            .unwrap();
+        drop(conn);
+
+        let mut out = Vec::new();
+        let result = run_db_command(
+            &DbCommand::Check {
+                fix: false,
+                dry_run: false,
+            },
+            &pool,
+            &mut out,
+        )
+        .await;
+        assert!(result.is_err());
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("foreign_key_check: 1 violations"));
+        assert!(output.contains("tasks (rowid 13) references missing projects"));
+        assert!(output.contains("data_check: 1 issues, 1 fixable"));
+        assert!(output.contains("project_mismatch task 13"));
+    }
+
+    #[sqlx::test(fixtures(path = "../../repository/tests/fixtures", scripts("tasks")))]
+    async fn test_db_check_fix(pool: SqlitePool) {
+        sqlx::query("INSERT INTO user_assign (user_id, task_id) VALUES (1, 15)")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let command = DbCommand::Check {
+            fix: true,
+            dry_run: true,
+        };
+        let mut out = Vec::new();
+        let result = run_db_command(&command, &pool, &mut out).await;
+        assert!(result.is_err());
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("data_check: 1 issues, 0 fixed (dry run)"));
+        assert!(output.contains("duplicate_user_assign user_assign"));
+        assert!(output.contains("-> Delete (dry run)"));
+
+        let command = DbCommand::Check {
+            fix: true,
+            dry_run: false,
+        };
+        let mut out = Vec::new();
+        run_db_command(&command, &pool, &mut out).await.unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("data_check: 1 issues, 1 fixed"));
+        assert!(output.contains("-> Delete (fixed)"));
+
+        let count: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM user_assign WHERE user_id = 1 AND task_id = 15",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(count, 1);
+    }
+}
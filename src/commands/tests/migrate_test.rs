@@ -0,0 +1,56 @@
+use crate::cli::MigrateCommand;
+use crate::commands::migrate::run_migrate_command;
+use crate::repository::migration_repo::MIGRATOR;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod migrate_command_test {
+    use super::*;
+
+    fn versions() -> Vec<i64> {
+        MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .collect()
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_run_migrate_command(pool: SqlitePool) {
+        let versions = versions();
+
+        let mut out = Vec::new();
+        run_migrate_command(&MigrateCommand::Status, &pool, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("Current schema version: none\n"));
+        assert_eq!(output.matches("pending").count(), versions.len());
+
+        let mut out = Vec::new();
+        run_migrate_command(&MigrateCommand::Up, &pool, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(output.lines().count(), versions.len());
+
+        let mut out = Vec::new();
+        run_migrate_command(&MigrateCommand::Down { target: None }, &pool, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert_eq!(output, format!("Reverted {}\n", versions.last().unwrap()));
+
+        let mut out = Vec::new();
+        run_migrate_command(&MigrateCommand::Status, &pool, &mut out)
+            .await
+            .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with(&format!(
+            "Current schema version: {}\n",
+            versions[versions.len() - 2]
+        )));
+        assert_eq!(output.matches("applied").count(), versions.len() - 1);
+        assert_eq!(output.matches("pending").count(), 1);
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(test)]
+mod admin_test;
+#[cfg(test)]
+mod migrate_test;
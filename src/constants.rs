@@ -1,2 +1,55 @@
 pub const API_VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const API_NAME: &str = "MENAHEL";
+
+// ログインで発行するセッショントークンの有効期間（秒）
+pub const SESSION_TTL_SECONDS: i64 = 60 * 60 * 24;
+
+// APIトークンの接頭辞。セッショントークンと区別するために使用する
+pub const API_TOKEN_PREFIX: &str = "mnh_";
+
+// APIトークンの最終使用日時を更新する最小間隔（秒）
+pub const API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS: i64 = 60;
+
+// サーバー設定のデフォルト値
+pub const DEFAULT_BIND_ADDRESS: &str = "0.0.0.0:3000";
+pub const DEFAULT_MAX_CONNECTIONS: u32 = 10;
+pub const DEFAULT_LOG_DESTINATION: &str = "log.txt";
+pub const DEFAULT_LOG_LEVEL: &str = "debug";
+pub const DEFAULT_LOG_FORMAT: &str = "json";
+pub const DEFAULT_LOG_MAX_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+pub const DEFAULT_LOG_MAX_FILES: u32 = 5;
+pub const DEFAULT_LANGUAGE: &str = "en";
+pub const DEFAULT_MAX_PAGE_SIZE: i32 = 100;
+pub const DEFAULT_TRASH_RETENTION_DAYS: i64 = 30;
+pub const DEFAULT_PASSWORD_HASH_MEMORY_KIB: u32 = 19 * 1024;
+pub const DEFAULT_PASSWORD_HASH_ITERATIONS: u32 = 2;
+pub const DEFAULT_PASSWORD_HASH_PARALLELISM: u32 = 1;
+
+// ログの出力形式
+pub const LOG_FORMATS: [&str; 2] = ["json", "text"];
+
+// ログに値を出力しないフィールド
+pub const SENSITIVE_LOG_FIELDS: [&str; 6] = [
+    "password_hash",
+    "password",
+    "email",
+    "token_hash",
+    "token",
+    "authorization",
+];
+
+// エラーメッセージが用意されている言語
+pub const SUPPORTED_LANGUAGES: [&str; 2] = ["en", "jp"];
+
+// メッセージカタログとして読み込むファイルの拡張子
+pub const MESSAGE_CATALOG_EXTENSIONS: [&str; 4] = ["json", "json5", "yaml", "yml"];
+
+// 言語を指定するクエリパラメータ（Accept-Languageより優先する）
+pub const LANGUAGE_QUERY_PARAM: &str = "lang";
+
+// レディネスチェックの各項目のタイムアウト（ミリ秒）
+pub const HEALTH_CHECK_TIMEOUT_MS: u64 = 2000;
+
+// クライアントとサーバーのログを突き合わせるためのリクエストID
+pub const REQUEST_ID_HEADER: &str = "X-Request-ID";
+pub const MAX_REQUEST_ID_LENGTH: usize = 128;
@@ -1,5 +1,6 @@
 use crate::errors::messages::{ErrorKey, get_error_message};
 use enum_iterator::{Sequence, all};
+use serde_json::json;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Sequence)]
 pub enum TaskLevel {
@@ -24,7 +25,7 @@ impl TaskLevel {
             2 => Ok(TaskLevel::Trivial),
             _ => Err(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskLevelInvalid,
-                format!("Level = {}", level)
+                [("level", json!(level))]
             ))),
         }
     }
@@ -62,7 +63,7 @@ impl TaskStatus {
             4 => Ok(TaskStatus::Done),
             _ => Err(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskStatusInvalid,
-                format!("Status = {}", status)
+                [("status", json!(status))]
             ))),
         }
     }
@@ -86,12 +87,193 @@ impl TaskStatus {
             "dn" => Ok(TaskStatus::Done),
             _ => Err(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskStatusInvalid,
-                format!("Status = {}", status)
+                [("status", json!(status))]
             ))),
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProjectRole {
+    Owner,
+    Member,
+    Viewer,
+}
+
+impl ProjectRole {
+    pub fn to_int(&self) -> i64 {
+        match self {
+            ProjectRole::Owner => 0,
+            ProjectRole::Member => 1,
+            ProjectRole::Viewer => 2,
+        }
+    }
+
+    pub fn from_int(role: i64) -> Result<ProjectRole, anyhow::Error> {
+        match role {
+            0 => Ok(ProjectRole::Owner),
+            1 => Ok(ProjectRole::Member),
+            2 => Ok(ProjectRole::Viewer),
+            _ => Err(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectRoleInvalid,
+                [("role", json!(role))]
+            ))),
+        }
+    }
+
+    pub fn from_string(role: &str) -> Result<ProjectRole, anyhow::Error> {
+        match role {
+            "owner" => Ok(ProjectRole::Owner),
+            "member" => Ok(ProjectRole::Member),
+            "viewer" => Ok(ProjectRole::Viewer),
+            _ => Err(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectRoleInvalid,
+                [("role", json!(role))]
+            ))),
+        }
+    }
+
+    // オーナーはメンバーの、メンバーは閲覧者の権限をすべて持つ
+    pub fn satisfies(&self, required: ProjectRole) -> bool {
+        self.to_int() <= required.to_int()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Restore,
+    Purge,
+}
+
+impl AuditAction {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::Purge => "purge",
+        }
+    }
+
+    pub fn from_string(action: &str) -> Result<AuditAction, anyhow::Error> {
+        match action {
+            "create" => Ok(AuditAction::Create),
+            "update" => Ok(AuditAction::Update),
+            "delete" => Ok(AuditAction::Delete),
+            "restore" => Ok(AuditAction::Restore),
+            "purge" => Ok(AuditAction::Purge),
+            _ => Err(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditActionInvalid,
+                [("action", json!(action))]
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditEntityType {
+    User,
+    Project,
+    Task,
+    UserAssign,
+    Comment,
+}
+
+impl AuditEntityType {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            AuditEntityType::User => "user",
+            AuditEntityType::Project => "project",
+            AuditEntityType::Task => "task",
+            AuditEntityType::UserAssign => "user_assign",
+            AuditEntityType::Comment => "comment",
+        }
+    }
+
+    pub fn from_string(entity_type: &str) -> Result<AuditEntityType, anyhow::Error> {
+        match entity_type {
+            "user" => Ok(AuditEntityType::User),
+            "project" => Ok(AuditEntityType::Project),
+            "task" => Ok(AuditEntityType::Task),
+            "user_assign" => Ok(AuditEntityType::UserAssign),
+            "comment" => Ok(AuditEntityType::Comment),
+            _ => Err(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditEntityTypeInvalid,
+                [("entity_type", json!(entity_type))]
+            ))),
+        }
+    }
+}
+
+// 削除するものに依存するデータ（子のタスク、割り当て、コメント）の扱い
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeletePolicy {
+    // 依存するデータがあれば削除しない
+    #[default]
+    Restrict,
+    // 依存するデータも一緒に削除する
+    Cascade,
+    // 子のタスクを親の親に付け替える。タスクのみ
+    Reparent,
+}
+
+impl DeletePolicy {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            DeletePolicy::Restrict => "restrict",
+            DeletePolicy::Cascade => "cascade",
+            DeletePolicy::Reparent => "reparent",
+        }
+    }
+
+    pub fn from_string(policy: &str) -> Result<DeletePolicy, anyhow::Error> {
+        match policy {
+            "restrict" => Ok(DeletePolicy::Restrict),
+            "cascade" => Ok(DeletePolicy::Cascade),
+            "reparent" => Ok(DeletePolicy::Reparent),
+            _ => Err(anyhow::anyhow!(get_error_message(
+                ErrorKey::DeletePolicyInvalid,
+                [("policy", json!(policy))]
+            ))),
+        }
+    }
+}
+
+// データ整合性チェックの項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntegrityCheck {
+    // 親タスクとのレベルが合わない（validate_parent_relationと同じ条件）
+    ParentLevel,
+    // 親タスクとプロジェクトが異なる
+    ProjectMismatch,
+    // タスクかユーザーが存在しない割り当て
+    OrphanedUserAssign,
+    // タスクかユーザーが存在しないコメント
+    OrphanedComment,
+    // 同じユーザーとタスクの割り当てが複数ある
+    DuplicateUserAssign,
+    InvalidStatus,
+    InvalidLevel,
+}
+
+impl IntegrityCheck {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            IntegrityCheck::ParentLevel => "parent_level",
+            IntegrityCheck::ProjectMismatch => "project_mismatch",
+            IntegrityCheck::OrphanedUserAssign => "orphaned_user_assign",
+            IntegrityCheck::OrphanedComment => "orphaned_comment",
+            IntegrityCheck::DuplicateUserAssign => "duplicate_user_assign",
+            IntegrityCheck::InvalidStatus => "invalid_status",
+            IntegrityCheck::InvalidLevel => "invalid_level",
+        }
+    }
+}
+
 pub enum TaskFilterValue {
     I64(i64),
     String(String),
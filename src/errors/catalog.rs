@@ -0,0 +1,234 @@
+use crate::constants::{MESSAGE_CATALOG_EXTENSIONS, SUPPORTED_LANGUAGES};
+use crate::errors::ConfigError;
+use crate::errors::messages::{ERROR_MESSAGES, ErrorKey, get_error_message};
+use crate::settings::{MessagesConfig, server_config};
+use once_cell::sync::Lazy;
+use signal_hook::consts::SIGHUP;
+use signal_hook::iterator::Signals;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+
+// 言語ごとのメッセージ。キーはErrorKeyの表示名（例: "AuthHandlerNoToken"）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MessageCatalog {
+    messages: BTreeMap<&'static str, HashMap<String, String>>,
+}
+
+static CATALOG: Lazy<RwLock<MessageCatalog>> = Lazy::new(Default::default);
+
+// リクエストごとの言語は&'static strで扱うため、カタログで追加された言語名は一度だけリークさせる
+static LANGUAGE_NAMES: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+fn intern_language(lang: &str) -> &'static str {
+    if let Some(lang) = SUPPORTED_LANGUAGES.iter().find(|l| **l == lang) {
+        return lang;
+    }
+    let mut names = LANGUAGE_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    match names.get(lang) {
+        Some(lang) => lang,
+        None => {
+            let lang: &'static str = Box::leak(lang.to_string().into_boxed_str());
+            names.insert(lang);
+            lang
+        }
+    }
+}
+
+fn catalog_files(dir: &Path) -> std::io::Result<Vec<(String, PathBuf)>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if !path.is_file() || !MESSAGE_CATALOG_EXTENSIONS.contains(&extension) {
+            continue;
+        }
+        if let Some(lang) = path.file_stem().and_then(|s| s.to_str()) {
+            files.push((lang.to_ascii_lowercase(), path));
+        }
+    }
+    // 同じ言語のファイルが複数ある場合は名前順に読み込み、後のファイルで上書きする
+    files.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(files)
+}
+
+// ディレクトリ内のカタログの言語。読み込めない場合は空
+pub fn catalog_languages(dir: &Path) -> Vec<String> {
+    catalog_files(dir)
+        .map(|files| files.into_iter().map(|(lang, _)| lang).collect())
+        .unwrap_or_default()
+}
+
+fn parse_catalog(path: &Path, content: &str) -> Result<HashMap<String, String>, String> {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some("json") => serde_json::from_str(content).map_err(|e| e.to_string()),
+        Some("json5") => json5::from_str(content).map_err(|e| e.to_string()),
+        _ => serde_yaml::from_str(content).map_err(|e| e.to_string()),
+    }
+}
+
+impl MessageCatalog {
+    pub fn load(dir: &Path) -> Result<Self, ConfigError> {
+        let files = catalog_files(dir).map_err(|e| {
+            ConfigError::LoadError(
+                get_error_message(
+                    ErrorKey::MessageCatalogDirReadFailed,
+                    format!("{}: {}", dir.display(), e),
+                )
+                .to_string(),
+            )
+        })?;
+
+        let mut catalog = MessageCatalog::default();
+        for (lang, path) in files {
+            let content = std::fs::read_to_string(&path).map_err(|e| {
+                ConfigError::LoadError(
+                    get_error_message(
+                        ErrorKey::MessageCatalogFileReadFailed,
+                        format!("{}: {}", path.display(), e),
+                    )
+                    .to_string(),
+                )
+            })?;
+            let messages = parse_catalog(&path, &content).map_err(|e| {
+                ConfigError::LoadError(
+                    get_error_message(
+                        ErrorKey::MessageCatalogParseFailed,
+                        format!("{}: {}", path.display(), e),
+                    )
+                    .to_string(),
+                )
+            })?;
+            catalog
+                .messages
+                .entry(intern_language(&lang))
+                .or_default()
+                .extend(messages);
+        }
+        Ok(catalog)
+    }
+
+    pub fn get(&self, lang: &str, key: &str) -> Option<&str> {
+        self.messages
+            .get(lang)
+            .and_then(|messages| messages.get(key))
+            .map(|message| message.as_str())
+    }
+
+    pub fn languages(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.messages.keys().copied()
+    }
+
+    // 存在しないキーと、各言語で組み込みのメッセージにもカタログにもないキーを報告する
+    pub fn check(&self) -> Vec<String> {
+        let builtin_keys: BTreeMap<String, &HashMap<&'static str, &'static str>> = ERROR_MESSAGES
+            .iter()
+            .map(|(key, messages)| (key.to_string(), messages))
+            .collect();
+
+        let mut reports = Vec::new();
+        for (lang, messages) in &self.messages {
+            let unknown: BTreeSet<&str> = messages
+                .keys()
+                .filter(|key| !builtin_keys.contains_key(*key))
+                .map(|key| key.as_str())
+                .collect();
+            if !unknown.is_empty() {
+                reports.push(
+                    get_error_message(
+                        ErrorKey::MessageCatalogUnknownKeys,
+                        format!(
+                            "lang = {}, keys = {}",
+                            lang,
+                            unknown.into_iter().collect::<Vec<_>>().join(", ")
+                        ),
+                    )
+                    .to_string(),
+                );
+            }
+        }
+
+        let languages: BTreeSet<&str> = SUPPORTED_LANGUAGES
+            .iter()
+            .copied()
+            .chain(self.languages())
+            .collect();
+        for lang in languages {
+            let missing: Vec<&str> = builtin_keys
+                .iter()
+                .filter(|(key, builtin)| {
+                    self.get(lang, key).is_none() && !builtin.contains_key(lang)
+                })
+                .map(|(key, _)| key.as_str())
+                .collect();
+            if !missing.is_empty() {
+                reports.push(
+                    get_error_message(
+                        ErrorKey::MessageCatalogMissingKeys,
+                        format!("lang = {}, keys = {}", lang, missing.join(", ")),
+                    )
+                    .to_string(),
+                );
+            }
+        }
+        reports
+    }
+}
+
+pub fn lookup_message(lang: &str, key: &str) -> Option<String> {
+    let catalog = CATALOG.read().unwrap_or_else(|e| e.into_inner());
+    catalog.get(lang, key).map(|message| message.to_string())
+}
+
+// 組み込みの言語とカタログで追加された言語
+pub fn available_languages() -> Vec<&'static str> {
+    let catalog = CATALOG.read().unwrap_or_else(|e| e.into_inner());
+    let languages: BTreeSet<&'static str> = SUPPORTED_LANGUAGES
+        .iter()
+        .copied()
+        .chain(catalog.languages())
+        .collect();
+    languages.into_iter().collect()
+}
+
+pub fn install_message_catalog(catalog: MessageCatalog) {
+    *CATALOG.write().unwrap_or_else(|e| e.into_inner()) = catalog;
+}
+
+// カタログを読み込んで差し替え、不足しているキーなどの報告を返す
+pub fn init_message_catalog(config: &MessagesConfig) -> Result<Vec<String>, ConfigError> {
+    let catalog = match &config.catalog_dir {
+        Some(dir) => MessageCatalog::load(Path::new(dir))?,
+        None => MessageCatalog::default(),
+    };
+    let reports = catalog.check();
+    install_message_catalog(catalog);
+    Ok(reports)
+}
+
+// 読み込みに失敗した場合は以前のカタログを使い続ける
+pub fn spawn_catalog_reloader() -> Result<(), ConfigError> {
+    let mut signals = Signals::new([SIGHUP]).map_err(|e| {
+        ConfigError::InitError(
+            get_error_message(ErrorKey::MessageCatalogSignalFailed, e.to_string()).to_string(),
+        )
+    })?;
+
+    std::thread::spawn(move || {
+        for _ in signals.forever() {
+            match init_message_catalog(&server_config().messages) {
+                Ok(reports) => {
+                    log::info!("Message catalogue reloaded");
+                    for report in reports {
+                        log::warn!("{}", report);
+                    }
+                }
+                Err(e) => log::error!("{}", e),
+            }
+        }
+    });
+    Ok(())
+}
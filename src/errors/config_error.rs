@@ -0,0 +1,14 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("LoadError: {0}")]
+    LoadError(String),
+
+    // 検証エラーはまとめて報告する
+    #[error("ValidationError:\n  - {}", .0.join("\n  - "))]
+    ValidationError(Vec<String>),
+
+    #[error("InitError: {0}")]
+    InitError(String),
+}
@@ -1,3 +1,5 @@
+use crate::errors::messages::{ErrorKey, ErrorMessage, get_error_message};
+use serde_json::json;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
 
@@ -10,10 +12,40 @@ pub enum DBAccessError {
     QueryError(#[from] anyhow::Error),
 
     #[error("ValidationError: {0}")]
-    ValidationError(String),
+    ValidationError(ErrorMessage),
 
     #[error("NotFoundError: {0}")]
-    NotFoundError(String),
+    NotFoundError(ErrorMessage),
+
+    // 一意制約違反などの既存データとの競合
+    #[error("ConflictError: {0}")]
+    ConflictError(ErrorMessage),
+
+    // If-Matchで指定されたバージョンと保存されているバージョンが異なる
+    #[error("PreconditionFailedError: {0}")]
+    PreconditionFailedError(ErrorMessage),
+}
+
+// バッチ操作の失敗。indexは失敗した操作の位置で、コミットなど操作によらない失敗ではNone
+#[derive(Debug)]
+pub struct BatchError {
+    pub index: Option<usize>,
+    pub error: DBAccessError,
+}
+
+impl BatchError {
+    pub fn at(index: usize, error: DBAccessError) -> Self {
+        Self {
+            index: Some(index),
+            error,
+        }
+    }
+}
+
+impl From<DBAccessError> for BatchError {
+    fn from(error: DBAccessError) -> Self {
+        Self { index: None, error }
+    }
 }
 
 impl DBAccessError {
@@ -29,4 +61,32 @@ impl DBAccessError {
             _ => false,
         }
     }
+
+    // 一意制約違反はConflictError、それ以外はQueryErrorにする
+    pub fn from_write_error(e: SqlxError, key: ErrorKey, conflict_key: ErrorKey) -> Self {
+        match unique_violation_columns(&e) {
+            Some(columns) => DBAccessError::ConflictError(get_error_message(
+                conflict_key,
+                [("field", json!(columns))],
+            )),
+            None => {
+                DBAccessError::QueryError(anyhow::anyhow!(get_error_message(key, e.to_string())))
+            }
+        }
+    }
+}
+
+// SQLiteのメッセージ（例: "UNIQUE constraint failed: users.username"）から列名を取得する。
+// 複合キーの場合は","で連結する
+fn unique_violation_columns(e: &SqlxError) -> Option<String> {
+    let db_error = e.as_database_error()?;
+    if !db_error.is_unique_violation() {
+        return None;
+    }
+    let (_, columns) = db_error.message().rsplit_once(": ")?;
+    let columns: Vec<&str> = columns
+        .split(", ")
+        .filter_map(|column| column.rsplit('.').next())
+        .collect();
+    Some(columns.join(","))
 }
@@ -1,16 +1,29 @@
 use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::ErrorMessage;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum HandlerError {
     #[error("NotFound: {0}")]
-    NotFound(String),
+    NotFound(ErrorMessage),
 
     #[error("InternalServerError: {0}")]
-    InternalServerError(String),
+    InternalServerError(ErrorMessage),
 
     #[error("BadRequest: {0}")]
-    BadRequest(String),
+    BadRequest(ErrorMessage),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(ErrorMessage),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(ErrorMessage),
+
+    #[error("Conflict: {0}")]
+    Conflict(ErrorMessage),
+
+    #[error("PreconditionFailed: {0}")]
+    PreconditionFailed(ErrorMessage),
 }
 
 impl From<DBAccessError> for HandlerError {
@@ -18,8 +31,12 @@ impl From<DBAccessError> for HandlerError {
         match error {
             DBAccessError::NotFoundError(msg) => HandlerError::NotFound(msg),
             DBAccessError::ValidationError(msg) => HandlerError::BadRequest(msg),
-            DBAccessError::ConnectionError(e) => HandlerError::InternalServerError(e.to_string()),
-            DBAccessError::QueryError(e) => HandlerError::InternalServerError(e.to_string()),
+            DBAccessError::ConflictError(msg) => HandlerError::Conflict(msg),
+            DBAccessError::PreconditionFailedError(msg) => HandlerError::PreconditionFailed(msg),
+            DBAccessError::ConnectionError(e) => {
+                HandlerError::InternalServerError(e.to_string().into())
+            }
+            DBAccessError::QueryError(e) => HandlerError::InternalServerError(e.into()),
         }
     }
 }
@@ -32,6 +49,47 @@ impl HandlerError {
                 actix_web::http::StatusCode::INTERNAL_SERVER_ERROR
             }
             HandlerError::BadRequest(_) => actix_web::http::StatusCode::BAD_REQUEST,
+            HandlerError::Unauthorized(_) => actix_web::http::StatusCode::UNAUTHORIZED,
+            HandlerError::Forbidden(_) => actix_web::http::StatusCode::FORBIDDEN,
+            HandlerError::Conflict(_) => actix_web::http::StatusCode::CONFLICT,
+            HandlerError::PreconditionFailed(_) => actix_web::http::StatusCode::PRECONDITION_FAILED,
+        }
+    }
+
+    // レスポンスのrc。クライアントがエラーの種類で分岐できるよう種類ごとに分ける
+    pub fn rc(&self) -> i32 {
+        match self {
+            HandlerError::InternalServerError(_) => 1,
+            HandlerError::BadRequest(_) => 2,
+            HandlerError::Unauthorized(_) => 3,
+            HandlerError::Forbidden(_) => 4,
+            HandlerError::NotFound(_) => 5,
+            HandlerError::Conflict(_) => 6,
+            HandlerError::PreconditionFailed(_) => 7,
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            HandlerError::NotFound(_) => "NotFound",
+            HandlerError::InternalServerError(_) => "InternalServerError",
+            HandlerError::BadRequest(_) => "BadRequest",
+            HandlerError::Unauthorized(_) => "Unauthorized",
+            HandlerError::Forbidden(_) => "Forbidden",
+            HandlerError::Conflict(_) => "Conflict",
+            HandlerError::PreconditionFailed(_) => "PreconditionFailed",
+        }
+    }
+
+    pub fn message(&self) -> &ErrorMessage {
+        match self {
+            HandlerError::NotFound(msg)
+            | HandlerError::InternalServerError(msg)
+            | HandlerError::BadRequest(msg)
+            | HandlerError::Unauthorized(msg)
+            | HandlerError::Forbidden(msg)
+            | HandlerError::Conflict(msg)
+            | HandlerError::PreconditionFailed(msg) => msg,
         }
     }
 }
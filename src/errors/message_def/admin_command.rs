@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_admin_command_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // 管理コマンド関連のエラーメッセージ
+    let mut admin_command_password_read_failed = HashMap::new();
+    admin_command_password_read_failed.insert("en", "Failed to read the password");
+    admin_command_password_read_failed.insert("jp", "パスワードの読み込みに失敗しました");
+    map.insert(
+        ErrorKey::AdminCommandPasswordReadFailed,
+        admin_command_password_read_failed,
+    );
+
+    let mut admin_command_task_parent_project_mismatch = HashMap::new();
+    admin_command_task_parent_project_mismatch
+        .insert("en", "Parent task must belong to the same project");
+    admin_command_task_parent_project_mismatch.insert(
+        "jp",
+        "親タスクは同じプロジェクトに属していなければなりません",
+    );
+    map.insert(
+        ErrorKey::AdminCommandTaskParentProjectMismatch,
+        admin_command_task_parent_project_mismatch,
+    );
+
+    let mut admin_command_task_has_subtasks = HashMap::new();
+    admin_command_task_has_subtasks
+        .insert("en", "Cannot change the level of a task that has subtasks");
+    admin_command_task_has_subtasks.insert("jp", "サブタスクを持つタスクのレベルは変更できません");
+    map.insert(
+        ErrorKey::AdminCommandTaskHasSubtasks,
+        admin_command_task_has_subtasks,
+    );
+
+    let mut admin_command_db_check_failed = HashMap::new();
+    admin_command_db_check_failed.insert("en", "Database check found problems");
+    admin_command_db_check_failed.insert("jp", "データベースの検査で問題が見つかりました");
+    map.insert(
+        ErrorKey::AdminCommandDbCheckFailed,
+        admin_command_db_check_failed,
+    );
+}
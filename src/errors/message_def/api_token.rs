@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_api_token_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // APIトークン関連のエラーメッセージ
+    let mut api_token_id_must_be_none = HashMap::new();
+    api_token_id_must_be_none.insert("en", "API token ID must be None");
+    api_token_id_must_be_none.insert("jp", "APIトークンIDはNoneである必要があります");
+    map.insert(ErrorKey::ApiTokenIdMustBeNone, api_token_id_must_be_none);
+
+    let mut api_token_id_invalid = HashMap::new();
+    api_token_id_invalid.insert("en", "Invalid API token ID");
+    api_token_id_invalid.insert("jp", "APIトークンIDが無効です");
+    map.insert(ErrorKey::ApiTokenIdInvalid, api_token_id_invalid);
+
+    let mut api_token_user_id_invalid = HashMap::new();
+    api_token_user_id_invalid.insert("en", "Invalid user ID for API token");
+    api_token_user_id_invalid.insert("jp", "APIトークンのユーザーIDが無効です");
+    map.insert(ErrorKey::ApiTokenUserIdInvalid, api_token_user_id_invalid);
+
+    let mut api_token_name_empty = HashMap::new();
+    api_token_name_empty.insert("en", "API token name cannot be empty");
+    api_token_name_empty.insert("jp", "APIトークン名は空にできません");
+    map.insert(ErrorKey::ApiTokenNameEmpty, api_token_name_empty);
+
+    let mut api_token_name_too_long = HashMap::new();
+    api_token_name_too_long.insert("en", "API token name is too long");
+    api_token_name_too_long.insert("jp", "APIトークン名が長すぎます");
+    map.insert(ErrorKey::ApiTokenNameTooLong, api_token_name_too_long);
+
+    let mut api_token_token_hash_empty = HashMap::new();
+    api_token_token_hash_empty.insert("en", "API token hash cannot be empty");
+    api_token_token_hash_empty.insert("jp", "APIトークンのハッシュは空にできません");
+    map.insert(ErrorKey::ApiTokenTokenHashEmpty, api_token_token_hash_empty);
+
+    let mut api_token_expires_at_invalid = HashMap::new();
+    api_token_expires_at_invalid.insert("en", "API token expiry must be in the future");
+    api_token_expires_at_invalid.insert(
+        "jp",
+        "APIトークンの有効期限は未来の日時である必要があります",
+    );
+    map.insert(
+        ErrorKey::ApiTokenExpiresAtInvalid,
+        api_token_expires_at_invalid,
+    );
+
+    let mut api_token_create_failed = HashMap::new();
+    api_token_create_failed.insert("en", "Failed to create API token");
+    api_token_create_failed.insert("jp", "APIトークンの作成に失敗しました");
+    map.insert(ErrorKey::ApiTokenCreateFailed, api_token_create_failed);
+
+    let mut api_token_get_by_user_id_failed = HashMap::new();
+    api_token_get_by_user_id_failed.insert("en", "Failed to get API tokens by user ID");
+    api_token_get_by_user_id_failed.insert("jp", "ユーザーIDによるAPIトークンの取得に失敗しました");
+    map.insert(
+        ErrorKey::ApiTokenGetByUserIdFailed,
+        api_token_get_by_user_id_failed,
+    );
+
+    let mut api_token_use_failed = HashMap::new();
+    api_token_use_failed.insert("en", "Failed to use API token");
+    api_token_use_failed.insert("jp", "APIトークンの使用に失敗しました");
+    map.insert(ErrorKey::ApiTokenUseFailed, api_token_use_failed);
+
+    let mut api_token_use_failed_by_token_hash_not_found = HashMap::new();
+    api_token_use_failed_by_token_hash_not_found
+        .insert("en", "API token not found, expired or revoked");
+    api_token_use_failed_by_token_hash_not_found.insert(
+        "jp",
+        "APIトークンが見つからないか、期限切れまたは無効化されています",
+    );
+    map.insert(
+        ErrorKey::ApiTokenUseFailedByTokenHashNotFound,
+        api_token_use_failed_by_token_hash_not_found,
+    );
+
+    let mut api_token_revoke_failed = HashMap::new();
+    api_token_revoke_failed.insert("en", "Failed to revoke API token");
+    api_token_revoke_failed.insert("jp", "APIトークンの無効化に失敗しました");
+    map.insert(ErrorKey::ApiTokenRevokeFailed, api_token_revoke_failed);
+
+    let mut api_token_revoke_failed_by_id_not_found = HashMap::new();
+    api_token_revoke_failed_by_id_not_found.insert("en", "API token to revoke not found");
+    api_token_revoke_failed_by_id_not_found.insert("jp", "無効化するAPIトークンが見つかりません");
+    map.insert(
+        ErrorKey::ApiTokenRevokeFailedByIdNotFound,
+        api_token_revoke_failed_by_id_not_found,
+    );
+}
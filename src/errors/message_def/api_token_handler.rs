@@ -0,0 +1,33 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_api_token_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // APIトークンハンドラ関連のエラーメッセージ
+    let mut api_token_handler_invalid_json_post = HashMap::new();
+    api_token_handler_invalid_json_post.insert("en", "Invalid JSON format");
+    api_token_handler_invalid_json_post.insert("jp", "JSON形式が無効です");
+    map.insert(
+        ErrorKey::ApiTokenHandlerInvalidJsonPost,
+        api_token_handler_invalid_json_post,
+    );
+
+    let mut api_token_handler_invalid_path = HashMap::new();
+    api_token_handler_invalid_path.insert("en", "Invalid path");
+    api_token_handler_invalid_path.insert("jp", "パスが無効です");
+    map.insert(
+        ErrorKey::ApiTokenHandlerInvalidPath,
+        api_token_handler_invalid_path,
+    );
+
+    let mut api_token_handler_not_authenticated = HashMap::new();
+    api_token_handler_not_authenticated
+        .insert("en", "Authentication is required to manage API tokens");
+    api_token_handler_not_authenticated.insert("jp", "APIトークンの管理には認証が必要です");
+    map.insert(
+        ErrorKey::ApiTokenHandlerNotAuthenticated,
+        api_token_handler_not_authenticated,
+    );
+}
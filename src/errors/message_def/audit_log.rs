@@ -0,0 +1,52 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_audit_log_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // 監査ログ関連のエラーメッセージ
+    let mut audit_action_invalid = HashMap::new();
+    audit_action_invalid.insert("en", "Invalid audit action");
+    audit_action_invalid.insert("jp", "監査ログの操作種別が無効です");
+    map.insert(ErrorKey::AuditActionInvalid, audit_action_invalid);
+
+    let mut audit_entity_type_invalid = HashMap::new();
+    audit_entity_type_invalid.insert("en", "Invalid audit entity type");
+    audit_entity_type_invalid.insert("jp", "監査ログの対象種別が無効です");
+    map.insert(ErrorKey::AuditEntityTypeInvalid, audit_entity_type_invalid);
+
+    let mut audit_log_entity_id_invalid = HashMap::new();
+    audit_log_entity_id_invalid.insert("en", "Invalid audit entity ID");
+    audit_log_entity_id_invalid.insert("jp", "監査ログの対象IDが無効です");
+    map.insert(
+        ErrorKey::AuditLogEntityIdInvalid,
+        audit_log_entity_id_invalid,
+    );
+
+    let mut audit_log_actor_user_id_invalid = HashMap::new();
+    audit_log_actor_user_id_invalid.insert("en", "Invalid audit actor user ID");
+    audit_log_actor_user_id_invalid.insert("jp", "監査ログの操作ユーザーIDが無効です");
+    map.insert(
+        ErrorKey::AuditLogActorUserIdInvalid,
+        audit_log_actor_user_id_invalid,
+    );
+
+    let mut audit_log_create_failed = HashMap::new();
+    audit_log_create_failed.insert("en", "Failed to create audit log");
+    audit_log_create_failed.insert("jp", "監査ログの作成に失敗しました");
+    map.insert(ErrorKey::AuditLogCreateFailed, audit_log_create_failed);
+
+    let mut audit_log_get_by_filter_failed = HashMap::new();
+    audit_log_get_by_filter_failed.insert("en", "Failed to get audit logs by filter");
+    audit_log_get_by_filter_failed.insert("jp", "フィルターによる監査ログの取得に失敗しました");
+    map.insert(
+        ErrorKey::AuditLogGetByFilterFailed,
+        audit_log_get_by_filter_failed,
+    );
+
+    let mut audit_log_get_count_failed = HashMap::new();
+    audit_log_get_count_failed.insert("en", "Failed to get audit log count");
+    audit_log_get_count_failed.insert("jp", "監査ログの件数の取得に失敗しました");
+    map.insert(ErrorKey::AuditLogGetCountFailed, audit_log_get_count_failed);
+}
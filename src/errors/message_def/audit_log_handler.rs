@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_audit_log_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // 監査ログハンドラ関連のエラーメッセージ
+    let mut audit_log_handler_invalid_query = HashMap::new();
+    audit_log_handler_invalid_query.insert("en", "Invalid query");
+    audit_log_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::AuditLogHandlerInvalidQuery,
+        audit_log_handler_invalid_query,
+    );
+
+    let mut audit_log_handler_invalid_page = HashMap::new();
+    audit_log_handler_invalid_page.insert("en", "Invalid page or page size");
+    audit_log_handler_invalid_page.insert("jp", "ページまたはページサイズが無効です");
+    map.insert(
+        ErrorKey::AuditLogHandlerInvalidPage,
+        audit_log_handler_invalid_page,
+    );
+}
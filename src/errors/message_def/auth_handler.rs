@@ -0,0 +1,62 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_auth_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // 認証ハンドラ関連のエラーメッセージ
+    let mut auth_handler_invalid_json_post = HashMap::new();
+    auth_handler_invalid_json_post.insert("en", "Invalid JSON format");
+    auth_handler_invalid_json_post.insert("jp", "JSON形式が無効です");
+    map.insert(
+        ErrorKey::AuthHandlerInvalidJsonPost,
+        auth_handler_invalid_json_post,
+    );
+
+    let mut auth_handler_invalid_query = HashMap::new();
+    auth_handler_invalid_query.insert("en", "Invalid query");
+    auth_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::AuthHandlerInvalidQuery,
+        auth_handler_invalid_query,
+    );
+
+    let mut auth_handler_invalid_credentials = HashMap::new();
+    auth_handler_invalid_credentials.insert("en", "Invalid username or password");
+    auth_handler_invalid_credentials.insert("jp", "ユーザー名またはパスワードが正しくありません");
+    map.insert(
+        ErrorKey::AuthHandlerInvalidCredentials,
+        auth_handler_invalid_credentials,
+    );
+
+    let mut auth_handler_no_token = HashMap::new();
+    auth_handler_no_token.insert("en", "Authorization header with a bearer token is required");
+    auth_handler_no_token.insert("jp", "Bearerトークンを含むAuthorizationヘッダーが必要です");
+    map.insert(ErrorKey::AuthHandlerNoToken, auth_handler_no_token);
+
+    let mut auth_handler_invalid_token = HashMap::new();
+    auth_handler_invalid_token.insert("en", "Invalid, expired or revoked token");
+    auth_handler_invalid_token.insert("jp", "トークンが無効、期限切れ、または無効化されています");
+    map.insert(
+        ErrorKey::AuthHandlerInvalidToken,
+        auth_handler_invalid_token,
+    );
+
+    let mut auth_handler_pool_not_configured = HashMap::new();
+    auth_handler_pool_not_configured
+        .insert("en", "Database pool is not configured for authentication");
+    auth_handler_pool_not_configured.insert("jp", "認証に使用するDBプールが設定されていません");
+    map.insert(
+        ErrorKey::AuthHandlerPoolNotConfigured,
+        auth_handler_pool_not_configured,
+    );
+
+    let mut auth_handler_admin_required = HashMap::new();
+    auth_handler_admin_required.insert("en", "Administrator privileges are required");
+    auth_handler_admin_required.insert("jp", "管理者権限が必要です");
+    map.insert(
+        ErrorKey::AuthHandlerAdminRequired,
+        auth_handler_admin_required,
+    );
+}
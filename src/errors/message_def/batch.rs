@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_batch_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>) {
+    // バッチ操作のエラーメッセージ
+    let mut batch_operations_empty = HashMap::new();
+    batch_operations_empty.insert("en", "The batch has no operations");
+    batch_operations_empty.insert("jp", "バッチに操作がありません");
+    map.insert(ErrorKey::BatchOperationsEmpty, batch_operations_empty);
+
+    let mut batch_operations_too_many = HashMap::new();
+    batch_operations_too_many.insert("en", "The batch has too many operations");
+    batch_operations_too_many.insert("jp", "バッチの操作が多すぎます");
+    map.insert(ErrorKey::BatchOperationsTooMany, batch_operations_too_many);
+
+    let mut batch_temp_id_duplicate = HashMap::new();
+    batch_temp_id_duplicate.insert("en", "The temporary ID is already used in the batch");
+    batch_temp_id_duplicate.insert("jp", "一時IDがバッチ内で重複しています");
+    map.insert(ErrorKey::BatchTempIdDuplicate, batch_temp_id_duplicate);
+
+    let mut batch_temp_id_not_found = HashMap::new();
+    batch_temp_id_not_found.insert(
+        "en",
+        "The temporary ID is not defined by an earlier operation in the batch",
+    );
+    batch_temp_id_not_found.insert("jp", "一時IDがバッチ内の前の操作で定義されていません");
+    map.insert(ErrorKey::BatchTempIdNotFound, batch_temp_id_not_found);
+
+    let mut batch_parent_id_conflict = HashMap::new();
+    batch_parent_id_conflict.insert(
+        "en",
+        "parent_id and parent_temp_id cannot be specified together",
+    );
+    batch_parent_id_conflict.insert("jp", "parent_idとparent_temp_idは同時に指定できません");
+    map.insert(ErrorKey::BatchParentIdConflict, batch_parent_id_conflict);
+
+    let mut batch_commit_failed = HashMap::new();
+    batch_commit_failed.insert("en", "Failed to commit the batch");
+    batch_commit_failed.insert("jp", "バッチのコミットに失敗しました");
+    map.insert(ErrorKey::BatchCommitFailed, batch_commit_failed);
+}
@@ -100,4 +100,15 @@ pub fn add_comment_handler_error_messages(
         ErrorKey::CommentHandlerInvalidPath,
         comment_handler_invalid_path,
     );
+
+    let mut comment_handler_user_mismatch = HashMap::new();
+    comment_handler_user_mismatch.insert(
+        "en",
+        "The user of the comment cannot be set to another user",
+    );
+    comment_handler_user_mismatch.insert("jp", "コメントのユーザーに他のユーザーは指定できません");
+    map.insert(
+        ErrorKey::CommentHandlerUserMismatch,
+        comment_handler_user_mismatch,
+    );
 }
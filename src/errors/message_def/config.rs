@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_config_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>) {
+    // 設定関連のエラーメッセージ
+    let mut config_load_failed = HashMap::new();
+    config_load_failed.insert("en", "Failed to load configuration");
+    config_load_failed.insert("jp", "設定の読み込みに失敗しました");
+    map.insert(ErrorKey::ConfigLoadFailed, config_load_failed);
+
+    let mut config_file_not_found = HashMap::new();
+    config_file_not_found.insert("en", "Configuration file not found");
+    config_file_not_found.insert("jp", "設定ファイルが見つかりません");
+    map.insert(ErrorKey::ConfigFileNotFound, config_file_not_found);
+
+    let mut config_already_initialized = HashMap::new();
+    config_already_initialized.insert("en", "Configuration is already initialized");
+    config_already_initialized.insert("jp", "設定は既に初期化されています");
+    map.insert(
+        ErrorKey::ConfigAlreadyInitialized,
+        config_already_initialized,
+    );
+
+    let mut config_bind_address_invalid = HashMap::new();
+    config_bind_address_invalid.insert("en", "Bind address must be in host:port format");
+    config_bind_address_invalid.insert(
+        "jp",
+        "バインドアドレスはhost:portの形式でなければなりません",
+    );
+    map.insert(
+        ErrorKey::ConfigBindAddressInvalid,
+        config_bind_address_invalid,
+    );
+
+    let mut config_database_url_missing = HashMap::new();
+    config_database_url_missing.insert("en", "Database URL must be set");
+    config_database_url_missing.insert("jp", "データベースURLを設定しなければなりません");
+    map.insert(
+        ErrorKey::ConfigDatabaseUrlMissing,
+        config_database_url_missing,
+    );
+
+    let mut config_max_connections_invalid = HashMap::new();
+    config_max_connections_invalid.insert("en", "Max connections must be greater than 0");
+    config_max_connections_invalid.insert("jp", "最大接続数は0より大きくなければなりません");
+    map.insert(
+        ErrorKey::ConfigMaxConnectionsInvalid,
+        config_max_connections_invalid,
+    );
+
+    let mut config_log_destination_invalid = HashMap::new();
+    config_log_destination_invalid.insert("en", "Log destination must not be empty");
+    config_log_destination_invalid.insert("jp", "ログの出力先は空にできません");
+    map.insert(
+        ErrorKey::ConfigLogDestinationInvalid,
+        config_log_destination_invalid,
+    );
+
+    let mut config_log_level_invalid = HashMap::new();
+    config_log_level_invalid.insert(
+        "en",
+        "Log level must be one of off, error, warn, info, debug, trace",
+    );
+    config_log_level_invalid.insert(
+        "jp",
+        "ログレベルはoff, error, warn, info, debug, traceのいずれかでなければなりません",
+    );
+    map.insert(ErrorKey::ConfigLogLevelInvalid, config_log_level_invalid);
+
+    let mut config_log_format_invalid = HashMap::new();
+    config_log_format_invalid.insert("en", "Invalid log format");
+    config_log_format_invalid.insert("jp", "ログの出力形式が不正です");
+    map.insert(ErrorKey::ConfigLogFormatInvalid, config_log_format_invalid);
+
+    let mut config_log_rotation_invalid = HashMap::new();
+    config_log_rotation_invalid.insert("en", "At least one rotated log file must be kept");
+    config_log_rotation_invalid.insert(
+        "jp",
+        "ローテーションするログファイルを1つ以上残す必要があります",
+    );
+    map.insert(
+        ErrorKey::ConfigLogRotationInvalid,
+        config_log_rotation_invalid,
+    );
+
+    let mut config_language_invalid = HashMap::new();
+    config_language_invalid.insert("en", "Language is not supported");
+    config_language_invalid.insert("jp", "サポートされていない言語です");
+    map.insert(ErrorKey::ConfigLanguageInvalid, config_language_invalid);
+
+    let mut config_max_page_size_invalid = HashMap::new();
+    config_max_page_size_invalid.insert("en", "Max page size must be greater than 0");
+    config_max_page_size_invalid.insert("jp", "最大ページサイズは0より大きくなければなりません");
+    map.insert(
+        ErrorKey::ConfigMaxPageSizeInvalid,
+        config_max_page_size_invalid,
+    );
+
+    let mut config_trash_retention_days_invalid = HashMap::new();
+    config_trash_retention_days_invalid.insert("en", "Trash retention days must be greater than 0");
+    config_trash_retention_days_invalid
+        .insert("jp", "ゴミ箱の保持日数は0より大きくなければなりません");
+    map.insert(
+        ErrorKey::ConfigTrashRetentionDaysInvalid,
+        config_trash_retention_days_invalid,
+    );
+
+    let mut config_password_hash_invalid = HashMap::new();
+    config_password_hash_invalid.insert("en", "Password hash parameters are invalid");
+    config_password_hash_invalid.insert("jp", "パスワードハッシュのパラメータが不正です");
+    map.insert(
+        ErrorKey::ConfigPasswordHashInvalid,
+        config_password_hash_invalid,
+    );
+
+    let mut logger_init_failed = HashMap::new();
+    logger_init_failed.insert("en", "Failed to initialize logger");
+    logger_init_failed.insert("jp", "ロガーの初期化に失敗しました");
+    map.insert(ErrorKey::LoggerInitFailed, logger_init_failed);
+}
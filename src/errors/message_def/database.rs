@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_database_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // データベース検査関連のエラーメッセージ
+    let mut database_integrity_check_failed = HashMap::new();
+    database_integrity_check_failed.insert("en", "Failed to run the integrity check");
+    database_integrity_check_failed.insert("jp", "整合性チェックの実行に失敗しました");
+    map.insert(
+        ErrorKey::DatabaseIntegrityCheckFailed,
+        database_integrity_check_failed,
+    );
+
+    let mut database_foreign_key_check_failed = HashMap::new();
+    database_foreign_key_check_failed.insert("en", "Failed to run the foreign key check");
+    database_foreign_key_check_failed.insert("jp", "外部キーチェックの実行に失敗しました");
+    map.insert(
+        ErrorKey::DatabaseForeignKeyCheckFailed,
+        database_foreign_key_check_failed,
+    );
+}
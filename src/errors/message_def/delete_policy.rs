@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_delete_policy_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // 削除ポリシー関連のエラーメッセージ
+    let mut delete_policy_invalid = HashMap::new();
+    delete_policy_invalid.insert("en", "Delete policy must be restrict, cascade or reparent");
+    delete_policy_invalid.insert(
+        "jp",
+        "削除ポリシーはrestrict、cascade、reparentのいずれかを指定してください",
+    );
+    map.insert(ErrorKey::DeletePolicyInvalid, delete_policy_invalid);
+
+    let mut delete_policy_not_supported = HashMap::new();
+    delete_policy_not_supported
+        .insert("en", "The delete policy is not supported for this resource");
+    delete_policy_not_supported.insert("jp", "この対象には指定された削除ポリシーを使用できません");
+    map.insert(
+        ErrorKey::DeletePolicyNotSupported,
+        delete_policy_not_supported,
+    );
+
+    let mut delete_policy_restricted = HashMap::new();
+    delete_policy_restricted.insert(
+        "en",
+        "Cannot delete because dependent records exist. Use the cascade policy to delete them together",
+    );
+    delete_policy_restricted.insert(
+        "jp",
+        "依存するデータがあるため削除できません。一緒に削除する場合はcascadeを指定してください",
+    );
+    map.insert(ErrorKey::DeletePolicyRestricted, delete_policy_restricted);
+
+    let mut delete_policy_reparent_level_conflict = HashMap::new();
+    delete_policy_reparent_level_conflict.insert(
+        "en",
+        "Cannot reparent because tasks with assignments or comments would no longer be at the lowest level",
+    );
+    delete_policy_reparent_level_conflict.insert(
+        "jp",
+        "割り当てやコメントのあるタスクが最下位の階層ではなくなるため、親を付け替えられません",
+    );
+    map.insert(
+        ErrorKey::DeletePolicyReparentLevelConflict,
+        delete_policy_reparent_level_conflict,
+    );
+
+    let mut delete_policy_dependents_failed = HashMap::new();
+    delete_policy_dependents_failed.insert("en", "Failed to delete dependent records");
+    delete_policy_dependents_failed.insert("jp", "依存するデータの削除に失敗しました");
+    map.insert(
+        ErrorKey::DeletePolicyDependentsFailed,
+        delete_policy_dependents_failed,
+    );
+}
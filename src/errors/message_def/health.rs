@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_health_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>) {
+    // ヘルスチェック関連のエラーメッセージ
+    let mut health_database_ping_failed = HashMap::new();
+    health_database_ping_failed.insert("en", "Failed to reach the database");
+    health_database_ping_failed.insert("jp", "データベースに接続できません");
+    map.insert(
+        ErrorKey::HealthDatabasePingFailed,
+        health_database_ping_failed,
+    );
+
+    let mut health_database_size_failed = HashMap::new();
+    health_database_size_failed.insert("en", "Failed to get the database size");
+    health_database_size_failed.insert("jp", "データベースのサイズを取得できません");
+    map.insert(
+        ErrorKey::HealthDatabaseSizeFailed,
+        health_database_size_failed,
+    );
+
+    let mut health_check_timeout = HashMap::new();
+    health_check_timeout.insert("en", "Health check timed out");
+    health_check_timeout.insert("jp", "ヘルスチェックがタイムアウトしました");
+    map.insert(ErrorKey::HealthCheckTimeout, health_check_timeout);
+
+    let mut health_migration_version_mismatch = HashMap::new();
+    health_migration_version_mismatch
+        .insert("en", "Applied schema version does not match the server");
+    health_migration_version_mismatch
+        .insert("jp", "適用済みのスキーマバージョンがサーバーと一致しません");
+    map.insert(
+        ErrorKey::HealthMigrationVersionMismatch,
+        health_migration_version_mismatch,
+    );
+
+    let mut health_not_ready = HashMap::new();
+    health_not_ready.insert("en", "Server is not ready");
+    health_not_ready.insert("jp", "サーバーの準備ができていません");
+    map.insert(ErrorKey::HealthNotReady, health_not_ready);
+}
@@ -0,0 +1,18 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_integrity_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // データ整合性チェック関連のエラーメッセージ
+    let mut integrity_check_failed = HashMap::new();
+    integrity_check_failed.insert("en", "Failed to check data integrity");
+    integrity_check_failed.insert("jp", "データ整合性のチェックに失敗しました");
+    map.insert(ErrorKey::IntegrityCheckFailed, integrity_check_failed);
+
+    let mut integrity_repair_failed = HashMap::new();
+    integrity_repair_failed.insert("en", "Failed to repair data integrity problems");
+    integrity_repair_failed.insert("jp", "データ整合性の問題の修正に失敗しました");
+    map.insert(ErrorKey::IntegrityRepairFailed, integrity_repair_failed);
+}
@@ -0,0 +1,16 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_integrity_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // データ整合性ハンドラ関連のエラーメッセージ
+    let mut integrity_handler_invalid_query = HashMap::new();
+    integrity_handler_invalid_query.insert("en", "Invalid query");
+    integrity_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::IntegrityHandlerInvalidQuery,
+        integrity_handler_invalid_query,
+    );
+}
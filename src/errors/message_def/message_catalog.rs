@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_message_catalog_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // メッセージカタログ関連のエラーメッセージ
+    let mut message_catalog_dir_read_failed = HashMap::new();
+    message_catalog_dir_read_failed.insert("en", "Failed to read the message catalogue directory");
+    message_catalog_dir_read_failed
+        .insert("jp", "メッセージカタログのディレクトリを読み込めません");
+    map.insert(
+        ErrorKey::MessageCatalogDirReadFailed,
+        message_catalog_dir_read_failed,
+    );
+
+    let mut message_catalog_file_read_failed = HashMap::new();
+    message_catalog_file_read_failed.insert("en", "Failed to read the message catalogue file");
+    message_catalog_file_read_failed.insert("jp", "メッセージカタログのファイルを読み込めません");
+    map.insert(
+        ErrorKey::MessageCatalogFileReadFailed,
+        message_catalog_file_read_failed,
+    );
+
+    let mut message_catalog_parse_failed = HashMap::new();
+    message_catalog_parse_failed.insert("en", "Failed to parse the message catalogue");
+    message_catalog_parse_failed.insert("jp", "メッセージカタログを解析できません");
+    map.insert(
+        ErrorKey::MessageCatalogParseFailed,
+        message_catalog_parse_failed,
+    );
+
+    let mut message_catalog_unknown_keys = HashMap::new();
+    message_catalog_unknown_keys.insert("en", "Message catalogue contains unknown keys");
+    message_catalog_unknown_keys.insert("jp", "メッセージカタログに存在しないキーが含まれています");
+    map.insert(
+        ErrorKey::MessageCatalogUnknownKeys,
+        message_catalog_unknown_keys,
+    );
+
+    let mut message_catalog_missing_keys = HashMap::new();
+    message_catalog_missing_keys.insert("en", "Message catalogue is missing keys");
+    message_catalog_missing_keys.insert("jp", "メッセージカタログに不足しているキーがあります");
+    map.insert(
+        ErrorKey::MessageCatalogMissingKeys,
+        message_catalog_missing_keys,
+    );
+
+    let mut message_catalog_signal_failed = HashMap::new();
+    message_catalog_signal_failed.insert("en", "Failed to register the catalogue reload signal");
+    message_catalog_signal_failed.insert(
+        "jp",
+        "メッセージカタログの再読み込み用シグナルを登録できません",
+    );
+    map.insert(
+        ErrorKey::MessageCatalogSignalFailed,
+        message_catalog_signal_failed,
+    );
+}
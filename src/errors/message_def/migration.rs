@@ -0,0 +1,45 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_migration_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // マイグレーション関連のエラーメッセージ
+    let mut migration_status_failed = HashMap::new();
+    migration_status_failed.insert("en", "Failed to get migration status");
+    migration_status_failed.insert("jp", "マイグレーションの状態の取得に失敗しました");
+    map.insert(ErrorKey::MigrationStatusFailed, migration_status_failed);
+
+    let mut migration_run_failed = HashMap::new();
+    migration_run_failed.insert("en", "Failed to apply migrations");
+    migration_run_failed.insert("jp", "マイグレーションの適用に失敗しました");
+    map.insert(ErrorKey::MigrationRunFailed, migration_run_failed);
+
+    let mut migration_revert_failed = HashMap::new();
+    migration_revert_failed.insert("en", "Failed to revert migrations");
+    migration_revert_failed.insert("jp", "マイグレーションの取り消しに失敗しました");
+    map.insert(ErrorKey::MigrationRevertFailed, migration_revert_failed);
+
+    let mut migration_target_invalid = HashMap::new();
+    migration_target_invalid.insert(
+        "en",
+        "Target version must be 0 or an applied migration version",
+    );
+    migration_target_invalid.insert(
+        "jp",
+        "対象バージョンは0または適用済みのマイグレーションのバージョンでなければなりません",
+    );
+    map.insert(ErrorKey::MigrationTargetInvalid, migration_target_invalid);
+
+    let mut migration_dirty = HashMap::new();
+    migration_dirty.insert(
+        "en",
+        "A migration previously failed and the database is in a dirty state",
+    );
+    migration_dirty.insert(
+        "jp",
+        "以前のマイグレーションが失敗しており、データベースが不整合な状態です",
+    );
+    map.insert(ErrorKey::MigrationDirty, migration_dirty);
+}
@@ -1,12 +1,38 @@
+pub mod admin_command;
+pub mod api_token;
+pub mod api_token_handler;
+pub mod audit_log;
+pub mod audit_log_handler;
+pub mod auth_handler;
+pub mod batch;
 pub mod comment;
 pub mod comment_handler;
+pub mod config;
+pub mod database;
+pub mod delete_policy;
+pub mod health;
+pub mod integrity;
+pub mod integrity_handler;
+pub mod message_catalog;
+pub mod migration;
+pub mod password;
 pub mod project;
 pub mod project_handler;
+pub mod project_member;
+pub mod project_member_handler;
 pub mod repository;
+pub mod session;
 pub mod task;
 pub mod task_handler;
+pub mod task_history;
+pub mod task_history_handler;
+pub mod task_tree;
+pub mod task_tree_handler;
 pub mod task_user;
+pub mod trash;
+pub mod trash_handler;
 pub mod user;
 pub mod user_assign;
 pub mod user_assign_handler;
 pub mod user_handler;
+pub mod version;
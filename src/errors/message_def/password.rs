@@ -0,0 +1,39 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_password_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // パスワード関連のエラーメッセージ
+    let mut password_hash_failed = HashMap::new();
+    password_hash_failed.insert("en", "Failed to hash password");
+    password_hash_failed.insert("jp", "パスワードのハッシュ化に失敗しました");
+    map.insert(ErrorKey::PasswordHashFailed, password_hash_failed);
+
+    let mut password_hash_params_invalid = HashMap::new();
+    password_hash_params_invalid.insert("en", "Password hash parameters are invalid");
+    password_hash_params_invalid.insert("jp", "パスワードハッシュのパラメータが不正です");
+    map.insert(
+        ErrorKey::PasswordHashParamsInvalid,
+        password_hash_params_invalid,
+    );
+
+    let mut password_hash_format_invalid = HashMap::new();
+    password_hash_format_invalid.insert("en", "Stored password hash has an invalid format");
+    password_hash_format_invalid.insert("jp", "保存されたパスワードハッシュの形式が不正です");
+    map.insert(
+        ErrorKey::PasswordHashFormatInvalid,
+        password_hash_format_invalid,
+    );
+
+    let mut password_verify_failed = HashMap::new();
+    password_verify_failed.insert("en", "Failed to verify password");
+    password_verify_failed.insert("jp", "パスワードの検証に失敗しました");
+    map.insert(ErrorKey::PasswordVerifyFailed, password_verify_failed);
+
+    let mut password_rehash_failed = HashMap::new();
+    password_rehash_failed.insert("en", "Failed to update password hash");
+    password_rehash_failed.insert("jp", "パスワードハッシュの更新に失敗しました");
+    map.insert(ErrorKey::PasswordRehashFailed, password_rehash_failed);
+}
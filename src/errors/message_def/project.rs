@@ -59,6 +59,11 @@ pub fn add_project_error_messages(
     );
     map.insert(ErrorKey::ProjectCreateFailed, project_create_failed);
 
+    let mut project_already_exists = HashMap::new();
+    project_already_exists.insert("en", "A project with the same name already exists");
+    project_already_exists.insert("jp", "同じ名前のプロジェクトが既に存在します");
+    map.insert(ErrorKey::ProjectAlreadyExists, project_already_exists);
+
     let mut project_get_by_id_failed = HashMap::new();
     project_get_by_id_failed.insert(
         "en",
@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_project_member_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // プロジェクトメンバー関連のエラーメッセージ
+    let mut project_member_id_must_be_none = HashMap::new();
+    project_member_id_must_be_none.insert("en", "Project member ID must be None");
+    project_member_id_must_be_none.insert("jp", "プロジェクトメンバーIDはNoneである必要があります");
+    map.insert(
+        ErrorKey::ProjectMemberIdMustBeNone,
+        project_member_id_must_be_none,
+    );
+
+    let mut project_member_project_id_invalid = HashMap::new();
+    project_member_project_id_invalid.insert("en", "Invalid project ID");
+    project_member_project_id_invalid.insert("jp", "プロジェクトIDが無効です");
+    map.insert(
+        ErrorKey::ProjectMemberProjectIdInvalid,
+        project_member_project_id_invalid,
+    );
+
+    let mut project_member_user_id_invalid = HashMap::new();
+    project_member_user_id_invalid.insert("en", "Invalid user ID");
+    project_member_user_id_invalid.insert("jp", "ユーザーIDが無効です");
+    map.insert(
+        ErrorKey::ProjectMemberUserIdInvalid,
+        project_member_user_id_invalid,
+    );
+
+    let mut project_role_invalid = HashMap::new();
+    project_role_invalid.insert(
+        "en",
+        "Invalid role. Role must be 0 (owner), 1 (member), or 2 (viewer)",
+    );
+    project_role_invalid.insert("jp", "ロールが無効です。ロールは0（オーナー）、1（メンバー）、2（閲覧者）のいずれかである必要があります");
+    map.insert(ErrorKey::ProjectRoleInvalid, project_role_invalid);
+
+    let mut project_member_create_failed = HashMap::new();
+    project_member_create_failed.insert("en", "Failed to add project member");
+    project_member_create_failed.insert("jp", "プロジェクトメンバーの追加に失敗しました");
+    map.insert(
+        ErrorKey::ProjectMemberCreateFailed,
+        project_member_create_failed,
+    );
+
+    let mut project_member_already_exists = HashMap::new();
+    project_member_already_exists.insert("en", "The user is already a member of the project");
+    project_member_already_exists.insert("jp", "ユーザーは既にプロジェクトのメンバーです");
+    map.insert(
+        ErrorKey::ProjectMemberAlreadyExists,
+        project_member_already_exists,
+    );
+
+    let mut project_member_project_id_not_found = HashMap::new();
+    project_member_project_id_not_found.insert("en", "Project not found");
+    project_member_project_id_not_found.insert("jp", "プロジェクトが見つかりません");
+    map.insert(
+        ErrorKey::ProjectMemberProjectIdNotFound,
+        project_member_project_id_not_found,
+    );
+
+    let mut project_member_get_by_project_id_failed = HashMap::new();
+    project_member_get_by_project_id_failed.insert("en", "Failed to get project members");
+    project_member_get_by_project_id_failed
+        .insert("jp", "プロジェクトメンバーの取得に失敗しました");
+    map.insert(
+        ErrorKey::ProjectMemberGetByProjectIdFailed,
+        project_member_get_by_project_id_failed,
+    );
+
+    let mut project_member_get_by_project_id_and_user_id_failed = HashMap::new();
+    project_member_get_by_project_id_and_user_id_failed
+        .insert("en", "Failed to get project member");
+    project_member_get_by_project_id_and_user_id_failed
+        .insert("jp", "プロジェクトメンバーの取得に失敗しました");
+    map.insert(
+        ErrorKey::ProjectMemberGetByProjectIdAndUserIdFailed,
+        project_member_get_by_project_id_and_user_id_failed,
+    );
+
+    let mut project_member_get_by_project_id_and_user_id_not_found = HashMap::new();
+    project_member_get_by_project_id_and_user_id_not_found.insert("en", "Project member not found");
+    project_member_get_by_project_id_and_user_id_not_found
+        .insert("jp", "プロジェクトメンバーが見つかりません");
+    map.insert(
+        ErrorKey::ProjectMemberGetByProjectIdAndUserIdNotFound,
+        project_member_get_by_project_id_and_user_id_not_found,
+    );
+
+    let mut project_member_update_failed = HashMap::new();
+    project_member_update_failed.insert("en", "Failed to update project member");
+    project_member_update_failed.insert("jp", "プロジェクトメンバーの更新に失敗しました");
+    map.insert(
+        ErrorKey::ProjectMemberUpdateFailed,
+        project_member_update_failed,
+    );
+
+    let mut project_member_delete_failed = HashMap::new();
+    project_member_delete_failed.insert("en", "Failed to remove project member");
+    project_member_delete_failed.insert("jp", "プロジェクトメンバーの削除に失敗しました");
+    map.insert(
+        ErrorKey::ProjectMemberDeleteFailed,
+        project_member_delete_failed,
+    );
+
+    let mut project_member_last_owner = HashMap::new();
+    project_member_last_owner.insert(
+        "en",
+        "Cannot remove or demote the last owner of the project",
+    );
+    project_member_last_owner.insert(
+        "jp",
+        "プロジェクトの最後のオーナーを削除または降格することはできません",
+    );
+    map.insert(ErrorKey::ProjectMemberLastOwner, project_member_last_owner);
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_project_member_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // プロジェクトメンバーハンドラ関連のエラーメッセージ
+    let mut project_member_handler_invalid_json_post = HashMap::new();
+    project_member_handler_invalid_json_post.insert("en", "Invalid JSON post");
+    project_member_handler_invalid_json_post.insert("jp", "無効なJSONが送信されました");
+    map.insert(
+        ErrorKey::ProjectMemberHandlerInvalidJsonPost,
+        project_member_handler_invalid_json_post,
+    );
+
+    let mut project_member_handler_invalid_path = HashMap::new();
+    project_member_handler_invalid_path.insert("en", "Invalid path");
+    project_member_handler_invalid_path.insert("jp", "無効なパスです");
+    map.insert(
+        ErrorKey::ProjectMemberHandlerInvalidPath,
+        project_member_handler_invalid_path,
+    );
+
+    let mut project_member_handler_path_and_body_id_mismatch = HashMap::new();
+    project_member_handler_path_and_body_id_mismatch.insert("en", "Path and body ID mismatch");
+    project_member_handler_path_and_body_id_mismatch.insert("jp", "パスとボディのIDが一致しません");
+    map.insert(
+        ErrorKey::ProjectMemberHandlerPathAndBodyIdMismatch,
+        project_member_handler_path_and_body_id_mismatch,
+    );
+
+    let mut project_member_handler_permission_denied = HashMap::new();
+    project_member_handler_permission_denied
+        .insert("en", "You do not have the required role in this project");
+    project_member_handler_permission_denied
+        .insert("jp", "このプロジェクトで必要なロールがありません");
+    map.insert(
+        ErrorKey::ProjectMemberHandlerPermissionDenied,
+        project_member_handler_permission_denied,
+    );
+}
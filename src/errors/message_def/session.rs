@@ -0,0 +1,92 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_session_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // セッション関連のエラーメッセージ
+    let mut session_id_must_be_none = HashMap::new();
+    session_id_must_be_none.insert(
+        "en",
+        "Session ID must be None. ID was specified in a process that cannot specify ID.",
+    );
+    session_id_must_be_none.insert("jp", "セッションIDが指定できない処理でIDが指定されました。");
+    map.insert(ErrorKey::SessionIdMustBeNone, session_id_must_be_none);
+
+    let mut session_user_id_invalid = HashMap::new();
+    session_user_id_invalid.insert("en", "Session user ID must be greater than or equal to 0");
+    session_user_id_invalid.insert("jp", "セッションのユーザーIDは0以上である必要があります");
+    map.insert(ErrorKey::SessionUserIdInvalid, session_user_id_invalid);
+
+    let mut session_token_hash_empty = HashMap::new();
+    session_token_hash_empty.insert("en", "Session token hash cannot be empty");
+    session_token_hash_empty.insert("jp", "セッションのトークンハッシュは空にできません");
+    map.insert(ErrorKey::SessionTokenHashEmpty, session_token_hash_empty);
+
+    let mut session_expires_at_invalid = HashMap::new();
+    session_expires_at_invalid.insert("en", "Session expiration must be in the future");
+    session_expires_at_invalid.insert("jp", "セッションの有効期限は未来の時刻である必要があります");
+    map.insert(
+        ErrorKey::SessionExpiresAtInvalid,
+        session_expires_at_invalid,
+    );
+
+    let mut session_create_failed = HashMap::new();
+    session_create_failed.insert(
+        "en",
+        "Failed to create session due to database operation failure",
+    );
+    session_create_failed.insert("jp", "DB操作処理の問題によりセッションの作成に失敗しました");
+    map.insert(ErrorKey::SessionCreateFailed, session_create_failed);
+
+    let mut session_get_by_token_hash_failed = HashMap::new();
+    session_get_by_token_hash_failed.insert(
+        "en",
+        "Failed to get session by token due to database operation failure",
+    );
+    session_get_by_token_hash_failed.insert(
+        "jp",
+        "DB操作処理の問題によりトークンによるセッションの取得に失敗しました",
+    );
+    map.insert(
+        ErrorKey::SessionGetByTokenHashFailed,
+        session_get_by_token_hash_failed,
+    );
+
+    let mut session_get_by_token_hash_not_found = HashMap::new();
+    session_get_by_token_hash_not_found.insert("en", "Session not found, expired or revoked");
+    session_get_by_token_hash_not_found.insert(
+        "jp",
+        "セッションが見つからないか、期限切れまたは無効化されています",
+    );
+    map.insert(
+        ErrorKey::SessionGetByTokenHashNotFound,
+        session_get_by_token_hash_not_found,
+    );
+
+    let mut session_revoke_failed = HashMap::new();
+    session_revoke_failed.insert(
+        "en",
+        "Failed to revoke session due to database operation failure",
+    );
+    session_revoke_failed.insert(
+        "jp",
+        "DB操作処理の問題によりセッションの無効化に失敗しました",
+    );
+    map.insert(ErrorKey::SessionRevokeFailed, session_revoke_failed);
+
+    let mut session_revoke_failed_by_token_hash_not_found = HashMap::new();
+    session_revoke_failed_by_token_hash_not_found.insert(
+        "en",
+        "Failed to revoke session because session does not exist or is already revoked",
+    );
+    session_revoke_failed_by_token_hash_not_found.insert(
+        "jp",
+        "存在しないか、既に無効化されたセッションを無効化しようとしました。",
+    );
+    map.insert(
+        ErrorKey::SessionRevokeFailedByTokenHashNotFound,
+        session_revoke_failed_by_token_hash_not_found,
+    );
+}
@@ -73,6 +73,11 @@ pub fn add_task_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str,
     task_project_id_not_found.insert("jp", "プロジェクトが見つかりません");
     map.insert(ErrorKey::TaskProjectIdNotFound, task_project_id_not_found);
 
+    let mut task_project_id_changed = HashMap::new();
+    task_project_id_changed.insert("en", "A task cannot be moved to another project");
+    task_project_id_changed.insert("jp", "タスクを別のプロジェクトに移動することはできません");
+    map.insert(ErrorKey::TaskProjectIdChanged, task_project_id_changed);
+
     let mut task_no_parent_id_on_non_major_task = HashMap::new();
     task_no_parent_id_on_non_major_task.insert("en", "Parent ID is required for non-major tasks");
     task_no_parent_id_on_non_major_task
@@ -0,0 +1,40 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_task_history_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // タスク履歴関連のエラーメッセージ
+    let mut task_history_task_id_invalid = HashMap::new();
+    task_history_task_id_invalid.insert("en", "Invalid task ID for task history");
+    task_history_task_id_invalid.insert("jp", "タスク履歴のタスクIDが無効です");
+    map.insert(
+        ErrorKey::TaskHistoryTaskIdInvalid,
+        task_history_task_id_invalid,
+    );
+
+    let mut task_history_create_failed = HashMap::new();
+    task_history_create_failed.insert("en", "Failed to create task history");
+    task_history_create_failed.insert("jp", "タスク履歴の作成に失敗しました");
+    map.insert(
+        ErrorKey::TaskHistoryCreateFailed,
+        task_history_create_failed,
+    );
+
+    let mut task_history_get_by_task_id_failed = HashMap::new();
+    task_history_get_by_task_id_failed.insert("en", "Failed to get task history by task ID");
+    task_history_get_by_task_id_failed.insert("jp", "タスクIDによるタスク履歴の取得に失敗しました");
+    map.insert(
+        ErrorKey::TaskHistoryGetByTaskIdFailed,
+        task_history_get_by_task_id_failed,
+    );
+
+    let mut task_history_get_count_failed = HashMap::new();
+    task_history_get_count_failed.insert("en", "Failed to get task history count");
+    task_history_get_count_failed.insert("jp", "タスク履歴の件数の取得に失敗しました");
+    map.insert(
+        ErrorKey::TaskHistoryGetCountFailed,
+        task_history_get_count_failed,
+    );
+}
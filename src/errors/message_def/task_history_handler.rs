@@ -0,0 +1,32 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_task_history_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // タスク履歴ハンドラ関連のエラーメッセージ
+    let mut task_history_handler_invalid_path = HashMap::new();
+    task_history_handler_invalid_path.insert("en", "Invalid path");
+    task_history_handler_invalid_path.insert("jp", "パスが無効です");
+    map.insert(
+        ErrorKey::TaskHistoryHandlerInvalidPath,
+        task_history_handler_invalid_path,
+    );
+
+    let mut task_history_handler_invalid_query = HashMap::new();
+    task_history_handler_invalid_query.insert("en", "Invalid query");
+    task_history_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::TaskHistoryHandlerInvalidQuery,
+        task_history_handler_invalid_query,
+    );
+
+    let mut task_history_handler_invalid_page = HashMap::new();
+    task_history_handler_invalid_page.insert("en", "Invalid page or page size");
+    task_history_handler_invalid_page.insert("jp", "ページまたはページサイズが無効です");
+    map.insert(
+        ErrorKey::TaskHistoryHandlerInvalidPage,
+        task_history_handler_invalid_page,
+    );
+}
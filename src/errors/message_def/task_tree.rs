@@ -0,0 +1,27 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_task_tree_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // タスクツリー関連のエラーメッセージ
+    let mut task_tree_get_failed = HashMap::new();
+    task_tree_get_failed.insert("en", "Failed to get task tree");
+    task_tree_get_failed.insert("jp", "タスクツリーの取得に失敗しました");
+    map.insert(ErrorKey::TaskTreeGetFailed, task_tree_get_failed);
+
+    let mut task_tree_max_depth_invalid = HashMap::new();
+    task_tree_max_depth_invalid.insert(
+        "en",
+        "Max depth must be between 0 and the maximum task level",
+    );
+    task_tree_max_depth_invalid.insert(
+        "jp",
+        "最大の深さは0からタスクの最大レベルまでの値にしてください",
+    );
+    map.insert(
+        ErrorKey::TaskTreeMaxDepthInvalid,
+        task_tree_max_depth_invalid,
+    );
+}
@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_task_tree_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // タスクツリーハンドラ関連のエラーメッセージ
+    let mut task_tree_handler_invalid_path = HashMap::new();
+    task_tree_handler_invalid_path.insert("en", "Invalid path");
+    task_tree_handler_invalid_path.insert("jp", "パスが無効です");
+    map.insert(
+        ErrorKey::TaskTreeHandlerInvalidPath,
+        task_tree_handler_invalid_path,
+    );
+
+    let mut task_tree_handler_invalid_query = HashMap::new();
+    task_tree_handler_invalid_query.insert("en", "Invalid query");
+    task_tree_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::TaskTreeHandlerInvalidQuery,
+        task_tree_handler_invalid_query,
+    );
+}
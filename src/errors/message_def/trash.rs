@@ -0,0 +1,58 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_trash_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>) {
+    // ゴミ箱関連のエラーメッセージ
+    let mut trash_get_failed = HashMap::new();
+    trash_get_failed.insert("en", "Failed to get items in the trash");
+    trash_get_failed.insert("jp", "ゴミ箱の取得に失敗しました");
+    map.insert(ErrorKey::TrashGetFailed, trash_get_failed);
+
+    let mut trash_item_not_found = HashMap::new();
+    trash_item_not_found.insert("en", "The item is not in the trash");
+    trash_item_not_found.insert("jp", "ゴミ箱に対象が見つかりません");
+    map.insert(ErrorKey::TrashItemNotFound, trash_item_not_found);
+
+    let mut trash_entity_type_invalid = HashMap::new();
+    trash_entity_type_invalid.insert(
+        "en",
+        "Only projects, tasks and comments can be in the trash",
+    );
+    trash_entity_type_invalid.insert("jp", "ゴミ箱の対象はプロジェクト、タスク、コメントのみです");
+    map.insert(ErrorKey::TrashEntityTypeInvalid, trash_entity_type_invalid);
+
+    let mut trash_parent_deleted = HashMap::new();
+    trash_parent_deleted.insert(
+        "en",
+        "The parent of the item is in the trash. Restore the parent first",
+    );
+    trash_parent_deleted.insert(
+        "jp",
+        "親がゴミ箱にあるため復元できません。先に親を復元してください",
+    );
+    map.insert(ErrorKey::TrashParentDeleted, trash_parent_deleted);
+
+    let mut trash_move_failed = HashMap::new();
+    trash_move_failed.insert("en", "Failed to move the item to the trash");
+    trash_move_failed.insert("jp", "ゴミ箱への移動に失敗しました");
+    map.insert(ErrorKey::TrashMoveFailed, trash_move_failed);
+
+    let mut trash_restore_failed = HashMap::new();
+    trash_restore_failed.insert("en", "Failed to restore the item from the trash");
+    trash_restore_failed.insert("jp", "ゴミ箱からの復元に失敗しました");
+    map.insert(ErrorKey::TrashRestoreFailed, trash_restore_failed);
+
+    let mut trash_retention_days_invalid = HashMap::new();
+    trash_retention_days_invalid.insert("en", "Retention days must be 0 or greater");
+    trash_retention_days_invalid.insert("jp", "保持日数は0以上でなければなりません");
+    map.insert(
+        ErrorKey::TrashRetentionDaysInvalid,
+        trash_retention_days_invalid,
+    );
+
+    let mut trash_purge_failed = HashMap::new();
+    trash_purge_failed.insert("en", "Failed to purge the trash");
+    trash_purge_failed.insert("jp", "ゴミ箱の完全削除に失敗しました");
+    map.insert(ErrorKey::TrashPurgeFailed, trash_purge_failed);
+}
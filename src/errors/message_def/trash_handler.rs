@@ -0,0 +1,24 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_trash_handler_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // ゴミ箱ハンドラ関連のエラーメッセージ
+    let mut trash_handler_invalid_path = HashMap::new();
+    trash_handler_invalid_path.insert("en", "Invalid path");
+    trash_handler_invalid_path.insert("jp", "パスが無効です");
+    map.insert(
+        ErrorKey::TrashHandlerInvalidPath,
+        trash_handler_invalid_path,
+    );
+
+    let mut trash_handler_invalid_query = HashMap::new();
+    trash_handler_invalid_query.insert("en", "Invalid query");
+    trash_handler_invalid_query.insert("jp", "クエリが無効です");
+    map.insert(
+        ErrorKey::TrashHandlerInvalidQuery,
+        trash_handler_invalid_query,
+    );
+}
@@ -80,6 +80,17 @@ pub fn add_user_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str,
     user_create_failed.insert("jp", "DB操作処理の問題によりユーザーの作成に失敗しました");
     map.insert(ErrorKey::UserCreateFailed, user_create_failed);
 
+    let mut user_already_exists = HashMap::new();
+    user_already_exists.insert(
+        "en",
+        "A user with the same username or email already exists",
+    );
+    user_already_exists.insert(
+        "jp",
+        "同じユーザー名またはメールアドレスのユーザーが既に存在します",
+    );
+    map.insert(ErrorKey::UserAlreadyExists, user_already_exists);
+
     let mut user_get_by_id_failed = HashMap::new();
     user_get_by_id_failed.insert(
         "en",
@@ -159,4 +170,14 @@ pub fn add_user_error_messages(map: &mut HashMap<ErrorKey, HashMap<&'static str,
         ErrorKey::UserGetUsersPaginationNotFound,
         user_get_users_pagination_not_found,
     );
+
+    let mut user_get_admin_failed = HashMap::new();
+    user_get_admin_failed.insert("en", "Failed to get the administrator flag of the user");
+    user_get_admin_failed.insert("jp", "ユーザーの管理者フラグの取得に失敗しました");
+    map.insert(ErrorKey::UserGetAdminFailed, user_get_admin_failed);
+
+    let mut user_set_admin_failed = HashMap::new();
+    user_set_admin_failed.insert("en", "Failed to set the administrator flag of the user");
+    user_set_admin_failed.insert("jp", "ユーザーの管理者フラグの設定に失敗しました");
+    map.insert(ErrorKey::UserSetAdminFailed, user_set_admin_failed);
 }
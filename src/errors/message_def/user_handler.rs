@@ -87,4 +87,15 @@ pub fn add_user_handler_error_messages(
     user_handler_invalid_path.insert("en", "Invalid path");
     user_handler_invalid_path.insert("jp", "パスが無効です");
     map.insert(ErrorKey::UserHandlerInvalidPath, user_handler_invalid_path);
+
+    let mut user_handler_permission_denied = HashMap::new();
+    user_handler_permission_denied.insert(
+        "en",
+        "Only the user or an administrator can change the user",
+    );
+    user_handler_permission_denied.insert("jp", "ユーザー本人または管理者のみ変更できます");
+    map.insert(
+        ErrorKey::UserHandlerPermissionDenied,
+        user_handler_permission_denied,
+    );
 }
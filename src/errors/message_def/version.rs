@@ -0,0 +1,26 @@
+use std::collections::HashMap;
+
+use crate::errors::messages::ErrorKey;
+
+pub fn add_version_error_messages(
+    map: &mut HashMap<ErrorKey, HashMap<&'static str, &'static str>>,
+) {
+    // バージョン（楽観的排他制御）のエラーメッセージ
+    let mut version_get_failed = HashMap::new();
+    version_get_failed.insert("en", "Failed to get the resource version");
+    version_get_failed.insert("jp", "リソースのバージョンの取得に失敗しました");
+    map.insert(ErrorKey::VersionGetFailed, version_get_failed);
+
+    let mut version_update_failed = HashMap::new();
+    version_update_failed.insert("en", "Failed to update the resource version");
+    version_update_failed.insert("jp", "リソースのバージョンの更新に失敗しました");
+    map.insert(ErrorKey::VersionUpdateFailed, version_update_failed);
+
+    let mut version_mismatch = HashMap::new();
+    version_mismatch.insert(
+        "en",
+        "The resource has been modified since it was retrieved",
+    );
+    version_mismatch.insert("jp", "取得後にリソースが更新されています");
+    map.insert(ErrorKey::VersionMismatch, version_mismatch);
+}
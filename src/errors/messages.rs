@@ -1,16 +1,45 @@
+use crate::errors::catalog::lookup_message;
+use crate::errors::message_def::admin_command::add_admin_command_error_messages;
+use crate::errors::message_def::api_token::add_api_token_error_messages;
+use crate::errors::message_def::api_token_handler::add_api_token_handler_error_messages;
+use crate::errors::message_def::audit_log::add_audit_log_error_messages;
+use crate::errors::message_def::audit_log_handler::add_audit_log_handler_error_messages;
+use crate::errors::message_def::auth_handler::add_auth_handler_error_messages;
+use crate::errors::message_def::batch::add_batch_error_messages;
 use crate::errors::message_def::comment::add_comment_error_messages;
 use crate::errors::message_def::comment_handler::add_comment_handler_error_messages;
+use crate::errors::message_def::config::add_config_error_messages;
+use crate::errors::message_def::database::add_database_error_messages;
+use crate::errors::message_def::delete_policy::add_delete_policy_error_messages;
+use crate::errors::message_def::health::add_health_error_messages;
+use crate::errors::message_def::integrity::add_integrity_error_messages;
+use crate::errors::message_def::integrity_handler::add_integrity_handler_error_messages;
+use crate::errors::message_def::message_catalog::add_message_catalog_error_messages;
+use crate::errors::message_def::migration::add_migration_error_messages;
+use crate::errors::message_def::password::add_password_error_messages;
 use crate::errors::message_def::project::add_project_error_messages;
 use crate::errors::message_def::project_handler::add_project_handler_error_messages;
+use crate::errors::message_def::project_member::add_project_member_error_messages;
+use crate::errors::message_def::project_member_handler::add_project_member_handler_error_messages;
 use crate::errors::message_def::repository::add_repository_error_messages;
+use crate::errors::message_def::session::add_session_error_messages;
 use crate::errors::message_def::task::add_task_error_messages;
 use crate::errors::message_def::task_handler::add_task_handler_error_messages;
+use crate::errors::message_def::task_history::add_task_history_error_messages;
+use crate::errors::message_def::task_history_handler::add_task_history_handler_error_messages;
+use crate::errors::message_def::task_tree::add_task_tree_error_messages;
+use crate::errors::message_def::task_tree_handler::add_task_tree_handler_error_messages;
 use crate::errors::message_def::task_user::add_task_user_error_messages;
+use crate::errors::message_def::trash::add_trash_error_messages;
+use crate::errors::message_def::trash_handler::add_trash_handler_error_messages;
 use crate::errors::message_def::user::add_user_error_messages;
 use crate::errors::message_def::user_assign::add_user_assign_error_messages;
 use crate::errors::message_def::user_assign_handler::add_user_assign_handler_error_messages;
 use crate::errors::message_def::user_handler::add_user_handler_error_messages;
+use crate::errors::message_def::version::add_version_error_messages;
+use crate::settings::server_config;
 use once_cell::sync::Lazy;
+use serde_json::{Map, Value, json};
 use std::collections::HashMap;
 use std::fmt;
 
@@ -28,6 +57,7 @@ pub enum ErrorKey {
     UserPasswordEmpty,
     UserPasswordInvalid,
     UserCreateFailed,
+    UserAlreadyExists,
     UserGetByIdFailed,
     UserGetByNameFailed,
     UserGetAllFailed,
@@ -38,6 +68,8 @@ pub enum ErrorKey {
     UserGetByIdNotFound,
     UserGetByNameNotFound,
     UserGetUsersPaginationNotFound,
+    UserGetAdminFailed,
+    UserSetAdminFailed,
 
     // プロジェクト関連のエラー
     ProjectIdInvalid,
@@ -45,6 +77,7 @@ pub enum ErrorKey {
     ProjectNameEmpty,
     ProjectNameTooLong,
     ProjectCreateFailed,
+    ProjectAlreadyExists,
     ProjectGetByIdFailed,
     ProjectGetByNameFailed,
     ProjectGetAllFailed,
@@ -68,6 +101,7 @@ pub enum ErrorKey {
     TaskTimestampInvalid,
     TaskTimestampOrNoneInvalid,
     TaskProjectIdNotFound,
+    TaskProjectIdChanged,
     TaskNoParentIdOnNonMajorTask,
     TaskParentIdNotFound,
     TaskParentLevelInvalid,
@@ -151,6 +185,7 @@ pub enum ErrorKey {
     UserHandlerInvalidJsonPost,
     UserHandlerInvalidQuery,
     UserHandlerInvalidPath,
+    UserHandlerPermissionDenied,
 
     // プロジェクトハンドラ関連のエラー
     ProjectHandlerGetProjectsInvalidPage,
@@ -193,6 +228,197 @@ pub enum ErrorKey {
     CommentHandlerPathAndBodyIdMismatch,
     CommentHandlerInvalidQuery,
     CommentHandlerInvalidPath,
+    CommentHandlerUserMismatch,
+
+    // セッション関連のエラー
+    SessionIdMustBeNone,
+    SessionUserIdInvalid,
+    SessionTokenHashEmpty,
+    SessionExpiresAtInvalid,
+    SessionCreateFailed,
+    SessionGetByTokenHashFailed,
+    SessionGetByTokenHashNotFound,
+    SessionRevokeFailed,
+    SessionRevokeFailedByTokenHashNotFound,
+
+    // 認証ハンドラ関連のエラー
+    AuthHandlerInvalidJsonPost,
+    AuthHandlerInvalidQuery,
+    AuthHandlerInvalidCredentials,
+    AuthHandlerNoToken,
+    AuthHandlerInvalidToken,
+    AuthHandlerPoolNotConfigured,
+    AuthHandlerAdminRequired,
+
+    // パスワード関連のエラー
+    PasswordHashFailed,
+    PasswordHashParamsInvalid,
+    PasswordHashFormatInvalid,
+    PasswordVerifyFailed,
+    PasswordRehashFailed,
+
+    // プロジェクトメンバー関連のエラー
+    ProjectMemberIdMustBeNone,
+    ProjectMemberProjectIdInvalid,
+    ProjectMemberUserIdInvalid,
+    ProjectRoleInvalid,
+    ProjectMemberCreateFailed,
+    ProjectMemberAlreadyExists,
+    ProjectMemberProjectIdNotFound,
+    ProjectMemberGetByProjectIdFailed,
+    ProjectMemberGetByProjectIdAndUserIdFailed,
+    ProjectMemberGetByProjectIdAndUserIdNotFound,
+    ProjectMemberUpdateFailed,
+    ProjectMemberDeleteFailed,
+    ProjectMemberLastOwner,
+
+    // プロジェクトメンバーハンドラ関連のエラー
+    ProjectMemberHandlerInvalidJsonPost,
+    ProjectMemberHandlerInvalidPath,
+    ProjectMemberHandlerPathAndBodyIdMismatch,
+    ProjectMemberHandlerPermissionDenied,
+
+    // APIトークン関連のエラー
+    ApiTokenIdMustBeNone,
+    ApiTokenIdInvalid,
+    ApiTokenUserIdInvalid,
+    ApiTokenNameEmpty,
+    ApiTokenNameTooLong,
+    ApiTokenTokenHashEmpty,
+    ApiTokenExpiresAtInvalid,
+    ApiTokenCreateFailed,
+    ApiTokenGetByUserIdFailed,
+    ApiTokenUseFailed,
+    ApiTokenUseFailedByTokenHashNotFound,
+    ApiTokenRevokeFailed,
+    ApiTokenRevokeFailedByIdNotFound,
+
+    // APIトークンハンドラ関連のエラー
+    ApiTokenHandlerInvalidJsonPost,
+    ApiTokenHandlerInvalidPath,
+    ApiTokenHandlerNotAuthenticated,
+
+    // 監査ログ関連のエラー
+    AuditActionInvalid,
+    AuditEntityTypeInvalid,
+    AuditLogEntityIdInvalid,
+    AuditLogActorUserIdInvalid,
+    AuditLogCreateFailed,
+    AuditLogGetByFilterFailed,
+    AuditLogGetCountFailed,
+
+    // 監査ログハンドラ関連のエラー
+    AuditLogHandlerInvalidQuery,
+    AuditLogHandlerInvalidPage,
+
+    // タスク履歴関連のエラー
+    TaskHistoryTaskIdInvalid,
+    TaskHistoryCreateFailed,
+    TaskHistoryGetByTaskIdFailed,
+    TaskHistoryGetCountFailed,
+
+    // タスク履歴ハンドラ関連のエラー
+    TaskHistoryHandlerInvalidPath,
+    TaskHistoryHandlerInvalidQuery,
+    TaskHistoryHandlerInvalidPage,
+
+    // 設定関連のエラー
+    ConfigLoadFailed,
+    ConfigFileNotFound,
+    ConfigAlreadyInitialized,
+    ConfigBindAddressInvalid,
+    ConfigDatabaseUrlMissing,
+    ConfigMaxConnectionsInvalid,
+    ConfigLogDestinationInvalid,
+    ConfigLogLevelInvalid,
+    ConfigLogFormatInvalid,
+    ConfigLogRotationInvalid,
+    ConfigLanguageInvalid,
+    ConfigMaxPageSizeInvalid,
+    ConfigTrashRetentionDaysInvalid,
+    ConfigPasswordHashInvalid,
+    LoggerInitFailed,
+
+    // マイグレーション関連のエラー
+    MigrationStatusFailed,
+    MigrationRunFailed,
+    MigrationRevertFailed,
+    MigrationTargetInvalid,
+    MigrationDirty,
+
+    // データベース検査関連のエラー
+    DatabaseIntegrityCheckFailed,
+    DatabaseForeignKeyCheckFailed,
+
+    // 管理コマンド関連のエラー
+    AdminCommandPasswordReadFailed,
+    AdminCommandTaskParentProjectMismatch,
+    AdminCommandTaskHasSubtasks,
+    AdminCommandDbCheckFailed,
+
+    // ヘルスチェック関連のエラー
+    HealthDatabasePingFailed,
+    HealthDatabaseSizeFailed,
+    HealthCheckTimeout,
+    HealthMigrationVersionMismatch,
+    HealthNotReady,
+
+    // メッセージカタログ関連のエラー
+    MessageCatalogDirReadFailed,
+    MessageCatalogFileReadFailed,
+    MessageCatalogParseFailed,
+    MessageCatalogUnknownKeys,
+    MessageCatalogMissingKeys,
+    MessageCatalogSignalFailed,
+
+    // バージョン（楽観的排他制御）のエラー
+    VersionGetFailed,
+    VersionUpdateFailed,
+    VersionMismatch,
+
+    // バッチ操作のエラー
+    BatchOperationsEmpty,
+    BatchOperationsTooMany,
+    BatchTempIdDuplicate,
+    BatchTempIdNotFound,
+    BatchParentIdConflict,
+    BatchCommitFailed,
+
+    // ゴミ箱関連のエラー
+    TrashGetFailed,
+    TrashItemNotFound,
+    TrashEntityTypeInvalid,
+    TrashParentDeleted,
+    TrashMoveFailed,
+    TrashRestoreFailed,
+    TrashRetentionDaysInvalid,
+    TrashPurgeFailed,
+
+    // ゴミ箱ハンドラ関連のエラー
+    TrashHandlerInvalidPath,
+    TrashHandlerInvalidQuery,
+
+    // 削除ポリシー関連のエラー
+    DeletePolicyInvalid,
+    DeletePolicyNotSupported,
+    DeletePolicyRestricted,
+    DeletePolicyReparentLevelConflict,
+    DeletePolicyDependentsFailed,
+
+    // データ整合性チェック関連のエラー
+    IntegrityCheckFailed,
+    IntegrityRepairFailed,
+
+    // データ整合性ハンドラ関連のエラー
+    IntegrityHandlerInvalidQuery,
+
+    // タスクツリー関連のエラー
+    TaskTreeGetFailed,
+    TaskTreeMaxDepthInvalid,
+
+    // タスクツリーハンドラ関連のエラー
+    TaskTreeHandlerInvalidPath,
+    TaskTreeHandlerInvalidQuery,
 }
 
 impl fmt::Display for ErrorKey {
@@ -212,6 +438,7 @@ impl fmt::Display for ErrorKey {
             ErrorKey::UserPasswordEmpty => write!(f, "UserPasswordEmpty"),
             ErrorKey::UserPasswordInvalid => write!(f, "UserPasswordInvalid"),
             ErrorKey::UserCreateFailed => write!(f, "UserCreateFailed"),
+            ErrorKey::UserAlreadyExists => write!(f, "UserAlreadyExists"),
             ErrorKey::UserGetByIdFailed => write!(f, "UserGetByIdFailed"),
             ErrorKey::UserGetByNameFailed => write!(f, "UserGetByNameFailed"),
             ErrorKey::UserGetAllFailed => write!(f, "UserGetAllFailed"),
@@ -222,6 +449,8 @@ impl fmt::Display for ErrorKey {
             ErrorKey::UserGetByIdNotFound => write!(f, "UserGetByIdNotFound"),
             ErrorKey::UserGetByNameNotFound => write!(f, "UserGetByNameNotFound"),
             ErrorKey::UserGetUsersPaginationNotFound => write!(f, "UserGetUsersPaginationNotFound"),
+            ErrorKey::UserGetAdminFailed => write!(f, "UserGetAdminFailed"),
+            ErrorKey::UserSetAdminFailed => write!(f, "UserSetAdminFailed"),
 
             // プロジェクト関連のエラー
             ErrorKey::ProjectIdInvalid => write!(f, "ProjectIdInvalid"),
@@ -231,6 +460,7 @@ impl fmt::Display for ErrorKey {
             ErrorKey::ProjectNameEmpty => write!(f, "ProjectNameEmpty"),
             ErrorKey::ProjectNameTooLong => write!(f, "ProjectNameTooLong"),
             ErrorKey::ProjectCreateFailed => write!(f, "ProjectCreateFailed"),
+            ErrorKey::ProjectAlreadyExists => write!(f, "ProjectAlreadyExists"),
             ErrorKey::ProjectGetByIdFailed => write!(f, "ProjectGetByIdFailed"),
             ErrorKey::ProjectGetByNameFailed => write!(f, "ProjectGetByNameFailed"),
             ErrorKey::ProjectGetAllFailed => write!(f, "ProjectGetAllFailed"),
@@ -254,6 +484,7 @@ impl fmt::Display for ErrorKey {
             ErrorKey::TaskTimestampInvalid => write!(f, "TaskTimestampInvalid"),
             ErrorKey::TaskTimestampOrNoneInvalid => write!(f, "TaskTimestampOrNoneInvalid"),
             ErrorKey::TaskProjectIdNotFound => write!(f, "TaskProjectIdNotFound"),
+            ErrorKey::TaskProjectIdChanged => write!(f, "TaskProjectIdChanged"),
             ErrorKey::TaskNoParentIdOnNonMajorTask => write!(f, "TaskNoParentIdOnNonMajorTask"),
             ErrorKey::TaskParentIdNotFound => write!(f, "TaskParentIdNotFound"),
             ErrorKey::TaskParentLevelInvalid => write!(f, "TaskParentLevelInvalid"),
@@ -361,6 +592,7 @@ impl fmt::Display for ErrorKey {
             ErrorKey::UserHandlerInvalidJsonPost => write!(f, "UserHandlerInvalidJsonPost"),
             ErrorKey::UserHandlerInvalidQuery => write!(f, "UserHandlerInvalidQuery"),
             ErrorKey::UserHandlerInvalidPath => write!(f, "UserHandlerInvalidPath"),
+            ErrorKey::UserHandlerPermissionDenied => write!(f, "UserHandlerPermissionDenied"),
 
             // プロジェクトハンドラ関連のエラー
             ErrorKey::ProjectHandlerGetProjectsInvalidPage => {
@@ -447,16 +679,230 @@ impl fmt::Display for ErrorKey {
             }
             ErrorKey::CommentHandlerInvalidQuery => write!(f, "CommentHandlerInvalidQuery"),
             ErrorKey::CommentHandlerInvalidPath => write!(f, "CommentHandlerInvalidPath"),
+            ErrorKey::CommentHandlerUserMismatch => write!(f, "CommentHandlerUserMismatch"),
+
+            // セッション関連のエラー
+            ErrorKey::SessionIdMustBeNone => write!(f, "SessionIdMustBeNone"),
+            ErrorKey::SessionUserIdInvalid => write!(f, "SessionUserIdInvalid"),
+            ErrorKey::SessionTokenHashEmpty => write!(f, "SessionTokenHashEmpty"),
+            ErrorKey::SessionExpiresAtInvalid => write!(f, "SessionExpiresAtInvalid"),
+            ErrorKey::SessionCreateFailed => write!(f, "SessionCreateFailed"),
+            ErrorKey::SessionGetByTokenHashFailed => write!(f, "SessionGetByTokenHashFailed"),
+            ErrorKey::SessionGetByTokenHashNotFound => write!(f, "SessionGetByTokenHashNotFound"),
+            ErrorKey::SessionRevokeFailed => write!(f, "SessionRevokeFailed"),
+            ErrorKey::SessionRevokeFailedByTokenHashNotFound => {
+                write!(f, "SessionRevokeFailedByTokenHashNotFound")
+            }
+
+            // 認証ハンドラ関連のエラー
+            ErrorKey::AuthHandlerInvalidJsonPost => write!(f, "AuthHandlerInvalidJsonPost"),
+            ErrorKey::AuthHandlerInvalidQuery => write!(f, "AuthHandlerInvalidQuery"),
+            ErrorKey::AuthHandlerInvalidCredentials => write!(f, "AuthHandlerInvalidCredentials"),
+            ErrorKey::AuthHandlerNoToken => write!(f, "AuthHandlerNoToken"),
+            ErrorKey::AuthHandlerInvalidToken => write!(f, "AuthHandlerInvalidToken"),
+            ErrorKey::AuthHandlerPoolNotConfigured => write!(f, "AuthHandlerPoolNotConfigured"),
+            ErrorKey::AuthHandlerAdminRequired => write!(f, "AuthHandlerAdminRequired"),
+
+            // パスワード関連のエラー
+            ErrorKey::PasswordHashFailed => write!(f, "PasswordHashFailed"),
+            ErrorKey::PasswordHashParamsInvalid => write!(f, "PasswordHashParamsInvalid"),
+            ErrorKey::PasswordHashFormatInvalid => write!(f, "PasswordHashFormatInvalid"),
+            ErrorKey::PasswordVerifyFailed => write!(f, "PasswordVerifyFailed"),
+            ErrorKey::PasswordRehashFailed => write!(f, "PasswordRehashFailed"),
+
+            // プロジェクトメンバー関連のエラー
+            ErrorKey::ProjectMemberIdMustBeNone => write!(f, "ProjectMemberIdMustBeNone"),
+            ErrorKey::ProjectMemberProjectIdInvalid => write!(f, "ProjectMemberProjectIdInvalid"),
+            ErrorKey::ProjectMemberUserIdInvalid => write!(f, "ProjectMemberUserIdInvalid"),
+            ErrorKey::ProjectRoleInvalid => write!(f, "ProjectRoleInvalid"),
+            ErrorKey::ProjectMemberCreateFailed => write!(f, "ProjectMemberCreateFailed"),
+            ErrorKey::ProjectMemberAlreadyExists => write!(f, "ProjectMemberAlreadyExists"),
+            ErrorKey::ProjectMemberProjectIdNotFound => write!(f, "ProjectMemberProjectIdNotFound"),
+            ErrorKey::ProjectMemberGetByProjectIdFailed => {
+                write!(f, "ProjectMemberGetByProjectIdFailed")
+            }
+            ErrorKey::ProjectMemberGetByProjectIdAndUserIdFailed => {
+                write!(f, "ProjectMemberGetByProjectIdAndUserIdFailed")
+            }
+            ErrorKey::ProjectMemberGetByProjectIdAndUserIdNotFound => {
+                write!(f, "ProjectMemberGetByProjectIdAndUserIdNotFound")
+            }
+            ErrorKey::ProjectMemberUpdateFailed => write!(f, "ProjectMemberUpdateFailed"),
+            ErrorKey::ProjectMemberDeleteFailed => write!(f, "ProjectMemberDeleteFailed"),
+            ErrorKey::ProjectMemberLastOwner => write!(f, "ProjectMemberLastOwner"),
+
+            // プロジェクトメンバーハンドラ関連のエラー
+            ErrorKey::ProjectMemberHandlerInvalidJsonPost => {
+                write!(f, "ProjectMemberHandlerInvalidJsonPost")
+            }
+            ErrorKey::ProjectMemberHandlerInvalidPath => {
+                write!(f, "ProjectMemberHandlerInvalidPath")
+            }
+            ErrorKey::ProjectMemberHandlerPathAndBodyIdMismatch => {
+                write!(f, "ProjectMemberHandlerPathAndBodyIdMismatch")
+            }
+            ErrorKey::ProjectMemberHandlerPermissionDenied => {
+                write!(f, "ProjectMemberHandlerPermissionDenied")
+            }
+
+            // APIトークン関連のエラー
+            ErrorKey::ApiTokenIdMustBeNone => write!(f, "ApiTokenIdMustBeNone"),
+            ErrorKey::ApiTokenIdInvalid => write!(f, "ApiTokenIdInvalid"),
+            ErrorKey::ApiTokenUserIdInvalid => write!(f, "ApiTokenUserIdInvalid"),
+            ErrorKey::ApiTokenNameEmpty => write!(f, "ApiTokenNameEmpty"),
+            ErrorKey::ApiTokenNameTooLong => write!(f, "ApiTokenNameTooLong"),
+            ErrorKey::ApiTokenTokenHashEmpty => write!(f, "ApiTokenTokenHashEmpty"),
+            ErrorKey::ApiTokenExpiresAtInvalid => write!(f, "ApiTokenExpiresAtInvalid"),
+            ErrorKey::ApiTokenCreateFailed => write!(f, "ApiTokenCreateFailed"),
+            ErrorKey::ApiTokenGetByUserIdFailed => write!(f, "ApiTokenGetByUserIdFailed"),
+            ErrorKey::ApiTokenUseFailed => write!(f, "ApiTokenUseFailed"),
+            ErrorKey::ApiTokenUseFailedByTokenHashNotFound => {
+                write!(f, "ApiTokenUseFailedByTokenHashNotFound")
+            }
+            ErrorKey::ApiTokenRevokeFailed => write!(f, "ApiTokenRevokeFailed"),
+            ErrorKey::ApiTokenRevokeFailedByIdNotFound => {
+                write!(f, "ApiTokenRevokeFailedByIdNotFound")
+            }
+
+            // APIトークンハンドラ関連のエラー
+            ErrorKey::ApiTokenHandlerInvalidJsonPost => write!(f, "ApiTokenHandlerInvalidJsonPost"),
+            ErrorKey::ApiTokenHandlerInvalidPath => write!(f, "ApiTokenHandlerInvalidPath"),
+            ErrorKey::ApiTokenHandlerNotAuthenticated => {
+                write!(f, "ApiTokenHandlerNotAuthenticated")
+            }
+
+            // 監査ログ関連のエラー
+            ErrorKey::AuditActionInvalid => write!(f, "AuditActionInvalid"),
+            ErrorKey::AuditEntityTypeInvalid => write!(f, "AuditEntityTypeInvalid"),
+            ErrorKey::AuditLogEntityIdInvalid => write!(f, "AuditLogEntityIdInvalid"),
+            ErrorKey::AuditLogActorUserIdInvalid => write!(f, "AuditLogActorUserIdInvalid"),
+            ErrorKey::AuditLogCreateFailed => write!(f, "AuditLogCreateFailed"),
+            ErrorKey::AuditLogGetByFilterFailed => write!(f, "AuditLogGetByFilterFailed"),
+            ErrorKey::AuditLogGetCountFailed => write!(f, "AuditLogGetCountFailed"),
+
+            // 監査ログハンドラ関連のエラー
+            ErrorKey::AuditLogHandlerInvalidQuery => write!(f, "AuditLogHandlerInvalidQuery"),
+            ErrorKey::AuditLogHandlerInvalidPage => write!(f, "AuditLogHandlerInvalidPage"),
+
+            // タスク履歴関連のエラー
+            ErrorKey::TaskHistoryTaskIdInvalid => write!(f, "TaskHistoryTaskIdInvalid"),
+            ErrorKey::TaskHistoryCreateFailed => write!(f, "TaskHistoryCreateFailed"),
+            ErrorKey::TaskHistoryGetByTaskIdFailed => write!(f, "TaskHistoryGetByTaskIdFailed"),
+            ErrorKey::TaskHistoryGetCountFailed => write!(f, "TaskHistoryGetCountFailed"),
+
+            // タスク履歴ハンドラ関連のエラー
+            ErrorKey::TaskHistoryHandlerInvalidPath => write!(f, "TaskHistoryHandlerInvalidPath"),
+            ErrorKey::TaskHistoryHandlerInvalidQuery => write!(f, "TaskHistoryHandlerInvalidQuery"),
+            ErrorKey::TaskHistoryHandlerInvalidPage => write!(f, "TaskHistoryHandlerInvalidPage"),
+
+            // 設定関連のエラー
+            ErrorKey::ConfigLoadFailed => write!(f, "ConfigLoadFailed"),
+            ErrorKey::ConfigFileNotFound => write!(f, "ConfigFileNotFound"),
+            ErrorKey::ConfigAlreadyInitialized => write!(f, "ConfigAlreadyInitialized"),
+            ErrorKey::ConfigBindAddressInvalid => write!(f, "ConfigBindAddressInvalid"),
+            ErrorKey::ConfigDatabaseUrlMissing => write!(f, "ConfigDatabaseUrlMissing"),
+            ErrorKey::ConfigMaxConnectionsInvalid => write!(f, "ConfigMaxConnectionsInvalid"),
+            ErrorKey::ConfigLogDestinationInvalid => write!(f, "ConfigLogDestinationInvalid"),
+            ErrorKey::ConfigLogLevelInvalid => write!(f, "ConfigLogLevelInvalid"),
+            ErrorKey::ConfigLogFormatInvalid => write!(f, "ConfigLogFormatInvalid"),
+            ErrorKey::ConfigLogRotationInvalid => write!(f, "ConfigLogRotationInvalid"),
+            ErrorKey::ConfigLanguageInvalid => write!(f, "ConfigLanguageInvalid"),
+            ErrorKey::ConfigMaxPageSizeInvalid => write!(f, "ConfigMaxPageSizeInvalid"),
+            ErrorKey::ConfigTrashRetentionDaysInvalid => {
+                write!(f, "ConfigTrashRetentionDaysInvalid")
+            }
+            ErrorKey::ConfigPasswordHashInvalid => write!(f, "ConfigPasswordHashInvalid"),
+            ErrorKey::LoggerInitFailed => write!(f, "LoggerInitFailed"),
+
+            // マイグレーション関連のエラー
+            ErrorKey::MigrationStatusFailed => write!(f, "MigrationStatusFailed"),
+            ErrorKey::MigrationRunFailed => write!(f, "MigrationRunFailed"),
+            ErrorKey::MigrationRevertFailed => write!(f, "MigrationRevertFailed"),
+            ErrorKey::MigrationTargetInvalid => write!(f, "MigrationTargetInvalid"),
+            ErrorKey::MigrationDirty => write!(f, "MigrationDirty"),
+
+            // データベース検査関連のエラー
+            ErrorKey::DatabaseIntegrityCheckFailed => write!(f, "DatabaseIntegrityCheckFailed"),
+            ErrorKey::DatabaseForeignKeyCheckFailed => write!(f, "DatabaseForeignKeyCheckFailed"),
+
+            // 管理コマンド関連のエラー
+            ErrorKey::AdminCommandPasswordReadFailed => write!(f, "AdminCommandPasswordReadFailed"),
+            ErrorKey::AdminCommandTaskParentProjectMismatch => {
+                write!(f, "AdminCommandTaskParentProjectMismatch")
+            }
+            ErrorKey::AdminCommandTaskHasSubtasks => write!(f, "AdminCommandTaskHasSubtasks"),
+            ErrorKey::AdminCommandDbCheckFailed => write!(f, "AdminCommandDbCheckFailed"),
+
+            // ヘルスチェック関連のエラー
+            ErrorKey::HealthDatabasePingFailed => write!(f, "HealthDatabasePingFailed"),
+            ErrorKey::HealthDatabaseSizeFailed => write!(f, "HealthDatabaseSizeFailed"),
+            ErrorKey::HealthCheckTimeout => write!(f, "HealthCheckTimeout"),
+            ErrorKey::HealthMigrationVersionMismatch => write!(f, "HealthMigrationVersionMismatch"),
+            ErrorKey::HealthNotReady => write!(f, "HealthNotReady"),
+
+            // メッセージカタログ関連のエラー
+            ErrorKey::MessageCatalogDirReadFailed => write!(f, "MessageCatalogDirReadFailed"),
+            ErrorKey::MessageCatalogFileReadFailed => write!(f, "MessageCatalogFileReadFailed"),
+            ErrorKey::MessageCatalogParseFailed => write!(f, "MessageCatalogParseFailed"),
+            ErrorKey::MessageCatalogUnknownKeys => write!(f, "MessageCatalogUnknownKeys"),
+            ErrorKey::MessageCatalogMissingKeys => write!(f, "MessageCatalogMissingKeys"),
+            ErrorKey::MessageCatalogSignalFailed => write!(f, "MessageCatalogSignalFailed"),
+
+            // バージョン（楽観的排他制御）のエラー
+            ErrorKey::VersionGetFailed => write!(f, "VersionGetFailed"),
+            ErrorKey::VersionUpdateFailed => write!(f, "VersionUpdateFailed"),
+            ErrorKey::VersionMismatch => write!(f, "VersionMismatch"),
+
+            // バッチ操作のエラー
+            ErrorKey::BatchOperationsEmpty => write!(f, "BatchOperationsEmpty"),
+            ErrorKey::BatchOperationsTooMany => write!(f, "BatchOperationsTooMany"),
+            ErrorKey::BatchTempIdDuplicate => write!(f, "BatchTempIdDuplicate"),
+            ErrorKey::BatchTempIdNotFound => write!(f, "BatchTempIdNotFound"),
+            ErrorKey::BatchParentIdConflict => write!(f, "BatchParentIdConflict"),
+            ErrorKey::BatchCommitFailed => write!(f, "BatchCommitFailed"),
+
+            // ゴミ箱関連のエラー
+            ErrorKey::TrashGetFailed => write!(f, "TrashGetFailed"),
+            ErrorKey::TrashItemNotFound => write!(f, "TrashItemNotFound"),
+            ErrorKey::TrashEntityTypeInvalid => write!(f, "TrashEntityTypeInvalid"),
+            ErrorKey::TrashParentDeleted => write!(f, "TrashParentDeleted"),
+            ErrorKey::TrashMoveFailed => write!(f, "TrashMoveFailed"),
+            ErrorKey::TrashRestoreFailed => write!(f, "TrashRestoreFailed"),
+            ErrorKey::TrashRetentionDaysInvalid => write!(f, "TrashRetentionDaysInvalid"),
+            ErrorKey::TrashPurgeFailed => write!(f, "TrashPurgeFailed"),
+
+            // ゴミ箱ハンドラ関連のエラー
+            ErrorKey::TrashHandlerInvalidPath => write!(f, "TrashHandlerInvalidPath"),
+            ErrorKey::TrashHandlerInvalidQuery => write!(f, "TrashHandlerInvalidQuery"),
+
+            // 削除ポリシー関連のエラー
+            ErrorKey::DeletePolicyInvalid => write!(f, "DeletePolicyInvalid"),
+            ErrorKey::DeletePolicyNotSupported => write!(f, "DeletePolicyNotSupported"),
+            ErrorKey::DeletePolicyRestricted => write!(f, "DeletePolicyRestricted"),
+            ErrorKey::DeletePolicyReparentLevelConflict => {
+                write!(f, "DeletePolicyReparentLevelConflict")
+            }
+            ErrorKey::DeletePolicyDependentsFailed => write!(f, "DeletePolicyDependentsFailed"),
+
+            // データ整合性チェック関連のエラー
+            ErrorKey::IntegrityCheckFailed => write!(f, "IntegrityCheckFailed"),
+            ErrorKey::IntegrityRepairFailed => write!(f, "IntegrityRepairFailed"),
+
+            // データ整合性ハンドラ関連のエラー
+            ErrorKey::IntegrityHandlerInvalidQuery => write!(f, "IntegrityHandlerInvalidQuery"),
+
+            // タスクツリー関連のエラー
+            ErrorKey::TaskTreeGetFailed => write!(f, "TaskTreeGetFailed"),
+            ErrorKey::TaskTreeMaxDepthInvalid => write!(f, "TaskTreeMaxDepthInvalid"),
+
+            // タスクツリーハンドラ関連のエラー
+            ErrorKey::TaskTreeHandlerInvalidPath => write!(f, "TaskTreeHandlerInvalidPath"),
+            ErrorKey::TaskTreeHandlerInvalidQuery => write!(f, "TaskTreeHandlerInvalidQuery"),
         }
     }
 }
 
-static GLOBAL_LANG: Lazy<&'static str> = Lazy::new(|| {
-    //[TODO] Configから取得するようにする
-    "en"
-});
-
-static ERROR_MESSAGES: Lazy<HashMap<ErrorKey, HashMap<&'static str, &'static str>>> =
+pub(crate) static ERROR_MESSAGES: Lazy<HashMap<ErrorKey, HashMap<&'static str, &'static str>>> =
     Lazy::new(|| {
         let mut map = HashMap::new();
 
@@ -472,28 +918,263 @@ static ERROR_MESSAGES: Lazy<HashMap<ErrorKey, HashMap<&'static str, &'static str
         add_task_user_error_messages(&mut map);
         add_repository_error_messages(&mut map);
         add_comment_handler_error_messages(&mut map);
+        add_session_error_messages(&mut map);
+        add_auth_handler_error_messages(&mut map);
+        add_password_error_messages(&mut map);
+        add_project_member_error_messages(&mut map);
+        add_project_member_handler_error_messages(&mut map);
+        add_api_token_error_messages(&mut map);
+        add_api_token_handler_error_messages(&mut map);
+        add_audit_log_error_messages(&mut map);
+        add_audit_log_handler_error_messages(&mut map);
+        add_task_history_error_messages(&mut map);
+        add_task_history_handler_error_messages(&mut map);
+        add_config_error_messages(&mut map);
+        add_migration_error_messages(&mut map);
+        add_database_error_messages(&mut map);
+        add_admin_command_error_messages(&mut map);
+        add_health_error_messages(&mut map);
+        add_message_catalog_error_messages(&mut map);
+        add_version_error_messages(&mut map);
+        add_batch_error_messages(&mut map);
+        add_trash_error_messages(&mut map);
+        add_trash_handler_error_messages(&mut map);
+        add_delete_policy_error_messages(&mut map);
+        add_integrity_error_messages(&mut map);
+        add_integrity_handler_error_messages(&mut map);
+        add_task_tree_error_messages(&mut map);
+        add_task_tree_handler_error_messages(&mut map);
 
         map
     });
 
-pub fn get_error_message(key: ErrorKey, error_info: String) -> String {
-    let lang = get_lang();
+// エラーの詳細。ErrorResponseのdetailsにそのまま使う
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum ErrorDetails {
+    #[default]
+    None,
+    // 元のエラーのメッセージなど、値に分けられない情報
+    Info(String),
+    Fields(Map<String, Value>),
+    // 原因となった別のエラー
+    Cause(Box<ErrorMessage>),
+}
 
-    let msg = ERROR_MESSAGES
-        .get(&key)
-        .and_then(|messages| messages.get(lang))
-        .unwrap_or_else(|| {
-            // デフォルトは英語
-            ERROR_MESSAGES
-                .get(&key)
-                .and_then(|messages| messages.get("en"))
-                .unwrap_or(&"Unknown error")
-        });
+impl ErrorDetails {
+    pub fn to_value(&self) -> Option<Value> {
+        match self {
+            ErrorDetails::None => None,
+            ErrorDetails::Info(info) => Some(json!({ "info": info })),
+            ErrorDetails::Fields(fields) => Some(Value::Object(fields.clone())),
+            ErrorDetails::Cause(cause) => {
+                let mut value = json!({ "code": cause.code(), "message": cause.message });
+                if let Some(details) = cause.details.to_value() {
+                    value["details"] = details;
+                }
+                Some(json!({ "cause": value }))
+            }
+        }
+    }
 
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        match self {
+            ErrorDetails::Fields(fields) => fields.get(name),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ErrorDetails {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ErrorDetails::None => Ok(()),
+            ErrorDetails::Info(info) => write!(f, "{}", info),
+            ErrorDetails::Fields(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    // 文字列は引用符を付けずに表示する
+                    .map(|(name, value)| match value {
+                        Value::String(value) => format!("{} = {}", name, value),
+                        value => format!("{} = {}", name, value),
+                    })
+                    .collect();
+                write!(f, "{}", fields.join(", "))
+            }
+            ErrorDetails::Cause(cause) => write!(f, "{}", cause),
+        }
+    }
+}
+
+// 空の場合は詳細なし
+impl From<String> for ErrorDetails {
+    fn from(info: String) -> Self {
+        match info.is_empty() {
+            true => ErrorDetails::None,
+            false => ErrorDetails::Info(info),
+        }
+    }
+}
+
+impl<const N: usize> From<[(&str, Value); N]> for ErrorDetails {
+    fn from(fields: [(&str, Value); N]) -> Self {
+        ErrorDetails::Fields(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        )
+    }
+}
+
+impl From<ErrorMessage> for ErrorDetails {
+    fn from(cause: ErrorMessage) -> Self {
+        ErrorDetails::Cause(Box::new(cause))
+    }
+}
+
+// エラーのキー、言語に応じたメッセージと詳細。表示は "[Key] message: (details)"
+// キーのないエラー（例: actix-webのエラー）はメッセージのみ
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorMessage {
+    pub key: Option<ErrorKey>,
+    pub message: String,
+    pub details: ErrorDetails,
+}
+
+impl ErrorMessage {
+    // ErrorKeyの表示名
+    pub fn code(&self) -> Option<String> {
+        self.key.map(|key| key.to_string())
+    }
+}
+
+impl fmt::Display for ErrorMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.key {
+            Some(key) => write!(f, "[{}] {}: ({})", key, self.message, self.details),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ErrorMessage {}
+
+impl From<String> for ErrorMessage {
+    fn from(message: String) -> Self {
+        Self {
+            key: None,
+            message,
+            details: ErrorDetails::None,
+        }
+    }
+}
+
+// get_error_messageのエラーはキーと詳細をそのまま使い、それ以外はメッセージのみにする
+impl From<anyhow::Error> for ErrorMessage {
+    fn from(error: anyhow::Error) -> Self {
+        match error.downcast::<ErrorMessage>() {
+            Ok(message) => message,
+            Err(error) => error.to_string().into(),
+        }
+    }
+}
+
+pub fn get_error_message(key: ErrorKey, details: impl Into<ErrorDetails>) -> ErrorMessage {
+    let lang = get_lang();
     let error_code = key.to_string();
-    format!("[{}] {}: ({})", error_code, msg, error_info)
+    let builtin = |lang: &str| {
+        ERROR_MESSAGES
+            .get(&key)
+            .and_then(|messages| messages.get(lang))
+            .map(|message| message.to_string())
+    };
+
+    // カタログのメッセージを組み込みのメッセージより優先する
+    let message = lookup_message(lang, &error_code)
+        .or_else(|| builtin(lang))
+        // デフォルトは英語
+        .or_else(|| lookup_message("en", &error_code))
+        .or_else(|| builtin("en"))
+        .unwrap_or_else(|| "Unknown error".to_string());
+
+    ErrorMessage {
+        key: Some(key),
+        message,
+        details: details.into(),
+    }
+}
+
+tokio::task_local! {
+    // リクエストごとに決定した言語（LanguageMiddlewareが設定する）
+    pub static REQUEST_LANG: &'static str;
 }
 
+// リクエストの処理中はその言語、それ以外（CLIなど）は設定のデフォルト言語
 pub fn get_lang() -> &'static str {
-    GLOBAL_LANG.as_ref()
+    REQUEST_LANG
+        .try_with(|lang| *lang)
+        .unwrap_or_else(|_| server_config().default_language.as_str())
+}
+
+impl ErrorKey {
+    // 入力値の検証エラーの原因となったリクエストのフィールド
+    pub fn field(&self) -> Option<&'static str> {
+        match self {
+            ErrorKey::UserIdInvalid | ErrorKey::UserIdMustBeNone => Some("user_id"),
+            ErrorKey::UserNameEmpty
+            | ErrorKey::UserNameTooLong
+            | ErrorKey::UserNameContainsInvalidCharacters => Some("username"),
+            ErrorKey::UserEmailEmpty | ErrorKey::UserEmailTooLong | ErrorKey::UserEmailInvalid => {
+                Some("email")
+            }
+            ErrorKey::UserPasswordEmpty | ErrorKey::UserPasswordInvalid => Some("password"),
+            ErrorKey::ProjectIdInvalid => Some("project_id"),
+            ErrorKey::ProjectNameEmpty | ErrorKey::ProjectNameTooLong => Some("name"),
+            ErrorKey::ProjectAlreadyExists => Some("name"),
+            ErrorKey::TaskIdInvalid | ErrorKey::TaskIdMustBeNone => Some("task_id"),
+            ErrorKey::TaskProjectIdInvalid
+            | ErrorKey::TaskProjectIdNotFound
+            | ErrorKey::TaskProjectIdChanged => Some("project_id"),
+            ErrorKey::TaskNoParentIdOnNonMajorTask
+            | ErrorKey::TaskParentIdCannotBeSameAsTaskId
+            | ErrorKey::TaskParentIdInvalid
+            | ErrorKey::TaskParentLevelInvalid => Some("parent_id"),
+            ErrorKey::TaskLevelInvalid => Some("level"),
+            ErrorKey::TaskNameEmpty | ErrorKey::TaskNameTooLong => Some("name"),
+            ErrorKey::TaskDescriptionTooLong => Some("description"),
+            ErrorKey::TaskStatusInvalid => Some("status"),
+            ErrorKey::UserAssignIdInvalid | ErrorKey::UserAssignIdMustBeNone => {
+                Some("user_assign_id")
+            }
+            ErrorKey::UserAssignTaskIdInvalid | ErrorKey::UserAssignToNotMaxLevelTask => {
+                Some("task_id")
+            }
+            ErrorKey::UserAssignUserIdInvalid | ErrorKey::UserAssignSameUserAssignExists => {
+                Some("user_id")
+            }
+            ErrorKey::CommentIdInvalid | ErrorKey::CommentIdMustBeNone => Some("comment_id"),
+            ErrorKey::CommentTaskIdInvalid | ErrorKey::CommentToNotMaxLevelTask => Some("task_id"),
+            ErrorKey::CommentUserIdInvalid => Some("user_id"),
+            ErrorKey::CommentContentEmpty | ErrorKey::CommentContentTooLong => Some("content"),
+            ErrorKey::ProjectMemberIdMustBeNone => Some("project_member_id"),
+            ErrorKey::ProjectMemberProjectIdInvalid => Some("project_id"),
+            ErrorKey::ProjectMemberUserIdInvalid | ErrorKey::ProjectMemberAlreadyExists => {
+                Some("user_id")
+            }
+            ErrorKey::ApiTokenNameEmpty | ErrorKey::ApiTokenNameTooLong => Some("name"),
+            ErrorKey::ApiTokenExpiresAtInvalid => Some("expires_at"),
+            ErrorKey::NoPageSpecified => Some("page"),
+            ErrorKey::NoPageSizeSpecified | ErrorKey::PageSizeTooLarge => Some("page_size"),
+            ErrorKey::BatchOperationsEmpty | ErrorKey::BatchOperationsTooMany => Some("operations"),
+            ErrorKey::BatchTempIdDuplicate => Some("temp_id"),
+            ErrorKey::BatchTempIdNotFound | ErrorKey::BatchParentIdConflict => {
+                Some("parent_temp_id")
+            }
+            ErrorKey::TrashEntityTypeInvalid => Some("entity_type"),
+            ErrorKey::TrashRetentionDaysInvalid => Some("older_than_days"),
+            ErrorKey::DeletePolicyInvalid | ErrorKey::DeletePolicyNotSupported => Some("policy"),
+            ErrorKey::TaskTreeMaxDepthInvalid => Some("max_depth"),
+            _ => None,
+        }
+    }
 }
@@ -1,6 +1,12 @@
+pub mod catalog;
+pub(crate) mod config_error;
 pub(crate) mod db_error;
 pub(crate) mod handler_errors;
 pub(crate) mod message_def;
 pub(crate) mod messages;
+#[cfg(test)]
+mod tests;
+
+pub use config_error::ConfigError;
 pub use db_error::DBAccessError;
 pub use handler_errors::HandlerError;
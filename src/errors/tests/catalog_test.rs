@@ -0,0 +1,160 @@
+use crate::errors::ConfigError;
+use crate::errors::catalog::{
+    MessageCatalog, available_languages, init_message_catalog, install_message_catalog,
+};
+use crate::errors::messages::{ErrorKey, REQUEST_LANG, get_error_message};
+use crate::settings::MessagesConfig;
+use std::path::{Path, PathBuf};
+
+#[cfg(test)]
+mod catalog_test {
+    use super::*;
+
+    #[ctor::ctor]
+    fn init() {
+        if Path::new("./test_db/catalog_test").exists() {
+            std::fs::remove_dir_all("./test_db/catalog_test").unwrap();
+        }
+        std::fs::create_dir_all("./test_db/catalog_test").unwrap();
+    }
+
+    fn write_catalog(dir: &str, name: &str, content: &str) -> PathBuf {
+        let dir = PathBuf::from(format!("./test_db/catalog_test/{}", dir));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join(name), content).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_load_catalog_formats() {
+        write_catalog(
+            "formats",
+            "en.json",
+            r#"{ "AuthHandlerNoToken": "Please log in" }"#,
+        );
+        write_catalog(
+            "formats",
+            "jp.yaml",
+            "AuthHandlerNoToken: ログインしてください\n",
+        );
+        let dir = write_catalog(
+            "formats",
+            "eo.json5",
+            r#"{
+                // コメントを書ける
+                AuthHandlerNoToken: "Bonvolu ensaluti",
+            }"#,
+        );
+        write_catalog("formats", "README.txt", "not a catalogue");
+
+        let catalog = MessageCatalog::load(&dir).unwrap();
+        assert_eq!(
+            catalog.languages().collect::<Vec<_>>(),
+            vec!["en", "eo", "jp"]
+        );
+        assert_eq!(
+            catalog.get("en", "AuthHandlerNoToken"),
+            Some("Please log in")
+        );
+        assert_eq!(
+            catalog.get("jp", "AuthHandlerNoToken"),
+            Some("ログインしてください")
+        );
+        assert_eq!(
+            catalog.get("eo", "AuthHandlerNoToken"),
+            Some("Bonvolu ensaluti")
+        );
+        assert_eq!(catalog.get("eo", "AuthHandlerInvalidToken"), None);
+    }
+
+    #[test]
+    fn test_check_catalog() {
+        write_catalog(
+            "check",
+            "en.json",
+            r#"{ "AuthHandlerNoToken": "Please log in", "AuthHandlerNoTokn": "typo" }"#,
+        );
+        let dir = write_catalog(
+            "check",
+            "eo.json",
+            r#"{ "AuthHandlerNoToken": "Bonvolu ensaluti" }"#,
+        );
+
+        let reports = MessageCatalog::load(&dir).unwrap().check();
+
+        // 組み込みのメッセージがあるen/jpは不足しない
+        assert_eq!(reports.len(), 2);
+        assert!(reports[0].contains("MessageCatalogUnknownKeys"));
+        assert!(reports[0].contains("lang = en, keys = AuthHandlerNoTokn"));
+        assert!(reports[1].contains("MessageCatalogMissingKeys"));
+        assert!(reports[1].contains("lang = eo"));
+        assert!(reports[1].contains("AuthHandlerInvalidToken"));
+        assert!(!reports[1].contains("AuthHandlerNoToken,"));
+
+        assert!(MessageCatalog::default().check().is_empty());
+    }
+
+    #[test]
+    fn test_load_catalog_errors() {
+        let result = MessageCatalog::load(Path::new("./test_db/catalog_test/not_found"));
+        assert!(
+            matches!(result, Err(ConfigError::LoadError(e)) if e.contains("MessageCatalogDirReadFailed"))
+        );
+
+        let dir = write_catalog("broken", "en.json", r#"{ "AuthHandlerNoToken": "#);
+        let result = MessageCatalog::load(&dir);
+        assert!(
+            matches!(result, Err(ConfigError::LoadError(e)) if e.contains("MessageCatalogParseFailed") && e.contains("en.json"))
+        );
+
+        let dir = write_catalog("wrong_type", "en.yaml", "- a\n- b\n");
+        let result = MessageCatalog::load(&dir);
+        assert!(
+            matches!(result, Err(ConfigError::LoadError(e)) if e.contains("MessageCatalogParseFailed"))
+        );
+    }
+
+    #[test]
+    fn test_init_and_reload_catalog() {
+        let dir = write_catalog(
+            "reload",
+            "eo.json",
+            r#"{ "MessageCatalogSignalFailed": "Unua versio" }"#,
+        );
+        let config = MessagesConfig {
+            catalog_dir: Some(dir.display().to_string()),
+            ..MessagesConfig::default()
+        };
+        let message = || {
+            REQUEST_LANG.sync_scope("eo", || {
+                get_error_message(ErrorKey::MessageCatalogSignalFailed, "".to_string()).to_string()
+            })
+        };
+
+        init_message_catalog(&config).unwrap();
+        assert!(available_languages().contains(&"eo"));
+        assert_eq!(message(), "[MessageCatalogSignalFailed] Unua versio: ()");
+
+        // 再読み込みで変更が反映される
+        write_catalog(
+            "reload",
+            "eo.json",
+            r#"{ "MessageCatalogSignalFailed": "Dua versio" }"#,
+        );
+        init_message_catalog(&config).unwrap();
+        assert_eq!(message(), "[MessageCatalogSignalFailed] Dua versio: ()");
+
+        // 読み込みに失敗した場合は以前のカタログを使い続ける
+        write_catalog("reload", "eo.json", "{");
+        assert!(init_message_catalog(&config).is_err());
+        assert_eq!(message(), "[MessageCatalogSignalFailed] Dua versio: ()");
+
+        // カタログにない言語は英語の組み込みメッセージを使う
+        install_message_catalog(MessageCatalog::default());
+        assert!(!available_languages().contains(&"eo"));
+        assert_eq!(
+            message(),
+            "[MessageCatalogSignalFailed] Failed to register the catalogue reload signal: ()"
+        );
+    }
+}
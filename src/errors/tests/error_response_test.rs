@@ -0,0 +1,143 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorDetails, ErrorKey, get_error_message};
+use crate::models::{ErrorResponse, Project, ProjectPatch};
+use actix_web::error::JsonPayloadError;
+use serde_json::json;
+
+#[cfg(test)]
+mod error_response_test {
+    use super::*;
+
+    #[test]
+    fn test_error_message() {
+        let message = get_error_message(ErrorKey::UserAlreadyExists, [("field", json!("email"))]);
+        assert_eq!(message.key, Some(ErrorKey::UserAlreadyExists));
+        assert_eq!(message.code().as_deref(), Some("UserAlreadyExists"));
+        assert_eq!(
+            message.message,
+            "A user with the same username or email already exists"
+        );
+        assert_eq!(message.details.field("field"), Some(&json!("email")));
+        assert_eq!(
+            message.to_string(),
+            "[UserAlreadyExists] A user with the same username or email already exists: (field = email)"
+        );
+
+        // 空の情報は詳細なし
+        let message = get_error_message(ErrorKey::AuthHandlerNoToken, "".to_string());
+        assert_eq!(message.details, ErrorDetails::None);
+
+        // anyhowを経由してもキーと詳細が残る
+        let error = DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::TaskGetByIdNotFound,
+            [("id", json!(1))],
+        )));
+        let res = ErrorResponse::from_error(&HandlerError::from(error), None);
+        assert_eq!(res.code, "TaskGetByIdNotFound");
+        assert_eq!(res.rc, 1);
+        assert_eq!(res.details, Some(json!({ "id": 1 })));
+    }
+
+    #[test]
+    fn test_error_response_details() {
+        let error = HandlerError::from(DBAccessError::ConflictError(get_error_message(
+            ErrorKey::ProjectMemberAlreadyExists,
+            [("project_id", json!(1)), ("user_id", json!(2))],
+        )));
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(res.code, "ProjectMemberAlreadyExists");
+        assert_eq!(res.rc, 6);
+        assert_eq!(res.field.as_deref(), Some("user_id"));
+        assert_eq!(res.details, Some(json!({ "project_id": 1, "user_id": 2 })));
+
+        // 複数のカラムの場合はfieldを設定しない
+        let error = HandlerError::Conflict(get_error_message(
+            ErrorKey::UserAlreadyExists,
+            [("field", json!("username,email"))],
+        ));
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(res.field, None);
+        assert_eq!(res.details, Some(json!({ "field": "username,email" })));
+
+        // 値に分けられない情報
+        let error = HandlerError::BadRequest(get_error_message(
+            ErrorKey::TaskHandlerInvalidPath,
+            "ActixWebError: invalid digit".to_string(),
+        ));
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(
+            res.details,
+            Some(json!({ "info": "ActixWebError: invalid digit" }))
+        );
+    }
+
+    #[test]
+    fn test_error_response_nested_cause() {
+        let cause = get_error_message(ErrorKey::UserAlreadyExists, [("field", json!("email"))]);
+        let error = HandlerError::BadRequest(get_error_message(ErrorKey::UserPasswordEmpty, cause));
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(res.code, "UserPasswordEmpty");
+        assert_eq!(res.rc, 2);
+        assert_eq!(res.field.as_deref(), Some("password"));
+        assert_eq!(
+            res.details,
+            Some(json!({
+                "cause": {
+                    "code": "UserAlreadyExists",
+                    "message": "A user with the same username or email already exists",
+                    "details": { "field": "email" }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn test_error_response_without_code() {
+        let error = HandlerError::NotFound("plain message".to_string().into());
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(res.code, error.kind());
+        assert_eq!(res.message, "plain message");
+        assert_eq!(res.details, None);
+        assert_eq!(res.rc, 5);
+
+        // serdeのエラーからフィールド名を取り出す
+        let error = HandlerError::BadRequest(
+            "Json deserialize error: missing field `name` at line 1 column 2"
+                .to_string()
+                .into(),
+        );
+        let res = ErrorResponse::from_error(&error, None);
+        assert_eq!(res.field.as_deref(), Some("name"));
+    }
+
+    // fieldはserde_jsonのメッセージから取り出すため、実際のエラーでメッセージの形式を固定する
+    #[test]
+    fn test_error_response_field_from_json_payload_error() {
+        let errors = [
+            (
+                serde_json::from_str::<Project>(r#"{"project_id":1}"#).unwrap_err(),
+                "name",
+            ),
+            (
+                serde_json::from_str::<ProjectPatch>(r#"{"title":"project"}"#).unwrap_err(),
+                "title",
+            ),
+            (
+                serde_json::from_str::<ProjectPatch>(r#"{"name":"a","name":"b"}"#).unwrap_err(),
+                "name",
+            ),
+        ];
+
+        for (e, field) in errors {
+            // ハンドラーと同じ形式でメッセージを作成する
+            let e = actix_web::Error::from(JsonPayloadError::Deserialize(e));
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ProjectHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let res = ErrorResponse::from_error(&error, None);
+            assert_eq!(res.field.as_deref(), Some(field), "{}", e);
+        }
+    }
+}
@@ -0,0 +1,4 @@
+#[cfg(test)]
+mod catalog_test;
+#[cfg(test)]
+mod error_response_test;
@@ -0,0 +1,206 @@
+use crate::constants::API_TOKEN_PREFIX;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_current_user;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{generate_token, hash_token};
+use crate::models::ApiToken;
+use crate::models::response_model::ApiTokenResponse;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::IssuedApiToken;
+use crate::models::response_model::IssuedApiTokenResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::api_token_repo::ApiTokenRepository;
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, web};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+struct ApiTokenRequest {
+    name: String,
+    // UNIX時間（秒）。省略した場合は無期限
+    expires_at: Option<i64>,
+}
+
+// APIトークンは認証済みのユーザー自身のものだけを操作できる
+fn current_user_id(req: &HttpRequest) -> Result<i64, HandlerError> {
+    match get_current_user(req).and_then(|user| user.user_id) {
+        Some(user_id) => Ok(user_id),
+        None => Err(HandlerError::Unauthorized(get_error_message(
+            ErrorKey::ApiTokenHandlerNotAuthenticated,
+            "".to_string(),
+        ))),
+    }
+}
+
+#[utoipa::path(
+    tag = "api_tokens",
+    summary = "Issue a personal access token",
+    request_body = ApiTokenRequest,
+    responses(
+        (status = 200, description = "The issued token. The plain token is only returned here", body = IssuedApiTokenResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/auth/tokens")]
+pub async fn create_api_token(
+    req: HttpRequest,
+    api_token_data: Result<web::Json<ApiTokenRequest>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let user_id = match current_user_id(&req) {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let api_token_data = match api_token_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ApiTokenHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    // 平文のトークンは保存せず、このレスポンスでのみ返す
+    let token = format!("{}{}", API_TOKEN_PREFIX, generate_token());
+    let api_token = ApiToken::new(
+        user_id,
+        api_token_data.name,
+        hash_token(&token),
+        api_token_data.expires_at,
+    );
+
+    let api_token_repo = ApiTokenRepository::new(pool.get_ref().clone());
+    let api_token = api_token_repo
+        .create_api_token(api_token)
+        .await
+        .map_err(HandlerError::from);
+
+    match api_token {
+        Ok(api_token) => {
+            let issued = IssuedApiToken::new(token, api_token.to_api_token_no_hash());
+            let response = IssuedApiTokenResponse::new(vec![issued], 1, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "api_tokens",
+    summary = "List the current user's personal access tokens",
+    responses(
+        (status = 200, description = "The current user's tokens", body = ApiTokenResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/auth/tokens")]
+pub async fn get_api_tokens(req: HttpRequest, pool: web::Data<SqlitePool>) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let user_id = match current_user_id(&req) {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let api_token_repo = ApiTokenRepository::new(pool.get_ref().clone());
+    let api_tokens = api_token_repo
+        .get_api_tokens_by_user_id(user_id)
+        .await
+        .map_err(HandlerError::from);
+
+    match api_tokens {
+        Ok(api_tokens) => {
+            let api_tokens = api_tokens
+                .iter()
+                .map(|api_token| api_token.to_api_token_no_hash())
+                .collect::<Vec<_>>();
+            let len = api_tokens.len() as i64;
+            let response = ApiTokenResponse::new(api_tokens, len, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "api_tokens",
+    summary = "Revoke a personal access token",
+    params(("id" = i64, Path, description = "API token ID")),
+    responses(
+        (status = 200, description = "The revoked token", body = ApiTokenResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[delete("/auth/tokens/{id}")]
+pub async fn revoke_api_token(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let user_id = match current_user_id(&req) {
+        Ok(user_id) => user_id,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let api_token_id = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ApiTokenHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let api_token_repo = ApiTokenRepository::new(pool.get_ref().clone());
+    let api_token = api_token_repo
+        .revoke_api_token(api_token_id, user_id)
+        .await
+        .map_err(HandlerError::from);
+
+    match api_token {
+        Ok(api_token) => {
+            let response =
+                ApiTokenResponse::new(vec![api_token.to_api_token_no_hash()], 1, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::require_admin;
+use crate::models::AuditLogFilter;
+use crate::models::PaginationParams;
+use crate::models::response_model::AuditLogResponse;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::Pagination;
+use crate::models::response_model::PaginationStatus;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::audit_log_repo::AuditLogRepository;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GetAuditLogsQuery {
+    page: Option<i32>,
+    page_size: Option<i32>,
+    actor_user_id: Option<i64>,
+    entity_type: Option<String>,
+    entity_id: Option<i64>,
+    action: Option<String>,
+    request_id: Option<String>,
+    created_at_from: Option<i64>,
+    created_at_to: Option<i64>,
+}
+
+impl GetAuditLogsQuery {
+    fn get_audit_log_filter(&self) -> Option<AuditLogFilter> {
+        let filter = AuditLogFilter {
+            actor_user_id: self.actor_user_id,
+            entity_type: self.entity_type.clone(),
+            entity_id: self.entity_id,
+            action: self.action.clone(),
+            request_id: self.request_id.clone(),
+            created_at_from: self.created_at_from,
+            created_at_to: self.created_at_to,
+        };
+
+        match filter.is_empty() {
+            true => None,
+            false => Some(filter),
+        }
+    }
+}
+
+// 新しい順に監査ログを返す
+#[utoipa::path(
+    tag = "audit",
+    summary = "Search the audit log",
+    params(GetAuditLogsQuery),
+    responses(
+        (status = 200, description = "Success", body = AuditLogResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/audit")]
+pub async fn get_audit_logs(
+    req: HttpRequest,
+    query: Result<web::Query<GetAuditLogsQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::AuditLogHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let mut pagination_params = PaginationParams::new(query.page, query.page_size);
+    pagination_params.validate();
+    let pagination = match pagination_params.status() {
+        PaginationStatus::Active => Some(Pagination {
+            current_page: *pagination_params.page().unwrap(),
+            page_size: *pagination_params.page_size().unwrap(),
+        }),
+        PaginationStatus::Inactive => None,
+        PaginationStatus::Error => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::AuditLogHandlerInvalidPage,
+                [
+                    ("page", json!(pagination_params.page())),
+                    ("page_size", json!(pagination_params.page_size())),
+                ],
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let audit_log_repo = AuditLogRepository::new(pool.get_ref().clone());
+    let audit_logs = audit_log_repo
+        .get_audit_logs_by_filter(
+            query.get_audit_log_filter().as_ref(),
+            pagination_params.page(),
+            pagination_params.page_size(),
+        )
+        .await
+        .map_err(HandlerError::from);
+
+    match audit_logs {
+        Ok(audit_logs) => {
+            let len = audit_logs.len() as i64;
+            let response = AuditLogResponse::new(audit_logs, len, pagination, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
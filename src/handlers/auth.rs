@@ -0,0 +1,213 @@
+use crate::constants::SESSION_TTL_SECONDS;
+use crate::errors::DBAccessError;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_bearer_token;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{generate_token, hash_token};
+use crate::models::Session;
+use crate::models::UserNoPassword;
+use crate::models::response_model::AuthResponse;
+use crate::models::response_model::AuthToken;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::session_repo::SessionRepository;
+use crate::repository::user_repo::UserRepository;
+use actix_web::{HttpRequest, HttpResponse, post, web};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, ToSchema)]
+struct LoginRequest {
+    username: String,
+    password: String,
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct LogoutQuery {
+    all: Option<bool>,
+}
+
+fn invalid_credentials(username: &str) -> HandlerError {
+    // ユーザーの存在有無が分からないよう、失敗理由は区別しない
+    HandlerError::Unauthorized(get_error_message(
+        ErrorKey::AuthHandlerInvalidCredentials,
+        [("username", json!(username))],
+    ))
+}
+
+async fn verify_credentials(
+    login_data: &LoginRequest,
+    pool: SqlitePool,
+) -> Result<UserNoPassword, HandlerError> {
+    let user_repo = UserRepository::new(pool);
+    let user = user_repo
+        .verify_user_password(&login_data.username, &login_data.password)
+        .await
+        .map_err(HandlerError::from)?;
+
+    match user {
+        Some(user) => Ok(user),
+        None => Err(invalid_credentials(&login_data.username)),
+    }
+}
+
+#[utoipa::path(
+    tag = "auth",
+    summary = "Log in and issue a session token",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Session token for the user", body = AuthResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+    security(()),
+)]
+#[post("/auth/login")]
+pub async fn login(
+    req: HttpRequest,
+    login_data: Result<web::Json<LoginRequest>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let login_data = match login_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::AuthHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let user = match verify_credentials(&login_data, pool.get_ref().clone()).await {
+        Ok(user) => user,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let token = generate_token();
+    let expires_at = Utc::now().timestamp() + SESSION_TTL_SECONDS;
+    // user_idはDBから取得したユーザーのため必ず存在する
+    let session = Session::new(user.user_id.unwrap(), hash_token(&token), expires_at);
+
+    let session_repo = SessionRepository::new(pool.get_ref().clone());
+    let session = session_repo
+        .create_session(session)
+        .await
+        .map_err(HandlerError::from);
+
+    match session {
+        Ok(session) => {
+            let auth_token = AuthToken::new(token, session.expires_at, user);
+            let response = AuthResponse::new(vec![auth_token], 1, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+async fn revoke(token: &str, all: bool, pool: SqlitePool) -> Result<(), HandlerError> {
+    let session_repo = SessionRepository::new(pool);
+    let token_hash = hash_token(token);
+
+    if all {
+        let session = session_repo
+            .get_active_session_by_token_hash(&token_hash)
+            .await
+            .map_err(|_| {
+                HandlerError::Unauthorized(get_error_message(
+                    ErrorKey::AuthHandlerInvalidToken,
+                    "".to_string(),
+                ))
+            })?;
+        session_repo
+            .revoke_sessions_by_user_id(session.user_id)
+            .await
+            .map_err(HandlerError::from)?;
+        return Ok(());
+    }
+
+    session_repo
+        .revoke_session(&token_hash)
+        .await
+        .map_err(|e| match e {
+            DBAccessError::NotFoundError(_) => HandlerError::Unauthorized(get_error_message(
+                ErrorKey::AuthHandlerInvalidToken,
+                "".to_string(),
+            )),
+            e => HandlerError::from(e),
+        })
+}
+
+// 現在のトークンを無効化する。all=trueの場合はユーザーの全セッションを無効化する
+#[utoipa::path(
+    tag = "auth",
+    summary = "Revoke the current session, or every session of the user with all=true",
+    params(LogoutQuery),
+    responses(
+        (status = 200, description = "The session was revoked", body = AuthResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/auth/logout")]
+pub async fn logout(
+    req: HttpRequest,
+    query: Result<web::Query<LogoutQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::AuthHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let token = match get_bearer_token(&req) {
+        Some(token) => token,
+        None => {
+            let error = HandlerError::Unauthorized(get_error_message(
+                ErrorKey::AuthHandlerNoToken,
+                "".to_string(),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let result = revoke(&token, query.all.unwrap_or(false), pool.get_ref().clone()).await;
+
+    match result {
+        Ok(()) => {
+            let response = AuthResponse::new(vec![], 0, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
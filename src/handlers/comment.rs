@@ -1,11 +1,21 @@
+use crate::enums::{AuditEntityType, ProjectRole};
 use crate::errors::handler_errors::HandlerError;
 use crate::errors::messages::ErrorKey;
 use crate::errors::messages::get_error_message;
+use crate::handlers::utils::authorize_trash_item;
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_if_match_versions;
+use crate::handlers::utils::get_member_scope;
 use crate::handlers::utils::get_request_id;
 use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{
+    authorize_comment, authorize_comment_author, authorize_comment_user, authorize_task,
+    get_current_user,
+};
+use crate::handlers::utils::{with_current_etag, with_etag};
 use crate::models::PaginationParams;
-use crate::models::repository_model::comment::Comment;
 use crate::models::repository_model::comment::CommentWithUser;
+use crate::models::repository_model::comment::{Comment, CommentPatch};
 use crate::models::response_model::CommentResponse;
 use crate::models::response_model::CommentUserResponse;
 use crate::models::response_model::ErrorResponse;
@@ -13,10 +23,15 @@ use crate::models::response_model::Pagination;
 use crate::models::response_model::PaginationStatus;
 use crate::models::response_model::ResponseMetadata;
 use crate::repository::comment_repo::CommentRepository;
-use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
+use crate::repository::version_repo::{INITIAL_VERSION, Versioned};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, patch, post, web};
 use serde::Deserialize;
+use serde_json::json;
 use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
 enum QueryTarget {
     All,
     Id,
@@ -39,8 +54,10 @@ impl QueryTarget {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct GetCommentsQuery {
+    #[param(value_type = Option<QueryTarget>, inline)]
     target: Option<String>,
     page: Option<i32>,
     page_size: Option<i32>,
@@ -97,9 +114,10 @@ impl GetCommentsQuery {
 
 async fn get_comments_with_pagination_all(
     pagination_params: &PaginationParams,
+    member_id: Option<i64>,
     pool: SqlitePool,
 ) -> Result<Vec<CommentWithUser>, HandlerError> {
-    let comment_repo = CommentRepository::new(pool);
+    let comment_repo = CommentRepository::new(pool).with_member_scope(member_id);
 
     match pagination_params.status() {
         PaginationStatus::Active => {
@@ -125,11 +143,10 @@ async fn get_comments_with_pagination_all(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::CommentHandlerGetCommentInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -137,9 +154,10 @@ async fn get_comments_with_pagination_all(
 async fn get_comments_with_pagination_by_task_id(
     pagination_params: &PaginationParams,
     task_id: i64,
+    member_id: Option<i64>,
     pool: SqlitePool,
 ) -> Result<Vec<CommentWithUser>, HandlerError> {
-    let comment_repo = CommentRepository::new(pool);
+    let comment_repo = CommentRepository::new(pool).with_member_scope(member_id);
 
     match pagination_params.status() {
         PaginationStatus::Active => {
@@ -167,11 +185,10 @@ async fn get_comments_with_pagination_by_task_id(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::CommentHandlerGetCommentInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -179,9 +196,10 @@ async fn get_comments_with_pagination_by_task_id(
 async fn get_comments_with_pagination_by_user_id(
     pagination_params: &PaginationParams,
     user_id: i64,
+    member_id: Option<i64>,
     pool: SqlitePool,
 ) -> Result<Vec<CommentWithUser>, HandlerError> {
-    let comment_repo = CommentRepository::new(pool);
+    let comment_repo = CommentRepository::new(pool).with_member_scope(member_id);
 
     match pagination_params.status() {
         PaginationStatus::Active => {
@@ -209,11 +227,10 @@ async fn get_comments_with_pagination_by_user_id(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::CommentHandlerGetCommentInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -231,17 +248,28 @@ async fn get_comments_with_pagination(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let member_id = match get_member_scope(&req) {
+        Ok(member_id) => member_id,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
     let result = match validated_query.target() {
-        Ok(QueryTarget::All) => get_comments_with_pagination_all(&pagination_params, pool).await,
+        Ok(QueryTarget::All) => {
+            get_comments_with_pagination_all(&pagination_params, member_id, pool).await
+        }
         Ok(QueryTarget::TaskId) => {
             get_comments_with_pagination_by_task_id(
                 &pagination_params,
                 validated_query.task_id.unwrap(),
+                member_id,
                 pool,
             )
             .await
@@ -250,6 +278,7 @@ async fn get_comments_with_pagination(
             get_comments_with_pagination_by_user_id(
                 &pagination_params,
                 validated_query.user_id.unwrap(),
+                member_id,
                 pool,
             )
             .await
@@ -276,12 +305,11 @@ async fn get_comments_with_pagination(
             };
 
             let response = CommentUserResponse::new(comments, len, pagination, Some(metadata));
-            log::debug!("Response: {:?}", response);
 
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -297,7 +325,7 @@ async fn get_comment_by_id(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
@@ -309,12 +337,17 @@ async fn get_comment_by_id(
                 ErrorKey::CommentHandlerGetCommentInvalidTarget,
                 "".to_string(),
             ));
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
-    let comment_repo = CommentRepository::new(pool);
+    if let Err(e) = authorize_comment(&req, &pool, id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.clone());
     let comment = comment_repo
         .get_comment_by_id(id)
         .await
@@ -322,17 +355,37 @@ async fn get_comment_by_id(
 
     match comment {
         Ok(comment) => {
+            let id = comment.comment_id;
             let response = CommentUserResponse::new(vec![comment], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_current_etag(
+                HttpResponse::Ok().json(response),
+                &pool,
+                AuditEntityType::Comment,
+                id,
+            )
+            .await;
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "comments",
+    summary = "Get comments with their authors",
+    description = "target selects the lookup: all (default, paginated with page and page_size), id (requires id), task_id (requires task_id) or user_id (requires user_id).",
+    params(GetCommentsQuery),
+    responses(
+        (status = 200, description = "Success", body = CommentUserResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[get("/comments")]
 pub async fn get_comments(
     req: HttpRequest,
@@ -346,7 +399,7 @@ pub async fn get_comments(
                 ErrorKey::CommentHandlerInvalidQuery,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -354,7 +407,7 @@ pub async fn get_comments(
     let target = match query.target() {
         Ok(target) => target,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&e, None);
             return handle_error(e, response);
         }
     };
@@ -371,6 +424,19 @@ pub async fn get_comments(
     }
 }
 
+#[utoipa::path(
+    tag = "comments",
+    summary = "Comment on a task",
+    request_body = Comment,
+    responses(
+        (status = 200, description = "The created comment", body = CommentResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/comments")]
 pub async fn create_comment(
     req: HttpRequest,
@@ -386,12 +452,31 @@ pub async fn create_comment(
                 ErrorKey::CommentHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let comment_repo = CommentRepository::new(pool.get_ref().clone());
+    let current_user_id = get_current_user(&req).and_then(|user| user.user_id);
+    if let Err(e) = authorize_comment_user(&req, comment_data.user_id, current_user_id) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    if let Err(e) = authorize_task(
+        &req,
+        pool.get_ref(),
+        comment_data.task_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let comment = comment_repo
         .create_comment(comment_data.into_inner())
         .await
@@ -400,16 +485,33 @@ pub async fn create_comment(
     match comment {
         Ok(comment) => {
             let response = CommentResponse::new(vec![comment], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), INITIAL_VERSION);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "comments",
+    summary = "Update a comment",
+    params(
+        ("id" = i64, Path, description = "Comment ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = Comment,
+    responses(
+        (status = 200, description = "The updated comment", body = CommentResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/comments/{id}")]
 pub async fn update_comment(
     req: HttpRequest,
@@ -426,7 +528,7 @@ pub async fn update_comment(
                 ErrorKey::CommentHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -438,7 +540,7 @@ pub async fn update_comment(
                 ErrorKey::CommentHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -453,29 +555,153 @@ pub async fn update_comment(
                 path, comment_data.comment_id
             ),
         ));
-        let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+        let response = ErrorResponse::from_error(&error, Some(metadata));
         return handle_error(error, response);
     }
 
-    let comment_repo = CommentRepository::new(pool.get_ref().clone());
+    // 投稿者は変更できない
+    let authorized = authorize_comment_author(&req, pool.get_ref(), path)
+        .await
+        .and_then(|comment| {
+            authorize_comment_user(&req, comment_data.user_id, Some(comment.user_id))
+        });
+    if let Err(e) = authorized {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    if let Err(e) = authorize_task(
+        &req,
+        pool.get_ref(),
+        comment_data.task_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let comment = comment_repo
         .update_comment(comment_data.into_inner())
         .await
         .map_err(HandlerError::from);
 
     match comment {
-        Ok(comment) => {
+        Ok(Versioned {
+            value: comment,
+            version,
+        }) => {
             let response = CommentResponse::new(vec![comment], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), version);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "comments",
+    summary = "Partially update a comment",
+    description = "Only the fields present in the body are validated and updated.",
+    params(
+        ("id" = i64, Path, description = "Comment ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = CommentPatch,
+    responses(
+        (status = 200, description = "The updated comment", body = CommentResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[patch("/comments/{id}")]
+pub async fn patch_comment(
+    req: HttpRequest,
+    patch_data: Result<web::Json<CommentPatch>, actix_web::Error>,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::CommentHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let patch_data = match patch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::CommentHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_comment_author(&req, pool.get_ref(), path).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let comment = comment_repo
+        .patch_comment(path, patch_data)
+        .await
+        .map_err(HandlerError::from);
+
+    match comment {
+        Ok(Versioned {
+            value: comment,
+            version,
+        }) => {
+            let response = CommentResponse::new(vec![comment], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "comments",
+    summary = "Move a comment to the trash",
+    params(
+        ("id" = i64, Path, description = "Comment ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    responses(
+        (status = 200, description = "The deleted comment", body = CommentResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[delete("/comments/{id}")]
 pub async fn delete_comment(
     req: HttpRequest,
@@ -491,12 +717,19 @@ pub async fn delete_comment(
                 ErrorKey::CommentHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let comment_repo = CommentRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_comment_author(&req, pool.get_ref(), path).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let comment = comment_repo
         .delete_comment(path)
         .await
@@ -508,8 +741,79 @@ pub async fn delete_comment(
             return HttpResponse::Ok().json(response);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
+
+#[utoipa::path(
+    tag = "comments",
+    summary = "Restore a comment from the trash",
+    params(
+        ("id" = i64, Path, description = "Comment ID"),
+    ),
+    responses(
+        (status = 200, description = "The restored comment", body = CommentResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The comment is not in the trash", body = ErrorResponse),
+        (status = 409, description = "The parent task is in the trash", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/comments/{id}/restore")]
+pub async fn restore_comment(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::CommentHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_trash_item(
+        &req,
+        pool.get_ref(),
+        AuditEntityType::Comment,
+        path,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let comment_repo = CommentRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
+    let comment = comment_repo
+        .restore_comment(path)
+        .await
+        .map_err(HandlerError::from);
+
+    match comment {
+        Ok(Versioned {
+            value: comment,
+            version,
+        }) => {
+            let response = CommentResponse::new(vec![comment], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
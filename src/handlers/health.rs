@@ -0,0 +1,141 @@
+use crate::constants::HEALTH_CHECK_TIMEOUT_MS;
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_request_id;
+use crate::models::response_model::{
+    HealthCheck, MigrationHealth, PoolHealth, ReadinessChecks, ReadinessResponse, ResponseMetadata,
+    RootResponse, StorageHealth,
+};
+use crate::repository::database_repo::DatabaseRepository;
+use crate::repository::migration_repo::{MigrationRepository, current_version, latest_version};
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use std::future::Future;
+use std::time::Duration;
+
+// データベースがロックされている場合に応答が返らなくなるのを防ぐ
+async fn with_timeout<T>(
+    future: impl Future<Output = Result<T, DBAccessError>>,
+) -> Result<T, String> {
+    match tokio::time::timeout(Duration::from_millis(HEALTH_CHECK_TIMEOUT_MS), future).await {
+        Ok(result) => result.map_err(|e| e.to_string()),
+        Err(_) => Err(get_error_message(
+            ErrorKey::HealthCheckTimeout,
+            [("timeout_ms", json!(HEALTH_CHECK_TIMEOUT_MS))],
+        )
+        .to_string()),
+    }
+}
+
+// プロセスが応答できるかのみを確認する
+#[utoipa::path(
+    tag = "health",
+    summary = "Liveness probe",
+    responses(
+        (status = 200, description = "The process is able to respond", body = RootResponse),
+    ),
+    security(()),
+)]
+#[get("/health/live")]
+pub async fn live(req: HttpRequest) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+    let response = RootResponse::new("MENAHEL IS RUNNING!".to_string(), 0, Some(metadata));
+    HttpResponse::Ok().json(response)
+}
+
+// いずれかの項目が失敗した場合は503を返す
+#[utoipa::path(
+    tag = "health",
+    summary = "Readiness probe",
+    responses(
+        (status = 200, description = "Every readiness check passed", body = ReadinessResponse),
+        (status = 503, description = "At least one readiness check failed", body = ReadinessResponse),
+    ),
+    security(()),
+)]
+#[get("/health/ready")]
+pub async fn ready(req: HttpRequest, pool: web::Data<SqlitePool>) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+    let database_repo = DatabaseRepository::new(pool.get_ref().clone());
+    let migration_repo = MigrationRepository::new(pool.get_ref().clone());
+
+    let database = match with_timeout(database_repo.ping()).await {
+        Ok(()) => HealthCheck::ok(),
+        Err(e) => HealthCheck::error(e),
+    };
+
+    let migrations = match with_timeout(migration_repo.get_migration_status()).await {
+        Ok(status) => {
+            let current = current_version(&status);
+            let expected = latest_version(&status);
+            let pending = status.iter().any(|migration| !migration.applied);
+            let modified = status.iter().any(|migration| migration.checksum_mismatch);
+            let check = match current == expected && !pending && !modified {
+                true => HealthCheck::ok(),
+                false => HealthCheck::error(
+                    get_error_message(
+                        ErrorKey::HealthMigrationVersionMismatch,
+                        [
+                            ("current", json!(current)),
+                            ("expected", json!(expected)),
+                            ("pending", json!(pending)),
+                            ("modified", json!(modified)),
+                        ],
+                    )
+                    .to_string(),
+                ),
+            };
+            MigrationHealth {
+                check,
+                current_version: current,
+                expected_version: expected,
+            }
+        }
+        Err(e) => MigrationHealth {
+            check: HealthCheck::error(e),
+            current_version: None,
+            expected_version: None,
+        },
+    };
+
+    let pool_status = database_repo.get_pool_status();
+    let pool = PoolHealth {
+        size: pool_status.size,
+        idle: pool_status.idle,
+        in_use: pool_status.in_use(),
+        max_connections: pool_status.max_connections,
+        saturation: match pool_status.max_connections {
+            0 => 0.0,
+            max => pool_status.in_use() as f64 / max as f64,
+        },
+    };
+
+    let storage = match with_timeout(database_repo.get_database_size()).await {
+        Ok(size) => StorageHealth {
+            check: HealthCheck::ok(),
+            size_bytes: Some(size),
+        },
+        Err(e) => StorageHealth {
+            check: HealthCheck::error(e),
+            size_bytes: None,
+        },
+    };
+
+    let checks = ReadinessChecks {
+        database,
+        migrations,
+        pool,
+        storage,
+    };
+    let failed = checks.failed();
+    if failed.is_empty() {
+        let response = ReadinessResponse::new(checks, "OK".to_string(), Some(metadata));
+        return HttpResponse::Ok().json(response);
+    }
+
+    let message = get_error_message(ErrorKey::HealthNotReady, [("failed", json!(failed))]);
+    log::warn!("Readiness check failed: {}", message);
+    let response = ReadinessResponse::new(checks, message.to_string(), Some(metadata));
+    HttpResponse::ServiceUnavailable().json(response)
+}
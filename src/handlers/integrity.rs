@@ -0,0 +1,116 @@
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::require_admin;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::IntegrityResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::integrity_repo::IntegrityRepository;
+use actix_web::{HttpRequest, HttpResponse, get, post, web};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct RepairIntegrityQuery {
+    // trueの場合は修正内容だけを返し、データベースは変更しない
+    dry_run: Option<bool>,
+}
+
+// データベースは変更しない
+#[utoipa::path(
+    tag = "admin",
+    summary = "Scan the data for broken task hierarchies, orphaned rows, duplicate assignments and invalid values",
+    responses(
+        (status = 200, description = "Integrity report", body = IntegrityResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/admin/integrity")]
+pub async fn get_integrity(req: HttpRequest, pool: web::Data<SqlitePool>) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let integrity_repo = IntegrityRepository::new(pool.get_ref().clone());
+    let report = integrity_repo
+        .check_integrity_issues()
+        .await
+        .map_err(HandlerError::from);
+
+    match report {
+        Ok(report) => {
+            let response = IntegrityResponse::new(report, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 自動で直せる問題をまとめて修正する。親子関係の問題は手動で直す
+#[utoipa::path(
+    tag = "admin",
+    summary = "Fix the integrity issues that can be repaired automatically",
+    params(RepairIntegrityQuery),
+    responses(
+        (status = 200, description = "Integrity report with the applied fixes", body = IntegrityResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/admin/integrity/repair")]
+pub async fn repair_integrity(
+    req: HttpRequest,
+    query: Result<web::Query<RepairIntegrityQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::IntegrityHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let integrity_repo = IntegrityRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
+    let report = integrity_repo
+        .repair_integrity_issues(query.dry_run.unwrap_or(false))
+        .await
+        .map_err(HandlerError::from);
+
+    match report {
+        Ok(report) => {
+            let response = IntegrityResponse::new(report, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use crate::handlers::utils::{get_request_id, handle_error, require_admin};
+use crate::metrics::metrics;
+use crate::models::{ErrorResponse, ResponseMetadata};
+use crate::repository::comment_repo::CommentRepository;
+use crate::repository::database_repo::DatabaseRepository;
+use crate::repository::project_repo::ProjectRepository;
+use crate::repository::task_repo::TaskRepository;
+use crate::repository::user_assign_repo::UserAssignRepository;
+use crate::repository::user_repo::UserRepository;
+use crate::settings::server_config;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use sqlx::sqlite::SqlitePool;
+
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+// 件数の取得に失敗したエンティティは出力しない
+async fn get_entity_counts(pool: &SqlitePool) -> Vec<(&'static str, i64)> {
+    let counts = [
+        (
+            "users",
+            UserRepository::new(pool.clone()).get_users_count().await,
+        ),
+        (
+            "projects",
+            ProjectRepository::new(pool.clone())
+                .get_projects_count()
+                .await,
+        ),
+        (
+            "tasks",
+            TaskRepository::new(pool.clone()).get_tasks_count().await,
+        ),
+        (
+            "user_assigns",
+            UserAssignRepository::new(pool.clone())
+                .get_user_assigns_count()
+                .await,
+        ),
+        (
+            "comments",
+            CommentRepository::new(pool.clone())
+                .get_comments_count()
+                .await,
+        ),
+    ];
+
+    counts
+        .into_iter()
+        .filter_map(|(entity, count)| match count {
+            Ok(count) => Some((entity, count)),
+            Err(e) => {
+                log::error!("Failed to get entity count: {}: {}", entity, e);
+                None
+            }
+        })
+        .collect()
+}
+
+// Prometheusのテキスト形式でメトリクスを返す
+#[utoipa::path(
+    tag = "metrics",
+    summary = "Prometheus metrics",
+    responses(
+        (status = 200, description = "Metrics in the Prometheus text exposition format", body = String, content_type = "text/plain"),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+    ),
+)]
+#[get("/metrics")]
+pub async fn get_metrics(req: HttpRequest, pool: web::Data<SqlitePool>) -> HttpResponse {
+    // metrics.publicが無効の場合は管理者のみ
+    if !server_config().metrics.public
+        && let Err(e) = require_admin(&req)
+    {
+        let metadata = ResponseMetadata::new(get_request_id(&req));
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let entities = get_entity_counts(pool.get_ref()).await;
+    let pool_status = DatabaseRepository::new(pool.get_ref().clone()).get_pool_status();
+
+    HttpResponse::Ok()
+        .content_type(PROMETHEUS_CONTENT_TYPE)
+        .body(metrics().render(&pool_status, &entities))
+}
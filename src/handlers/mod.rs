@@ -1,10 +1,21 @@
+pub mod api_token;
+pub mod audit_log;
+pub mod auth;
 pub mod comment;
+pub mod health;
+pub mod integrity;
+pub mod metrics;
+pub mod openapi;
 pub mod project;
+pub mod project_member;
 pub mod root;
 pub mod task;
+pub mod task_history;
+pub mod task_tree;
+pub mod trash;
 pub mod user;
 pub mod user_assign;
-mod utils;
+pub(crate) mod utils;
 
 #[cfg(test)]
 mod test;
@@ -0,0 +1,102 @@
+use actix_web::{HttpResponse, get};
+use once_cell::sync::Lazy;
+use utoipa::openapi::security::{Http, HttpAuthScheme, SecurityScheme};
+use utoipa::{Modify, OpenApi};
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "MENAHEL API",
+        description = "Task management API. Every route except the public ones (marked with an empty security requirement) needs `Authorization: Bearer <token>` with a session token from /auth/login or a personal access token.",
+    ),
+    paths(
+        super::root::root,
+        super::root::health,
+        super::health::live,
+        super::health::ready,
+        super::metrics::get_metrics,
+        get_openapi,
+        super::auth::login,
+        super::auth::logout,
+        super::api_token::get_api_tokens,
+        super::api_token::create_api_token,
+        super::api_token::revoke_api_token,
+        super::user::get_users,
+        super::user::create_user,
+        super::user::update_user,
+        super::user::patch_user,
+        super::user::delete_user,
+        super::project::get_projects,
+        super::project::create_project,
+        super::project::update_project,
+        super::project::patch_project,
+        super::project::delete_project,
+        super::project::restore_project,
+        super::project_member::get_project_members,
+        super::project_member::create_project_member,
+        super::project_member::update_project_member,
+        super::project_member::delete_project_member,
+        super::task::get_tasks,
+        super::task::create_task,
+        super::task::batch_tasks,
+        super::task::update_task,
+        super::task::patch_task,
+        super::task::delete_task,
+        super::task::restore_task,
+        super::task_history::get_task_history,
+        super::task_tree::get_task_tree,
+        super::task_tree::get_project_tree,
+        super::user_assign::get_user_assigns,
+        super::user_assign::create_user_assign,
+        super::user_assign::batch_user_assigns,
+        super::user_assign::update_user_assign,
+        super::user_assign::delete_user_assign,
+        super::comment::get_comments,
+        super::comment::create_comment,
+        super::comment::update_comment,
+        super::comment::patch_comment,
+        super::comment::delete_comment,
+        super::comment::restore_comment,
+        super::audit_log::get_audit_logs,
+        super::trash::get_trash,
+        super::trash::purge_trash,
+        super::integrity::get_integrity,
+        super::integrity::repair_integrity,
+    ),
+    modifiers(&SecurityAddon),
+    security(("bearer_auth" = [])),
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        // Cargo.tomlにライセンスの記載がないため空の項目を出力しない
+        openapi.info.license = None;
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "bearer_auth",
+                SecurityScheme::Http(Http::new(HttpAuthScheme::Bearer)),
+            );
+    }
+}
+
+static OPENAPI_JSON: Lazy<String> = Lazy::new(|| ApiDoc::openapi().to_pretty_json().unwrap());
+
+#[utoipa::path(
+    tag = "root",
+    summary = "This OpenAPI document",
+    responses(
+        (status = 200, description = "OpenAPI 3 document of the API", content_type = "application/json"),
+    ),
+    security(()),
+)]
+#[get("/openapi.json")]
+pub async fn get_openapi() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("application/json")
+        .body(OPENAPI_JSON.as_str())
+}
@@ -1,20 +1,33 @@
+use crate::enums::{AuditEntityType, ProjectRole};
 use crate::errors::handler_errors::HandlerError;
 use crate::errors::messages::ErrorKey;
 use crate::errors::messages::get_error_message;
+use crate::handlers::utils::DeletePolicyQuery;
+use crate::handlers::utils::authorize_trash_item;
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_if_match_versions;
 use crate::handlers::utils::get_request_id;
 use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{authorize_project, get_member_scope, require_current_user};
+use crate::handlers::utils::{with_current_etag, with_etag};
+use crate::models::DeleteResponse;
 use crate::models::PaginationParams;
-use crate::models::project::Project;
+use crate::models::project::{Project, ProjectPatch};
 use crate::models::response_model::ErrorResponse;
 use crate::models::response_model::Pagination;
 use crate::models::response_model::PaginationStatus;
 use crate::models::response_model::ProjectResponse;
 use crate::models::response_model::ResponseMetadata;
 use crate::repository::project_repo::ProjectRepository;
-use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
+use crate::repository::version_repo::{INITIAL_VERSION, Versioned};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, patch, post, web};
 use serde::Deserialize;
+use serde_json::json;
 use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
 enum QueryTarget {
     All,
     Name,
@@ -35,8 +48,10 @@ impl QueryTarget {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct GetProjectsQuery {
+    #[param(value_type = Option<QueryTarget>, inline)]
     target: Option<String>,
     page: Option<i32>,
     page_size: Option<i32>,
@@ -83,9 +98,10 @@ impl GetProjectsQuery {
 
 async fn get_projects_with_pagination(
     pagination_params: &PaginationParams,
+    member_id: Option<i64>,
     pool: SqlitePool,
 ) -> Result<Vec<Project>, HandlerError> {
-    let project_repo = ProjectRepository::new(pool);
+    let project_repo = ProjectRepository::new(pool).with_member_scope(member_id);
 
     match pagination_params.status() {
         PaginationStatus::Active => {
@@ -111,11 +127,10 @@ async fn get_projects_with_pagination(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::ProjectHandlerGetProjectsInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -130,7 +145,10 @@ async fn get_all_projects(
     let mut pagination_params = PaginationParams::new(query.page, query.page_size);
     pagination_params.validate();
 
-    let result = get_projects_with_pagination(&pagination_params, pool).await;
+    let result = match get_member_scope(&req) {
+        Ok(member_id) => get_projects_with_pagination(&pagination_params, member_id, pool).await,
+        Err(e) => Err(e),
+    };
 
     match result {
         Ok(projects) => {
@@ -147,11 +165,10 @@ async fn get_all_projects(
                 _ => None,
             };
             let response = ProjectResponse::new(projects, len, pagination, Some(metadata));
-            log::debug!("Response: {:?}", response);
             return HttpResponse::Ok().json(response);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -167,25 +184,33 @@ async fn get_project_by_name(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
-    let project_repo = ProjectRepository::new(pool);
-    let project = project_repo
+    let project_repo = ProjectRepository::new(pool.clone());
+    let project = match project_repo
         .get_project_by_name(validated_query.name.clone().unwrap().as_str())
         .await
-        .map_err(HandlerError::from);
+        .map_err(HandlerError::from)
+    {
+        Ok(project) => {
+            let project_id = project.project_id.unwrap_or_default();
+            authorize_project(&req, &pool, project_id, ProjectRole::Viewer)
+                .await
+                .map(|_| project)
+        }
+        Err(e) => Err(e),
+    };
 
     match project {
         Ok(project) => {
             let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
             return HttpResponse::Ok().json(response);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -201,7 +226,7 @@ async fn get_project_by_id(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
@@ -213,12 +238,17 @@ async fn get_project_by_id(
                 ErrorKey::ProjectHandlerGetProjectsInvalidTarget,
                 "".to_string(),
             ));
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
-    let project_repo = ProjectRepository::new(pool);
+    if let Err(e) = authorize_project(&req, &pool, id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_repo = ProjectRepository::new(pool.clone());
     let project = project_repo
         .get_project_by_id(id)
         .await
@@ -226,17 +256,37 @@ async fn get_project_by_id(
 
     match project {
         Ok(project) => {
+            let id = project.project_id;
             let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_current_etag(
+                HttpResponse::Ok().json(response),
+                &pool,
+                AuditEntityType::Project,
+                id,
+            )
+            .await;
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "projects",
+    summary = "Get projects",
+    description = "target selects the lookup: all (default, paginated with page and page_size), name (requires name) or id (requires id).",
+    params(GetProjectsQuery),
+    responses(
+        (status = 200, description = "Success", body = ProjectResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[get("/projects")]
 pub async fn get_projects(
     req: HttpRequest,
@@ -250,7 +300,7 @@ pub async fn get_projects(
                 ErrorKey::ProjectHandlerInvalidQuery,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -258,7 +308,7 @@ pub async fn get_projects(
     let target = match query.target() {
         Ok(target) => target,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&e, None);
             return handle_error(e, response);
         }
     };
@@ -270,6 +320,18 @@ pub async fn get_projects(
     }
 }
 
+#[utoipa::path(
+    tag = "projects",
+    summary = "Create a project owned by the current user",
+    request_body = Project,
+    responses(
+        (status = 200, description = "The created project", body = ProjectResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/projects")]
 pub async fn create_project(
     req: HttpRequest,
@@ -278,6 +340,14 @@ pub async fn create_project(
 ) -> impl Responder {
     let metadata = ResponseMetadata::new(get_request_id(&req));
 
+    let user = match require_current_user(&req) {
+        Ok(user) => user,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
     let project_data = match project_data {
         Ok(data) => data,
         Err(e) => {
@@ -285,29 +355,50 @@ pub async fn create_project(
                 ErrorKey::ProjectHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let project_repo = ProjectRepository::new(pool.get_ref().clone());
+    // 作成したユーザーがプロジェクトのオーナーになる
+    let project_repo = ProjectRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let project = project_repo
-        .create_project(project_data.into_inner())
+        .create_project_with_owner(project_data.into_inner(), user.user_id.unwrap())
         .await
         .map_err(HandlerError::from);
 
     match project {
         Ok(project) => {
             let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), INITIAL_VERSION);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "projects",
+    summary = "Update a project (owner only)",
+    params(
+        ("id" = i64, Path, description = "Project ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = Project,
+    responses(
+        (status = 200, description = "The updated project", body = ProjectResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/projects/{id}")]
 pub async fn update_project(
     req: HttpRequest,
@@ -324,7 +415,7 @@ pub async fn update_project(
                 ErrorKey::ProjectHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -336,7 +427,7 @@ pub async fn update_project(
                 ErrorKey::ProjectHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -351,32 +442,144 @@ pub async fn update_project(
                 path, project_data.project_id
             ),
         ));
-        let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+        let response = ErrorResponse::from_error(&error, Some(metadata));
         return handle_error(error, response);
     }
 
-    let project_repo = ProjectRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_project(&req, pool.get_ref(), path, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_repo = ProjectRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let project = project_repo
         .update_project(project_data.into_inner())
         .await
         .map_err(HandlerError::from);
 
     match project {
-        Ok(project) => {
+        Ok(Versioned {
+            value: project,
+            version,
+        }) => {
             let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), version);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "projects",
+    summary = "Partially update a project (owner only)",
+    description = "Only the fields present in the body are validated and updated.",
+    params(
+        ("id" = i64, Path, description = "Project ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = ProjectPatch,
+    responses(
+        (status = 200, description = "The updated project", body = ProjectResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[patch("/projects/{id}")]
+pub async fn patch_project(
+    req: HttpRequest,
+    patch_data: Result<web::Json<ProjectPatch>, actix_web::Error>,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ProjectHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let patch_data = match patch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ProjectHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), path, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_repo = ProjectRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let project = project_repo
+        .patch_project(path, patch_data)
+        .await
+        .map_err(HandlerError::from);
+
+    match project {
+        Ok(Versioned {
+            value: project,
+            version,
+        }) => {
+            let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 削除ポリシーの既定はrestrict。cascadeはタスクと一緒にゴミ箱に移動する。reparentは使えない
+#[utoipa::path(
+    tag = "projects",
+    summary = "Move a project to the trash according to the delete policy (owner only)",
+    params(
+        ("id" = i64, Path, description = "Project ID"),
+        DeletePolicyQuery,
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    responses(
+        (status = 200, description = "Number of removed rows", body = DeleteResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Dependent records prevent deletion under the policy", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[delete("/projects/{id}")]
 pub async fn delete_project(
     req: HttpRequest,
     path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<DeletePolicyQuery>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
 ) -> HttpResponse {
     let metadata = ResponseMetadata::new(get_request_id(&req));
@@ -388,25 +591,117 @@ pub async fn delete_project(
                 ErrorKey::ProjectHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let project_repo = ProjectRepository::new(pool.get_ref().clone());
+    let policy = match query {
+        Ok(query) => query.get_delete_policy(),
+        Err(e) => Err(HandlerError::BadRequest(get_error_message(
+            ErrorKey::ProjectHandlerInvalidQuery,
+            format!("ActixWebError: {}", e),
+        ))),
+    };
+    let policy = match policy {
+        Ok(policy) => policy,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), path, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_repo = ProjectRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let summary = project_repo
+        .delete_project(path, policy)
+        .await
+        .map_err(HandlerError::from);
+
+    match summary {
+        Ok(summary) => {
+            let response = DeleteResponse::new(summary, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "projects",
+    summary = "Restore a project from the trash together with its tasks (owner only)",
+    params(
+        ("id" = i64, Path, description = "Project ID"),
+    ),
+    responses(
+        (status = 200, description = "The restored project", body = ProjectResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The project is not in the trash", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/projects/{id}/restore")]
+pub async fn restore_project(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::ProjectHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_trash_item(
+        &req,
+        pool.get_ref(),
+        AuditEntityType::Project,
+        path,
+        ProjectRole::Owner,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_repo = ProjectRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let project = project_repo
-        .delete_project(path)
+        .restore_project(path)
         .await
         .map_err(HandlerError::from);
 
     match project {
-        Ok(()) => {
-            let response = ProjectResponse::new(vec![], 0, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+        Ok(Versioned {
+            value: project,
+            version,
+        }) => {
+            let response = ProjectResponse::new(vec![project], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
-            return handle_error(e, response);
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
         }
     }
 }
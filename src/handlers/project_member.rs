@@ -0,0 +1,290 @@
+use crate::enums::ProjectRole;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::authorize_project;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::models::ProjectMember;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::ProjectMemberResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::project_member_repo::ProjectMemberRepository;
+use actix_web::{HttpRequest, HttpResponse, delete, get, post, web};
+use sqlx::sqlite::SqlitePool;
+
+fn invalid_path(e: actix_web::Error) -> HandlerError {
+    HandlerError::BadRequest(get_error_message(
+        ErrorKey::ProjectMemberHandlerInvalidPath,
+        format!("ActixWebError: {}", e),
+    ))
+}
+
+fn invalid_json(e: actix_web::Error) -> HandlerError {
+    HandlerError::BadRequest(get_error_message(
+        ErrorKey::ProjectMemberHandlerInvalidJsonPost,
+        format!("ActixWebError: {}", e),
+    ))
+}
+
+#[utoipa::path(
+    tag = "project_members",
+    summary = "List the members of a project",
+    params(("id" = i64, Path, description = "Project ID")),
+    responses(
+        (status = 200, description = "Success", body = ProjectMemberResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/projects/{id}/members")]
+pub async fn get_project_members(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let project_id = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = invalid_path(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_member_repo = ProjectMemberRepository::new(pool.get_ref().clone());
+    let project_members = project_member_repo
+        .get_project_members_by_project_id(project_id)
+        .await
+        .map_err(HandlerError::from);
+
+    match project_members {
+        Ok(project_members) => {
+            let len = project_members.len() as i64;
+            let response = ProjectMemberResponse::new(project_members, len, None, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "project_members",
+    summary = "Add a member to a project (owner only)",
+    params(("id" = i64, Path, description = "Project ID")),
+    request_body = ProjectMember,
+    responses(
+        (status = 200, description = "The added member", body = ProjectMemberResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/projects/{id}/members")]
+pub async fn create_project_member(
+    req: HttpRequest,
+    project_member_data: Result<web::Json<ProjectMember>, actix_web::Error>,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let project_id = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = invalid_path(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let project_member_data = match project_member_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = invalid_json(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if project_member_data.project_id != project_id {
+        let error = HandlerError::BadRequest(get_error_message(
+            ErrorKey::ProjectMemberHandlerPathAndBodyIdMismatch,
+            format!(
+                "path_id: {:?}, body_id: {:?}",
+                project_id, project_member_data.project_id
+            ),
+        ));
+        let response = ErrorResponse::from_error(&error, Some(metadata));
+        return handle_error(error, response);
+    }
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_member_repo = ProjectMemberRepository::new(pool.get_ref().clone());
+    let project_member = project_member_repo
+        .create_project_member(project_member_data)
+        .await
+        .map_err(HandlerError::from);
+
+    match project_member {
+        Ok(project_member) => {
+            let response =
+                ProjectMemberResponse::new(vec![project_member], 1, None, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// メンバーのロールを変更する
+#[utoipa::path(
+    tag = "project_members",
+    summary = "Change the role of a project member (owner only)",
+    params(("id" = i64, Path, description = "Project ID"), ("user_id" = i64, Path, description = "User ID")),
+    request_body = ProjectMember,
+    responses(
+        (status = 200, description = "The updated member", body = ProjectMemberResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/projects/{id}/members/{user_id}")]
+pub async fn update_project_member(
+    req: HttpRequest,
+    project_member_data: Result<web::Json<ProjectMember>, actix_web::Error>,
+    path: Result<web::Path<(i64, i64)>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let (project_id, user_id) = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = invalid_path(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let project_member_data = match project_member_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = invalid_json(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if project_member_data.project_id != project_id || project_member_data.user_id != user_id {
+        let error = HandlerError::BadRequest(get_error_message(
+            ErrorKey::ProjectMemberHandlerPathAndBodyIdMismatch,
+            format!(
+                "path: ({}, {}), body: ({}, {})",
+                project_id, user_id, project_member_data.project_id, project_member_data.user_id
+            ),
+        ));
+        let response = ErrorResponse::from_error(&error, Some(metadata));
+        return handle_error(error, response);
+    }
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_member_repo = ProjectMemberRepository::new(pool.get_ref().clone());
+    let project_member = project_member_repo
+        .update_project_member_role(project_id, user_id, project_member_data.role)
+        .await
+        .map_err(HandlerError::from);
+
+    match project_member {
+        Ok(project_member) => {
+            let response =
+                ProjectMemberResponse::new(vec![project_member], 1, None, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "project_members",
+    summary = "Remove a member from a project (owner only)",
+    params(("id" = i64, Path, description = "Project ID"), ("user_id" = i64, Path, description = "User ID")),
+    responses(
+        (status = 200, description = "The removed member", body = ProjectMemberResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[delete("/projects/{id}/members/{user_id}")]
+pub async fn delete_project_member(
+    req: HttpRequest,
+    path: Result<web::Path<(i64, i64)>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let (project_id, user_id) = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = invalid_path(e);
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Owner).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let project_member_repo = ProjectMemberRepository::new(pool.get_ref().clone());
+    let result = project_member_repo
+        .delete_project_member(project_id, user_id)
+        .await
+        .map_err(HandlerError::from);
+
+    match result {
+        Ok(()) => {
+            let response = ProjectMemberResponse::new(vec![], 0, None, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
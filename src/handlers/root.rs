@@ -3,31 +3,49 @@ use crate::handlers::utils::get_request_id;
 use crate::models::response_model::{ResponseMetadata, RootResponse};
 use actix_web::{HttpRequest, Responder, get, web};
 
+#[utoipa::path(
+    tag = "root",
+    summary = "Show API information",
+    responses(
+        (status = 200, description = "API information", body = RootResponse),
+    ),
+    security(()),
+)]
 #[get("/")]
-async fn root(req: HttpRequest) -> impl Responder {
+pub async fn root(req: HttpRequest) -> impl Responder {
     let request_id = get_request_id(&req);
 
     let metadata = ResponseMetadata {
-        request_id: request_id.clone(),
+        request_id,
         api_version: API_VERSION.to_string(),
+        start_time: None,
+        end_time: None,
+        duration_ms: None,
     };
 
     let response = RootResponse::new("I AM MENAHEL!".to_string(), 0, Some(metadata));
     web::Json(response)
-        .customize()
-        .append_header(("X-Request-ID", request_id))
 }
 
+#[utoipa::path(
+    tag = "root",
+    summary = "Legacy health check",
+    responses(
+        (status = 200, description = "The server is running", body = RootResponse),
+    ),
+    security(()),
+)]
 #[get("/health")]
-async fn health(req: HttpRequest) -> impl Responder {
+pub async fn health(req: HttpRequest) -> impl Responder {
     let request_id = get_request_id(&req);
 
     let metadata = ResponseMetadata {
-        request_id: request_id.clone(),
+        request_id,
         api_version: API_VERSION.to_string(),
+        start_time: None,
+        end_time: None,
+        duration_ms: None,
     };
     let response = RootResponse::new("MENAHEL IS RUNNING!".to_string(), 0, Some(metadata));
     web::Json(response)
-        .customize()
-        .append_header(("X-Request-ID", request_id))
 }
@@ -1,22 +1,40 @@
+use crate::enums::{AuditEntityType, ProjectRole};
 use crate::errors::handler_errors::HandlerError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::DeletePolicyQuery;
+use crate::handlers::utils::authorize_trash_item;
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_if_match_versions;
+use crate::handlers::utils::get_member_scope;
 use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_batch_error;
 use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{authorize_project, authorize_task};
+use crate::handlers::utils::{with_current_etag, with_etag};
+use crate::models::DeleteResponse;
+use crate::models::GetTasksResponse;
 use crate::models::PaginationParams;
+use crate::models::TaskBatchResponse;
 use crate::models::TaskUserResponse;
 use crate::models::TaskWithUser;
-use crate::models::repository_model::task::{Task, TaskFilter};
+use crate::models::repository_model::task::{Task, TaskFilter, TaskPatch};
 use crate::models::response_model::ErrorResponse;
 use crate::models::response_model::Pagination;
 use crate::models::response_model::PaginationStatus;
 use crate::models::response_model::ResponseMetadata;
 use crate::models::response_model::TaskResponse;
+use crate::models::{TaskBatchOperation, TaskBatchRequest};
 use crate::repository::task_repo::TaskRepository;
 use crate::repository::task_user_repo::TaskUserRepository;
-use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
+use crate::repository::version_repo::{INITIAL_VERSION, Versioned};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, patch, post, web};
 use serde::Deserialize;
+use serde_json::json;
 use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
 enum QueryTarget {
     All,
     Id,
@@ -37,8 +55,10 @@ impl QueryTarget {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct GetTasksQuery {
+    #[param(value_type = Option<QueryTarget>, inline)]
     target: Option<String>,
     page: Option<i32>,
     page_size: Option<i32>,
@@ -89,7 +109,8 @@ impl GetTasksQuery {
         }
     }
 
-    fn get_task_filter(&self) -> Option<TaskFilter> {
+    // member_idを指定した場合はそのユーザーがメンバーのプロジェクトに絞り込む
+    fn get_task_filter(&self, member_id: Option<i64>) -> Option<TaskFilter> {
         let filter = TaskFilter {
             project_id: self.project_id,
             parent_id: self.parent_id,
@@ -104,6 +125,7 @@ impl GetTasksQuery {
             updated_at_from: self.updated_at_from,
             updated_at_to: self.updated_at_to,
             assignee_id: self.assignee_id,
+            member_id,
         };
 
         match filter.is_empty() {
@@ -160,11 +182,10 @@ async fn get_tasks_with_pagination(
             .map_err(HandlerError::from),
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::TaskHandlerGetTasksInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -193,11 +214,10 @@ async fn get_tasks_with_user_pagination(
             .map_err(HandlerError::from),
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::TaskHandlerGetTasksInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -228,32 +248,38 @@ async fn get_all_or_filtered_tasks(
     let user_ids = match query.get_user_ids() {
         Ok(ids) => ids,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let task_filter = match get_member_scope(&req) {
+        Ok(member_id) => query.get_task_filter(member_id),
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
     if !*with_user {
         let result =
-            get_tasks_with_pagination(&pagination_params, query.get_task_filter().as_ref(), pool)
-                .await;
+            get_tasks_with_pagination(&pagination_params, task_filter.as_ref(), pool).await;
 
         match result {
             Ok(tasks) => {
                 let len = tasks.len() as i64;
                 let response = TaskResponse::new(tasks, len, pagination, Some(metadata));
-                log::debug!("Response: {:?}", response);
                 return HttpResponse::Ok().json(response);
             }
             Err(e) => {
-                let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+                let response = ErrorResponse::from_error(&e, Some(metadata));
                 return handle_error(e, response);
             }
         }
     } else {
         let result = get_tasks_with_user_pagination(
             &pagination_params,
-            query.get_task_filter().as_ref(),
+            task_filter.as_ref(),
             pool,
             user_ids.as_ref(),
         )
@@ -263,11 +289,10 @@ async fn get_all_or_filtered_tasks(
             Ok(tasks) => {
                 let len = tasks.len() as i64;
                 let response = TaskUserResponse::new(tasks, len, pagination, Some(metadata));
-                log::debug!("Response: {:?}", response);
                 return HttpResponse::Ok().json(response);
             }
             Err(e) => {
-                let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+                let response = ErrorResponse::from_error(&e, Some(metadata));
                 return handle_error(e, response);
             }
         }
@@ -290,12 +315,18 @@ async fn get_task_or_task_with_user(
 
             match task {
                 Ok(task) => {
+                    let id = task.task_id;
                     let response = TaskResponse::new(vec![task], 1, None, Some(metadata));
-                    log::debug!("Response: {:?}", response);
-                    return HttpResponse::Ok().json(response);
+                    return with_current_etag(
+                        HttpResponse::Ok().json(response),
+                        &pool,
+                        AuditEntityType::Task,
+                        id,
+                    )
+                    .await;
                 }
                 Err(e) => {
-                    let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+                    let response = ErrorResponse::from_error(&e, Some(metadata));
                     return handle_error(e, response);
                 }
             }
@@ -309,12 +340,18 @@ async fn get_task_or_task_with_user(
 
             match task {
                 Ok(task) => {
+                    let id = task.task_id;
                     let response = TaskUserResponse::new(vec![task], 1, None, Some(metadata));
-                    log::debug!("Response: {:?}", response);
-                    return HttpResponse::Ok().json(response);
+                    return with_current_etag(
+                        HttpResponse::Ok().json(response),
+                        &pool,
+                        AuditEntityType::Task,
+                        Some(id),
+                    )
+                    .await;
                 }
                 Err(e) => {
-                    let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+                    let response = ErrorResponse::from_error(&e, Some(metadata));
                     return handle_error(e, response);
                 }
             }
@@ -333,20 +370,34 @@ async fn get_task_by_id(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return HttpResponse::InternalServerError().json(response);
         }
     };
 
-    get_task_or_task_with_user(
-        validated_query.id.clone().unwrap(),
-        pool.clone(),
-        &with_user,
-        metadata,
-    )
-    .await
+    let task_id = validated_query.id.unwrap();
+    if let Err(e) = authorize_task(&req, &pool, task_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    get_task_or_task_with_user(task_id, pool.clone(), &with_user, metadata).await
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Get tasks",
+    description = "target selects the lookup: all (default) or filter return the tasks matching the filter parameters, paginated with page and page_size; id requires id. With with_user=true each task includes its assigned users (TaskUserResponse), optionally limited to user_ids (comma separated).",
+    params(GetTasksQuery),
+    responses(
+        (status = 200, description = "TaskResponse, or TaskUserResponse when with_user=true", body = GetTasksResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[get("/tasks")]
 pub async fn get_tasks(
     req: HttpRequest,
@@ -360,7 +411,7 @@ pub async fn get_tasks(
                 ErrorKey::TaskHandlerInvalidQuery,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -368,7 +419,7 @@ pub async fn get_tasks(
     let target = match query.target() {
         Ok(target) => target,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&e, None);
             return handle_error(e, response);
         }
     };
@@ -386,6 +437,19 @@ pub async fn get_tasks(
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Create a task",
+    request_body = Task,
+    responses(
+        (status = 200, description = "The created task", body = TaskResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/tasks")]
 pub async fn create_task(
     req: HttpRequest,
@@ -401,12 +465,25 @@ pub async fn create_task(
                 ErrorKey::TaskHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let task_repo = TaskRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_project(
+        &req,
+        pool.get_ref(),
+        task_data.project_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let task = task_repo
         .create_task(task_data.into_inner())
         .await
@@ -415,16 +492,33 @@ pub async fn create_task(
     match task {
         Ok(task) => {
             let response = TaskResponse::new(vec![task], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), INITIAL_VERSION);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Update a task",
+    params(
+        ("id" = i64, Path, description = "Task ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = Task,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/tasks/{id}")]
 pub async fn update_task(
     req: HttpRequest,
@@ -441,7 +535,7 @@ pub async fn update_task(
                 ErrorKey::TaskHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -453,7 +547,7 @@ pub async fn update_task(
                 ErrorKey::TaskHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -465,33 +559,143 @@ pub async fn update_task(
             ErrorKey::TaskHandlerPathAndBodyIdMismatch,
             format!("path.id: {:?}, task_data.id: {:?}", path, task_data.task_id),
         ));
-        let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+        let response = ErrorResponse::from_error(&e, Some(metadata));
         return handle_error(e, response);
     }
 
-    let task_repo = TaskRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_task(&req, pool.get_ref(), path, ProjectRole::Member).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let task = task_repo
         .update_task(task_data.into_inner())
         .await
         .map_err(HandlerError::from);
 
     match task {
-        Ok(task) => {
+        Ok(Versioned {
+            value: task,
+            version,
+        }) => {
             let response = TaskResponse::new(vec![task], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), version);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Partially update a task",
+    description = "Only the fields present in the body are validated and updated. `parent_id`, `description` and `deadline` can be cleared with null.",
+    params(
+        ("id" = i64, Path, description = "Task ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = TaskPatch,
+    responses(
+        (status = 200, description = "The updated task", body = TaskResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[patch("/tasks/{id}")]
+pub async fn patch_task(
+    req: HttpRequest,
+    patch_data: Result<web::Json<TaskPatch>, actix_web::Error>,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let patch_data = match patch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_task(&req, pool.get_ref(), path, ProjectRole::Member).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let task = task_repo
+        .patch_task(path, patch_data)
+        .await
+        .map_err(HandlerError::from);
+
+    match task {
+        Ok(Versioned {
+            value: task,
+            version,
+        }) => {
+            let response = TaskResponse::new(vec![task], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 削除ポリシーの既定はrestrict。cascadeは子孫と一緒に、reparentは子を親の親に付け替えてゴミ箱に移動する
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Move a task to the trash according to the delete policy",
+    params(
+        ("id" = i64, Path, description = "Task ID"),
+        DeletePolicyQuery,
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    responses(
+        (status = 200, description = "Number of removed and reparented rows", body = DeleteResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Dependent records prevent deletion under the policy", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[delete("/tasks/{id}")]
 pub async fn delete_task(
     req: HttpRequest,
     path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<DeletePolicyQuery>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
 ) -> HttpResponse {
     let metadata = ResponseMetadata::new(get_request_id(&req));
@@ -503,25 +707,192 @@ pub async fn delete_task(
                 ErrorKey::TaskHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let task_repo = TaskRepository::new(pool.get_ref().clone());
+    let policy = match query {
+        Ok(query) => query.get_delete_policy(),
+        Err(e) => Err(HandlerError::BadRequest(get_error_message(
+            ErrorKey::TaskHandlerInvalidQuery,
+            format!("ActixWebError: {}", e),
+        ))),
+    };
+    let policy = match policy {
+        Ok(policy) => policy,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    if let Err(e) = authorize_task(&req, pool.get_ref(), path, ProjectRole::Member).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let summary = task_repo
+        .delete_task(path, policy)
+        .await
+        .map_err(HandlerError::from);
+
+    match summary {
+        Ok(summary) => {
+            let response = DeleteResponse::new(summary, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Restore a task from the trash together with its subtree, assignments and comments",
+    params(
+        ("id" = i64, Path, description = "Task ID"),
+    ),
+    responses(
+        (status = 200, description = "The restored task", body = TaskResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The task is not in the trash", body = ErrorResponse),
+        (status = 409, description = "The parent task or project is in the trash", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/tasks/{id}/restore")]
+pub async fn restore_task(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_trash_item(
+        &req,
+        pool.get_ref(),
+        AuditEntityType::Task,
+        path,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let task = task_repo
-        .delete_task(path)
+        .restore_task(path)
         .await
         .map_err(HandlerError::from);
 
     match task {
-        Ok(()) => {
-            let response = TaskResponse::new(vec![], 0, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+        Ok(Versioned {
+            value: task,
+            version,
+        }) => {
+            let response = TaskResponse::new(vec![task], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
-            return handle_error(e, response);
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 操作対象のタスク（作成の場合はプロジェクト）で必要なロールを持っているか確認する
+async fn authorize_task_batch_operation(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    operation: &TaskBatchOperation,
+) -> Result<(), HandlerError> {
+    match operation {
+        TaskBatchOperation::Create { task, .. } => {
+            authorize_project(req, pool, task.project_id, ProjectRole::Member).await
+        }
+        TaskBatchOperation::Update { task, .. } => {
+            if let Some(task_id) = task.task_id {
+                authorize_task(req, pool, task_id, ProjectRole::Member).await?;
+            }
+            authorize_project(req, pool, task.project_id, ProjectRole::Member).await
+        }
+        TaskBatchOperation::Delete { task_id, .. } => {
+            authorize_task(req, pool, *task_id, ProjectRole::Member).await
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Create, update and delete tasks in one transaction",
+    description = "Operations run in order in a single transaction and any failure rolls back the whole batch. The error body has the index of the failed operation in `details.index`. A task created with `temp_id` can be used as the parent of later operations through `parent_temp_id`.",
+    request_body = TaskBatchRequest,
+    responses(
+        (status = 200, description = "The result of each operation", body = TaskBatchResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the given version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/tasks/batch")]
+pub async fn batch_tasks(
+    req: HttpRequest,
+    batch_data: Result<web::Json<TaskBatchRequest>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let batch_data = match batch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    for (index, operation) in batch_data.operations.iter().enumerate() {
+        if let Err(e) = authorize_task_batch_operation(&req, pool.get_ref(), operation).await {
+            return handle_batch_error(e, Some(index), metadata);
+        }
+    }
+
+    let task_repo = TaskRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
+    match task_repo.run_task_batch(batch_data.operations).await {
+        Ok(results) => {
+            let response = TaskBatchResponse::new(results, Some(metadata));
+            HttpResponse::Ok().json(response)
         }
+        Err(e) => handle_batch_error(HandlerError::from(e.error), e.index, metadata),
     }
 }
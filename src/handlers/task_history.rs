@@ -0,0 +1,122 @@
+use crate::enums::ProjectRole;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::authorize_task;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::models::PaginationParams;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::Pagination;
+use crate::models::response_model::PaginationStatus;
+use crate::models::response_model::ResponseMetadata;
+use crate::models::response_model::TaskHistoryResponse;
+use crate::repository::task_history_repo::TaskHistoryRepository;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GetTaskHistoryQuery {
+    page: Option<i32>,
+    page_size: Option<i32>,
+}
+
+// 古い順にタスクの変更履歴を返す
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Get the per-field change history of a task",
+    params(("id" = i64, Path, description = "Task ID"), GetTaskHistoryQuery),
+    responses(
+        (status = 200, description = "Success", body = TaskHistoryResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/tasks/{id}/history")]
+pub async fn get_task_history(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<GetTaskHistoryQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let task_id = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHistoryHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHistoryHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let mut pagination_params = PaginationParams::new(query.page, query.page_size);
+    pagination_params.validate();
+    let pagination = match pagination_params.status() {
+        PaginationStatus::Active => Some(Pagination {
+            current_page: *pagination_params.page().unwrap(),
+            page_size: *pagination_params.page_size().unwrap(),
+        }),
+        PaginationStatus::Inactive => None,
+        PaginationStatus::Error => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TaskHistoryHandlerInvalidPage,
+                [
+                    ("page", json!(pagination_params.page())),
+                    ("page_size", json!(pagination_params.page_size())),
+                ],
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    if let Err(e) = authorize_task(&req, pool.get_ref(), task_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_history_repo = TaskHistoryRepository::new(pool.get_ref().clone());
+    let task_histories = task_history_repo
+        .get_task_history_by_task_id(
+            task_id,
+            pagination_params.page(),
+            pagination_params.page_size(),
+        )
+        .await
+        .map_err(HandlerError::from);
+
+    match task_histories {
+        Ok(task_histories) => {
+            let len = task_histories.len() as i64;
+            let response =
+                TaskHistoryResponse::new(task_histories, len, pagination, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use crate::enums::ProjectRole;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{authorize_project, authorize_task};
+use crate::models::TaskFilter;
+use crate::models::TaskTreeNode;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::models::response_model::TaskTreeResponse;
+use crate::repository::task_tree_repo::TaskTreeRepository;
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GetTaskTreeQuery {
+    // ルートからの深さの上限。未指定の場合はすべての子孫
+    max_depth: Option<i64>,
+    level: Option<i64>,
+    name: Option<String>,
+    description: Option<String>,
+    status: Option<i64>,
+    deadline_from: Option<i64>,
+    deadline_to: Option<i64>,
+    assignee_id: Option<i64>,
+}
+
+impl GetTaskTreeQuery {
+    fn get_task_filter(&self) -> Option<TaskFilter> {
+        let filter = TaskFilter {
+            level: self.level,
+            name: self.name.clone(),
+            description: self.description.clone(),
+            status: self.status,
+            deadline_from: self.deadline_from,
+            deadline_to: self.deadline_to,
+            ..TaskFilter::new()
+        };
+
+        match filter.is_empty() {
+            true => None,
+            false => Some(filter),
+        }
+    }
+
+    fn get_user_ids(&self) -> Option<Vec<i64>> {
+        self.assignee_id.map(|assignee_id| vec![assignee_id])
+    }
+}
+
+fn parse_tree_request(
+    path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<GetTaskTreeQuery>, actix_web::Error>,
+) -> Result<(i64, GetTaskTreeQuery), HandlerError> {
+    let id = path.map_err(|e| {
+        HandlerError::BadRequest(get_error_message(
+            ErrorKey::TaskTreeHandlerInvalidPath,
+            format!("ActixWebError: {}", e),
+        ))
+    })?;
+    let query = query.map_err(|e| {
+        HandlerError::BadRequest(get_error_message(
+            ErrorKey::TaskTreeHandlerInvalidQuery,
+            format!("ActixWebError: {}", e),
+        ))
+    })?;
+    Ok((id.into_inner(), query.into_inner()))
+}
+
+fn tree_response(
+    nodes: Result<Vec<TaskTreeNode>, HandlerError>,
+    metadata: ResponseMetadata,
+) -> HttpResponse {
+    match nodes {
+        Ok(nodes) => {
+            let response = TaskTreeResponse::new(nodes, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// フィルタを指定した場合は一致したタスクとその祖先だけを残す
+#[utoipa::path(
+    tag = "tasks",
+    summary = "Get a task and its descendants as a nested tree with assignees",
+    params(("id" = i64, Path, description = "Task ID"), GetTaskTreeQuery),
+    responses(
+        (status = 200, description = "Success", body = TaskTreeResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/tasks/{id}/tree")]
+pub async fn get_task_tree(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<GetTaskTreeQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let (task_id, query) = match parse_tree_request(path, query) {
+        Ok(request) => request,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    if let Err(e) = authorize_task(&req, pool.get_ref(), task_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_tree_repo = TaskTreeRepository::new(pool.get_ref().clone());
+    let nodes = task_tree_repo
+        .get_task_tree(
+            task_id,
+            query.get_task_filter().as_ref(),
+            query.get_user_ids().as_ref(),
+            query.max_depth,
+        )
+        .await
+        .map_err(HandlerError::from);
+
+    tree_response(nodes, metadata)
+}
+
+// プロジェクトの親のないタスクをルートとして返す
+#[utoipa::path(
+    tag = "projects",
+    summary = "Get all tasks of a project as nested trees with assignees",
+    params(("id" = i64, Path, description = "Project ID"), GetTaskTreeQuery),
+    responses(
+        (status = 200, description = "Success", body = TaskTreeResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/projects/{id}/tree")]
+pub async fn get_project_tree(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<GetTaskTreeQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let (project_id, query) = match parse_tree_request(path, query) {
+        Ok(request) => request,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    if let Err(e) = authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let task_tree_repo = TaskTreeRepository::new(pool.get_ref().clone());
+    let nodes = task_tree_repo
+        .get_project_tree(
+            project_id,
+            query.get_task_filter().as_ref(),
+            query.get_user_ids().as_ref(),
+            query.max_depth,
+        )
+        .await
+        .map_err(HandlerError::from);
+
+    tree_response(nodes, metadata)
+}
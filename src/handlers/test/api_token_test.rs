@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod api_token_handler_test {
+    use crate::constants::API_TOKEN_PREFIX;
+    use crate::handlers::api_token::{create_api_token, get_api_tokens, revoke_api_token};
+    use crate::handlers::test::utils::{login_as, setup_test_db};
+    use crate::handlers::user::get_users;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::{ApiTokenResponse, ErrorResponse, IssuedApiTokenResponse, UserResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+    use chrono::Utc;
+    use serde_json::json;
+
+    #[ctor::ctor]
+    fn init() {
+        // Create test_db directory if it doesn't exist
+        if !std::path::Path::new("./test_db/api_token_handler_test").exists() {
+            std::fs::create_dir_all("./test_db/api_token_handler_test").unwrap();
+        }
+
+        // Remove all files in the test_db directory
+        let files = std::fs::read_dir("./test_db/api_token_handler_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_api_token_lifecycle() {
+        let pool = setup_test_db("api_token_handler_test", "test_api_token_lifecycle").await;
+        let session = login_as(&pool, 1).await;
+        let other = login_as(&pool, 2).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_api_token)
+                .service(get_api_tokens)
+                .service(revoke_api_token)
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let expires_at = Utc::now().timestamp() + 3600;
+        let req = test::TestRequest::post()
+            .uri("/auth/tokens")
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .set_json(json!({"name": "ci", "expires_at": expires_at}))
+            .to_request();
+        let res: IssuedApiTokenResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 1);
+        let issued = res.results[0].clone();
+        assert!(issued.token.starts_with(API_TOKEN_PREFIX));
+        assert_eq!(issued.token_type, "Bearer");
+        assert_eq!(issued.api_token.user_id, 1);
+        assert_eq!(issued.api_token.name, "ci");
+        assert_eq!(issued.api_token.expires_at, Some(expires_at));
+        assert_eq!(issued.api_token.last_used_at, None);
+
+        // APIトークンでセッションと同様にアクセスできる
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header(("Authorization", format!("Bearer {}", issued.token)))
+            .to_request();
+        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/auth/tokens")
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .to_request();
+        let res: ApiTokenResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_ne!(res.results[0].last_used_at, None);
+
+        // 他のユーザーのトークンは無効化できない
+        let req = test::TestRequest::delete()
+            .uri(&format!(
+                "/auth/tokens/{}",
+                issued.api_token.api_token_id.unwrap()
+            ))
+            .insert_header(("Authorization", format!("Bearer {}", other)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::delete()
+            .uri(&format!(
+                "/auth/tokens/{}",
+                issued.api_token.api_token_id.unwrap()
+            ))
+            .insert_header(("Authorization", format!("Bearer {}", session)))
+            .to_request();
+        let res: ApiTokenResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_ne!(res.results[0].revoked_at, None);
+
+        // 無効化したトークンは使えない
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header(("Authorization", format!("Bearer {}", issued.token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_create_api_token_invalid() {
+        let pool = setup_test_db("api_token_handler_test", "test_create_api_token_invalid").await;
+        let session = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_api_token)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/auth/tokens")
+            .set_json(json!({"name": "ci"}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        for body in [
+            json!({"name": ""}),
+            json!({"name": "ci", "expires_at": 1}),
+            json!({"expires_at": 1}),
+        ] {
+            let req = test::TestRequest::post()
+                .uri("/auth/tokens")
+                .insert_header(("Authorization", format!("Bearer {}", session)))
+                .set_json(body)
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+            let body: ErrorResponse = test::read_body_json(res).await;
+            assert_eq!(body.rc, 2);
+        }
+
+        // 存在しないAPIトークンは拒否される
+        let req = test::TestRequest::post()
+            .uri("/auth/tokens")
+            .insert_header((
+                "Authorization",
+                format!("Bearer {}invalid_token", API_TOKEN_PREFIX),
+            ))
+            .set_json(json!({"name": "ci"}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod audit_log_handler_test {
+    use crate::enums::ProjectRole;
+    use crate::handlers::audit_log::get_audit_logs;
+    use crate::handlers::project::create_project;
+    use crate::handlers::task::delete_task;
+    use crate::handlers::test::utils::{as_admin, login_as, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::{AuditLogResponse, ErrorResponse, Project, ProjectMember, ProjectResponse};
+    use crate::repository::project_member_repo::ProjectMemberRepository;
+    use crate::repository::user_repo::UserRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        // Create test_db directory if it doesn't exist
+        if !std::path::Path::new("./test_db/audit_log_handler_test").exists() {
+            std::fs::create_dir_all("./test_db/audit_log_handler_test").unwrap();
+        }
+
+        // Remove all files in the test_db directory
+        let files = std::fs::read_dir("./test_db/audit_log_handler_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_audit_logs() {
+        let pool = setup_test_db("audit_log_handler_test", "test_get_audit_logs").await;
+        let token = login_as(&pool, 1).await;
+        ProjectMemberRepository::new(pool.clone())
+            .create_project_member(ProjectMember::new(2, 1, ProjectRole::Member))
+            .await
+            .unwrap();
+        UserRepository::new(pool.clone())
+            .set_admin(1, true)
+            .await
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_project)
+                .service(delete_task)
+                .service(get_audit_logs)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/projects")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .insert_header(("X-Request-ID", "audit-create-project"))
+            .set_json(Project::new("AuditedProject".to_string()))
+            .to_request();
+        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        let project_id = res.results[0].project_id.unwrap();
+
+        let req = test::TestRequest::delete()
+            .uri("/tasks/9")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .insert_header(("X-Request-ID", "audit-delete-task"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/audit?entity_type=project&entity_id={}",
+                project_id
+            ))
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res: AuditLogResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].action, "create");
+        assert_eq!(res.results[0].actor_user_id, Some(1));
+        assert_eq!(
+            res.results[0].request_id.as_deref(),
+            Some("audit-create-project")
+        );
+
+        let req = test::TestRequest::get()
+            .uri("/audit?action=delete&actor_user_id=1&page=1&page_size=10")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res: AuditLogResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].entity_type, "task");
+        assert_eq!(res.results[0].entity_id, 9);
+        assert_eq!(
+            res.results[0].request_id.as_deref(),
+            Some("audit-delete-task")
+        );
+        assert!(res.pagination.is_some());
+    }
+
+    #[actix_web::test]
+    async fn test_get_audit_logs_invalid_query() {
+        let pool = setup_test_db(
+            "audit_log_handler_test",
+            "test_get_audit_logs_invalid_query",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_audit_logs)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        for uri in [
+            "/audit?page=1",
+            "/audit?page=0&page_size=10",
+            "/audit?entity_type=unknown",
+            "/audit?action=unknown",
+            "/audit?entity_id=abc",
+        ] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "{}", uri);
+            let body: ErrorResponse = test::read_body_json(res).await;
+            assert_eq!(body.rc, 2);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_audit_logs_not_admin() {
+        let pool = setup_test_db("audit_log_handler_test", "test_get_audit_logs_not_admin").await;
+        let token = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(get_audit_logs)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/audit")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let body: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(body.rc, 4);
+    }
+}
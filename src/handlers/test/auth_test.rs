@@ -0,0 +1,204 @@
+#[cfg(test)]
+mod auth_handler_test {
+    use crate::handlers::auth::{login, logout};
+    use crate::handlers::root::health;
+    use crate::handlers::test::utils::setup_test_db;
+    use crate::handlers::user::get_users;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::response_model::AuthResponse;
+    use crate::models::{ErrorResponse, RootResponse, User, UserResponse};
+    use crate::repository::user_repo::UserRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+    use serde_json::json;
+    use sqlx::sqlite::SqlitePool;
+
+    #[ctor::ctor]
+    fn init() {
+        // Create test_db directory if it doesn't exist
+        if !std::path::Path::new("./test_db/auth_handler_test").exists() {
+            std::fs::create_dir_all("./test_db/auth_handler_test").unwrap();
+        }
+
+        // Remove all files in the test_db directory
+        let files = std::fs::read_dir("./test_db/auth_handler_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    async fn setup_auth_user(pool: &SqlitePool) {
+        let user_repo = UserRepository::new(pool.clone());
+        let user = User::new(
+            "auth_user".to_string(),
+            "auth_user@example.com".to_string(),
+            "auth_password".to_string(),
+        )
+        .unwrap();
+        user_repo.create_user(user).await.unwrap();
+    }
+
+    #[actix_web::test]
+    async fn test_login_and_logout() {
+        let pool = setup_test_db("auth_handler_test", "test_login_and_logout").await;
+        setup_auth_user(&pool).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(login)
+                .service(logout)
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({"username": "auth_user", "password": "auth_password"}))
+            .to_request();
+        let res: AuthResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].token_type, "Bearer");
+        assert_eq!(res.results[0].user.username, "auth_user");
+        let token = res.results[0].token.clone();
+
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        let req = test::TestRequest::post()
+            .uri("/auth/logout")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res: AuthResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        // ログアウト後のトークンは使えない
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_logout_all() {
+        let pool = setup_test_db("auth_handler_test", "test_logout_all").await;
+        setup_auth_user(&pool).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(login)
+                .service(logout)
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let mut tokens = Vec::new();
+        for _ in 0..2 {
+            let req = test::TestRequest::post()
+                .uri("/auth/login")
+                .set_json(json!({"username": "auth_user", "password": "auth_password"}))
+                .to_request();
+            let res: AuthResponse = test::call_and_read_body_json(&app, req).await;
+            tokens.push(res.results[0].token.clone());
+        }
+        assert_ne!(tokens[0], tokens[1]);
+
+        let req = test::TestRequest::post()
+            .uri("/auth/logout?all=true")
+            .insert_header(("Authorization", format!("Bearer {}", tokens[0])))
+            .to_request();
+        let res: AuthResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        for token in tokens {
+            let req = test::TestRequest::get()
+                .uri("/users")
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_login_invalid_credentials() {
+        let pool = setup_test_db("auth_handler_test", "test_login_invalid_credentials").await;
+        setup_auth_user(&pool).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(login)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        for (username, password) in [("auth_user", "wrong"), ("no_such_user", "auth_password")] {
+            let req = test::TestRequest::post()
+                .uri("/auth/login")
+                .set_json(json!({"username": username, "password": password}))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+            let body: ErrorResponse = test::read_body_json(res).await;
+            assert_eq!(body.rc, 3);
+        }
+
+        let req = test::TestRequest::post()
+            .uri("/auth/login")
+            .set_json(json!({"username": "auth_user"}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[actix_web::test]
+    async fn test_auth_middleware_rejects_request() {
+        let pool = setup_test_db("auth_handler_test", "test_auth_middleware_rejects_request").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(health)
+                .service(get_users)
+                .service(logout)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 公開パスは認証不要
+        let req = test::TestRequest::get().uri("/health").to_request();
+        let res: RootResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.message, "MENAHEL IS RUNNING!");
+
+        let req = test::TestRequest::get().uri("/users").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get()
+            .uri("/users")
+            .insert_header(("Authorization", "Bearer invalid_token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let body: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(body.rc, 3);
+
+        let req = test::TestRequest::post().uri("/auth/logout").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod batch_test {
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::task::{batch_tasks, get_tasks, update_task};
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::handlers::user_assign::{batch_user_assigns, get_user_assigns, update_user_assign};
+    use crate::models::{
+        ErrorResponse, TaskBatchResponse, TaskResponse, UserAssignBatchResponse, UserAssignResponse,
+    };
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+    use serde_json::json;
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/batch_test").exists() {
+            std::fs::create_dir_all("./test_db/batch_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/batch_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    fn task_json(task_id: Option<i64>, level: i64, name: &str) -> serde_json::Value {
+        json!({
+            "task_id": task_id,
+            "project_id": 0,
+            "parent_id": null,
+            "level": level,
+            "name": name,
+            "description": null,
+            "status": 0,
+            "deadline": null,
+            "created_at": 0,
+            "updated_at": null,
+        })
+    }
+
+    #[actix_web::test]
+    async fn test_batch_tasks_with_temp_ids() {
+        let pool = setup_test_db("batch_test", "test_batch_tasks_with_temp_ids").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(batch_tasks)
+                .service(update_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "temp_id": "major", "task": task_json(None, 0, "BatchMajor") },
+                    { "op": "create", "temp_id": "minor", "parent_temp_id": "major", "task": task_json(None, 1, "BatchMinor") },
+                    { "op": "create", "parent_temp_id": "minor", "task": task_json(None, 2, "BatchTrivial") },
+                    { "op": "update", "version": 1, "task": task_json(Some(0), 0, "BatchUpdated") },
+                    { "op": "delete", "task_id": 9 },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: TaskBatchResponse = test::read_body_json(res).await;
+        assert_eq!(res.count, 5);
+        let ops: Vec<&str> = res.results.iter().map(|r| r.op.as_str()).collect();
+        assert_eq!(ops, ["create", "create", "create", "update", "delete"]);
+        assert_eq!(res.results[0].temp_id.as_deref(), Some("major"));
+        assert_eq!(res.results[1].task.parent_id, res.results[0].task.task_id);
+        assert_eq!(res.results[2].task.parent_id, res.results[1].task.task_id);
+        assert_eq!(res.results[2].temp_id, None);
+        assert_eq!(res.results[3].task.name, "BatchUpdated");
+        assert_eq!(res.results[4].task.task_id, Some(9));
+
+        let req = test::TestRequest::get()
+            .uri(&format!(
+                "/tasks?target=id&id={}",
+                res.results[2].task.task_id.unwrap()
+            ))
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].name, "BatchTrivial");
+    }
+
+    #[actix_web::test]
+    async fn test_batch_tasks_rollback() {
+        let pool = setup_test_db("batch_test", "test_batch_tasks_rollback").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(batch_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/tasks").to_request();
+        let before: TaskResponse = test::call_and_read_body_json(&app, req).await;
+
+        // 2件目の一時IDが未定義のため、1件目の作成も取り消される
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "temp_id": "major", "task": task_json(None, 0, "BatchMajor") },
+                    { "op": "create", "parent_temp_id": "unknown", "task": task_json(None, 1, "BatchMinor") },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::BatchTempIdNotFound.to_string());
+        assert_eq!(res.field.as_deref(), Some("parent_temp_id"));
+        let details = res.details.unwrap();
+        assert_eq!(details["index"], 1);
+        assert_eq!(details["temp_id"], "unknown");
+
+        // 一時IDは後の操作で作成するタスクを参照できない
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "parent_temp_id": "major", "task": task_json(None, 1, "BatchMinor") },
+                    { "op": "create", "temp_id": "major", "task": task_json(None, 0, "BatchMajor") },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.details.unwrap()["index"], 0);
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "temp_id": "major", "task": task_json(None, 0, "BatchMajor") },
+                    { "op": "create", "temp_id": "major", "task": task_json(None, 0, "BatchMajor") },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::BatchTempIdDuplicate.to_string());
+
+        // バージョンが一致しない場合は412になり、前の更新も取り消される
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "update", "task": task_json(Some(0), 0, "BatchUpdated") },
+                    { "op": "delete", "task_id": 9, "version": 2 },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::VersionMismatch.to_string());
+        let details = res.details.unwrap();
+        assert_eq!(details["index"], 1);
+        assert_eq!(details["version"], 1);
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/batch")
+            .set_json(json!({ "operations": [] }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::BatchOperationsEmpty.to_string());
+        assert_eq!(res.details.unwrap().get("index"), None);
+
+        let req = test::TestRequest::get().uri("/tasks").to_request();
+        let after: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(after.count, before.count);
+        assert_eq!(after.results, before.results);
+    }
+
+    #[actix_web::test]
+    async fn test_batch_user_assigns() {
+        let pool = setup_test_db("batch_test", "test_batch_user_assigns").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_user_assigns)
+                .service(batch_user_assigns)
+                .service(update_user_assign)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/userassigns/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "user_assign": { "user_assign_id": null, "user_id": 3, "task_id": 7 } },
+                    { "op": "update", "version": 1, "user_assign": { "user_assign_id": 0, "user_id": 0, "task_id": 7 } },
+                    { "op": "delete", "user_assign_id": 1 },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: UserAssignBatchResponse = test::read_body_json(res).await;
+        assert_eq!(res.count, 3);
+        assert_eq!(res.results[0].op, "create");
+        assert_eq!(res.results[0].user_assign.user_id, 3);
+        assert_eq!(res.results[1].user_assign.task_id, 7);
+        assert_eq!(res.results[2].op, "delete");
+        assert_eq!(res.results[2].user_assign.user_assign_id, Some(1));
+
+        // 2件目が既存の割り当てと重複するため、1件目の作成も取り消される
+        let req = test::TestRequest::post()
+            .uri("/userassigns/batch")
+            .set_json(json!({
+                "operations": [
+                    { "op": "create", "user_assign": { "user_assign_id": null, "user_id": 4, "task_id": 7 } },
+                    { "op": "create", "user_assign": { "user_assign_id": null, "user_id": 3, "task_id": 7 } },
+                ]
+            }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(
+            res.code,
+            ErrorKey::UserAssignSameUserAssignExists.to_string()
+        );
+        assert_eq!(res.details.unwrap()["index"], 1);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=filter&userid=4&taskid=7")
+            .to_request();
+        let res: UserAssignResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+    }
+}
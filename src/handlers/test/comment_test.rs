@@ -1,10 +1,19 @@
 #[cfg(test)]
 mod comment_handler_test {
-    use crate::handlers::comment::{create_comment, delete_comment, get_comments, update_comment};
-    use crate::handlers::test::utils::setup_test_db;
+    use crate::enums::ProjectRole;
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::comment::{
+        create_comment, delete_comment, get_comments, patch_comment, update_comment,
+    };
+    use crate::handlers::test::utils::{as_admin, login_as, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::ProjectMember;
     use crate::models::comment::Comment;
     use crate::models::response_model::ErrorResponse;
     use crate::models::response_model::{CommentResponse, CommentUserResponse};
+    use crate::repository::project_member_repo::ProjectMemberRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
     use actix_web::{App, test, web};
 
     #[ctor::ctor]
@@ -30,6 +39,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -51,6 +61,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -78,6 +89,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -101,6 +113,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -123,6 +136,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -150,6 +164,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -183,6 +198,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -210,6 +226,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -248,6 +265,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -258,16 +276,14 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/comments?page=1&page_size=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target指定なし + page/page_size指定(page_sizeが1000以上)
@@ -281,6 +297,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -291,8 +308,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target指定なし + pageのみ指定
@@ -306,6 +322,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -316,8 +333,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target指定なし + page_sizeのみ指定
@@ -331,6 +347,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -341,8 +358,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target: id指定 + idが無い
@@ -352,6 +368,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -362,8 +379,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target: id指定 + idが数値でない
@@ -373,6 +389,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -382,8 +399,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target: id指定 + idが存在しない
@@ -393,6 +409,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -402,8 +419,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     // - target: id指定 + idが0以下
@@ -417,6 +433,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -426,8 +443,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - target: task_id指定 + task_idが無い
@@ -441,6 +457,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -450,8 +467,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - target: task_id指定 + task_idが数値でない
     #[actix_web::test]
@@ -464,6 +480,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -473,8 +490,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - target: task_id指定 + task_idが存在しない
     #[actix_web::test]
@@ -487,6 +503,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -512,6 +529,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -521,8 +539,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - target: user_id指定 + user_idが無い
     #[actix_web::test]
@@ -535,6 +552,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -544,8 +562,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - target: user_id指定 + user_idが数値でない
     #[actix_web::test]
@@ -558,6 +575,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -567,8 +585,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - target: user_id指定 + user_idが存在しない
     #[actix_web::test]
@@ -581,6 +598,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -606,6 +624,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_comments)
                 .app_data(web::Data::new(pool)),
         )
@@ -615,8 +634,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // [CUD正常系]
@@ -636,6 +654,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -667,6 +686,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -689,6 +709,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -710,6 +731,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -717,8 +739,7 @@ mod comment_handler_test {
         let req = test::TestRequest::post().uri("/comments").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント作成: POST user_idが-1
@@ -740,6 +761,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -750,8 +772,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント作成: POST user_idが存在しない
@@ -773,6 +794,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -783,8 +805,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     // - コメント作成: POST task_idが-1
@@ -806,6 +827,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -816,8 +838,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - コメント作成: POST task_idが存在しない
     #[actix_web::test]
@@ -838,6 +859,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -848,8 +870,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
     // - コメント作成: POST taskのlevelがmax_levelでない
     #[actix_web::test]
@@ -870,6 +891,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -880,8 +902,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント作成: POST contentが2025文字以上
@@ -903,6 +924,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -913,8 +935,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - コメント作成: POST contentが空文字
     #[actix_web::test]
@@ -935,6 +956,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -945,8 +967,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント作成: POST comment_idを指定して追加
@@ -968,6 +989,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -978,8 +1000,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST JSONデータ無し
@@ -989,6 +1010,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -996,8 +1018,7 @@ mod comment_handler_test {
         let req = test::TestRequest::post().uri("/comments/1").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - コメント更新: POST pathが数値でない
     #[actix_web::test]
@@ -1015,6 +1036,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1025,8 +1047,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST pathが0以下
@@ -1048,6 +1069,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1058,9 +1080,8 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
+        assert_eq!(res.rc, 2);
         println!("{}", res.message);
-        assert!(res.message.contains("BadRequest"));
     }
 
     // - コメント更新: POST pathとJSONデータのcomment_idが一致しない
@@ -1082,6 +1103,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1092,8 +1114,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST pathで指定したidが存在しない。
@@ -1115,6 +1136,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1125,9 +1147,8 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
+        assert_eq!(res.rc, 5);
         println!("{}", res.message);
-        assert!(res.message.contains("NotFound"));
     }
 
     // - コメント更新: POST user_idが-1
@@ -1149,6 +1170,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1159,8 +1181,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST user_idが存在しない
@@ -1182,6 +1203,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1192,8 +1214,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     // - コメント更新: POST taskのlevelがmax_levelでない
@@ -1215,6 +1236,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1225,8 +1247,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST task_idが-1
@@ -1248,6 +1269,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1258,8 +1280,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST task_idが存在しない
@@ -1281,6 +1302,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1291,8 +1313,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     // - コメント更新: POST contentが2025文字以上
@@ -1314,6 +1335,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1324,8 +1346,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント更新: POST contentが空文字
@@ -1347,6 +1368,7 @@ mod comment_handler_test {
         };
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1357,8 +1379,7 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     // - コメント削除: DELETE pathが数値でない
@@ -1368,6 +1389,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1375,8 +1397,7 @@ mod comment_handler_test {
         let req = test::TestRequest::delete().uri("/comments/a").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - コメント削除: DELETE pathが0以下
     #[actix_web::test]
@@ -1389,6 +1410,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1396,8 +1418,7 @@ mod comment_handler_test {
         let req = test::TestRequest::delete().uri("/comments/-1").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
     // - コメント削除: DELETE pathで指定したidが存在しない
     #[actix_web::test]
@@ -1410,6 +1431,7 @@ mod comment_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_comment)
                 .app_data(web::Data::new(pool)),
         )
@@ -1419,7 +1441,132 @@ mod comment_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
+    }
+
+    #[actix_web::test]
+    async fn test_patch_comment() {
+        let pool = setup_test_db("comment_handler_test", "test_patch_comment").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_comment)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/comments/1")
+            .set_json(serde_json::json!({ "content": "PATCH_comment" }))
+            .to_request();
+        let res: CommentResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].content, "PATCH_comment");
+        assert_eq!(res.results[0].task_id, 3);
+        assert_eq!(res.results[0].user_id, 0);
+
+        let req = test::TestRequest::patch()
+            .uri("/comments/1")
+            .set_json(serde_json::json!({ "content": "" }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 2);
+
+        let req = test::TestRequest::patch()
+            .uri("/comments/100")
+            .set_json(serde_json::json!({ "content": "PATCH_comment" }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 5);
+    }
+
+    // - 他のユーザーとしてコメントできず、他のユーザーのコメントはオーナーだけが変更・削除できる
+    #[actix_web::test]
+    async fn test_comment_author() {
+        let pool = setup_test_db("comment_handler_test", "test_comment_author").await;
+        let project_member_repo = ProjectMemberRepository::new(pool.clone());
+        project_member_repo
+            .create_project_member(ProjectMember::new(0, 1, ProjectRole::Member))
+            .await
+            .unwrap();
+        let token = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_comment)
+                .service(update_comment)
+                .service(patch_comment)
+                .service(delete_comment)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/comments")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(Comment::new(2, 5, "test".to_string()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::CommentHandlerUserMismatch.to_string());
+
+        let req = test::TestRequest::post()
+            .uri("/comments")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(Comment::new(1, 5, "test".to_string()))
+            .to_request();
+        let res: CommentResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].user_id, 1);
+
+        // 自分のコメントは変更できるが、投稿者は変更できない
+        let req = test::TestRequest::patch()
+            .uri("/comments/6")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(serde_json::json!({ "content": "PATCH_comment" }))
+            .to_request();
+        let res: CommentResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        let mut comment = Comment::new(2, 3, "test".to_string());
+        comment.comment_id = Some(6);
+        let req = test::TestRequest::post()
+            .uri("/comments/6")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(comment)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // メンバーは他のユーザーのコメントを変更・削除できない
+        let req = test::TestRequest::patch()
+            .uri("/comments/1")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(serde_json::json!({ "content": "PATCH_comment" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::delete()
+            .uri("/comments/2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // オーナーは他のユーザーのコメントを削除できる
+        project_member_repo
+            .update_project_member_role(0, 1, ProjectRole::Owner.to_int())
+            .await
+            .unwrap();
+        let req = test::TestRequest::delete()
+            .uri("/comments/2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
     }
 }
@@ -0,0 +1,208 @@
+#[cfg(test)]
+mod delete_policy_test {
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::project::delete_project;
+    use crate::handlers::task::{delete_task, get_tasks};
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::handlers::user::delete_user;
+    use crate::handlers::user_assign::get_user_assigns;
+    use crate::models::{DeleteResponse, ErrorResponse, UserAssignResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/delete_policy_test").exists() {
+            std::fs::create_dir_all("./test_db/delete_policy_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/delete_policy_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_delete_task_with_policy() {
+        let pool = setup_test_db("delete_policy_test", "test_delete_task_with_policy").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(delete_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 未指定の場合はrestrictになり、依存するデータの件数を返す
+        let req = test::TestRequest::delete().uri("/tasks/2").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::DeletePolicyRestricted.to_string());
+        let details = res.details.unwrap();
+        assert_eq!(details["children"], 5);
+        assert_eq!(details["user_assigns"], 3);
+        assert_eq!(details["comments"], 4);
+
+        let req = test::TestRequest::delete()
+            .uri("/tasks/2?policy=unknown")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::DeletePolicyInvalid.to_string());
+        assert_eq!(res.field.as_deref(), Some("policy"));
+
+        // 割り当てのある小タスク（タスク10）は中タスクにできない
+        let req = test::TestRequest::delete()
+            .uri("/tasks/0?policy=reparent")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(
+            res.code,
+            ErrorKey::DeletePolicyReparentLevelConflict.to_string()
+        );
+        assert_eq!(res.details.unwrap()["id"], 10);
+
+        let req = test::TestRequest::delete()
+            .uri("/tasks/2?policy=cascade")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: DeleteResponse = test::read_body_json(res).await;
+        let summary = &res.results[0];
+        assert_eq!(summary.policy, "cascade");
+        assert_eq!(summary.tasks, 6);
+        assert_eq!(summary.user_assigns, 9);
+        assert_eq!(summary.comments, 14);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=7")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_project_and_user_with_policy() {
+        let pool = setup_test_db(
+            "delete_policy_test",
+            "test_delete_project_and_user_with_policy",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(delete_project)
+                .service(delete_user)
+                .service(get_user_assigns)
+                .app_data(web::Data::new(pool.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::delete().uri("/projects/2").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.details.unwrap()["tasks"], 3);
+
+        // reparentはタスクのみ
+        let req = test::TestRequest::delete()
+            .uri("/projects/2?policy=reparent")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::DeletePolicyNotSupported.to_string());
+
+        let req = test::TestRequest::delete()
+            .uri("/projects/2?policy=cascade")
+            .to_request();
+        let res: DeleteResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].projects, 1);
+        assert_eq!(res.results[0].tasks, 3);
+        assert_eq!(res.results[0].user_assigns, 1);
+
+        let req = test::TestRequest::delete().uri("/users/1").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        let details = res.details.unwrap();
+        assert_eq!(details["user_assigns"], 3);
+        assert_eq!(details["comments"], 3);
+
+        let req = test::TestRequest::delete()
+            .uri("/users/1?policy=cascade")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: DeleteResponse = test::read_body_json(res).await;
+        let summary = &res.results[0];
+        assert_eq!(summary.users, 1);
+        assert_eq!(summary.user_assigns, 3);
+        assert_eq!(summary.comments, 3);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=filter&userid=1")
+            .to_request();
+        let res: UserAssignResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+
+        // ゴミ箱にある割り当て（タスク10）は別に数える
+        let req = test::TestRequest::delete().uri("/users/2").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        let details = res.details.unwrap();
+        assert_eq!(details["user_assigns"], 1);
+        assert_eq!(details["trashed_user_assigns"], 1);
+        assert_eq!(details["comments"], 3);
+        assert_eq!(details["trashed_comments"], 0);
+
+        // ゴミ箱を経由して削除し、削除した行ごとに監査ログを残す
+        let req = test::TestRequest::delete()
+            .uri("/users/2?policy=cascade")
+            .to_request();
+        let res: DeleteResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].user_assigns, 2);
+        assert_eq!(res.results[0].comments, 3);
+
+        let audit_logs: Vec<(String, i64, String)> = sqlx::query_as(
+            r#"
+                SELECT entity_type, entity_id, action FROM audit_log
+                WHERE entity_type IN ('user_assign', 'comment') AND action IN ('delete', 'purge')
+                    AND entity_id IN (8, 9, 10)
+                ORDER BY action, entity_type, entity_id
+            "#,
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let expected = [
+            ("comment", 8, "delete"),
+            ("comment", 9, "delete"),
+            ("comment", 10, "delete"),
+            ("user_assign", 8, "delete"),
+            ("user_assign", 9, "delete"),
+            ("comment", 8, "purge"),
+            ("comment", 9, "purge"),
+            ("comment", 10, "purge"),
+            ("user_assign", 8, "purge"),
+            ("user_assign", 9, "purge"),
+        ];
+        let expected: Vec<(String, i64, String)> = expected
+            .iter()
+            .map(|(entity_type, id, action)| (entity_type.to_string(), *id, action.to_string()))
+            .collect();
+        assert_eq!(audit_logs, expected);
+    }
+}
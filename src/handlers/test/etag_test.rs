@@ -0,0 +1,238 @@
+#[cfg(test)]
+mod etag_test {
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::project::{get_projects, patch_project};
+    use crate::handlers::task::{delete_task, get_tasks, patch_task, update_task};
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::handlers::user_assign::{delete_user_assign, get_user_assigns};
+    use crate::models::{ErrorResponse, Task, TaskResponse};
+    use actix_web::dev::ServiceResponse;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/etag_test").exists() {
+            std::fs::create_dir_all("./test_db/etag_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/etag_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    fn etag<B>(res: &ServiceResponse<B>) -> Option<String> {
+        res.headers()
+            .get("ETag")
+            .map(|value| value.to_str().unwrap().to_string())
+    }
+
+    fn task(name: &str) -> Task {
+        Task {
+            task_id: Some(0),
+            project_id: 0,
+            parent_id: None,
+            level: 0,
+            name: name.to_string(),
+            description: None,
+            status: 0,
+            deadline: None,
+            created_at: 0,
+            updated_at: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_update_task_with_if_match() {
+        let pool = setup_test_db("etag_test", "test_update_task_with_if_match").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(update_task)
+                .service(patch_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=0")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"1\""));
+
+        // 一覧にはETagを付けない
+        let req = test::TestRequest::get().uri("/tasks").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(etag(&res), None);
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "\"1\""))
+            .set_json(task("first"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"2\""));
+
+        // 古いバージョンでの更新は失敗し、内容は変わらない
+        let req = test::TestRequest::post()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "\"1\""))
+            .set_json(task("second"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.rc, 7);
+        assert_eq!(res.code, ErrorKey::VersionMismatch.to_string());
+        assert_eq!(res.details.unwrap()["version"], 2);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=0")
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].name, "first");
+
+        // 弱いETagと複数の指定も受け付ける
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "\"1\", W/\"2\""))
+            .set_json(serde_json::json!({ "status": 1 }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"3\""));
+
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "\"2\""))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+        // *と未指定の場合は確認しない
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "*"))
+            .set_json(serde_json::json!({ "status": 2 }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"4\""));
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/0")
+            .set_json(task("third"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"5\""));
+
+        // バージョンとして解釈できない指定は一致しない
+        let req = test::TestRequest::post()
+            .uri("/tasks/0")
+            .insert_header(("If-Match", "\"abc\""))
+            .set_json(task("fourth"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[actix_web::test]
+    async fn test_delete_with_if_match() {
+        let pool = setup_test_db("etag_test", "test_delete_with_if_match").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(delete_task)
+                .service(get_user_assigns)
+                .service(delete_user_assign)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::delete()
+            .uri("/tasks/9")
+            .insert_header(("If-Match", "\"2\""))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+        let req = test::TestRequest::delete()
+            .uri("/tasks/9")
+            .insert_header(("If-Match", "\"1\""))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=id&id=0")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(etag(&res).as_deref(), Some("\"1\""));
+
+        let req = test::TestRequest::delete()
+            .uri("/userassigns/0")
+            .insert_header(("If-Match", "\"2\""))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+    }
+
+    #[actix_web::test]
+    async fn test_patch_project_with_if_match() {
+        let pool = setup_test_db("etag_test", "test_patch_project_with_if_match").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_projects)
+                .service(patch_project)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .insert_header(("If-Match", "\"1\""))
+            .set_json(serde_json::json!({ "name": "PATCH_project" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"2\""));
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .insert_header(("If-Match", "\"1\""))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+        // 変更のない部分更新ではバージョンは進まない
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .insert_header(("If-Match", "\"2\""))
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(etag(&res).as_deref(), Some("\"2\""));
+
+        let req = test::TestRequest::get()
+            .uri("/projects?target=id&id=1")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(etag(&res).as_deref(), Some("\"2\""));
+    }
+}
@@ -0,0 +1,150 @@
+#[cfg(test)]
+mod handler_health_test {
+    use crate::handlers::health::{live, ready};
+    use crate::handlers::test::utils::setup_test_db;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::{ReadinessResponse, RootResponse};
+    use crate::repository::migration_repo::MigrationRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/handler_health_test").exists() {
+            std::fs::create_dir_all("./test_db/handler_health_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/handler_health_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_live() {
+        let app = test::init_service(App::new().wrap(AuthMiddleware).service(live)).await;
+        let req = test::TestRequest::get().uri("/health/live").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: RootResponse = test::read_body_json(res).await;
+        assert_eq!(body.message, "MENAHEL IS RUNNING!");
+        assert_eq!(body.rc, 0);
+    }
+
+    #[actix_web::test]
+    async fn test_ready() {
+        let pool = setup_test_db("handler_health_test", "test_ready").await;
+
+        // 認証なしでアクセスできる
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(ready)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: ReadinessResponse = test::read_body_json(res).await;
+        assert_eq!(body.status, "ok");
+        assert_eq!(body.rc, 0);
+        assert_eq!(body.checks.database.status, "ok");
+        assert_eq!(body.checks.migrations.check.status, "ok");
+        assert!(body.checks.migrations.current_version.is_some());
+        assert_eq!(
+            body.checks.migrations.current_version,
+            body.checks.migrations.expected_version
+        );
+        assert_eq!(body.checks.pool.max_connections, 1);
+        assert!(body.checks.pool.saturation <= 1.0);
+        assert_eq!(body.checks.storage.check.status, "ok");
+        assert!(body.checks.storage.size_bytes.unwrap() > 0);
+    }
+
+    #[actix_web::test]
+    async fn test_ready_with_pending_migration() {
+        let pool = setup_test_db("handler_health_test", "test_ready_with_pending_migration").await;
+        MigrationRepository::new(pool.clone())
+            .revert_migrations(None)
+            .await
+            .unwrap();
+
+        let app =
+            test::init_service(App::new().service(ready).app_data(web::Data::new(pool))).await;
+
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: ReadinessResponse = test::read_body_json(res).await;
+        assert_eq!(body.status, "error");
+        assert_eq!(body.rc, 1);
+        assert!(body.message.contains("HealthNotReady"));
+        assert_eq!(body.checks.database.status, "ok");
+        assert_eq!(body.checks.migrations.check.status, "error");
+        assert!(body.checks.migrations.current_version < body.checks.migrations.expected_version);
+    }
+
+    #[actix_web::test]
+    async fn test_ready_without_migrations_table() {
+        let pool =
+            setup_test_db("handler_health_test", "test_ready_without_migrations_table").await;
+        sqlx::query("DROP TABLE _sqlx_migrations")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .service(ready)
+                .app_data(web::Data::new(pool.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: ReadinessResponse = test::read_body_json(res).await;
+        assert_eq!(body.checks.migrations.check.status, "error");
+        assert_eq!(body.checks.migrations.current_version, None);
+        assert!(body.checks.migrations.expected_version.is_some());
+
+        // 確認のためにテーブルを作成しない
+        let exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(!exists);
+    }
+
+    #[actix_web::test]
+    async fn test_ready_with_closed_database() {
+        let pool = setup_test_db("handler_health_test", "test_ready_with_closed_database").await;
+        pool.close().await;
+
+        let app =
+            test::init_service(App::new().service(ready).app_data(web::Data::new(pool))).await;
+
+        let req = test::TestRequest::get().uri("/health/ready").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::SERVICE_UNAVAILABLE);
+        let body: ReadinessResponse = test::read_body_json(res).await;
+        assert_eq!(body.status, "error");
+        assert_eq!(body.checks.database.status, "error");
+        assert!(
+            body.checks
+                .database
+                .message
+                .unwrap()
+                .contains("HealthDatabasePingFailed")
+        );
+        assert_eq!(body.checks.storage.check.status, "error");
+        assert_eq!(body.checks.storage.size_bytes, None);
+    }
+}
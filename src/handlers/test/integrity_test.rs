@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod integrity_test {
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::audit_log::get_audit_logs;
+    use crate::handlers::integrity::{get_integrity, repair_integrity};
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::models::{AuditLogResponse, ErrorResponse, IntegrityResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/integrity_test").exists() {
+            std::fs::create_dir_all("./test_db/integrity_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/integrity_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_integrity() {
+        let pool = setup_test_db("integrity_test", "test_get_integrity").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_integrity)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/admin/integrity")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: IntegrityResponse = test::read_body_json(res).await;
+        let report = &res.results[0];
+
+        // 自分自身が親のタスク1と2、レベル2の親を持つタスク3〜7、レベル0の親を持つタスク8と10
+        let parent_level: Vec<i64> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.check == "parent_level")
+            .map(|issue| issue.id)
+            .collect();
+        assert_eq!(parent_level, [1, 2, 3, 4, 5, 6, 7, 8, 10]);
+        assert!(
+            report
+                .issues
+                .iter()
+                .filter(|issue| issue.check == "parent_level")
+                .all(|issue| issue.fix.is_none())
+        );
+
+        // 親が正しいタスク9だけを自動で直す
+        let project_mismatch: Vec<(i64, Option<&str>)> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.check == "project_mismatch")
+            .map(|issue| (issue.id, issue.fix.as_deref()))
+            .collect();
+        assert_eq!(
+            project_mismatch,
+            [(8, None), (9, Some("Set Project ID = 0")), (10, None)]
+        );
+        assert_eq!(report.issues.len(), 12);
+        assert_eq!(report.fixable, 1);
+        assert_eq!(report.fixed, 0);
+    }
+
+    #[actix_web::test]
+    async fn test_repair_integrity() {
+        let pool = setup_test_db("integrity_test", "test_repair_integrity").await;
+
+        // 外部キー制約を有効にする前に作られたデータを再現する
+        let mut conn = pool.acquire().await.unwrap();
+        for sql in [
+            "PRAGMA foreign_keys = OFF",
+            "INSERT INTO user_assign (user_assign_id, user_id, task_id) VALUES (10, 0, 2)",
+            "INSERT INTO comments (comment_id, task_id, user_id, content, created_at, updated_at) VALUES (100, 999, 0, 'Orphaned', 1000, 1000)",
+            "UPDATE tasks SET status = 9 WHERE task_id = 7",
+            "PRAGMA foreign_keys = ON",
+        ] {
+            sqlx::query(sql).execute(&mut *conn).await.unwrap();
+        }
+        drop(conn);
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_integrity)
+                .service(repair_integrity)
+                .service(get_audit_logs)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/admin/integrity/repair?dry_run=true")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: IntegrityResponse = test::read_body_json(res).await;
+        let report = &res.results[0];
+        assert!(report.dry_run);
+        assert_eq!(report.issues.len(), 15);
+        assert_eq!(report.fixable, 4);
+        assert_eq!(report.fixed, 0);
+        let fixes: Vec<(&str, i64)> = report
+            .issues
+            .iter()
+            .filter(|issue| issue.fix.is_some())
+            .map(|issue| (issue.check.as_str(), issue.id))
+            .collect();
+        assert_eq!(
+            fixes,
+            [
+                ("invalid_status", 7),
+                ("project_mismatch", 9),
+                ("orphaned_comment", 100),
+                ("duplicate_user_assign", 10),
+            ]
+        );
+
+        // 試しに実行した場合は何も変更しない
+        let req = test::TestRequest::get()
+            .uri("/admin/integrity")
+            .to_request();
+        let res: IntegrityResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].fixable, 4);
+
+        let req = test::TestRequest::post()
+            .uri("/admin/integrity/repair")
+            .to_request();
+        let res: IntegrityResponse = test::call_and_read_body_json(&app, req).await;
+        let report = &res.results[0];
+        assert!(!report.dry_run);
+        assert_eq!(report.fixed, 4);
+        assert_eq!(report.remaining(), 11);
+
+        let req = test::TestRequest::get()
+            .uri("/admin/integrity")
+            .to_request();
+        let res: IntegrityResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].issues.len(), 11);
+        assert_eq!(res.results[0].fixable, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/audit?entity_type=task&entity_id=9")
+            .to_request();
+        let res: AuditLogResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].action, "update");
+
+        let req = test::TestRequest::post()
+            .uri("/admin/integrity/repair?dry_run=maybe")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::IntegrityHandlerInvalidQuery.to_string());
+    }
+}
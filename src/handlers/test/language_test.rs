@@ -0,0 +1,121 @@
+#[cfg(test)]
+mod language_middleware_test {
+    use crate::errors::messages::{ErrorKey, REQUEST_LANG, get_error_message, get_lang};
+    use crate::handlers::task_history::get_task_history;
+    use crate::handlers::test::utils::{login_as, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::middleware::language::{LanguageMiddleware, negotiate_language};
+    use crate::models::ErrorResponse;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/language_test").exists() {
+            std::fs::create_dir_all("./test_db/language_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/language_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_negotiate_language() {
+        assert_eq!(negotiate_language(None, Some("ja")), Some("jp"));
+        assert_eq!(negotiate_language(None, Some("ja-JP,en;q=0.8")), Some("jp"));
+        assert_eq!(negotiate_language(None, Some("en-US,ja;q=0.9")), Some("en"));
+        assert_eq!(
+            negotiate_language(None, Some("fr, ja;q=0.5, en;q=0.7")),
+            Some("en")
+        );
+        assert_eq!(
+            negotiate_language(None, Some("ja;q=0, en;q=0.1")),
+            Some("en")
+        );
+        assert_eq!(negotiate_language(None, Some("fr, *;q=0.5")), None);
+        assert_eq!(negotiate_language(None, None), None);
+
+        // langパラメータはAccept-Languageより優先する
+        assert_eq!(negotiate_language(Some("jp"), Some("en")), Some("jp"));
+        assert_eq!(negotiate_language(Some("en"), Some("ja")), Some("en"));
+        // 対応していない言語の場合はAccept-Languageを使う
+        assert_eq!(negotiate_language(Some("fr"), Some("ja")), Some("jp"));
+    }
+
+    #[actix_web::test]
+    async fn test_get_lang_outside_request() {
+        assert_eq!(get_lang(), "en");
+        assert_eq!(REQUEST_LANG.sync_scope("jp", get_lang), "jp");
+    }
+
+    #[actix_web::test]
+    async fn test_localized_error_message() {
+        let pool = setup_test_db("language_test", "test_localized_error_message").await;
+        let token = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .wrap(LanguageMiddleware)
+                .service(get_task_history)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let no_token_jp = REQUEST_LANG.sync_scope("jp", || {
+            get_error_message(ErrorKey::AuthHandlerNoToken, "".to_string()).message
+        });
+        let no_token_en = REQUEST_LANG.sync_scope("en", || {
+            get_error_message(ErrorKey::AuthHandlerNoToken, "".to_string()).message
+        });
+        assert_ne!(no_token_jp, no_token_en);
+
+        // 認証ミドルウェアのエラー
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .insert_header(("Accept-Language", "ja-JP,ja;q=0.9,en;q=0.8"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let body: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(body.code, "AuthHandlerNoToken");
+        assert_eq!(body.message, no_token_jp);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history?lang=en")
+            .insert_header(("Accept-Language", "ja"))
+            .to_request();
+        let body: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body.message, no_token_en);
+
+        // 指定がない場合はデフォルトの言語
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .to_request();
+        let body: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(body.message, no_token_en);
+
+        // ハンドラーのエラー
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history?lang=jp")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let jp: ErrorResponse = test::read_body_json(res).await;
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history?lang=en")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let en: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_ne!(jp.message, en.message);
+        assert!(!jp.message.is_ascii());
+        assert!(en.message.is_ascii());
+    }
+}
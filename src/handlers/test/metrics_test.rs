@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod handler_metrics_test {
+    use crate::handlers::metrics::get_metrics;
+    use crate::handlers::task_history::get_task_history;
+    use crate::handlers::test::utils::{login_as, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::middleware::metrics::MetricsMiddleware;
+    use crate::repository::user_repo::UserRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::http::header::CONTENT_TYPE;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/handler_metrics_test").exists() {
+            std::fs::create_dir_all("./test_db/handler_metrics_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/handler_metrics_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_metrics() {
+        let pool = setup_test_db("handler_metrics_test", "test_get_metrics").await;
+        let token = login_as(&pool, 1).await;
+        let admin_token = login_as(&pool, 2).await;
+        UserRepository::new(pool.clone())
+            .set_admin(2, true)
+            .await
+            .unwrap();
+        let users_count = UserRepository::new(pool.clone())
+            .get_users_count()
+            .await
+            .unwrap();
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .wrap(MetricsMiddleware)
+                .service(get_metrics)
+                .service(get_task_history)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 認証エラーもルートのパターンで集計される
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        // 公開していない場合は管理者のみアクセスできる
+        let req = test::TestRequest::get().uri("/metrics").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::get()
+            .uri("/metrics")
+            .insert_header(("Authorization", format!("Bearer {}", admin_token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert!(
+            res.headers()
+                .get(CONTENT_TYPE)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .starts_with("text/plain; version=0.0.4")
+        );
+        let body = test::read_body(res).await;
+        let body = String::from_utf8(body.to_vec()).unwrap();
+
+        assert!(body.contains(
+            "menahel_http_requests_total{method=\"GET\",route=\"/tasks/{id}/history\",status=\"401\"}"
+        ));
+        assert!(body.contains(
+            "menahel_http_requests_total{method=\"GET\",route=\"/tasks/{id}/history\",status=\"404\"}"
+        ));
+        assert!(!body.contains("route=\"/tasks/999999/history\""));
+        assert!(body.contains("menahel_http_request_duration_seconds_bucket{"));
+        assert!(body.contains("menahel_db_pool_max_connections 1"));
+        assert!(body.contains("menahel_db_pool_waiting "));
+        assert!(
+            body.contains("menahel_repository_query_duration_seconds_count{repository=\"user\"")
+        );
+        assert!(body.contains("menahel_errors_total{key=\"TaskGetByIdNotFound\"}"));
+        assert!(body.contains(&format!(
+            "menahel_entities{{entity=\"users\"}} {}",
+            users_count
+        )));
+        assert!(body.contains("menahel_entities{entity=\"comments\"} "));
+    }
+}
@@ -1,12 +1,48 @@
 #[cfg(test)]
+mod api_token_test;
+#[cfg(test)]
+mod audit_log_test;
+#[cfg(test)]
+mod auth_test;
+#[cfg(test)]
+mod batch_test;
+#[cfg(test)]
 mod comment_test;
 #[cfg(test)]
+mod delete_policy_test;
+#[cfg(test)]
+mod etag_test;
+#[cfg(test)]
+mod health_test;
+#[cfg(test)]
+mod integrity_test;
+#[cfg(test)]
+mod language_test;
+#[cfg(test)]
+mod metrics_test;
+#[cfg(test)]
+mod openapi_test;
+#[cfg(test)]
+mod processing_time_test;
+#[cfg(test)]
+mod project_member_test;
+#[cfg(test)]
 mod project_test;
 #[cfg(test)]
+mod request_id_test;
+#[cfg(test)]
+mod request_log_test;
+#[cfg(test)]
 mod root_test;
 #[cfg(test)]
+mod task_history_test;
+#[cfg(test)]
 mod task_test;
 #[cfg(test)]
+mod task_tree_test;
+#[cfg(test)]
+mod trash_test;
+#[cfg(test)]
 mod user_assign_test;
 #[cfg(test)]
 mod user_test;
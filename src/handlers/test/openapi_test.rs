@@ -0,0 +1,102 @@
+#[cfg(test)]
+mod openapi_test {
+    use crate::handlers::openapi::{ApiDoc, get_openapi};
+    use crate::middleware::auth::AuthMiddleware;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test};
+    use regex::Regex;
+    use serde_json::Value;
+    use std::collections::BTreeSet;
+    use utoipa::OpenApi;
+
+    // 更新する場合は UPDATE_OPENAPI=1 cargo test openapi で書き出す
+    const OPENAPI_SNAPSHOT: &str = "docs/openapi.json";
+
+    #[actix_web::test]
+    async fn test_get_openapi() {
+        let app = test::init_service(App::new().wrap(AuthMiddleware).service(get_openapi)).await;
+
+        // 認証なしで取得できる
+        let req = test::TestRequest::get().uri("/openapi.json").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let spec: Value = test::read_body_json(res).await;
+
+        assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+        assert_eq!(spec["info"]["version"], env!("CARGO_PKG_VERSION"));
+
+        let get_tasks = &spec["paths"]["/tasks"]["get"];
+        let params: Vec<&str> = get_tasks["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|param| param["name"].as_str().unwrap())
+            .collect();
+        assert!(params.contains(&"target"));
+        assert!(params.contains(&"with_user"));
+        assert!(params.contains(&"user_ids"));
+        let target = get_tasks["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|param| param["name"] == "target")
+            .unwrap();
+        assert_eq!(
+            target["schema"]["enum"],
+            serde_json::json!(["all", "id", "filter"])
+        );
+        assert!(get_tasks["responses"]["400"].is_object());
+
+        let schemas = &spec["components"]["schemas"];
+        for name in ["TaskResponse", "TaskUserResponse", "ErrorResponse"] {
+            assert!(schemas[name].is_object(), "{} is missing", name);
+        }
+        assert!(schemas["ErrorResponse"]["properties"]["field"].is_object());
+
+        // 公開されているパスは認証を要求しない
+        assert_eq!(
+            spec["paths"]["/auth/login"]["post"]["security"],
+            serde_json::json!([{}])
+        );
+        assert_eq!(spec["security"], serde_json::json!([{ "bearer_auth": [] }]));
+    }
+
+    // main.rsに登録したハンドラーとドキュメントのパスが一致する
+    #[actix_web::test]
+    async fn test_openapi_covers_registered_services() {
+        let main = include_str!("../../main.rs");
+        let service = Regex::new(r"\.service\((\w+)\)").unwrap();
+        let registered: BTreeSet<String> = service
+            .captures_iter(main)
+            .map(|captures| captures[1].to_string())
+            .collect();
+
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let documented: BTreeSet<String> = spec["paths"]
+            .as_object()
+            .unwrap()
+            .values()
+            .flat_map(|operations| operations.as_object().unwrap().values())
+            .map(|operation| operation["operationId"].as_str().unwrap().to_string())
+            .collect();
+
+        assert!(!registered.is_empty());
+        assert_eq!(registered, documented);
+    }
+
+    // ハンドラーのパラメータやレスポンスを変更した場合はスナップショットも更新する
+    #[actix_web::test]
+    async fn test_openapi_snapshot() {
+        let generated = ApiDoc::openapi().to_pretty_json().unwrap() + "\n";
+        if std::env::var("UPDATE_OPENAPI").is_ok() {
+            std::fs::write(OPENAPI_SNAPSHOT, &generated).unwrap();
+        }
+
+        let snapshot = std::fs::read_to_string(OPENAPI_SNAPSHOT).unwrap_or_default();
+        assert!(
+            snapshot == generated,
+            "{} is out of date. Run `UPDATE_OPENAPI=1 cargo test openapi` and commit the result",
+            OPENAPI_SNAPSHOT
+        );
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod processing_time_middleware_test {
+    use crate::handlers::root::root;
+    use crate::handlers::task::get_tasks;
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::middleware::processing_time::ProcessingTimeMiddleware;
+    use crate::models::{ErrorResponse, RootResponse, TaskResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/processing_time_middleware_test").exists() {
+            std::fs::create_dir_all("./test_db/processing_time_middleware_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/processing_time_middleware_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    fn server_timing_duration(value: &str) -> i64 {
+        value
+            .strip_prefix("app;dur=")
+            .and_then(|duration| duration.parse().ok())
+            .unwrap()
+    }
+
+    #[actix_web::test]
+    async fn test_processing_time_in_metadata() {
+        let pool = setup_test_db(
+            "processing_time_middleware_test",
+            "test_processing_time_in_metadata",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .wrap(ProcessingTimeMiddleware)
+                .service(root)
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/tasks").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let server_timing = res
+            .headers()
+            .get("Server-Timing")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let res: TaskResponse = test::read_body_json(res).await;
+        assert_eq!(res.rc, 0);
+        assert!(!res.results.is_empty());
+
+        let metadata = res.metadata.unwrap();
+        let start_time = metadata.start_time.unwrap();
+        let end_time = metadata.end_time.unwrap();
+        assert!(start_time > 0);
+        assert!(end_time >= start_time);
+        assert_eq!(metadata.duration_ms, Some(end_time - start_time));
+        assert_eq!(
+            server_timing_duration(&server_timing),
+            end_time - start_time
+        );
+
+        let req = test::TestRequest::get().uri("/").to_request();
+        let res: RootResponse = test::call_and_read_body_json(&app, req).await;
+        assert!(res.metadata.unwrap().duration_ms.is_some());
+
+        // JSON以外のレスポンスはボディを変更しない
+        let req = test::TestRequest::get().uri("/unknown").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert!(res.headers().contains_key("Server-Timing"));
+    }
+
+    #[actix_web::test]
+    async fn test_processing_time_in_error_response() {
+        let pool = setup_test_db(
+            "processing_time_middleware_test",
+            "test_processing_time_in_error_response",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .wrap(ProcessingTimeMiddleware)
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 認証ミドルウェアが返すエラーレスポンスにも設定される
+        let req = test::TestRequest::get()
+            .uri("/tasks")
+            .insert_header(("Authorization", "Bearer invalid-token"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert!(res.headers().contains_key("Server-Timing"));
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.rc, 3);
+        assert!(res.metadata.unwrap().duration_ms.is_some());
+    }
+}
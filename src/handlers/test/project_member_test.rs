@@ -0,0 +1,425 @@
+#[cfg(test)]
+mod project_member_handler_test {
+    use crate::enums::ProjectRole;
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::comment::{create_comment, get_comments};
+    use crate::handlers::project::{create_project, delete_project, get_projects, update_project};
+    use crate::handlers::project_member::{
+        create_project_member, delete_project_member, get_project_members, update_project_member,
+    };
+    use crate::handlers::task::{create_task, delete_task, get_tasks, update_task};
+    use crate::handlers::test::utils::{login_as, setup_test_db};
+    use crate::handlers::user_assign::get_user_assigns;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::{
+        Comment, CommentUserResponse, ErrorResponse, Project, ProjectMember, ProjectMemberResponse,
+        ProjectResponse, Task, TaskResponse, UserAssignResponse,
+    };
+    use crate::repository::project_member_repo::ProjectMemberRepository;
+    use crate::repository::user_repo::UserRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+    use sqlx::sqlite::SqlitePool;
+
+    #[ctor::ctor]
+    fn init() {
+        // Create test_db directory if it doesn't exist
+        if !std::path::Path::new("./test_db/project_member_handler_test").exists() {
+            std::fs::create_dir_all("./test_db/project_member_handler_test").unwrap();
+        }
+
+        // Remove all files in the test_db directory
+        let files = std::fs::read_dir("./test_db/project_member_handler_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    // プロジェクト0: user0がオーナー、user1がメンバー、user2が閲覧者
+    async fn setup_members(pool: &SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool.clone());
+        for (user_id, role) in [
+            (0, ProjectRole::Owner),
+            (1, ProjectRole::Member),
+            (2, ProjectRole::Viewer),
+        ] {
+            project_member_repo
+                .create_project_member(ProjectMember::new(0, user_id, role))
+                .await
+                .unwrap();
+        }
+    }
+
+    fn new_task(name: &str) -> Task {
+        Task {
+            task_id: None,
+            project_id: 0,
+            parent_id: None,
+            level: 0,
+            name: name.to_string(),
+            description: None,
+            status: 0,
+            deadline: None,
+            created_at: 0,
+            updated_at: None,
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_project_members_crud() {
+        let pool = setup_test_db("project_member_handler_test", "test_project_members_crud").await;
+        setup_members(&pool).await;
+        let owner = login_as(&pool, 0).await;
+        let viewer = login_as(&pool, 2).await;
+        let outsider = login_as(&pool, 3).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(get_project_members)
+                .service(create_project_member)
+                .service(update_project_member)
+                .service(delete_project_member)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/projects/0/members")
+            .insert_header(("Authorization", format!("Bearer {}", viewer)))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 3);
+
+        let req = test::TestRequest::get()
+            .uri("/projects/0/members")
+            .insert_header(("Authorization", format!("Bearer {}", outsider)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 閲覧者はメンバーを追加できない
+        let req = test::TestRequest::post()
+            .uri("/projects/0/members")
+            .insert_header(("Authorization", format!("Bearer {}", viewer)))
+            .set_json(ProjectMember::new(0, 3, ProjectRole::Viewer))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/projects/0/members")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .set_json(ProjectMember::new(0, 3, ProjectRole::Viewer))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].user_id, 3);
+        assert_eq!(res.results[0].role, ProjectRole::Viewer.to_int());
+
+        let req = test::TestRequest::post()
+            .uri("/projects/0/members/3")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .set_json(ProjectMember::new(0, 3, ProjectRole::Member))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].role, ProjectRole::Member.to_int());
+
+        // 唯一のオーナーは降格できない
+        let req = test::TestRequest::post()
+            .uri("/projects/0/members/0")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .set_json(ProjectMember::new(0, 0, ProjectRole::Member))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+
+        let req = test::TestRequest::delete()
+            .uri("/projects/0/members/3")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/projects/0/members")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 3);
+    }
+
+    #[actix_web::test]
+    async fn test_project_role_enforcement() {
+        let pool = setup_test_db(
+            "project_member_handler_test",
+            "test_project_role_enforcement",
+        )
+        .await;
+        setup_members(&pool).await;
+        let owner = login_as(&pool, 0).await;
+        let member = login_as(&pool, 1).await;
+        let viewer = login_as(&pool, 2).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(update_project)
+                .service(delete_project)
+                .service(create_task)
+                .service(update_task)
+                .service(delete_task)
+                .service(create_comment)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 閲覧者はタスクを作成できない
+        let req = test::TestRequest::post()
+            .uri("/tasks")
+            .insert_header(("Authorization", format!("Bearer {}", viewer)))
+            .set_json(new_task("ViewerTask"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let body: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(body.rc, 4);
+
+        // 閲覧者はコメントできない
+        let req = test::TestRequest::post()
+            .uri("/comments")
+            .insert_header(("Authorization", format!("Bearer {}", viewer)))
+            .set_json(Comment::new(2, 2, "ViewerComment".to_string()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 閲覧者はタスクを削除できない
+        let req = test::TestRequest::delete()
+            .uri("/tasks/7")
+            .insert_header(("Authorization", format!("Bearer {}", viewer)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // メンバーはタスクを作成できる
+        let req = test::TestRequest::post()
+            .uri("/tasks")
+            .insert_header(("Authorization", format!("Bearer {}", member)))
+            .set_json(new_task("MemberTask"))
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        let mut task = res.results[0].clone();
+
+        // 権限のない別プロジェクトへは移動できない
+        task.project_id = 2;
+        let req = test::TestRequest::post()
+            .uri(&format!("/tasks/{}", task.task_id.unwrap()))
+            .insert_header(("Authorization", format!("Bearer {}", member)))
+            .set_json(task)
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // メンバーはプロジェクト名を変更できない
+        let project = Project {
+            project_id: Some(0),
+            name: "RenamedProject".to_string(),
+        };
+        let req = test::TestRequest::post()
+            .uri("/projects/0")
+            .insert_header(("Authorization", format!("Bearer {}", member)))
+            .set_json(project.clone())
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::delete()
+            .uri("/projects/0")
+            .insert_header(("Authorization", format!("Bearer {}", member)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        let req = test::TestRequest::post()
+            .uri("/projects/0")
+            .insert_header(("Authorization", format!("Bearer {}", owner)))
+            .set_json(project)
+            .to_request();
+        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].name, "RenamedProject");
+    }
+
+    #[actix_web::test]
+    async fn test_create_project_makes_creator_owner() {
+        let pool = setup_test_db(
+            "project_member_handler_test",
+            "test_create_project_makes_creator_owner",
+        )
+        .await;
+        let user = login_as(&pool, 5).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_project)
+                .service(get_project_members)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::post()
+            .uri("/projects")
+            .insert_header(("Authorization", format!("Bearer {}", user)))
+            .set_json(Project::new("OwnedProject".to_string()))
+            .to_request();
+        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        let project_id = res.results[0].project_id.unwrap();
+
+        let req = test::TestRequest::get()
+            .uri(&format!("/projects/{}/members", project_id))
+            .insert_header(("Authorization", format!("Bearer {}", user)))
+            .to_request();
+        let res: ProjectMemberResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].user_id, 5);
+        assert_eq!(res.results[0].role, ProjectRole::Owner.to_int());
+    }
+
+    #[actix_web::test]
+    async fn test_requests_without_user_are_unauthorized() {
+        let pool = setup_test_db(
+            "project_member_handler_test",
+            "test_requests_without_user_are_unauthorized",
+        )
+        .await;
+        setup_members(&pool).await;
+
+        // 認証ミドルウェアを通っていない場合も権限の確認を省略しない
+        let app = test::init_service(
+            App::new()
+                .service(get_tasks)
+                .service(create_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get().uri("/tasks").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::AuthHandlerNoToken.to_string());
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=2")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let req = test::TestRequest::post()
+            .uri("/tasks")
+            .set_json(new_task("Unauthorized"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[actix_web::test]
+    async fn test_lists_are_scoped_to_member_projects() {
+        let pool = setup_test_db(
+            "project_member_handler_test",
+            "test_lists_are_scoped_to_member_projects",
+        )
+        .await;
+        setup_members(&pool).await;
+        ProjectMemberRepository::new(pool.clone())
+            .create_project_member(ProjectMember::new(2, 3, ProjectRole::Viewer))
+            .await
+            .unwrap();
+        UserRepository::new(pool.clone())
+            .set_admin(4, true)
+            .await
+            .unwrap();
+        let viewer = login_as(&pool, 2).await;
+        let other = login_as(&pool, 3).await;
+        let admin = login_as(&pool, 4).await;
+        let outsider = login_as(&pool, 5).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(get_projects)
+                .service(get_tasks)
+                .service(get_comments)
+                .service(get_user_assigns)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let get = |uri: &str, token: &str| {
+            test::TestRequest::get()
+                .uri(uri)
+                .insert_header(("Authorization", format!("Bearer {}", token)))
+                .to_request()
+        };
+
+        // user2はプロジェクト0、user3はプロジェクト2の閲覧者
+        let res: ProjectResponse =
+            test::call_and_read_body_json(&app, get("/projects", &viewer)).await;
+        let project_ids: Vec<Option<i64>> = res.results.iter().map(|p| p.project_id).collect();
+        assert_eq!(project_ids, [Some(0)]);
+        let res: ProjectResponse =
+            test::call_and_read_body_json(&app, get("/projects", &other)).await;
+        let project_ids: Vec<Option<i64>> = res.results.iter().map(|p| p.project_id).collect();
+        assert_eq!(project_ids, [Some(2)]);
+
+        let res: TaskResponse = test::call_and_read_body_json(&app, get("/tasks", &viewer)).await;
+        assert_eq!(res.count, 8);
+        assert!(res.results.iter().all(|task| task.project_id == 0));
+        let res: TaskResponse = test::call_and_read_body_json(&app, get("/tasks", &other)).await;
+        let task_ids: Vec<Option<i64>> = res.results.iter().map(|t| t.task_id).collect();
+        assert_eq!(task_ids, [Some(8), Some(9), Some(10)]);
+
+        let res: CommentUserResponse =
+            test::call_and_read_body_json(&app, get("/comments", &viewer)).await;
+        assert_eq!(res.count, 14);
+        let res: CommentUserResponse =
+            test::call_and_read_body_json(&app, get("/comments", &other)).await;
+        assert_eq!(res.count, 0);
+
+        let res: UserAssignResponse =
+            test::call_and_read_body_json(&app, get("/userassigns", &viewer)).await;
+        assert_eq!(res.count, 9);
+        let res: UserAssignResponse =
+            test::call_and_read_body_json(&app, get("/userassigns", &other)).await;
+        let user_assign_ids: Vec<Option<i64>> =
+            res.results.iter().map(|u| u.user_assign_id).collect();
+        assert_eq!(user_assign_ids, [Some(9)]);
+
+        // メンバーでないプロジェクトのデータはIDを指定しても取得できない
+        let res = test::call_service(&app, get("/tasks?target=id&id=2", &other)).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res = test::call_service(&app, get("/projects?target=id&id=0", &other)).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // どのプロジェクトのメンバーでもない場合は空になる
+        let res: ProjectResponse =
+            test::call_and_read_body_json(&app, get("/projects", &outsider)).await;
+        assert_eq!(res.count, 0);
+
+        // 管理者はすべてのプロジェクトを参照できる
+        let res: ProjectResponse =
+            test::call_and_read_body_json(&app, get("/projects", &admin)).await;
+        assert_eq!(res.count, 10);
+        let res = test::call_service(&app, get("/tasks?target=id&id=2", &admin)).await;
+        assert_eq!(res.status(), StatusCode::OK);
+    }
+}
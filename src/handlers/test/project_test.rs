@@ -1,12 +1,16 @@
 #[cfg(test)]
 mod project_handler_test {
+    use crate::errors::messages::ErrorKey;
     use crate::handlers::project::create_project;
     use crate::handlers::project::delete_project;
     use crate::handlers::project::get_projects;
+    use crate::handlers::project::patch_project;
     use crate::handlers::project::update_project;
-    use crate::handlers::test::utils::setup_test_db;
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::models::DeleteResponse;
     use crate::models::ErrorResponse;
     use crate::models::{Project, ProjectResponse};
+    use actix_web::middleware::from_fn;
     use actix_web::{App, test, web};
 
     #[ctor::ctor]
@@ -30,6 +34,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -51,6 +56,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -107,6 +113,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -117,8 +124,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -127,6 +133,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -150,6 +157,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -172,6 +180,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -182,8 +191,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -192,6 +200,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -202,8 +211,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -213,6 +221,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -223,8 +232,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -237,6 +245,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -247,8 +256,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -261,6 +269,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -271,8 +280,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -285,6 +293,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
         )
@@ -295,8 +304,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -305,6 +313,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -344,6 +353,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -359,8 +369,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -369,6 +378,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -384,8 +394,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        assert_eq!(res.rc, 6);
     }
 
     #[actix_web::test]
@@ -394,6 +403,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -403,8 +413,7 @@ mod project_handler_test {
         let req = test::TestRequest::post().uri("/projects").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -413,6 +422,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -451,6 +461,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -466,8 +477,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -476,6 +486,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -491,8 +502,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -501,6 +511,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -516,9 +527,8 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
+        assert_eq!(res.rc, 5);
         println!("res: {:?}", res.message);
-        assert!(res.message.contains("NotFound"));
     }
 
     #[actix_web::test]
@@ -528,6 +538,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -543,8 +554,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        assert_eq!(res.rc, 6);
     }
 
     #[actix_web::test]
@@ -553,6 +563,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -562,8 +573,7 @@ mod project_handler_test {
         let req = test::TestRequest::post().uri("/projects/abc").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -572,6 +582,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -579,12 +590,13 @@ mod project_handler_test {
         .await;
 
         let req = test::TestRequest::delete().uri("/projects/9").to_request();
-        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        let res: DeleteResponse = test::call_and_read_body_json(&app, req).await;
 
         assert_eq!(res.rc, 0);
-        assert_eq!(res.results.len(), 0);
         assert_eq!(res.message, "OK");
-        assert_eq!(res.count, 0);
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].projects, 1);
+        assert_eq!(res.results[0].tasks, 0);
     }
 
     #[actix_web::test]
@@ -593,6 +605,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -604,8 +617,7 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -614,6 +626,7 @@ mod project_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_project)
                 .service(get_projects)
                 .app_data(web::Data::new(pool)),
@@ -625,7 +638,56 @@ mod project_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::ProjectDeleteFailedByIdNotFound.to_string()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_patch_project() {
+        let pool = setup_test_db("project_handler_test", "test_patch_project").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_project)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .set_json(serde_json::json!({ "name": "PATCH_project" }))
+            .to_request();
+        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].name, "PATCH_project");
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res: ProjectResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].name, "PATCH_project");
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/1")
+            .set_json(serde_json::json!({ "name": null }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::ProjectHandlerInvalidJsonPost.to_string()
+        );
+
+        let req = test::TestRequest::patch()
+            .uri("/projects/100")
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 5);
     }
 }
@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod request_id_middleware_test {
+    use crate::handlers::root::root;
+    use crate::handlers::task_history::get_task_history;
+    use crate::handlers::test::utils::{login_as, setup_test_db};
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::middleware::request_id::{RequestIdMiddleware, is_valid_request_id};
+    use crate::models::RootResponse;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/request_id_test").exists() {
+            std::fs::create_dir_all("./test_db/request_id_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/request_id_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    fn header_request_id<B>(res: &actix_web::dev::ServiceResponse<B>) -> String {
+        res.headers()
+            .get("X-Request-ID")
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    #[actix_web::test]
+    async fn test_request_id_echoed() {
+        let app = test::init_service(App::new().wrap(RequestIdMiddleware).service(root)).await;
+
+        // クライアントが送信したIDをヘッダーとメタデータの両方で返す
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header(("X-Request-ID", "client-request-1"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(header_request_id(&res), "client-request-1");
+        let body: RootResponse = test::read_body_json(res).await;
+        assert_eq!(body.metadata.unwrap().request_id, "client-request-1");
+
+        // 未指定の場合は採番したIDを返す
+        let req = test::TestRequest::get().uri("/").to_request();
+        let res = test::call_service(&app, req).await;
+        let request_id = header_request_id(&res);
+        assert!(is_valid_request_id(&request_id));
+        let body: RootResponse = test::read_body_json(res).await;
+        assert_eq!(body.metadata.unwrap().request_id, request_id);
+    }
+
+    #[actix_web::test]
+    async fn test_invalid_request_id_replaced() {
+        let app = test::init_service(App::new().wrap(RequestIdMiddleware).service(root)).await;
+
+        let too_long = "a".repeat(129);
+        for invalid in ["", "has space", too_long.as_str()] {
+            let req = test::TestRequest::get()
+                .uri("/")
+                .insert_header(("X-Request-ID", invalid))
+                .to_request();
+            let res = test::call_service(&app, req).await;
+            let request_id = header_request_id(&res);
+            assert_ne!(request_id, invalid);
+            assert!(is_valid_request_id(&request_id));
+        }
+
+        let max_length = "a".repeat(128);
+        let req = test::TestRequest::get()
+            .uri("/")
+            .insert_header(("X-Request-ID", max_length.as_str()))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(header_request_id(&res), max_length);
+    }
+
+    #[actix_web::test]
+    async fn test_request_id_on_error_responses() {
+        let pool = setup_test_db("request_id_test", "test_request_id_on_error_responses").await;
+        let token = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .wrap(RequestIdMiddleware)
+                .service(get_task_history)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 認証ミドルウェアのエラー
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .insert_header(("X-Request-ID", "unauthorized-request"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(header_request_id(&res), "unauthorized-request");
+
+        // ハンドラーのエラー
+        let req = test::TestRequest::get()
+            .uri("/tasks/999999/history")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .insert_header(("X-Request-ID", "not-found-request"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert_eq!(header_request_id(&res), "not-found-request");
+
+        // 存在しないルート
+        let req = test::TestRequest::get()
+            .uri("/unknown")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        assert!(is_valid_request_id(&header_request_id(&res)));
+    }
+}
@@ -0,0 +1,122 @@
+#[cfg(test)]
+mod request_log_middleware_test {
+    use crate::handlers::utils::get_request_id;
+    use crate::logging::{LogWriter, build_subscriber};
+    use crate::middleware::request_id::RequestIdMiddleware;
+    use crate::middleware::request_log::RequestLogMiddleware;
+    use crate::models::UserNoPassword;
+    use crate::settings::LogConfig;
+    use actix_web::http::StatusCode;
+    use actix_web::{App, HttpRequest, HttpResponse, get, test};
+    use serde_json::Value;
+    use std::io::Write;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Buffer {
+        fn lines(&self) -> Vec<Value> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(|line| serde_json::from_str(line).unwrap())
+                .collect()
+        }
+    }
+
+    #[get("/users/{id}/profile")]
+    async fn profile(req: HttpRequest) -> HttpResponse {
+        let user = UserNoPassword {
+            user_id: Some(1),
+            username: "TestUser0".to_string(),
+            email: "test0@example.com".to_string(),
+        };
+        tracing::debug!("Response: {:?}", user);
+        HttpResponse::Ok().body(get_request_id(&req))
+    }
+
+    #[actix_web::test]
+    async fn test_request_span() {
+        let buffer = Buffer::default();
+        let subscriber = build_subscriber(
+            &LogConfig::default(),
+            LogWriter::new(Box::new(buffer.clone())),
+        )
+        .unwrap();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = test::init_service(App::new().wrap(RequestLogMiddleware).service(profile)).await;
+        let req = test::TestRequest::get()
+            .uri("/users/1/profile")
+            .insert_header(("X-Request-ID", "request-log-test"))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 2);
+
+        // ハンドラーのログにもリクエストのスパンが付与され、機密情報は伏せられる
+        let message = lines[0]["message"].as_str().unwrap();
+        assert!(message.contains("email: \"[REDACTED]\""));
+        assert!(!message.contains("test0@example.com"));
+        assert_eq!(lines[0]["span"]["request_id"], "request-log-test");
+        assert_eq!(lines[0]["span"]["route"], "/users/{id}/profile");
+
+        assert_eq!(lines[1]["target"], "access");
+        assert_eq!(lines[1]["message"], "request completed");
+        assert_eq!(lines[1]["span"]["request_id"], "request-log-test");
+        assert_eq!(lines[1]["span"]["method"], "GET");
+        assert_eq!(lines[1]["span"]["status"], 200);
+        assert!(lines[1]["span"]["duration_ms"].is_u64());
+    }
+
+    #[actix_web::test]
+    async fn test_request_span_without_request_id() {
+        let buffer = Buffer::default();
+        let subscriber = build_subscriber(
+            &LogConfig::default(),
+            LogWriter::new(Box::new(buffer.clone())),
+        )
+        .unwrap();
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let app = test::init_service(
+            App::new()
+                .wrap(RequestLogMiddleware)
+                .wrap(RequestIdMiddleware)
+                .service(profile),
+        )
+        .await;
+
+        // 採番したIDがハンドラーとログで一致する
+        let req = test::TestRequest::get()
+            .uri("/users/1/profile")
+            .to_request();
+        let body = test::call_and_read_body(&app, req).await;
+        let request_id = String::from_utf8(body.to_vec()).unwrap();
+        assert!(!request_id.is_empty());
+
+        let req = test::TestRequest::get().uri("/unknown").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let lines = buffer.lines();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[1]["span"]["request_id"], request_id.as_str());
+        assert_eq!(lines[2]["span"]["route"], "unmatched");
+        assert_eq!(lines[2]["span"]["status"], 404);
+    }
+}
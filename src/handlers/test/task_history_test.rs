@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod task_history_handler_test {
+    use crate::handlers::task::update_task;
+    use crate::handlers::task_history::get_task_history;
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::models::{ErrorResponse, Task, TaskHistoryResponse, TaskResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/task_history_handler_test").exists() {
+            std::fs::create_dir_all("./test_db/task_history_handler_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/task_history_handler_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_task_history() {
+        let pool = setup_test_db("task_history_handler_test", "test_get_task_history").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(update_task)
+                .service(get_task_history)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/0/history")
+            .to_request();
+        let res: TaskHistoryResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 0);
+
+        let task = Task {
+            task_id: Some(0),
+            project_id: 0,
+            parent_id: None,
+            level: 0,
+            name: "RenamedTask0".to_string(),
+            description: Some("TestTask0Description".to_string()),
+            status: 1,
+            deadline: None,
+            created_at: 0,
+            updated_at: None,
+        };
+        let req = test::TestRequest::post()
+            .uri("/tasks/0")
+            .set_json(task)
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/0/history")
+            .to_request();
+        let res: TaskHistoryResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.count, 3);
+        assert!(res.pagination.is_none());
+
+        let fields: Vec<&str> = res
+            .results
+            .iter()
+            .map(|history| history.field_name.as_str())
+            .collect();
+        assert_eq!(fields, vec!["name", "status", "deadline"]);
+        assert_eq!(res.results[0].old_value.as_deref(), Some("TestMajorTask0"));
+        assert_eq!(res.results[0].new_value.as_deref(), Some("RenamedTask0"));
+        assert_eq!(res.results[1].old_value.as_deref(), Some("0"));
+        assert_eq!(res.results[1].new_value.as_deref(), Some("1"));
+        assert_eq!(res.results[2].old_value.as_deref(), Some("1000"));
+        assert_eq!(res.results[2].new_value, None);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/0/history?page=2&page_size=2")
+            .to_request();
+        let res: TaskHistoryResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].field_name, "deadline");
+        assert!(res.pagination.is_some());
+    }
+
+    #[actix_web::test]
+    async fn test_get_task_history_with_invalid_request() {
+        let pool = setup_test_db(
+            "task_history_handler_test",
+            "test_get_task_history_with_invalid_request",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_task_history)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        for uri in [
+            "/tasks/abc/history",
+            "/tasks/0/history?page=1",
+            "/tasks/0/history?page=0&page_size=10",
+            "/tasks/0/history?page=abc",
+        ] {
+            let req = test::TestRequest::get().uri(uri).to_request();
+            let res = test::call_service(&app, req).await;
+            assert_eq!(res.status(), StatusCode::BAD_REQUEST, "uri: {}", uri);
+            let res: ErrorResponse = test::read_body_json(res).await;
+            assert_eq!(res.rc, 2);
+        }
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/9999/history")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -1,14 +1,18 @@
 #[cfg(test)]
 
 mod task_handler_test {
+    use crate::errors::messages::ErrorKey;
     use crate::handlers::task::create_task;
     use crate::handlers::task::delete_task;
     use crate::handlers::task::get_tasks;
+    use crate::handlers::task::patch_task;
     use crate::handlers::task::update_task;
-    use crate::handlers::test::utils::setup_test_db;
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::models::DeleteResponse;
     use crate::models::ErrorResponse;
     use crate::models::TaskUserResponse;
     use crate::models::{Task, TaskResponse};
+    use actix_web::middleware::from_fn;
     use actix_web::{App, test, web};
 
     #[ctor::ctor]
@@ -30,8 +34,13 @@ mod task_handler_test {
     async fn test_get_tasks() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get().uri("/tasks").to_request();
 
@@ -47,8 +56,13 @@ mod task_handler_test {
     async fn test_get_tasks_with_user_ids() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_with_user_ids").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true")
@@ -71,8 +85,13 @@ mod task_handler_test {
     async fn test_get_tasks_with_pagination() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_with_pagination").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?page=1&page_size=4")
@@ -99,8 +118,13 @@ mod task_handler_test {
     async fn test_get_tasks_with_user_pagination() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_with_user_pagination").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=1&page_size=4")
@@ -143,16 +167,20 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?page=1&page_size=1000")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -163,16 +191,20 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=1&page_size=1000")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -183,46 +215,52 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get().uri("/tasks?page=1").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/tasks?page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_tasks_with_user_with_invalid_no_page_or_page_size() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_with_invalid_user_ids").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=1")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -233,24 +271,27 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?page=0&page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/tasks?page=1&page_size=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -261,72 +302,87 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=0&page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=1&page_size=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_all_tasks_by_target() {
         let pool = setup_test_db("task_handler_test", "test_get_all_tasks_by_target").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=0&page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
 
         let req = test::TestRequest::get()
             .uri("/tasks?with_user=true&page=1&page_size=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_tasks_by_invalid_target() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_invalid_target").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=invalid")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_tasks_by_id() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_id").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=id&id=1")
@@ -343,8 +399,13 @@ mod task_handler_test {
     async fn test_get_tasks_by_id_with_user() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_id_with_user").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=id&id=2&with_user=true")
@@ -365,32 +426,40 @@ mod task_handler_test {
     async fn test_get_tasks_by_invalid_query() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_invalid_query").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=id&id=abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_tasks_by_invalid_id() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_invalid_id").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=id&id=-1")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -398,24 +467,33 @@ mod task_handler_test {
         let pool =
             setup_test_db("task_handler_test", "test_get_task_with_user_by_invalid_id").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=id&id=-1&with_user=true")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_task_by_filter() {
         let pool = setup_test_db("task_handler_test", "test_get_task_by_filter").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&project_id=0&level=2")
@@ -436,8 +514,13 @@ mod task_handler_test {
     async fn test_get_task_by_filter_with_user() {
         let pool = setup_test_db("task_handler_test", "test_get_task_by_filter_with_user").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&project_id=0&level=2&with_user=true")
@@ -465,8 +548,13 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&project_id=0&level=2&page=1&page_size=4")
@@ -491,8 +579,13 @@ mod task_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&project_id=0&level=2&with_user=true&page=1&page_size=4")
@@ -517,8 +610,13 @@ mod task_handler_test {
         let pool =
             setup_test_db("task_handler_test", "test_get_task_by_filter_with_user_ids").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&level=2&user_ids=2&with_user=true")
@@ -536,8 +634,13 @@ mod task_handler_test {
     async fn test_get_tasks_by_full_filter() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_full_filter").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
         // (3, 'TestNotStartedTask3', 'TestTask3Description', 2, 1, 0, 2, 1500, 3000, 3000);
         let mut filter_value = Vec::new();
         filter_value.push("project_id=0");
@@ -582,8 +685,13 @@ mod task_handler_test {
     async fn test_get_tasks_by_invalid_filter() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_invalid_filter").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&project_id=-1&level=-1&name=TestNotStartedTask3")
@@ -600,16 +708,20 @@ mod task_handler_test {
     async fn test_get_tasks_by_invalid_user_ids() {
         let pool = setup_test_db("task_handler_test", "test_get_tasks_by_invalid_user_ids").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&level=2&user_ids=ab,cd,_&with_user=true")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -617,8 +729,13 @@ mod task_handler_test {
         let pool =
             setup_test_db("task_handler_test", "test_get_tasks_by_user_ids_not_exists").await;
 
-        let app =
-            test::init_service(App::new().service(get_tasks).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/tasks?target=filter&level=2&user_ids=100,200,300&with_user=true")
@@ -636,6 +753,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -689,6 +807,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -726,6 +845,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -735,8 +855,7 @@ mod task_handler_test {
         let req = test::TestRequest::post().uri("/tasks").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -749,6 +868,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -774,8 +894,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -788,6 +907,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -813,8 +933,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -827,6 +946,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -852,8 +972,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -866,6 +985,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -891,8 +1011,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -901,6 +1020,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -955,6 +1075,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -964,8 +1085,7 @@ mod task_handler_test {
         let req = test::TestRequest::post().uri("/tasks/0").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -974,6 +1094,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -999,8 +1120,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -1013,6 +1133,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1038,8 +1159,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -1052,6 +1172,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1077,8 +1198,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -1087,6 +1207,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1112,8 +1233,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -1122,6 +1242,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1147,8 +1268,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -1157,6 +1277,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1182,8 +1303,7 @@ mod task_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -1192,6 +1312,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1199,12 +1320,13 @@ mod task_handler_test {
         .await;
 
         let req = test::TestRequest::delete().uri("/tasks/9").to_request();
-        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        let res: DeleteResponse = test::call_and_read_body_json(&app, req).await;
 
         assert_eq!(res.rc, 0);
-        assert_eq!(res.results.len(), 0);
         assert_eq!(res.message, "OK");
-        assert_eq!(res.count, 0);
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].policy, "restrict");
+        assert_eq!(res.results[0].tasks, 1);
     }
 
     #[actix_web::test]
@@ -1213,6 +1335,7 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
@@ -1222,8 +1345,7 @@ mod task_handler_test {
         let req = test::TestRequest::delete().uri("/tasks/abc").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -1232,16 +1354,159 @@ mod task_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_task)
                 .service(get_tasks)
                 .app_data(web::Data::new(pool)),
         )
         .await;
 
+        // 権限の確認でタスクを取得するため、見つからないエラーになる
         let req = test::TestRequest::delete().uri("/tasks/100").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
+        assert_eq!(res.code, ErrorKey::TaskGetByIdNotFound.to_string());
+    }
+
+    #[actix_web::test]
+    async fn test_patch_task() {
+        let pool = setup_test_db("task_handler_test", "test_patch_task").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_task)
+                .service(get_tasks)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // nullを指定したフィールドは値を消し、省略したフィールドは変更しない
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .set_json(serde_json::json!({
+                "name": "PATCH_task",
+                "description": null,
+                "deadline": null,
+            }))
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results.len(), 1);
+        assert_eq!(res.results[0].name, "PATCH_task");
+        assert_eq!(res.results[0].description, None);
+        assert_eq!(res.results[0].deadline, None);
+        assert_eq!(res.results[0].level, 0);
+        assert_eq!(res.results[0].status, 0);
+        assert_eq!(res.results[0].parent_id, None);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=0")
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].name, "PATCH_task");
+        assert_eq!(res.results[0].description, None);
+        assert!(res.results[0].updated_at.unwrap() > 1000);
+
+        // 空のボディは何も変更しない
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .set_json(serde_json::json!({}))
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].name, "PATCH_task");
+    }
+
+    #[actix_web::test]
+    async fn test_patch_task_validates_only_changed_fields() {
+        let pool = setup_test_db(
+            "task_handler_test",
+            "test_patch_task_validates_only_changed_fields",
+        )
+        .await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 親子関係を変更しない場合は親子関係を検証しない
+        let req = test::TestRequest::patch()
+            .uri("/tasks/1")
+            .set_json(serde_json::json!({ "status": 1 }))
+            .to_request();
+        let res: TaskResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].status, 1);
+        assert_eq!(res.results[0].name, "TestMinorTask1");
+
+        let req = test::TestRequest::patch()
+            .uri("/tasks/3")
+            .set_json(serde_json::json!({ "level": 1 }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 2);
+        assert_eq!(res.code, ErrorKey::TaskParentLevelInvalid.to_string());
+
+        let req = test::TestRequest::patch()
+            .uri("/tasks/0")
+            .set_json(serde_json::json!({ "name": "" }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 2);
+        assert_eq!(res.code, ErrorKey::TaskNameEmpty.to_string());
+    }
+
+    #[actix_web::test]
+    async fn test_patch_task_with_invalid_json() {
+        let pool = setup_test_db("task_handler_test", "test_patch_task_with_invalid_json").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 未知のフィールドとnullにできないフィールドへのnullはエラー
+        for body in [
+            serde_json::json!({ "project_id": 1 }),
+            serde_json::json!({ "name": null }),
+            serde_json::json!({ "level": "1" }),
+        ] {
+            let req = test::TestRequest::patch()
+                .uri("/tasks/0")
+                .set_json(body)
+                .to_request();
+            let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+            assert_eq!(res.rc, 2);
+            assert_eq!(res.code, ErrorKey::TaskHandlerInvalidJsonPost.to_string());
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_patch_task_with_id_not_exists() {
+        let pool = setup_test_db("task_handler_test", "test_patch_task_with_id_not_exists").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_task)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/tasks/100")
+            .set_json(serde_json::json!({ "name": "PATCH_task" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), actix_web::http::StatusCode::NOT_FOUND);
     }
 }
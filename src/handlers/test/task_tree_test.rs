@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod task_tree_test {
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::task_tree::{get_project_tree, get_task_tree};
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
+    use crate::models::{ErrorResponse, TaskTreeResponse};
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/task_tree_test").exists() {
+            std::fs::create_dir_all("./test_db/task_tree_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/task_tree_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_get_task_tree() {
+        let pool = setup_test_db("task_tree_test", "test_get_task_tree").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_task_tree)
+                .service(get_project_tree)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // タスク2は自分自身が親になっているが、ルートは1回だけ返す
+        let req = test::TestRequest::get().uri("/tasks/2/tree").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: TaskTreeResponse = test::read_body_json(res).await;
+        assert_eq!(res.count, 1);
+        let root = &res.results[0];
+        assert_eq!(root.task.task_id, 2);
+        assert_eq!(root.task.users.len(), 3);
+        let children: Vec<i64> = root.children.iter().map(|node| node.task.task_id).collect();
+        assert_eq!(children, [3, 4, 5, 6, 7]);
+        assert!(root.children.iter().all(|node| node.children.is_empty()));
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/2/tree?assignee_id=1")
+            .to_request();
+        let res: TaskTreeResponse = test::call_and_read_body_json(&app, req).await;
+        let children: Vec<i64> = res.results[0]
+            .children
+            .iter()
+            .map(|node| node.task.task_id)
+            .collect();
+        assert_eq!(children, [3, 4]);
+        assert!(res.results[0].matched);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/2/tree?max_depth=0")
+            .to_request();
+        let res: TaskTreeResponse = test::call_and_read_body_json(&app, req).await;
+        assert!(res.results[0].children.is_empty());
+
+        let req = test::TestRequest::get()
+            .uri("/projects/0/tree")
+            .to_request();
+        let res: TaskTreeResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].task.task_id, 0);
+    }
+
+    #[actix_web::test]
+    async fn test_get_task_tree_errors() {
+        let pool = setup_test_db("task_tree_test", "test_get_task_tree_errors").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_task_tree)
+                .service(get_project_tree)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::get()
+            .uri("/tasks/2/tree?max_depth=5")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::TaskTreeMaxDepthInvalid.to_string());
+        assert_eq!(res.field.as_deref(), Some("max_depth"));
+
+        let req = test::TestRequest::get().uri("/tasks/abc/tree").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::TaskTreeHandlerInvalidPath.to_string());
+
+        let req = test::TestRequest::get().uri("/tasks/999/tree").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::get()
+            .uri("/projects/999/tree")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,338 @@
+#[cfg(test)]
+mod trash_test {
+    use crate::enums::ProjectRole;
+    use crate::errors::messages::ErrorKey;
+    use crate::handlers::comment::{delete_comment, restore_comment};
+    use crate::handlers::project::{delete_project, restore_project};
+    use crate::handlers::task::{delete_task, get_tasks, restore_task};
+    use crate::handlers::test::utils::{as_admin, login_as, setup_test_db};
+    use crate::handlers::trash::{get_trash, purge_trash};
+    use crate::handlers::user_assign::get_user_assigns;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::{
+        ErrorResponse, ProjectMember, TaskResponse, TrashPurgeResponse, TrashResponse,
+        UserAssignResponse,
+    };
+    use crate::repository::project_member_repo::ProjectMemberRepository;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::{App, test, web};
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/trash_test").exists() {
+            std::fs::create_dir_all("./test_db/trash_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/trash_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[actix_web::test]
+    async fn test_delete_and_restore_task() {
+        let pool = setup_test_db("trash_test", "test_delete_and_restore_task").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(delete_task)
+                .service(restore_task)
+                .service(get_trash)
+                .service(get_user_assigns)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // タスク2の子孫（3〜7）と、その割り当て・コメントも一緒にゴミ箱に移動する
+        let req = test::TestRequest::delete()
+            .uri("/tasks/2?policy=cascade")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=3")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=filter&taskid=3")
+            .to_request();
+        let res: UserAssignResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/trash?entity_type=task")
+            .to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 6);
+        assert!(res.results.iter().all(|item| item.entity_type == "task"));
+
+        let req = test::TestRequest::get()
+            .uri("/trash?entity_type=comment")
+            .to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 14);
+
+        // 親がゴミ箱にある間は子だけを戻せない
+        let req = test::TestRequest::post()
+            .uri("/tasks/3/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::TrashParentDeleted.to_string());
+        let details = res.details.unwrap();
+        assert_eq!(details["parent_type"], "task");
+        assert_eq!(details["parent_id"], 2);
+
+        let req = test::TestRequest::post()
+            .uri("/tasks/2/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: TaskResponse = test::read_body_json(res).await;
+        assert_eq!(res.results[0].name, "TestTrivialTask2");
+
+        let req = test::TestRequest::get().uri("/trash").to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=filter&taskid=3")
+            .to_request();
+        let res: UserAssignResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 2);
+
+        // ゴミ箱にないものは戻せない
+        let req = test::TestRequest::post()
+            .uri("/tasks/2/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::TrashItemNotFound.to_string());
+    }
+
+    #[actix_web::test]
+    async fn test_delete_and_restore_project() {
+        let pool = setup_test_db("trash_test", "test_delete_and_restore_project").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_tasks)
+                .service(delete_project)
+                .service(restore_project)
+                .service(restore_task)
+                .service(get_trash)
+                .service(get_user_assigns)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::delete()
+            .uri("/projects/2?policy=cascade")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/trash?project_id=2")
+            .to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 4);
+        let projects: Vec<i64> = res
+            .results
+            .iter()
+            .filter(|item| item.entity_type == "project")
+            .map(|item| item.id)
+            .collect();
+        assert_eq!(projects, [2]);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=id&id=9")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        // プロジェクトがゴミ箱にある間はタスクだけを戻せない
+        let req = test::TestRequest::post()
+            .uri("/tasks/9/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.details.unwrap()["parent_type"], "project");
+
+        let req = test::TestRequest::post()
+            .uri("/projects/2/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/tasks?target=id&id=10")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get()
+            .uri("/userassigns?target=id&id=9")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::get().uri("/trash").to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+    }
+
+    #[actix_web::test]
+    async fn test_purge_trash() {
+        let pool = setup_test_db("trash_test", "test_purge_trash").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(delete_comment)
+                .service(restore_comment)
+                .service(delete_project)
+                .service(get_trash)
+                .service(purge_trash)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::delete().uri("/comments/1").to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let req = test::TestRequest::delete()
+            .uri("/projects/2?policy=cascade")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // 保持期間内のものは削除しない
+        let req = test::TestRequest::delete().uri("/trash").to_request();
+        let res: TrashPurgeResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.results[0].comments, 0);
+        assert_eq!(res.results[0].projects, 0);
+
+        let req = test::TestRequest::delete()
+            .uri("/trash?older_than_days=0")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let res: TrashPurgeResponse = test::read_body_json(res).await;
+        let summary = &res.results[0];
+        assert_eq!(summary.comments, 1);
+        assert_eq!(summary.projects, 1);
+        assert_eq!(summary.tasks, 3);
+        assert_eq!(summary.user_assigns, 1);
+
+        let req = test::TestRequest::get().uri("/trash").to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 0);
+
+        let req = test::TestRequest::post()
+            .uri("/comments/1/restore")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        let req = test::TestRequest::delete()
+            .uri("/trash?older_than_days=-1")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.field.as_deref(), Some("older_than_days"));
+
+        let req = test::TestRequest::get()
+            .uri("/trash?entity_type=user")
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::TrashEntityTypeInvalid.to_string());
+        assert_eq!(res.field.as_deref(), Some("entity_type"));
+    }
+
+    #[actix_web::test]
+    async fn test_trash_not_admin() {
+        let pool = setup_test_db("trash_test", "test_trash_not_admin").await;
+        let token = login_as(&pool, 1).await;
+        ProjectMemberRepository::new(pool.clone())
+            .create_project_member(ProjectMember::new(0, 1, ProjectRole::Member))
+            .await
+            .unwrap();
+
+        let admin_app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(delete_comment)
+                .service(delete_project)
+                .service(get_trash)
+                .app_data(web::Data::new(pool.clone())),
+        )
+        .await;
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(get_trash)
+                .service(purge_trash)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // プロジェクト0のコメント1と、プロジェクト2とそのタスク8〜10をゴミ箱に移動する
+        let req = test::TestRequest::delete().uri("/comments/1").to_request();
+        let res = test::call_service(&admin_app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+        let req = test::TestRequest::delete()
+            .uri("/projects/2?policy=cascade")
+            .to_request();
+        let res = test::call_service(&admin_app, req).await;
+        assert_eq!(res.status(), StatusCode::OK);
+
+        // メンバーのプロジェクトのものだけを返す
+        let req = test::TestRequest::get()
+            .uri("/trash")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.count, 1);
+        assert_eq!(res.results[0].entity_type, "comment");
+        assert_eq!(res.results[0].id, 1);
+
+        let req = test::TestRequest::get()
+            .uri("/trash?project_id=2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 完全削除は管理者だけができる
+        let req = test::TestRequest::delete()
+            .uri("/trash?older_than_days=0")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::AuthHandlerAdminRequired.to_string());
+
+        let req = test::TestRequest::get().uri("/trash").to_request();
+        let res: TrashResponse = test::call_and_read_body_json(&admin_app, req).await;
+        assert_eq!(res.count, 5);
+    }
+}
@@ -1,12 +1,13 @@
 #[cfg(test)]
 mod user_assign_handler_test {
     use crate::errors::messages::ErrorKey;
-    use crate::handlers::test::utils::setup_test_db;
+    use crate::handlers::test::utils::{as_admin, setup_test_db};
     use crate::handlers::user_assign::{
         create_user_assign, delete_user_assign, get_user_assigns, update_user_assign,
     };
     use crate::models::ErrorResponse;
     use crate::models::{UserAssign, UserAssignResponse};
+    use actix_web::middleware::from_fn;
     use actix_web::{App, test, web};
 
     #[ctor::ctor]
@@ -32,6 +33,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -57,6 +59,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -113,6 +116,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -122,13 +126,10 @@ mod user_assign_handler_test {
             .uri("/userassigns?page=0&page_size=4")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(
-            res.message.contains(
-                ErrorKey::UserAssignHandlerGetUserAssignsInvalidPage
-                    .to_string()
-                    .as_str()
-            )
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::UserAssignHandlerGetUserAssignsInvalidPage.to_string()
         );
     }
 
@@ -142,6 +143,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -151,8 +153,7 @@ mod user_assign_handler_test {
             .uri("/userassigns?page=1&page_size=101")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -161,6 +162,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -189,6 +191,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -218,6 +221,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -247,6 +251,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -256,8 +261,7 @@ mod user_assign_handler_test {
             .uri("/userassigns?target=invalid&id=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -270,6 +274,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -279,8 +284,7 @@ mod user_assign_handler_test {
             .uri("/userassigns?target=id")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -293,6 +297,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -318,6 +323,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -358,6 +364,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -368,8 +375,7 @@ mod user_assign_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -382,6 +388,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -408,6 +415,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -434,6 +442,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
         )
@@ -443,8 +452,7 @@ mod user_assign_handler_test {
             .uri("/userassigns?target=id&id=abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -453,6 +461,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -503,6 +512,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -520,8 +530,7 @@ mod user_assign_handler_test {
             .set_json(user_assign)
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -534,6 +543,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -551,8 +561,7 @@ mod user_assign_handler_test {
             .set_json(user_assign)
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -565,6 +574,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -573,8 +583,7 @@ mod user_assign_handler_test {
 
         let req = test::TestRequest::post().uri("/userassigns").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -587,6 +596,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -605,8 +615,7 @@ mod user_assign_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 6);
     }
 
     #[actix_web::test]
@@ -619,6 +628,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -636,8 +646,7 @@ mod user_assign_handler_test {
             .set_json(user_assign)
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -650,6 +659,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -668,8 +678,7 @@ mod user_assign_handler_test {
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
 
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -678,6 +687,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -712,6 +722,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -720,8 +731,7 @@ mod user_assign_handler_test {
 
         let req = test::TestRequest::post().uri("/userassigns/0").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -734,6 +744,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -751,8 +762,7 @@ mod user_assign_handler_test {
             .set_json(user_assign)
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -765,6 +775,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -782,8 +793,7 @@ mod user_assign_handler_test {
             .set_json(user_assign)
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -796,6 +806,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -806,8 +817,7 @@ mod user_assign_handler_test {
             .uri("/userassigns/abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -816,6 +826,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -842,6 +853,7 @@ mod user_assign_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_user_assign)
                 .service(get_user_assigns)
                 .app_data(web::Data::new(pool)),
@@ -852,7 +864,6 @@ mod user_assign_handler_test {
             .uri("/userassigns/abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 }
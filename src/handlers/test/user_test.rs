@@ -1,16 +1,21 @@
 #[cfg(test)]
 mod user_handler_test {
     use crate::errors::messages::ErrorKey;
-    use crate::handlers::test::utils::setup_test_db;
-    use crate::handlers::user::{create_user, delete_user, get_users, update_user};
+    use crate::handlers::test::utils::{as_admin, login_as, setup_test_db};
+    use crate::handlers::user::{create_user, delete_user, get_users, patch_user, update_user};
     use crate::init_logger;
+    use crate::middleware::auth::AuthMiddleware;
+    use crate::models::DeleteResponse;
     use crate::models::ErrorResponse;
     use crate::models::{User, UserResponse};
+    use crate::settings::LogConfig;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
     use actix_web::{App, test, web};
 
     #[ctor::ctor]
     fn init() {
-        init_logger();
+        init_logger(&LogConfig::default()).unwrap();
 
         // Create test_db directory if it doesn't exist
         if !std::path::Path::new("./test_db/user_handler_test").exists() {
@@ -31,8 +36,13 @@ mod user_handler_test {
     async fn test_get_users() {
         let pool = setup_test_db("user_handler_test", "test_get_users").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get().uri("/users").to_request();
 
@@ -48,8 +58,13 @@ mod user_handler_test {
     async fn test_get_users_with_pagination() {
         let pool = setup_test_db("user_handler_test", "test_get_users_with_pagination").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?page=1&page_size=4")
@@ -112,20 +127,22 @@ mod user_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?page=0&page_size=4")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(
-            res.message.contains(
-                ErrorKey::UserHandlerGetUsersInvalidPage
-                    .to_string()
-                    .as_str()
-            )
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::UserHandlerGetUsersInvalidPage.to_string()
         );
     }
 
@@ -137,20 +154,22 @@ mod user_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?page=1&page_size=1000")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(
-            res.message.contains(
-                ErrorKey::UserHandlerGetUsersInvalidPage
-                    .to_string()
-                    .as_str()
-            )
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::UserHandlerGetUsersInvalidPage.to_string()
         );
     }
 
@@ -158,8 +177,13 @@ mod user_handler_test {
     async fn test_get_users_by_name() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_name").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=name&name=testuser0")
@@ -176,8 +200,13 @@ mod user_handler_test {
     async fn test_get_users_by_id() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_id").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=id&id=0")
@@ -194,75 +223,95 @@ mod user_handler_test {
     async fn test_get_users_by_id_invalid_target() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_id_invalid_target").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=invalid&id=0")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_users_by_id_no_id() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_id_no_id").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=id")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_users_by_name_no_name() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_name_no_name").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=name")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
     async fn test_get_users_by_id_not_exists() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_id_not_exists").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=id&id=1000")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
     async fn test_get_users_by_name_not_exists() {
         let pool = setup_test_db("user_handler_test", "test_get_users_by_name_not_exists").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=name&name=not_exists")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -273,18 +322,20 @@ mod user_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get().uri("/users?page=1").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(
-            res.message.contains(
-                ErrorKey::UserHandlerGetUsersInvalidPage
-                    .to_string()
-                    .as_str()
-            )
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::UserHandlerGetUsersInvalidPage.to_string()
         );
     }
 
@@ -296,20 +347,22 @@ mod user_handler_test {
         )
         .await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?page_size=10")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(
-            res.message.contains(
-                ErrorKey::UserHandlerGetUsersInvalidPage
-                    .to_string()
-                    .as_str()
-            )
+        assert_eq!(res.rc, 2);
+        assert_eq!(
+            res.code,
+            ErrorKey::UserHandlerGetUsersInvalidPage.to_string()
         );
     }
 
@@ -317,15 +370,19 @@ mod user_handler_test {
     async fn test_get_user_by_id_invalid_query() {
         let pool = setup_test_db("user_handler_test", "test_get_user_by_id_invalid_query").await;
 
-        let app =
-            test::init_service(App::new().service(get_users).app_data(web::Data::new(pool))).await;
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(get_users)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
 
         let req = test::TestRequest::get()
             .uri("/users?target=id&id=abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -334,6 +391,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -374,6 +432,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -391,8 +450,7 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -401,6 +459,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -412,14 +471,17 @@ mod user_handler_test {
             .set_json(User {
                 user_id: None,
                 username: "testuser9".to_string(),
-                email: "test9@example.com".to_string(),
+                email: "test_new@example.com".to_string(),
                 password_hash: "dummy_hash_9".to_string(),
             })
             .to_request();
 
-        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.rc, 6);
+        assert_eq!(res.code, ErrorKey::UserAlreadyExists.to_string());
+        assert_eq!(res.field.as_deref(), Some("username"));
     }
 
     #[actix_web::test]
@@ -428,6 +490,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -437,8 +500,7 @@ mod user_handler_test {
         let req = test::TestRequest::post().uri("/users").to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -447,6 +509,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -464,8 +527,10 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
+        assert_eq!(res.code, ErrorKey::UserPasswordEmpty.to_string());
+        assert_eq!(res.field.as_deref(), Some("password"));
+        assert_eq!(res.details, None);
     }
 
     #[actix_web::test]
@@ -474,6 +539,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(create_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -490,9 +556,12 @@ mod user_handler_test {
             })
             .to_request();
 
-        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.rc, 6);
+        assert_eq!(res.code, ErrorKey::UserAlreadyExists.to_string());
+        assert_eq!(res.field.as_deref(), Some("email"));
     }
 
     #[actix_web::test]
@@ -501,6 +570,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -541,6 +611,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -558,8 +629,7 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
     }
 
     #[actix_web::test]
@@ -568,6 +638,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -585,8 +656,7 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -595,6 +665,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -612,9 +683,8 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
+        assert_eq!(res.rc, 2);
         println!("res: {:?}", res.message);
-        assert!(res.message.contains("BadRequest"));
     }
 
     #[actix_web::test]
@@ -623,6 +693,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -632,8 +703,7 @@ mod user_handler_test {
         let req = test::TestRequest::post().uri("/users/1").to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -642,6 +712,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -659,8 +730,10 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
+        assert_eq!(res.code, ErrorKey::UserPasswordEmpty.to_string());
+        assert_eq!(res.field.as_deref(), Some("password"));
+        assert_eq!(res.details, None);
     }
 
     #[actix_web::test]
@@ -669,6 +742,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -686,8 +760,7 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        assert_eq!(res.rc, 6);
     }
 
     #[actix_web::test]
@@ -696,6 +769,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -713,8 +787,7 @@ mod user_handler_test {
             .to_request();
 
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("InternalServerError"));
+        assert_eq!(res.rc, 6);
     }
 
     #[actix_web::test]
@@ -723,6 +796,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(update_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -739,8 +813,7 @@ mod user_handler_test {
             .uri("/users/abc")
             .to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -749,6 +822,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -756,10 +830,10 @@ mod user_handler_test {
         .await;
 
         let req = test::TestRequest::delete().uri("/users/9").to_request();
-        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        let res: DeleteResponse = test::call_and_read_body_json(&app, req).await;
         assert_eq!(res.rc, 0);
         assert_eq!(res.message, "OK");
-        assert_eq!(res.results.len(), 0);
+        assert_eq!(res.results[0].users, 1);
     }
 
     #[actix_web::test]
@@ -768,6 +842,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -776,8 +851,7 @@ mod user_handler_test {
 
         let req = test::TestRequest::delete().uri("/users/abc").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("BadRequest"));
+        assert_eq!(res.rc, 2);
     }
 
     #[actix_web::test]
@@ -786,6 +860,7 @@ mod user_handler_test {
 
         let app = test::init_service(
             App::new()
+                .wrap(from_fn(as_admin))
                 .service(delete_user)
                 .service(get_users)
                 .app_data(web::Data::new(pool)),
@@ -794,7 +869,165 @@ mod user_handler_test {
 
         let req = test::TestRequest::delete().uri("/users/100").to_request();
         let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
-        assert_eq!(res.rc, 1);
-        assert!(res.message.contains("NotFound"));
+        assert_eq!(res.rc, 5);
+    }
+
+    #[actix_web::test]
+    async fn test_patch_user() {
+        let pool = setup_test_db("user_handler_test", "test_patch_user").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_user)
+                .app_data(web::Data::new(pool.clone())),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .set_json(serde_json::json!({ "email": "test1_patch@example.com" }))
+            .to_request();
+        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].username, "testuser1");
+        assert_eq!(res.results[0].email, "test1_patch@example.com");
+
+        // パスワードはハッシュ化して保存する
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .set_json(serde_json::json!({ "password": "new_password" }))
+            .to_request();
+        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].email, "test1_patch@example.com");
+
+        let password_hash: String =
+            sqlx::query_scalar("SELECT password_hash FROM users WHERE user_id = 1")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_ne!(password_hash, "new_password");
+        assert!(
+            crate::repository::password::verify_password("new_password", &password_hash).unwrap()
+        );
+    }
+
+    #[actix_web::test]
+    async fn test_patch_user_invalid() {
+        let pool = setup_test_db("user_handler_test", "test_patch_user_invalid").await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(as_admin))
+                .service(patch_user)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .set_json(serde_json::json!({ "username": "testuser2" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::UserAlreadyExists.to_string());
+        assert_eq!(res.field.as_deref(), Some("username"));
+
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .set_json(serde_json::json!({ "password": "" }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.code, ErrorKey::UserPasswordEmpty.to_string());
+
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .set_json(serde_json::json!({ "password_hash": "dummy" }))
+            .to_request();
+        let res: ErrorResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.code, ErrorKey::UserHandlerInvalidJsonPost.to_string());
+
+        let req = test::TestRequest::patch()
+            .uri("/users/100")
+            .set_json(serde_json::json!({ "username": "testuser100" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[actix_web::test]
+    async fn test_user_handlers_not_admin() {
+        let pool = setup_test_db("user_handler_test", "test_user_handlers_not_admin").await;
+        let token = login_as(&pool, 1).await;
+
+        let app = test::init_service(
+            App::new()
+                .wrap(AuthMiddleware)
+                .service(create_user)
+                .service(update_user)
+                .service(patch_user)
+                .service(delete_user)
+                .app_data(web::Data::new(pool)),
+        )
+        .await;
+
+        // 管理者以外はユーザーを作成・削除できない
+        let req = test::TestRequest::post()
+            .uri("/users")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(User {
+                user_id: None,
+                username: "testuser10".to_string(),
+                email: "test10@example.com".to_string(),
+                password_hash: "dummy_hash_10".to_string(),
+            })
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::AuthHandlerAdminRequired.to_string());
+
+        let req = test::TestRequest::delete()
+            .uri("/users/2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 他のユーザーは変更できない
+        let req = test::TestRequest::post()
+            .uri("/users/2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(User {
+                user_id: Some(2),
+                username: "testuser2".to_string(),
+                email: "test2@example.com".to_string(),
+                password_hash: "new_password".to_string(),
+            })
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+        let res: ErrorResponse = test::read_body_json(res).await;
+        assert_eq!(res.code, ErrorKey::UserHandlerPermissionDenied.to_string());
+
+        let req = test::TestRequest::patch()
+            .uri("/users/2")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(serde_json::json!({ "password": "new_password" }))
+            .to_request();
+        let res = test::call_service(&app, req).await;
+        assert_eq!(res.status(), StatusCode::FORBIDDEN);
+
+        // 自分自身は変更できる
+        let req = test::TestRequest::patch()
+            .uri("/users/1")
+            .insert_header(("Authorization", format!("Bearer {}", token)))
+            .set_json(serde_json::json!({ "email": "test1_patch@example.com" }))
+            .to_request();
+        let res: UserResponse = test::call_and_read_body_json(&app, req).await;
+        assert_eq!(res.rc, 0);
+        assert_eq!(res.results[0].email, "test1_patch@example.com");
     }
 }
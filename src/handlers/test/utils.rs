@@ -1,3 +1,13 @@
+use crate::constants::SESSION_TTL_SECONDS;
+use crate::handlers::utils::{generate_token, hash_token};
+use crate::middleware::auth::AdminUser;
+use crate::models::{Session, UserNoPassword};
+use crate::repository::session_repo::SessionRepository;
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{Error, HttpMessage};
+use chrono::Utc;
 use sqlx::migrate::MigrateDatabase;
 use sqlx::sqlite::Sqlite;
 use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
@@ -22,3 +32,32 @@ pub async fn setup_test_db(dir: &str, name: &str) -> SqlitePool {
 
     pool
 }
+
+// 指定したユーザーのセッションを作成し、Bearerトークンを返す
+pub async fn login_as(pool: &SqlitePool, user_id: i64) -> String {
+    let token = generate_token();
+    let session = Session::new(
+        user_id,
+        hash_token(&token),
+        Utc::now().timestamp() + SESSION_TTL_SECONDS,
+    );
+    SessionRepository::new(pool.clone())
+        .create_session(session)
+        .await
+        .unwrap();
+    token
+}
+
+// 認証ミドルウェアの代わりに、フィクスチャのユーザー0を管理者として格納する
+pub async fn as_admin(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    req.extensions_mut().insert(UserNoPassword {
+        user_id: Some(0),
+        username: "testuser0".to_string(),
+        email: "test0@example.com".to_string(),
+    });
+    req.extensions_mut().insert(AdminUser);
+    next.call(req).await
+}
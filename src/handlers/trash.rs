@@ -0,0 +1,175 @@
+use crate::enums::ProjectRole;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::authorize_project;
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_member_scope;
+use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_error;
+use crate::handlers::utils::require_admin;
+use crate::models::TrashFilter;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::models::response_model::{TrashPurgeResponse, TrashResponse};
+use crate::repository::trash_repo::TrashRepository;
+use actix_web::{HttpRequest, HttpResponse, delete, get, web};
+use serde::Deserialize;
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct GetTrashQuery {
+    // project、task、commentのいずれか
+    entity_type: Option<String>,
+    project_id: Option<i64>,
+}
+
+impl GetTrashQuery {
+    // member_idを指定した場合はそのユーザーがメンバーのプロジェクトに絞り込む
+    fn get_trash_filter(&self, member_id: Option<i64>) -> Option<TrashFilter> {
+        if self.entity_type.is_none() && self.project_id.is_none() && member_id.is_none() {
+            return None;
+        }
+
+        Some(TrashFilter {
+            entity_type: self.entity_type.clone(),
+            project_id: self.project_id,
+            member_id,
+        })
+    }
+}
+
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+struct PurgeTrashQuery {
+    // 未指定の場合は設定の保持日数（trash.retention_days）
+    older_than_days: Option<i64>,
+}
+
+// 新しくゴミ箱に移動した順に返す
+#[utoipa::path(
+    tag = "trash",
+    summary = "List projects, tasks and comments in the trash",
+    params(GetTrashQuery),
+    responses(
+        (status = 200, description = "Success", body = TrashResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[get("/trash")]
+pub async fn get_trash(
+    req: HttpRequest,
+    query: Result<web::Query<GetTrashQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TrashHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let member_id = match get_member_scope(&req) {
+        Ok(member_id) => member_id,
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+    // プロジェクトを指定した場合はそのプロジェクトの閲覧権限が必要
+    let authorized = match query.project_id {
+        Some(project_id) => {
+            authorize_project(&req, pool.get_ref(), project_id, ProjectRole::Viewer).await
+        }
+        None => Ok(()),
+    };
+    if let Err(e) = authorized {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let trash_repo = TrashRepository::new(pool.get_ref().clone());
+    let items = trash_repo
+        .get_trash_items(query.get_trash_filter(member_id).as_ref())
+        .await
+        .map_err(HandlerError::from);
+
+    match items {
+        Ok(items) => {
+            let response = TrashResponse::new(items, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 保持期間を過ぎたものを完全に削除する。元に戻せない
+#[utoipa::path(
+    tag = "trash",
+    summary = "Permanently delete items that have been in the trash longer than the retention window",
+    params(PurgeTrashQuery),
+    responses(
+        (status = 200, description = "Number of purged rows per entity", body = TrashPurgeResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[delete("/trash")]
+pub async fn purge_trash(
+    req: HttpRequest,
+    query: Result<web::Query<PurgeTrashQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let query = match query {
+        Ok(query) => query.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::TrashHandlerInvalidQuery,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    let trash_repo = TrashRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
+    let summary = trash_repo
+        .purge_trash(query.older_than_days)
+        .await
+        .map_err(HandlerError::from);
+
+    match summary {
+        Ok(summary) => {
+            let response = TrashPurgeResponse::new(summary, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
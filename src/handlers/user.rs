@@ -1,20 +1,33 @@
+use crate::enums::AuditEntityType;
 use crate::errors::handler_errors::HandlerError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::DeletePolicyQuery;
+use crate::handlers::utils::authorize_user;
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_if_match_versions;
 use crate::handlers::utils::get_request_id;
 use crate::handlers::utils::handle_error;
-use crate::handlers::utils::hash_password;
+use crate::handlers::utils::require_admin;
+use crate::handlers::utils::{with_current_etag, with_etag};
+use crate::models::DeleteResponse;
 use crate::models::PaginationParams;
 use crate::models::response_model::ErrorResponse;
 use crate::models::response_model::Pagination;
 use crate::models::response_model::PaginationStatus;
 use crate::models::response_model::ResponseMetadata;
 use crate::models::response_model::UserResponse;
-use crate::models::{User, UserNoPassword};
+use crate::models::{User, UserNoPassword, UserPatch};
+use crate::repository::password::hash_password_async;
 use crate::repository::user_repo::*;
-use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
+use crate::repository::version_repo::{INITIAL_VERSION, Versioned};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, patch, post, web};
 use serde::Deserialize;
+use serde_json::json;
 use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
 enum QueryTarget {
     All,
     Name,
@@ -35,8 +48,10 @@ impl QueryTarget {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct GetUsersQuery {
+    #[param(value_type = Option<QueryTarget>, inline)]
     target: Option<String>,
     page: Option<i32>,
     page_size: Option<i32>,
@@ -111,11 +126,10 @@ async fn get_users_with_pagination(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::UserHandlerGetUsersInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -143,11 +157,10 @@ async fn get_all_users(req: HttpRequest, query: GetUsersQuery, pool: SqlitePool)
                 _ => None,
             };
             let response = UserResponse::new(users, len, pagination, Some(metadata));
-            log::debug!("Response: {:?}", response);
             return HttpResponse::Ok().json(response);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -163,7 +176,7 @@ async fn get_user_by_name(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
@@ -177,11 +190,10 @@ async fn get_user_by_name(
     match user {
         Ok(user) => {
             let response = UserResponse::new(vec![user], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
             return HttpResponse::Ok().json(response);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -193,7 +205,7 @@ async fn get_user_by_id(req: HttpRequest, query: GetUsersQuery, pool: SqlitePool
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return HttpResponse::InternalServerError().json(response);
         }
     };
@@ -206,17 +218,36 @@ async fn get_user_by_id(req: HttpRequest, query: GetUsersQuery, pool: SqlitePool
 
     match user {
         Ok(user) => {
+            let id = user.user_id;
             let response = UserResponse::new(vec![user], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            return HttpResponse::Ok().json(response);
+            return with_current_etag(
+                HttpResponse::Ok().json(response),
+                &pool,
+                AuditEntityType::User,
+                id,
+            )
+            .await;
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Get users",
+    description = "target selects the lookup: all (default, paginated with page and page_size), name (requires name) or id (requires id).",
+    params(GetUsersQuery),
+    responses(
+        (status = 200, description = "Success", body = UserResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[get("/users")]
 pub async fn get_users(
     req: HttpRequest,
@@ -230,14 +261,14 @@ pub async fn get_users(
                 ErrorKey::UserHandlerInvalidQuery,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
     let target = match query.target() {
         Ok(target) => target,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&e, None);
             return handle_error(e, response);
         }
     };
@@ -249,6 +280,19 @@ pub async fn get_users(
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Create a user",
+    request_body = User,
+    responses(
+        (status = 200, description = "The created user", body = UserResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/users")]
 pub async fn create_user(
     req: HttpRequest,
@@ -257,6 +301,11 @@ pub async fn create_user(
 ) -> HttpResponse {
     let metadata = ResponseMetadata::new(get_request_id(&req));
 
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
     let user_data = match user_data {
         Ok(data) => data,
         Err(e) => {
@@ -264,12 +313,19 @@ pub async fn create_user(
                 ErrorKey::UserHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let hashed_password = hash_password(&user_data.password_hash);
+    let hashed_password = match hash_password_async(user_data.password_hash.clone()).await {
+        Ok(hash) => hash,
+        Err(e) => {
+            let e = HandlerError::from(e);
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
     let insert_user = User {
         user_id: None,
         username: user_data.username.clone(),
@@ -277,7 +333,8 @@ pub async fn create_user(
         password_hash: hashed_password,
     };
 
-    let user_repo = UserRepository::new(pool.get_ref().clone());
+    let user_repo = UserRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let user = user_repo
         .create_user(insert_user)
         .await
@@ -286,15 +343,34 @@ pub async fn create_user(
     match user {
         Ok(user) => {
             let response = UserResponse::new(vec![user], 1, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), INITIAL_VERSION);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "users",
+    summary = "Update a user",
+    params(
+        ("id" = i64, Path, description = "User ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = User,
+    responses(
+        (status = 200, description = "The updated user", body = UserResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Only the user or an administrator can change the user", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/users/{id}")]
 pub async fn update_user(
     req: HttpRequest,
@@ -311,11 +387,16 @@ pub async fn update_user(
                 ErrorKey::UserHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
+    if let Err(e) = authorize_user(&req, path) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
     let user_data = match user_data {
         Ok(data) => data,
         Err(e) => {
@@ -323,7 +404,7 @@ pub async fn update_user(
                 ErrorKey::UserHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
@@ -340,11 +421,18 @@ pub async fn update_user(
                 path_id, user_data.user_id
             ),
         ));
-        let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+        let response = ErrorResponse::from_error(&e, Some(metadata));
         return handle_error(e, response);
     }
 
-    let hashed_password = hash_password(&user_data.password_hash);
+    let hashed_password = match hash_password_async(user_data.password_hash.clone()).await {
+        Ok(hash) => hash,
+        Err(e) => {
+            let e = HandlerError::from(e);
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
     let update_user = User {
         user_id: Some(path_id),
         username: user_data.username.clone(),
@@ -352,27 +440,53 @@ pub async fn update_user(
         password_hash: hashed_password,
     };
 
-    let user_repo = UserRepository::new(pool.get_ref().clone());
+    let user_repo = UserRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let user = user_repo
         .update_user(update_user)
         .await
         .map_err(HandlerError::from);
 
     match user {
-        Ok(user) => {
+        Ok(Versioned {
+            value: user,
+            version,
+        }) => {
             let response = UserResponse::new(vec![user], 1, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+            return with_etag(HttpResponse::Ok().json(response), version);
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
-#[delete("/users/{id}")]
-pub async fn delete_user(
+#[utoipa::path(
+    tag = "users",
+    summary = "Partially update a user",
+    description = "Only the fields present in the body are validated and updated. A new password is hashed before it is stored.",
+    params(
+        ("id" = i64, Path, description = "User ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = UserPatch,
+    responses(
+        (status = 200, description = "The updated user", body = UserResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Only the user or an administrator can change the user", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[patch("/users/{id}")]
+pub async fn patch_user(
     req: HttpRequest,
+    patch_data: Result<web::Json<UserPatch>, actix_web::Error>,
     path: Result<web::Path<i64>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
 ) -> HttpResponse {
@@ -385,27 +499,146 @@ pub async fn delete_user(
                 ErrorKey::UserHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
             return handle_error(error, response);
         }
     };
 
-    let path_id = path;
+    if let Err(e) = authorize_user(&req, path) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let patch_data = match patch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::UserHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
 
-    let user_repo = UserRepository::new(pool.get_ref().clone());
+    // パスワードはハッシュ化して保存する
+    let patch_data = match patch_data.password {
+        Some(password) => match hash_password_async(password).await {
+            Ok(hash) => UserPatch {
+                password: Some(hash),
+                ..patch_data
+            },
+            Err(e) => {
+                let e = HandlerError::from(e);
+                let response = ErrorResponse::from_error(&e, Some(metadata));
+                return handle_error(e, response);
+            }
+        },
+        None => patch_data,
+    };
+
+    let user_repo = UserRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let user = user_repo
-        .delete_user(path_id)
+        .patch_user(path, patch_data)
         .await
         .map_err(HandlerError::from);
 
     match user {
-        Ok(()) => {
-            let response = UserResponse::new(vec![], 0, None, Some(metadata));
-            return HttpResponse::Ok().json(response);
+        Ok(Versioned {
+            value: user,
+            version,
+        }) => {
+            let response = UserResponse::new(vec![user], 1, None, Some(metadata));
+            with_etag(HttpResponse::Ok().json(response), version)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
+    }
+}
+
+// 削除ポリシーの既定はrestrict。cascadeは割り当てとコメントも一緒に削除する。reparentは使えない
+#[utoipa::path(
+    tag = "users",
+    summary = "Delete a user according to the delete policy",
+    params(
+        ("id" = i64, Path, description = "User ID"),
+        DeletePolicyQuery,
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    responses(
+        (status = 200, description = "Number of removed rows", body = DeleteResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "Administrator privileges are required", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Dependent records prevent deletion under the policy", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[delete("/users/{id}")]
+pub async fn delete_user(
+    req: HttpRequest,
+    path: Result<web::Path<i64>, actix_web::Error>,
+    query: Result<web::Query<DeletePolicyQuery>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    if let Err(e) = require_admin(&req) {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let path = match path {
+        Ok(path) => path.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::UserHandlerInvalidPath,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
         }
+    };
+
+    let path_id = path;
+
+    let policy = match query {
+        Ok(query) => query.get_delete_policy(),
+        Err(e) => Err(HandlerError::BadRequest(get_error_message(
+            ErrorKey::UserHandlerInvalidQuery,
+            format!("ActixWebError: {}", e),
+        ))),
+    };
+    let policy = match policy {
+        Ok(policy) => policy,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
+    };
+
+    let user_repo = UserRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
+    let summary = user_repo
+        .delete_user(path_id, policy)
+        .await
+        .map_err(HandlerError::from);
+
+    match summary {
+        Ok(summary) => {
+            let response = DeleteResponse::new(summary, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            handle_error(e, response)
+        }
     }
 }
@@ -1,19 +1,34 @@
+use crate::enums::{AuditEntityType, ProjectRole};
 use crate::errors::handler_errors::HandlerError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::get_audit_context;
+use crate::handlers::utils::get_if_match_versions;
+use crate::handlers::utils::get_member_scope;
 use crate::handlers::utils::get_request_id;
+use crate::handlers::utils::handle_batch_error;
 use crate::handlers::utils::handle_error;
+use crate::handlers::utils::{authorize_task, authorize_user_assign};
+use crate::handlers::utils::{with_current_etag, with_etag};
 use crate::models::PaginationParams;
 use crate::models::response_model::ErrorResponse;
 use crate::models::response_model::Pagination;
 use crate::models::response_model::PaginationStatus;
 use crate::models::response_model::ResponseMetadata;
+use crate::models::response_model::UserAssignBatchResponse;
 use crate::models::response_model::UserAssignResponse;
-use crate::models::{UserAssign, UserAssignFilter};
+use crate::models::{
+    UserAssign, UserAssignBatchOperation, UserAssignBatchRequest, UserAssignFilter,
+};
 use crate::repository::user_assign_repo::*;
+use crate::repository::version_repo::{INITIAL_VERSION, Versioned};
 use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
 use serde::Deserialize;
+use serde_json::json;
 use sqlx::sqlite::SqlitePool;
+use utoipa::{IntoParams, ToSchema};
 
+#[derive(ToSchema)]
+#[schema(rename_all = "snake_case")]
 enum QueryTarget {
     All,
     Id,
@@ -34,8 +49,10 @@ impl QueryTarget {
     }
 }
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
 struct GetUserAssignsQuery {
+    #[param(value_type = Option<QueryTarget>, inline)]
     target: Option<String>,
     page: Option<i32>,
     page_size: Option<i32>,
@@ -73,10 +90,12 @@ impl GetUserAssignsQuery {
         }
     }
 
-    fn get_user_assign_filter(&self) -> Option<UserAssignFilter> {
+    // member_idを指定した場合はそのユーザーがメンバーのプロジェクトに絞り込む
+    fn get_user_assign_filter(&self, member_id: Option<i64>) -> Option<UserAssignFilter> {
         let filter = UserAssignFilter {
             user_id: self.userid,
             task_id: self.taskid,
+            member_id,
         };
 
         match filter.is_empty() {
@@ -119,11 +138,10 @@ async fn get_user_assigns_with_pagination(
         }
         PaginationStatus::Error => Err(HandlerError::BadRequest(get_error_message(
             ErrorKey::UserAssignHandlerGetUserAssignsInvalidPage,
-            format!(
-                "page: {:?}, page_size: {:?}",
-                pagination_params.page(),
-                pagination_params.page_size()
-            ),
+            [
+                ("page", json!(pagination_params.page())),
+                ("page_size", json!(pagination_params.page_size())),
+            ],
         ))),
     }
 }
@@ -138,12 +156,15 @@ async fn get_all_or_filtered_user_assigns(
     let mut pagination_params = PaginationParams::new(query.page, query.page_size);
     pagination_params.validate();
 
-    let result = get_user_assigns_with_pagination(
-        &pagination_params,
-        query.get_user_assign_filter().as_ref(),
-        pool,
-    )
-    .await;
+    let filter = match get_member_scope(&req) {
+        Ok(member_id) => query.get_user_assign_filter(member_id),
+        Err(e) => {
+            let response = ErrorResponse::from_error(&e, Some(metadata));
+            return handle_error(e, response);
+        }
+    };
+
+    let result = get_user_assigns_with_pagination(&pagination_params, filter.as_ref(), pool).await;
 
     match result {
         Ok(user_assigns) => {
@@ -160,11 +181,10 @@ async fn get_all_or_filtered_user_assigns(
                 _ => None,
             };
             let response = UserAssignResponse::new(user_assigns, len, pagination, Some(metadata));
-            log::debug!("Response: {:?}", response);
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
@@ -180,32 +200,58 @@ async fn get_user_assign_by_id(
     let validated_query = match query.validate() {
         Ok(()) => query,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     };
 
+    let user_assign_id = validated_query.id.unwrap();
+    if let Err(e) = authorize_user_assign(&req, &pool, user_assign_id, ProjectRole::Viewer).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
     let user_assign_repo = UserAssignRepository::new(pool.clone());
     let user_assign = user_assign_repo
-        .get_user_assign_by_id(validated_query.id.unwrap())
+        .get_user_assign_by_id(user_assign_id)
         .await
         .map_err(HandlerError::from);
 
     match user_assign {
         Ok(user_assign) => {
+            let id = user_assign.user_assign_id;
             let response = UserAssignResponse::new(vec![user_assign], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            HttpResponse::Ok().json(response)
+            with_current_etag(
+                HttpResponse::Ok().json(response),
+                &pool,
+                AuditEntityType::UserAssign,
+                id,
+            )
+            .await
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "user_assigns",
+    summary = "Get task assignments",
+    description = "target selects the lookup: all (default, paginated with page and page_size), id (requires id) or filter (userid and/or taskid).",
+    params(GetUserAssignsQuery),
+    responses(
+        (status = 200, description = "Success", body = UserAssignResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[get("/userassigns")]
-async fn get_user_assigns(
+pub async fn get_user_assigns(
     req: HttpRequest,
     query: Result<web::Query<GetUserAssignsQuery>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
@@ -217,7 +263,7 @@ async fn get_user_assigns(
                 ErrorKey::UserAssignHandlerInvalidQuery,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -225,7 +271,7 @@ async fn get_user_assigns(
     let target = match query.target() {
         Ok(target) => target,
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&e, None);
             return handle_error(e, response);
         }
     };
@@ -241,8 +287,22 @@ async fn get_user_assigns(
     }
 }
 
+#[utoipa::path(
+    tag = "user_assigns",
+    summary = "Assign a user to a task",
+    request_body = UserAssign,
+    responses(
+        (status = 200, description = "The created assignment", body = UserAssignResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/userassigns")]
-async fn create_user_assign(
+pub async fn create_user_assign(
     req: HttpRequest,
     user_assign_data: Result<web::Json<UserAssign>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
@@ -256,12 +316,25 @@ async fn create_user_assign(
                 ErrorKey::UserAssignHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
 
-    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_task(
+        &req,
+        pool.get_ref(),
+        user_assign_data.task_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
     let user_assign = user_assign_repo
         .create_user_assign(user_assign_data.into_inner())
         .await
@@ -270,18 +343,36 @@ async fn create_user_assign(
     match user_assign {
         Ok(user_assign) => {
             let response = UserAssignResponse::new(vec![user_assign], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            HttpResponse::Ok().json(response)
+            with_etag(HttpResponse::Ok().json(response), INITIAL_VERSION)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "user_assigns",
+    summary = "Update a task assignment",
+    params(
+        ("id" = i64, Path, description = "User assign ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    request_body = UserAssign,
+    responses(
+        (status = 200, description = "The updated assignment", body = UserAssignResponse, headers(("ETag" = String, description = "Current version of the resource"))),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[post("/userassigns/{id}")]
-async fn update_user_assign(
+pub async fn update_user_assign(
     req: HttpRequest,
     user_assign_data: Result<web::Json<UserAssign>, actix_web::Error>,
     path: Result<web::Path<i64>, actix_web::Error>,
@@ -296,7 +387,7 @@ async fn update_user_assign(
                 ErrorKey::UserAssignHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -308,7 +399,7 @@ async fn update_user_assign(
                 ErrorKey::UserAssignHandlerInvalidJsonPost,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
@@ -324,31 +415,69 @@ async fn update_user_assign(
                 path, user_assign_data.user_assign_id
             ),
         ));
-        let response = ErrorResponse::new(error.to_string(), 1, None);
+        let response = ErrorResponse::from_error(&error, None);
         return handle_error(error, response);
     }
 
-    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_user_assign(&req, pool.get_ref(), path, ProjectRole::Member).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    if let Err(e) = authorize_task(
+        &req,
+        pool.get_ref(),
+        user_assign_data.task_id,
+        ProjectRole::Member,
+    )
+    .await
+    {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let user_assign = user_assign_repo
         .update_user_assign(user_assign_data.into_inner())
         .await
         .map_err(HandlerError::from);
 
     match user_assign {
-        Ok(user_assign) => {
+        Ok(Versioned {
+            value: user_assign,
+            version,
+        }) => {
             let response = UserAssignResponse::new(vec![user_assign], 1, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
-            HttpResponse::Ok().json(response)
+            with_etag(HttpResponse::Ok().json(response), version)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
 
+#[utoipa::path(
+    tag = "user_assigns",
+    summary = "Delete a task assignment",
+    params(
+        ("id" = i64, Path, description = "User assign ID"),
+        ("If-Match" = Option<String>, Header, description = "Fail with 412 unless the current version (ETag) matches"),
+    ),
+    responses(
+        (status = 200, description = "The deleted assignment", body = UserAssignResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the If-Match version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
 #[delete("/userassigns/{id}")]
-async fn delete_user_assign(
+pub async fn delete_user_assign(
     req: HttpRequest,
     path: Result<web::Path<i64>, actix_web::Error>,
     pool: web::Data<SqlitePool>,
@@ -362,12 +491,19 @@ async fn delete_user_assign(
                 ErrorKey::UserAssignHandlerInvalidPath,
                 format!("ActixWebError: {}", e),
             ));
-            let response = ErrorResponse::new(error.to_string(), 1, None);
+            let response = ErrorResponse::from_error(&error, None);
             return handle_error(error, response);
         }
     };
 
-    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone());
+    if let Err(e) = authorize_user_assign(&req, pool.get_ref(), path, ProjectRole::Member).await {
+        let response = ErrorResponse::from_error(&e, Some(metadata));
+        return handle_error(e, response);
+    }
+
+    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata))
+        .with_expected_versions(get_if_match_versions(&req));
     let user_assign = user_assign_repo
         .delete_user_assign(path)
         .await
@@ -376,12 +512,90 @@ async fn delete_user_assign(
     match user_assign {
         Ok(()) => {
             let response = UserAssignResponse::new(vec![], 0, None, Some(metadata));
-            log::debug!("Response: {:?}", response);
             HttpResponse::Ok().json(response)
         }
         Err(e) => {
-            let response = ErrorResponse::new(e.to_string(), 1, Some(metadata));
+            let response = ErrorResponse::from_error(&e, Some(metadata));
             return handle_error(e, response);
         }
     }
 }
+
+// 割り当て先のタスクのプロジェクトで必要なロールを持っているか確認する
+async fn authorize_user_assign_batch_operation(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    operation: &UserAssignBatchOperation,
+) -> Result<(), HandlerError> {
+    match operation {
+        UserAssignBatchOperation::Create { user_assign } => {
+            authorize_task(req, pool, user_assign.task_id, ProjectRole::Member).await
+        }
+        UserAssignBatchOperation::Update { user_assign, .. } => {
+            if let Some(user_assign_id) = user_assign.user_assign_id {
+                authorize_user_assign(req, pool, user_assign_id, ProjectRole::Member).await?;
+            }
+            authorize_task(req, pool, user_assign.task_id, ProjectRole::Member).await
+        }
+        UserAssignBatchOperation::Delete { user_assign_id, .. } => {
+            authorize_user_assign(req, pool, *user_assign_id, ProjectRole::Member).await
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "user_assigns",
+    summary = "Create, update and delete task assignments in one transaction",
+    description = "Operations run in order in a single transaction and any failure rolls back the whole batch. The error body has the index of the failed operation in `details.index`.",
+    request_body = UserAssignBatchRequest,
+    responses(
+        (status = 200, description = "The result of each operation", body = UserAssignBatchResponse),
+        (status = 400, description = "Invalid request parameters or body", body = ErrorResponse),
+        (status = 401, description = "Missing, invalid or expired bearer token", body = ErrorResponse),
+        (status = 403, description = "The user lacks the required project role", body = ErrorResponse),
+        (status = 404, description = "The resource does not exist", body = ErrorResponse),
+        (status = 409, description = "Conflicts with an existing resource", body = ErrorResponse),
+        (status = 412, description = "The resource has been modified since the given version", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse),
+    ),
+)]
+#[post("/userassigns/batch")]
+pub async fn batch_user_assigns(
+    req: HttpRequest,
+    batch_data: Result<web::Json<UserAssignBatchRequest>, actix_web::Error>,
+    pool: web::Data<SqlitePool>,
+) -> HttpResponse {
+    let metadata = ResponseMetadata::new(get_request_id(&req));
+
+    let batch_data = match batch_data {
+        Ok(data) => data.into_inner(),
+        Err(e) => {
+            let error = HandlerError::BadRequest(get_error_message(
+                ErrorKey::UserAssignHandlerInvalidJsonPost,
+                format!("ActixWebError: {}", e),
+            ));
+            let response = ErrorResponse::from_error(&error, Some(metadata));
+            return handle_error(error, response);
+        }
+    };
+
+    for (index, operation) in batch_data.operations.iter().enumerate() {
+        if let Err(e) = authorize_user_assign_batch_operation(&req, pool.get_ref(), operation).await
+        {
+            return handle_batch_error(e, Some(index), metadata);
+        }
+    }
+
+    let user_assign_repo = UserAssignRepository::new(pool.get_ref().clone())
+        .with_audit_context(get_audit_context(&req, &metadata));
+    match user_assign_repo
+        .run_user_assign_batch(batch_data.operations)
+        .await
+    {
+        Ok(results) => {
+            let response = UserAssignBatchResponse::new(results, Some(metadata));
+            HttpResponse::Ok().json(response)
+        }
+        Err(e) => handle_batch_error(HandlerError::from(e.error), e.index, metadata),
+    }
+}
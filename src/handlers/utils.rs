@@ -1,29 +1,371 @@
+use crate::constants::REQUEST_ID_HEADER;
+use crate::enums::{AuditEntityType, DeletePolicy, ProjectRole};
 use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::metrics;
+use crate::middleware::auth::AdminUser;
+use crate::middleware::request_id::RequestId;
+use crate::models::AuditContext;
+use crate::models::CommentWithUser;
 use crate::models::ErrorResponse;
+use crate::models::ResponseMetadata;
+use crate::models::UserNoPassword;
+use crate::repository::comment_repo::CommentRepository;
+use crate::repository::project_member_repo::ProjectMemberRepository;
+use crate::repository::task_repo::TaskRepository;
+use crate::repository::trash_repo::TrashRepository;
+use crate::repository::user_assign_repo::UserAssignRepository;
+use crate::repository::version_repo::get_version;
+use actix_web::HttpMessage;
 use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::http::header::{ETAG, EntityTag, HeaderValue, IF_MATCH};
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
 use sha2::{Digest, Sha256};
+use sqlx::sqlite::SqlitePool;
+use utoipa::IntoParams;
 use uuid::Uuid;
 
 pub fn get_request_id(req: &HttpRequest) -> String {
+    // RequestIdMiddlewareが確定したIDを優先する
+    if let Some(RequestId(request_id)) = req.extensions().get::<RequestId>() {
+        return request_id.clone();
+    }
+
     // クライアントから送信されたリクエストIDを取得
     req.headers()
-        .get("X-Request-ID")
+        .get(REQUEST_ID_HEADER)
         .and_then(|v| v.to_str().ok())
         .map(|s| s.to_string())
         .unwrap_or_else(|| Uuid::new_v4().to_string())
 }
 
+// エラーのメトリクスはレスポンスを返すときに1回だけ記録する
 pub fn handle_error(e: HandlerError, response: ErrorResponse) -> HttpResponse {
     log::error!("Error: {:?}", e);
+    metrics().record_error(&response.code);
     match e {
         HandlerError::NotFound(_) => HttpResponse::NotFound().json(response),
         HandlerError::InternalServerError(_) => HttpResponse::InternalServerError().json(response),
         HandlerError::BadRequest(_) => HttpResponse::BadRequest().json(response),
+        HandlerError::Unauthorized(_) => HttpResponse::Unauthorized().json(response),
+        HandlerError::Forbidden(_) => HttpResponse::Forbidden().json(response),
+        HandlerError::Conflict(_) => HttpResponse::Conflict().json(response),
+        HandlerError::PreconditionFailed(_) => HttpResponse::PreconditionFailed().json(response),
+    }
+}
+
+// バッチの操作が失敗した場合のレスポンス。失敗した操作の位置をdetails.indexに入れる
+pub fn handle_batch_error(
+    e: HandlerError,
+    index: Option<usize>,
+    metadata: ResponseMetadata,
+) -> HttpResponse {
+    let mut response = ErrorResponse::from_error(&e, Some(metadata));
+    if let Some(index) = index {
+        let mut details = match response.details.take() {
+            Some(Value::Object(details)) => details,
+            _ => Map::new(),
+        };
+        details.insert("index".to_string(), json!(index));
+        response.details = Some(Value::Object(details));
+    }
+    handle_error(e, response)
+}
+
+// Authorization: Bearer <token> ヘッダーからトークンを取得
+pub fn get_bearer_token(req: &HttpRequest) -> Option<String> {
+    req.headers()
+        .get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+pub fn generate_token() -> String {
+    format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+// 認証ミドルウェアがリクエストに格納したユーザーを取得
+pub fn get_current_user(req: &HttpRequest) -> Option<UserNoPassword> {
+    req.extensions().get::<UserNoPassword>().cloned()
+}
+
+// 認証ミドルウェアを通っていない（ユーザーが格納されていない）場合は認証エラーにする
+pub fn require_current_user(req: &HttpRequest) -> Result<UserNoPassword, HandlerError> {
+    get_current_user(req).ok_or_else(|| {
+        HandlerError::Unauthorized(get_error_message(
+            ErrorKey::AuthHandlerNoToken,
+            "".to_string(),
+        ))
+    })
+}
+
+// 管理者はすべてのプロジェクトで必要なロールを持つものとして扱う
+pub fn is_admin(req: &HttpRequest) -> bool {
+    req.extensions().get::<AdminUser>().is_some()
+}
+
+// 管理用のエンドポイントは管理者だけが利用できる
+pub fn require_admin(req: &HttpRequest) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+    match is_admin(req) {
+        true => Ok(()),
+        false => Err(HandlerError::Forbidden(get_error_message(
+            ErrorKey::AuthHandlerAdminRequired,
+            [("path", json!(req.path()))],
+        ))),
+    }
+}
+
+// ユーザー本人または管理者だけがユーザーを変更できる
+pub fn authorize_user(req: &HttpRequest, user_id: i64) -> Result<(), HandlerError> {
+    let user = require_current_user(req)?;
+    match is_admin(req) || user.user_id == Some(user_id) {
+        true => Ok(()),
+        false => Err(HandlerError::Forbidden(get_error_message(
+            ErrorKey::UserHandlerPermissionDenied,
+            [
+                ("user_id", json!(user_id)),
+                ("current_user_id", json!(user.user_id)),
+            ],
+        ))),
+    }
+}
+
+// 一覧を絞り込むユーザーID。管理者の場合はNoneを返し、すべてのプロジェクトを対象にする
+pub fn get_member_scope(req: &HttpRequest) -> Result<Option<i64>, HandlerError> {
+    let user = require_current_user(req)?;
+    match is_admin(req) {
+        true => Ok(None),
+        false => Ok(Some(user.user_id.unwrap_or(-1))),
+    }
+}
+
+// 監査ログに記録する操作者とリクエストIDを取得
+pub fn get_audit_context(req: &HttpRequest, metadata: &ResponseMetadata) -> AuditContext {
+    AuditContext::new(
+        get_current_user(req).and_then(|user| user.user_id),
+        Some(metadata.request_id.clone()),
+    )
+}
+
+// 現在のユーザーがプロジェクトで必要なロールを持っているか確認する
+pub async fn authorize_project(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    project_id: i64,
+    required: ProjectRole,
+) -> Result<(), HandlerError> {
+    let user = require_current_user(req)?;
+    if is_admin(req) {
+        return Ok(());
+    }
+    let user_id = user.user_id.unwrap_or(-1);
+
+    let project_member_repo = ProjectMemberRepository::new(pool.clone());
+    let role = project_member_repo
+        .get_project_role(project_id, user_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    match role {
+        Some(role) if role.satisfies(required) => Ok(()),
+        _ => Err(HandlerError::Forbidden(get_error_message(
+            ErrorKey::ProjectMemberHandlerPermissionDenied,
+            [
+                ("project_id", json!(project_id)),
+                ("user_id", json!(user_id)),
+                ("required", json!(required.to_str())),
+            ],
+        ))),
+    }
+}
+
+// タスクが属するプロジェクトで必要なロールを持っているか確認する
+pub async fn authorize_task(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    task_id: i64,
+    required: ProjectRole,
+) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+
+    let task_repo = TaskRepository::new(pool.clone());
+    let task = task_repo
+        .get_task_by_id(task_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    authorize_project(req, pool, task.project_id, required).await
+}
+
+// 割り当てが対象とするタスクのプロジェクトで必要なロールを持っているか確認する
+pub async fn authorize_user_assign(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    user_assign_id: i64,
+    required: ProjectRole,
+) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+
+    let user_assign_repo = UserAssignRepository::new(pool.clone());
+    let user_assign = user_assign_repo
+        .get_user_assign_by_id(user_assign_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    authorize_task(req, pool, user_assign.task_id, required).await
+}
+
+// コメントが対象とするタスクのプロジェクトで必要なロールを持っているか確認する
+pub async fn authorize_comment(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    comment_id: i64,
+    required: ProjectRole,
+) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+
+    let comment_repo = CommentRepository::new(pool.clone());
+    let comment = comment_repo
+        .get_comment_by_id(comment_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    authorize_task(req, pool, comment.task_id, required).await
+}
+
+// 他のユーザーのコメントを変更・削除できるのはプロジェクトのオーナーと管理者だけ
+pub async fn authorize_comment_author(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    comment_id: i64,
+) -> Result<CommentWithUser, HandlerError> {
+    let user = require_current_user(req)?;
+
+    let comment_repo = CommentRepository::new(pool.clone());
+    let comment = comment_repo
+        .get_comment_by_id(comment_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    let required = match user.user_id == Some(comment.user_id) {
+        true => ProjectRole::Member,
+        false => ProjectRole::Owner,
+    };
+    authorize_task(req, pool, comment.task_id, required).await?;
+
+    Ok(comment)
+}
+
+// コメントのユーザーに指定できるのは自分自身だけ。管理者は任意のユーザーを指定できる
+pub fn authorize_comment_user(
+    req: &HttpRequest,
+    user_id: i64,
+    expected: Option<i64>,
+) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+    match is_admin(req) || expected == Some(user_id) {
+        true => Ok(()),
+        false => Err(HandlerError::Forbidden(get_error_message(
+            ErrorKey::CommentHandlerUserMismatch,
+            [("user_id", json!(user_id)), ("expected", json!(expected))],
+        ))),
+    }
+}
+
+// ゴミ箱にある対象が属するプロジェクトで必要なロールを持っているか確認する
+pub async fn authorize_trash_item(
+    req: &HttpRequest,
+    pool: &SqlitePool,
+    entity_type: AuditEntityType,
+    id: i64,
+    required: ProjectRole,
+) -> Result<(), HandlerError> {
+    require_current_user(req)?;
+
+    let trash_repo = TrashRepository::new(pool.clone());
+    let item = trash_repo
+        .get_trash_item(entity_type, id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    authorize_project(req, pool, item.project_id, required).await
+}
+
+// 削除エンドポイントで依存するデータの扱いを指定するクエリ
+#[derive(Deserialize, Debug, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeletePolicyQuery {
+    // restrict、cascade、reparentのいずれか。未指定の場合はrestrict
+    policy: Option<String>,
+}
+
+impl DeletePolicyQuery {
+    pub fn get_delete_policy(&self) -> Result<DeletePolicy, HandlerError> {
+        match &self.policy {
+            Some(policy) => {
+                DeletePolicy::from_string(policy).map_err(|e| HandlerError::BadRequest(e.into()))
+            }
+            None => Ok(DeletePolicy::default()),
+        }
+    }
+}
+
+// If-Matchヘッダーで指定されたバージョン。未指定または*の場合はNone。
+// バージョンとして解釈できないタグはどのバージョンとも一致しない
+pub fn get_if_match_versions(req: &HttpRequest) -> Option<Vec<i64>> {
+    let value = req.headers().get(IF_MATCH)?.to_str().unwrap_or_default();
+    let tags: Vec<&str> = value.split(',').map(|tag| tag.trim()).collect();
+    if tags.contains(&"*") {
+        return None;
+    }
+
+    Some(
+        tags.into_iter()
+            .filter_map(|tag| {
+                let tag = tag.strip_prefix("W/").unwrap_or(tag);
+                tag.trim_matches('"').parse::<i64>().ok()
+            })
+            .collect(),
+    )
+}
+
+// レスポンスにバージョンをETagとして付ける。作成・更新では書き込んだトランザクションで得たバージョンを渡す
+pub fn with_etag(mut response: HttpResponse, version: i64) -> HttpResponse {
+    let etag = EntityTag::new_strong(version.to_string());
+    if let Ok(value) = HeaderValue::from_str(&etag.to_string()) {
+        response.headers_mut().insert(ETAG, value);
+    }
+    response
+}
+
+// 取得したエンティティのレスポンスに現在のバージョンをETagとして付ける。取得できない場合は付けない
+pub async fn with_current_etag(
+    response: HttpResponse,
+    pool: &SqlitePool,
+    entity_type: AuditEntityType,
+    id: Option<i64>,
+) -> HttpResponse {
+    let Some(id) = id else {
+        return response;
+    };
+
+    match get_version(pool, entity_type, id).await {
+        Ok(Some(version)) => with_etag(response, version),
+        Ok(None) => response,
+        Err(e) => {
+            log::warn!("{}", e);
+            response
+        }
     }
 }
 
-pub fn hash_password(password: &str) -> String {
-    let hash = Sha256::digest(password.as_bytes());
-    return format!("{:x}", hash);
+// トークンは十分なエントロピーを持つため、保存用には高速なハッシュで十分
+pub fn hash_token(token: &str) -> String {
+    let hash = Sha256::digest(token.as_bytes());
+    format!("{:x}", hash)
 }
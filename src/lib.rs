@@ -1,16 +1,15 @@
+pub mod cli;
+pub mod commands;
 pub mod constants;
 pub mod enums;
 pub mod errors;
 pub mod handlers;
+pub mod logging;
+pub mod metrics;
+pub mod middleware;
 pub mod models;
 pub mod repository;
+pub mod settings;
 pub mod client;
 
-pub fn init_logger() {
-    simplelog::CombinedLogger::init(vec![simplelog::WriteLogger::new(
-        simplelog::LevelFilter::Debug,
-        simplelog::Config::default(),
-        std::fs::File::create("log.txt").unwrap(),
-    )])
-    .unwrap();
-}
+pub use logging::init_logger;
@@ -0,0 +1,211 @@
+use crate::constants::SENSITIVE_LOG_FIELDS;
+use crate::errors::ConfigError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::settings::LogConfig;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::borrow::Cow;
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use tracing::Subscriber;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::MakeWriter;
+use tracing_subscriber::util::SubscriberInitExt;
+
+#[cfg(test)]
+mod tests;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+// JSONの文字列内（\"でエスケープされた値）に埋め込まれたフィールド。
+// 例: "message":"Response: User { email: \"a@example.com\" }"
+static ESCAPED_SENSITIVE_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(\b(?:{})(?:\\")?\s*[:=]\s*(?:Some\()?)\\"(?:[^"\\]|\\[^"])*\\""#,
+        SENSITIVE_LOG_FIELDS.join("|")
+    ))
+    .unwrap()
+});
+
+// JSONのフィールドとDebug出力のフィールド。
+// 例: "email":"a@example.com", email: "a@example.com", password: Some("secret")
+static SENSITIVE_FIELD: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r#"(?i)(\b(?:{})"?\s*[:=]\s*(?:Some\()?)"(?:[^"\\]|\\.)*""#,
+        SENSITIVE_LOG_FIELDS.join("|")
+    ))
+    .unwrap()
+});
+
+// 機密情報を含むフィールドの値を伏せる
+pub fn redact(line: &str) -> Cow<'_, str> {
+    let line = ESCAPED_SENSITIVE_FIELD.replace_all(line, format!(r#"${{1}}\"{}\""#, REDACTED));
+    match SENSITIVE_FIELD.replace_all(&line, format!(r#"${{1}}"{}""#, REDACTED)) {
+        Cow::Borrowed(_) => line,
+        Cow::Owned(redacted) => Cow::Owned(redacted),
+    }
+}
+
+// サイズを超えたら log.txt -> log.txt.1 -> log.txt.2 ... の順に名前を変える
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size_bytes: u64,
+    max_files: u32,
+}
+
+impl RotatingFile {
+    pub fn open(path: &Path, max_size_bytes: u64, max_files: u32) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path: path.to_path_buf(),
+            file,
+            size,
+            max_size_bytes,
+            max_files,
+        })
+    }
+
+    pub fn rotated_path(&self, index: u32) -> PathBuf {
+        let mut path = self.path.clone().into_os_string();
+        path.push(format!(".{}", index));
+        PathBuf::from(path)
+    }
+
+    fn rotate(&mut self) -> io::Result<()> {
+        self.file.flush()?;
+        for index in (1..self.max_files).rev() {
+            let from = self.rotated_path(index);
+            if from.exists() {
+                std::fs::rename(from, self.rotated_path(index + 1))?;
+            }
+        }
+        std::fs::rename(&self.path, self.rotated_path(1))?;
+
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.max_size_bytes > 0
+            && self.size > 0
+            && self.size + buf.len() as u64 > self.max_size_bytes
+        {
+            self.rotate()?;
+        }
+        self.file.write_all(buf)?;
+        self.size += buf.len() as u64;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+// 1回の書き込みが1件のログになるため、書き込みごとに機密情報を伏せる
+#[derive(Clone)]
+pub struct LogWriter {
+    inner: Arc<Mutex<Box<dyn Write + Send>>>,
+}
+
+impl LogWriter {
+    pub fn new(inner: Box<dyn Write + Send>) -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(inner)),
+        }
+    }
+
+    pub fn from_config(log_config: &LogConfig) -> io::Result<Self> {
+        let inner: Box<dyn Write + Send> = match log_config.destination.as_str() {
+            "stdout" => Box::new(io::stdout()),
+            "stderr" => Box::new(io::stderr()),
+            path => Box::new(RotatingFile::open(
+                Path::new(path),
+                log_config.max_size_bytes,
+                log_config.max_files,
+            )?),
+        };
+        Ok(Self::new(inner))
+    }
+}
+
+impl Write for LogWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let line = String::from_utf8_lossy(buf);
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.write_all(redact(&line).as_bytes())?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        inner.flush()
+    }
+}
+
+impl<'a> MakeWriter<'a> for LogWriter {
+    type Writer = LogWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        self.clone()
+    }
+}
+
+// リクエストのスパン（request_id, route, status, duration_ms）を各ログに含める
+pub fn build_subscriber(
+    log_config: &LogConfig,
+    writer: LogWriter,
+) -> Result<Box<dyn Subscriber + Send + Sync>, ConfigError> {
+    let level = LevelFilter::from_str(&log_config.level).map_err(|_| {
+        ConfigError::InitError(
+            get_error_message(
+                ErrorKey::LoggerInitFailed,
+                format!("log.level = {}", log_config.level),
+            )
+            .to_string(),
+        )
+    })?;
+
+    let builder = tracing_subscriber::fmt()
+        .with_max_level(level)
+        .with_writer(writer)
+        .with_ansi(false);
+    let subscriber: Box<dyn Subscriber + Send + Sync> = match log_config.format.as_str() {
+        "text" => Box::new(builder.finish()),
+        _ => Box::new(
+            builder
+                .json()
+                .flatten_event(true)
+                .with_current_span(true)
+                .with_span_list(false)
+                .finish(),
+        ),
+    };
+    Ok(subscriber)
+}
+
+// logクレートのマクロで出力したログもtracingに転送する
+pub fn init_logger(log_config: &LogConfig) -> Result<(), ConfigError> {
+    let init_error = |e: String| {
+        ConfigError::InitError(get_error_message(ErrorKey::LoggerInitFailed, e).to_string())
+    };
+
+    let writer = LogWriter::from_config(log_config)
+        .map_err(|e| init_error(format!("{}: {}", log_config.destination, e)))?;
+    build_subscriber(log_config, writer)?
+        .try_init()
+        .map_err(|e| init_error(e.to_string()))
+}
@@ -0,0 +1,114 @@
+use crate::logging::{LogWriter, RotatingFile, redact};
+use std::io::Write;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod logging_test {
+    use super::*;
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new("./test_db/logging_test").exists() {
+            std::fs::create_dir_all("./test_db/logging_test").unwrap();
+        }
+
+        let files = std::fs::read_dir("./test_db/logging_test").unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_redact_debug_output() {
+        let line = r#"Response: User { user_id: Some(1), username: "TestUser0", email: "test0@example.com", password_hash: "$argon2id$v=19$abc" }"#;
+        assert_eq!(
+            redact(line),
+            r#"Response: User { user_id: Some(1), username: "TestUser0", email: "[REDACTED]", password_hash: "[REDACTED]" }"#
+        );
+
+        let line = r#"LoginRequest { username: "TestUser0", password: Some("secret \"quoted\"") }"#;
+        assert_eq!(
+            redact(line),
+            r#"LoginRequest { username: "TestUser0", password: Some("[REDACTED]") }"#
+        );
+
+        // 機密情報を含まない行はそのまま返す
+        let line = r#"Get tasks: [Task { task_id: Some(1), name: "email" }]"#;
+        assert_eq!(redact(line), line);
+    }
+
+    #[test]
+    fn test_redact_json_output() {
+        let line = r#"{"level":"DEBUG","message":"Response: User { email: \"test0@example.com\", token: \"abc\" }","email":"test0@example.com","api_token_id":1}"#;
+        assert_eq!(
+            redact(line),
+            r#"{"level":"DEBUG","message":"Response: User { email: \"[REDACTED]\", token: \"[REDACTED]\" }","email":"[REDACTED]","api_token_id":1}"#
+        );
+
+        let line = r#"{"headers":{"Authorization":"Bearer abc"}}"#;
+        assert_eq!(
+            redact(line),
+            r#"{"headers":{"Authorization":"[REDACTED]"}}"#
+        );
+    }
+
+    #[test]
+    fn test_log_writer_redacts() {
+        let buffer = Buffer::default();
+        let mut writer = LogWriter::new(Box::new(buffer.clone()));
+        writer
+            .write_all(b"{\"message\":\"login\",\"email\":\"test0@example.com\"}\n")
+            .unwrap();
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        assert_eq!(output, "{\"message\":\"login\",\"email\":\"[REDACTED]\"}\n");
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let path = Path::new("./test_db/logging_test/rotate.log");
+        let mut file = RotatingFile::open(path, 10, 2).unwrap();
+
+        // 上限を超える書き込みの前にローテーションする
+        for line in ["line-1\n", "line-2\n", "line-3\n", "line-4\n"] {
+            file.write_all(line.as_bytes()).unwrap();
+        }
+        file.flush().unwrap();
+
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "line-4\n");
+        assert_eq!(
+            std::fs::read_to_string(file.rotated_path(1)).unwrap(),
+            "line-3\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(file.rotated_path(2)).unwrap(),
+            "line-2\n"
+        );
+        // max_filesより古いファイルは残さない
+        assert!(!file.rotated_path(3).exists());
+
+        // 再度開いた場合は既存のファイルに追記する
+        let mut file = RotatingFile::open(path, 0, 2).unwrap();
+        file.write_all(b"line-5\n").unwrap();
+        file.flush().unwrap();
+        assert_eq!(std::fs::read_to_string(path).unwrap(), "line-4\nline-5\n");
+    }
+}
@@ -1,26 +1,63 @@
 use actix_web::{App, HttpServer, web};
+use clap::Parser;
+use menahel::cli::{Cli, Command};
+use menahel::commands::admin::{
+    run_db_command,
+    run_project_command,
+    run_task_command,
+    run_user_command,
+};
+use menahel::commands::migrate::run_migrate_command;
+use menahel::handlers::auth::{login, logout};
+use menahel::handlers::audit_log::get_audit_logs;
+use menahel::handlers::api_token::{
+    get_api_tokens,
+    create_api_token,
+    revoke_api_token,
+};
 use menahel::handlers::root::{health, root};
+use menahel::handlers::health::{live, ready};
+use menahel::handlers::metrics::get_metrics;
+use menahel::handlers::openapi::get_openapi;
 use menahel::handlers::user::{
     get_users,
     create_user,
     update_user,
+    patch_user,
     delete_user,
 };
 use menahel::handlers::project::{
     get_projects,
     create_project,
     update_project,
+    patch_project,
     delete_project,
+    restore_project,
+};
+use menahel::handlers::project_member::{
+    get_project_members,
+    create_project_member,
+    update_project_member,
+    delete_project_member,
 };
 use menahel::handlers::task::{
     get_tasks,
     create_task,
+    batch_tasks,
     update_task,
+    patch_task,
     delete_task,
+    restore_task,
+};
+use menahel::handlers::task_history::get_task_history;
+use menahel::handlers::task_tree::{
+    get_task_tree,
+    get_project_tree,
 };
 use menahel::handlers::user_assign::{
     get_user_assigns,
     create_user_assign,
+    batch_user_assigns,
     update_user_assign,
     delete_user_assign,
 };
@@ -28,51 +65,200 @@ use menahel::handlers::comment::{
     get_comments,
     create_comment,
     update_comment,
+    patch_comment,
     delete_comment,
+    restore_comment,
+};
+use menahel::handlers::trash::{
+    get_trash,
+    purge_trash,
+};
+use menahel::handlers::integrity::{
+    get_integrity,
+    repair_integrity,
+};
+use menahel::errors::catalog::{
+    init_message_catalog,
+    spawn_catalog_reloader,
 };
 use menahel::init_logger;
-use sqlx::sqlite::SqlitePoolOptions;
+use menahel::middleware::auth::AuthMiddleware;
+use menahel::middleware::language::LanguageMiddleware;
+use menahel::middleware::metrics::MetricsMiddleware;
+use menahel::middleware::processing_time::ProcessingTimeMiddleware;
+use menahel::middleware::request_id::RequestIdMiddleware;
+use menahel::middleware::request_log::RequestLogMiddleware;
+use menahel::repository::migration_repo::MigrationRepository;
+use menahel::settings::{ServerConfig, init_server_config, server_config};
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use std::process::exit;
+use std::str::FromStr;
 
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    init_logger();
-    log::info!("Starting server");
+    let cli = Cli::parse();
+
+    // 設定の誤りはパニックさせずに内容を表示して終了する
+    let config = match ServerConfig::load(&cli.config) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Invalid configuration: {}", e);
+            exit(1);
+        }
+    };
+    // サブコマンドでもパスワードのハッシュなどに設定を反映するため、最初に初期化する
+    if let Err(e) = init_server_config(config) {
+        eprintln!("{}", e);
+        exit(1);
+    }
+    let config = server_config();
+    if let Err(e) = init_logger(&config.log) {
+        eprintln!("{}", e);
+        exit(1);
+    }
+    log::info!("Configuration: {:?}", config);
 
-    println!("DATABASE_URL: {}", std::env::var("DATABASE_URL").unwrap());
+    // カタログの誤りは起動時に報告し、不足しているキーは組み込みのメッセージで補う
+    match init_message_catalog(&config.messages) {
+        Ok(reports) => {
+            for report in reports {
+                log::warn!("{}", report);
+            }
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
 
-    let pool = SqlitePoolOptions::new()
-        .max_connections(10)
-        .connect(std::env::var("DATABASE_URL").unwrap().as_str())
+    // 新規環境ではデータベースファイルを作成する。
+    // 削除ポリシーで依存するデータを扱うため、外部キー制約は必ず有効にする
+    let connect_options = match SqliteConnectOptions::from_str(config.database_url()) {
+        Ok(options) => options.create_if_missing(true).foreign_keys(true),
+        Err(e) => {
+            eprintln!("Invalid database URL ({}): {}", config.database_url(), e);
+            exit(1);
+        }
+    };
+    let pool = match SqlitePoolOptions::new()
+        .max_connections(config.database.max_connections)
+        .connect_with(connect_options)
         .await
-        .unwrap();
+    {
+        Ok(pool) => pool,
+        Err(e) => {
+            eprintln!("Failed to connect to database ({}): {}", config.database_url(), e);
+            exit(1);
+        }
+    };
+
+    // サブコマンドはサーバーを起動せずに実行して終了する
+    if let Some(command) = &cli.command {
+        let mut out = std::io::stdout();
+        let result = match command {
+            Command::Migrate(command) => run_migrate_command(command, &pool, &mut out).await,
+            Command::User(command) => {
+                run_user_command(command, &pool, &mut std::io::stdin().lock(), &mut out).await
+            }
+            Command::Project(command) => run_project_command(command, &pool, &mut out).await,
+            Command::Task(command) => run_task_command(command, &pool, &mut out).await,
+            Command::Db(command) => run_db_command(command, &pool, &mut out).await,
+        };
+        if let Err(e) = result {
+            eprintln!("{}", e);
+            exit(1);
+        }
+        return Ok(());
+    }
+
+    if config.database.auto_migrate {
+        let migration_repo = MigrationRepository::new(pool.clone());
+        if let Err(e) = migration_repo.run_pending_migrations().await {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+
+    let bind_address = config.server.bind_address.clone();
+    let reload_catalog = config.messages.catalog_dir.is_some() && config.messages.reload_on_sighup;
+    if reload_catalog {
+        if let Err(e) = spawn_catalog_reloader() {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    }
+    log::info!("Starting server");
+    println!("Listening on {}", bind_address);
 
     HttpServer::new(move || {
         App::new()
             .app_data(web::Data::new(pool.clone()))
+            .wrap(AuthMiddleware)
+            .wrap(MetricsMiddleware)
+            // 認証を含めた処理時間を計測するため認証の後にwrapする
+            .wrap(ProcessingTimeMiddleware)
+            // 認証エラーのメッセージにも言語を反映するため認証の外側にwrapする
+            .wrap(LanguageMiddleware)
+            // 後続のミドルウェアのログもリクエストのスパンに含めるため外側にwrapする
+            .wrap(RequestLogMiddleware)
+            // 全てのレスポンス（エラーを含む）にリクエストIDを返すため最後にwrapする
+            .wrap(RequestIdMiddleware)
             .service(root)
             .service(health)
+            .service(live)
+            .service(ready)
+            .service(get_metrics)
+            .service(get_openapi)
+            .service(login)
+            .service(logout)
+            .service(get_api_tokens)
+            .service(create_api_token)
+            .service(revoke_api_token)
             .service(get_users)
             .service(create_user)
             .service(update_user)
+            .service(patch_user)
             .service(delete_user)
             .service(get_projects)
             .service(create_project)
             .service(update_project)
+            .service(patch_project)
             .service(delete_project)
+            .service(restore_project)
+            .service(get_project_members)
+            .service(create_project_member)
+            .service(update_project_member)
+            .service(delete_project_member)
             .service(get_tasks)
             .service(create_task)
+            // /tasks/{id}より先に登録する
+            .service(batch_tasks)
             .service(update_task)
+            .service(patch_task)
             .service(delete_task)
+            .service(restore_task)
+            .service(get_task_history)
+            .service(get_task_tree)
+            .service(get_project_tree)
             .service(get_user_assigns)
             .service(create_user_assign)
+            // /userassigns/{id}より先に登録する
+            .service(batch_user_assigns)
             .service(update_user_assign)
             .service(delete_user_assign)
             .service(get_comments)
             .service(create_comment)
             .service(update_comment)
+            .service(patch_comment)
             .service(delete_comment)
+            .service(restore_comment)
+            .service(get_audit_logs)
+            .service(get_trash)
+            .service(purge_trash)
+            .service(get_integrity)
+            .service(repair_integrity)
     })
-    .bind("0.0.0.0:3000")?
+    .bind(bind_address)?
     .run()
     .await
 }
@@ -0,0 +1,265 @@
+use crate::models::PoolStatus;
+use once_cell::sync::Lazy;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::time::{Duration, Instant};
+
+#[cfg(test)]
+mod tests;
+
+// ヒストグラムのバケットの上限（秒）
+pub const DURATION_BUCKETS: [f64; 12] = [
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0,
+];
+
+// ルートにマッチしなかったリクエストのラベル。パスをそのまま使うとラベルが増え続けるため
+pub const UNMATCHED_ROUTE: &str = "unmatched";
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    buckets: [u64; DURATION_BUCKETS.len()],
+    count: u64,
+    sum: f64,
+}
+
+impl Histogram {
+    fn observe(&mut self, seconds: f64) {
+        for (bucket, upper_bound) in self.buckets.iter_mut().zip(DURATION_BUCKETS.iter()) {
+            if seconds <= *upper_bound {
+                *bucket += 1;
+            }
+        }
+        self.count += 1;
+        self.sum += seconds;
+    }
+
+    fn render(&self, out: &mut String, name: &str, labels: &str) {
+        for (bucket, upper_bound) in self.buckets.iter().zip(DURATION_BUCKETS.iter()) {
+            let _ = writeln!(
+                out,
+                "{}_bucket{{{},le=\"{}\"}} {}",
+                name, labels, upper_bound, bucket
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{}_bucket{{{},le=\"+Inf\"}} {}",
+            name, labels, self.count
+        );
+        let _ = writeln!(out, "{}_sum{{{}}} {}", name, labels, self.sum);
+        let _ = writeln!(out, "{}_count{{{}}} {}", name, labels, self.count);
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Metrics {
+    // (method, route, status)
+    http_requests: Mutex<BTreeMap<(String, String, u16), Histogram>>,
+    // (repository, operation)
+    repository_queries: Mutex<BTreeMap<(&'static str, &'static str), Histogram>>,
+    errors: Mutex<BTreeMap<String, u64>>,
+    queries_in_flight: AtomicI64,
+}
+
+static METRICS: Lazy<Metrics> = Lazy::new(Metrics::default);
+
+pub fn metrics() -> &'static Metrics {
+    &METRICS
+}
+
+impl Metrics {
+    pub fn record_http_request(&self, method: &str, route: &str, status: u16, elapsed: Duration) {
+        let mut http_requests = self.http_requests.lock().unwrap();
+        http_requests
+            .entry((method.to_string(), route.to_string(), status))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn record_query(
+        &self,
+        repository: &'static str,
+        operation: &'static str,
+        elapsed: Duration,
+    ) {
+        let mut repository_queries = self.repository_queries.lock().unwrap();
+        repository_queries
+            .entry((repository, operation))
+            .or_default()
+            .observe(elapsed.as_secs_f64());
+    }
+
+    pub fn record_error(&self, key: &str) {
+        let mut errors = self.errors.lock().unwrap();
+        *errors.entry(key.to_string()).or_default() += 1;
+    }
+
+    pub fn queries_in_flight(&self) -> i64 {
+        self.queries_in_flight.load(Ordering::Relaxed)
+    }
+
+    // Prometheusのテキスト形式で出力する
+    pub fn render(&self, pool: &PoolStatus, entities: &[(&str, i64)]) -> String {
+        let mut out = String::new();
+
+        let http_requests = self.http_requests.lock().unwrap().clone();
+        let _ = writeln!(
+            out,
+            "# HELP menahel_http_requests_total Total number of HTTP requests."
+        );
+        let _ = writeln!(out, "# TYPE menahel_http_requests_total counter");
+        for ((method, route, status), histogram) in http_requests.iter() {
+            let _ = writeln!(
+                out,
+                "menahel_http_requests_total{{{}}} {}",
+                http_labels(method, route, *status),
+                histogram.count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "# HELP menahel_http_request_duration_seconds HTTP request latency in seconds."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE menahel_http_request_duration_seconds histogram"
+        );
+        for ((method, route, status), histogram) in http_requests.iter() {
+            histogram.render(
+                &mut out,
+                "menahel_http_request_duration_seconds",
+                &http_labels(method, route, *status),
+            );
+        }
+
+        // sqlxは待機数を公開していないため、実行中のリポジトリ操作のうち接続を確保できていない数で近似する
+        let in_use = pool.in_use();
+        let waiting = (self.queries_in_flight() - in_use as i64).max(0);
+        let _ = writeln!(
+            out,
+            "# HELP menahel_db_pool_connections Number of database connections by state."
+        );
+        let _ = writeln!(out, "# TYPE menahel_db_pool_connections gauge");
+        let _ = writeln!(
+            out,
+            "menahel_db_pool_connections{{state=\"in_use\"}} {}",
+            in_use
+        );
+        let _ = writeln!(
+            out,
+            "menahel_db_pool_connections{{state=\"idle\"}} {}",
+            pool.idle
+        );
+        let _ = writeln!(
+            out,
+            "# HELP menahel_db_pool_max_connections Maximum number of database connections."
+        );
+        let _ = writeln!(out, "# TYPE menahel_db_pool_max_connections gauge");
+        let _ = writeln!(
+            out,
+            "menahel_db_pool_max_connections {}",
+            pool.max_connections
+        );
+        let _ = writeln!(
+            out,
+            "# HELP menahel_db_pool_waiting Estimated number of operations waiting for a connection."
+        );
+        let _ = writeln!(out, "# TYPE menahel_db_pool_waiting gauge");
+        let _ = writeln!(out, "menahel_db_pool_waiting {}", waiting);
+
+        let repository_queries = self.repository_queries.lock().unwrap().clone();
+        let _ = writeln!(
+            out,
+            "# HELP menahel_repository_query_duration_seconds Repository operation latency in seconds."
+        );
+        let _ = writeln!(
+            out,
+            "# TYPE menahel_repository_query_duration_seconds histogram"
+        );
+        for ((repository, operation), histogram) in repository_queries.iter() {
+            histogram.render(
+                &mut out,
+                "menahel_repository_query_duration_seconds",
+                &format!(
+                    "repository=\"{}\",operation=\"{}\"",
+                    escape_label(repository),
+                    escape_label(operation)
+                ),
+            );
+        }
+
+        let errors = self.errors.lock().unwrap().clone();
+        let _ = writeln!(
+            out,
+            "# HELP menahel_errors_total Total number of errors by error key."
+        );
+        let _ = writeln!(out, "# TYPE menahel_errors_total counter");
+        for (key, count) in errors.iter() {
+            let _ = writeln!(
+                out,
+                "menahel_errors_total{{key=\"{}\"}} {}",
+                escape_label(key),
+                count
+            );
+        }
+
+        let _ = writeln!(
+            out,
+            "# HELP menahel_entities Number of stored entities by type."
+        );
+        let _ = writeln!(out, "# TYPE menahel_entities gauge");
+        for (entity, count) in entities.iter() {
+            let _ = writeln!(
+                out,
+                "menahel_entities{{entity=\"{}\"}} {}",
+                escape_label(entity),
+                count
+            );
+        }
+
+        out
+    }
+}
+
+fn http_labels(method: &str, route: &str, status: u16) -> String {
+    format!(
+        "method=\"{}\",route=\"{}\",status=\"{}\"",
+        escape_label(method),
+        escape_label(route),
+        status
+    )
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+// リポジトリの操作の開始時に生成し、ドロップ時に処理時間を記録する
+pub struct QueryTimer {
+    repository: &'static str,
+    operation: &'static str,
+    start: Instant,
+}
+
+impl QueryTimer {
+    pub fn start(repository: &'static str, operation: &'static str) -> Self {
+        metrics().queries_in_flight.fetch_add(1, Ordering::Relaxed);
+        Self {
+            repository,
+            operation,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        metrics().queries_in_flight.fetch_sub(1, Ordering::Relaxed);
+        metrics().record_query(self.repository, self.operation, self.start.elapsed());
+    }
+}
@@ -0,0 +1,102 @@
+use crate::metrics::{Metrics, QueryTimer, metrics};
+use crate::models::PoolStatus;
+use std::time::Duration;
+
+#[cfg(test)]
+mod metrics_test {
+    use super::*;
+
+    fn pool_status() -> PoolStatus {
+        PoolStatus {
+            size: 3,
+            idle: 1,
+            max_connections: 10,
+        }
+    }
+
+    #[test]
+    fn test_render_http_requests() {
+        let metrics = Metrics::default();
+        metrics.record_http_request("GET", "/tasks/{id}", 200, Duration::from_millis(3));
+        metrics.record_http_request("GET", "/tasks/{id}", 200, Duration::from_millis(30));
+        metrics.record_http_request("GET", "/tasks/{id}", 404, Duration::from_secs(10));
+
+        let output = metrics.render(&pool_status(), &[]);
+        assert!(output.contains("# TYPE menahel_http_requests_total counter"));
+        assert!(output.contains(
+            "menahel_http_requests_total{method=\"GET\",route=\"/tasks/{id}\",status=\"200\"} 2"
+        ));
+        assert!(output.contains(
+            "menahel_http_requests_total{method=\"GET\",route=\"/tasks/{id}\",status=\"404\"} 1"
+        ));
+
+        // バケットは累積で数える
+        let labels = "method=\"GET\",route=\"/tasks/{id}\",status=\"200\"";
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_bucket{{{},le=\"0.001\"}} 0",
+            labels
+        )));
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_bucket{{{},le=\"0.005\"}} 1",
+            labels
+        )));
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_bucket{{{},le=\"0.05\"}} 2",
+            labels
+        )));
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_count{{{}}} 2",
+            labels
+        )));
+
+        // 最大のバケットを超えた値は+Infのみに数える
+        let labels = "method=\"GET\",route=\"/tasks/{id}\",status=\"404\"";
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_bucket{{{},le=\"5\"}} 0",
+            labels
+        )));
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_bucket{{{},le=\"+Inf\"}} 1",
+            labels
+        )));
+        assert!(output.contains(&format!(
+            "menahel_http_request_duration_seconds_sum{{{}}} 10",
+            labels
+        )));
+    }
+
+    #[test]
+    fn test_render_pool_errors_and_entities() {
+        let metrics = Metrics::default();
+        metrics.record_query("task", "get_task_by_id", Duration::from_millis(2));
+        metrics.record_error("TaskGetByIdNotFound");
+        metrics.record_error("TaskGetByIdNotFound");
+        metrics.record_error("Key\"With\\Quote");
+
+        let output = metrics.render(&pool_status(), &[("users", 4), ("tasks", 17)]);
+        assert!(output.contains("menahel_db_pool_connections{state=\"in_use\"} 2"));
+        assert!(output.contains("menahel_db_pool_connections{state=\"idle\"} 1"));
+        assert!(output.contains("menahel_db_pool_max_connections 10"));
+        assert!(output.contains("menahel_db_pool_waiting 0"));
+        assert!(output.contains(
+            "menahel_repository_query_duration_seconds_count{repository=\"task\",operation=\"get_task_by_id\"} 1"
+        ));
+        assert!(output.contains("menahel_errors_total{key=\"TaskGetByIdNotFound\"} 2"));
+        assert!(output.contains("menahel_errors_total{key=\"Key\\\"With\\\\Quote\"} 1"));
+        assert!(output.contains("menahel_entities{entity=\"users\"} 4"));
+        assert!(output.contains("menahel_entities{entity=\"tasks\"} 17"));
+    }
+
+    #[test]
+    fn test_query_timer() {
+        {
+            let _timer = QueryTimer::start("metrics_test", "test_query_timer");
+            assert!(metrics().queries_in_flight() >= 1);
+        }
+
+        let output = metrics().render(&pool_status(), &[]);
+        assert!(output.contains(
+            "menahel_repository_query_duration_seconds_count{repository=\"metrics_test\",operation=\"test_query_timer\"} 1"
+        ));
+    }
+}
@@ -0,0 +1,170 @@
+use crate::constants::API_TOKEN_PREFIX;
+use crate::errors::handler_errors::HandlerError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::handlers::utils::{get_bearer_token, get_request_id, handle_error, hash_token};
+use crate::models::UserNoPassword;
+use crate::models::response_model::ErrorResponse;
+use crate::models::response_model::ResponseMetadata;
+use crate::repository::api_token_repo::ApiTokenRepository;
+use crate::repository::session_repo::SessionRepository;
+use crate::repository::user_repo::UserRepository;
+use crate::settings::server_config;
+use actix_web::{
+    Error, HttpMessage,
+    body::EitherBody,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    web,
+};
+use futures::future::{LocalBoxFuture, Ready, ready};
+use serde_json::json;
+use sqlx::sqlite::SqlitePool;
+use std::rc::Rc;
+
+// 認証なしでアクセスできるパス
+const PUBLIC_PATHS: [&str; 6] = [
+    "/",
+    "/openapi.json",
+    "/health",
+    "/health/live",
+    "/health/ready",
+    "/auth/login",
+];
+
+// 設定で公開した場合のみ認証なしでアクセスできる
+const METRICS_PATH: &str = "/metrics";
+
+fn is_public_path(path: &str) -> bool {
+    PUBLIC_PATHS.contains(&path) || (path == METRICS_PATH && server_config().metrics.public)
+}
+
+// 管理者として認証された場合にリクエストに格納する
+#[derive(Debug, Clone, Copy)]
+pub struct AdminUser;
+
+pub struct AuthMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for AuthMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Transform = AuthMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuthMiddlewareService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct AuthMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+// セッショントークンまたはAPIトークンを検証し、ユーザーIDを返す
+async fn authenticate_token(token: &str, pool: SqlitePool) -> Result<i64, HandlerError> {
+    let invalid_token = |_| {
+        HandlerError::Unauthorized(get_error_message(
+            ErrorKey::AuthHandlerInvalidToken,
+            "".to_string(),
+        ))
+    };
+
+    if token.starts_with(API_TOKEN_PREFIX) {
+        let api_token_repo = ApiTokenRepository::new(pool);
+        let api_token = api_token_repo
+            .use_api_token(&hash_token(token))
+            .await
+            .map_err(invalid_token)?;
+        return Ok(api_token.user_id);
+    }
+
+    let session_repo = SessionRepository::new(pool);
+    let session = session_repo
+        .get_active_session_by_token_hash(&hash_token(token))
+        .await
+        .map_err(invalid_token)?;
+    Ok(session.user_id)
+}
+
+// ユーザーと管理者かどうかを返す
+async fn authenticate(
+    token: &str,
+    pool: SqlitePool,
+) -> Result<(UserNoPassword, bool), HandlerError> {
+    let user_id = authenticate_token(token, pool.clone()).await?;
+
+    let user_repo = UserRepository::new(pool);
+    let user = user_repo.get_user_by_id(user_id).await.map_err(|_| {
+        HandlerError::Unauthorized(get_error_message(
+            ErrorKey::AuthHandlerInvalidToken,
+            [("user_id", json!(user_id))],
+        ))
+    })?;
+    let is_admin = user_repo
+        .is_admin(user_id)
+        .await
+        .map_err(HandlerError::from)?;
+
+    Ok((user, is_admin))
+}
+
+impl<S, B> Service<ServiceRequest> for AuthMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = Rc::clone(&self.service);
+
+        Box::pin(async move {
+            if is_public_path(req.path()) {
+                let res = service.call(req).await?;
+                return Ok(res.map_into_left_body());
+            }
+
+            let result = match req.app_data::<web::Data<SqlitePool>>() {
+                Some(pool) => match get_bearer_token(req.request()) {
+                    Some(token) => authenticate(&token, pool.get_ref().clone()).await,
+                    None => Err(HandlerError::Unauthorized(get_error_message(
+                        ErrorKey::AuthHandlerNoToken,
+                        "".to_string(),
+                    ))),
+                },
+                None => Err(HandlerError::InternalServerError(get_error_message(
+                    ErrorKey::AuthHandlerPoolNotConfigured,
+                    "".to_string(),
+                ))),
+            };
+
+            match result {
+                Ok((user, is_admin)) => {
+                    req.extensions_mut().insert(user);
+                    if is_admin {
+                        req.extensions_mut().insert(AdminUser);
+                    }
+                    let res = service.call(req).await?;
+                    Ok(res.map_into_left_body())
+                }
+                Err(e) => {
+                    let metadata = ResponseMetadata::new(get_request_id(req.request()));
+                    let response = ErrorResponse::from_error(&e, Some(metadata));
+                    let http_response = handle_error(e, response);
+                    Ok(req.into_response(http_response).map_into_right_body())
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use crate::constants::{LANGUAGE_QUERY_PARAM, SUPPORTED_LANGUAGES};
+use crate::errors::catalog::available_languages;
+use crate::errors::messages::REQUEST_LANG;
+use crate::settings::server_config;
+use actix_web::{
+    Error,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    http::header::ACCEPT_LANGUAGE,
+};
+use futures::future::{LocalBoxFuture, Ready, ready};
+
+pub struct LanguageMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for LanguageMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = LanguageMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(LanguageMiddlewareService { service }))
+    }
+}
+
+pub struct LanguageMiddlewareService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for LanguageMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let accept_language = req
+            .headers()
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok());
+        let lang = negotiate_language(query_language(req.query_string()), accept_language)
+            .unwrap_or_else(default_language);
+
+        // 後続のミドルウェアが同期的に返すエラーにも言語を反映する
+        let fut = REQUEST_LANG.sync_scope(lang, || self.service.call(req));
+        Box::pin(REQUEST_LANG.scope(lang, fut))
+    }
+}
+
+fn default_language() -> &'static str {
+    supported_language(&server_config().default_language).unwrap_or(SUPPORTED_LANGUAGES[0])
+}
+
+fn query_language(query_string: &str) -> Option<&str> {
+    query_string
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == LANGUAGE_QUERY_PARAM)
+        .map(|(_, value)| value)
+}
+
+// ja-JPなどの言語タグを組み込み（en, jp）またはカタログの言語に対応させる
+fn supported_language(tag: &str) -> Option<&'static str> {
+    let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+    let languages = available_languages();
+    let find = |primary: &str| languages.iter().find(|lang| **lang == primary).copied();
+    find(&primary).or_else(|| match primary.as_str() {
+        "ja" => find("jp"),
+        _ => None,
+    })
+}
+
+// langパラメータ、Accept-Language（q値の高い順）の順に対応している言語を探す
+pub fn negotiate_language(
+    query_lang: Option<&str>,
+    accept_language: Option<&str>,
+) -> Option<&'static str> {
+    if let Some(lang) = query_lang.and_then(supported_language) {
+        return Some(lang);
+    }
+
+    let mut candidates: Vec<(&str, f32)> = accept_language?
+        .split(',')
+        .filter_map(|item| {
+            let mut parts = item.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse::<f32>().ok())?;
+            (!tag.is_empty() && quality > 0.0).then_some((tag, quality))
+        })
+        .collect();
+    // 同じq値の場合はヘッダーの記述順を維持する
+    candidates.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    candidates
+        .into_iter()
+        .find_map(|(tag, _)| supported_language(tag))
+}
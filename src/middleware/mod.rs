@@ -0,0 +1,6 @@
+pub mod auth;
+pub mod language;
+pub mod metrics;
+pub mod processing_time;
+pub mod request_id;
+pub mod request_log;
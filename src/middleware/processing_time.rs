@@ -1,13 +1,16 @@
+use crate::models::response_model::ResponseMetadata;
 use actix_web::{
-    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     Error,
+    body::{BoxBody, MessageBody, to_bytes},
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    http::header::{CONTENT_TYPE, HeaderName, HeaderValue},
+    web::Bytes,
 };
-use futures::future::{ready, LocalBoxFuture, Ready};
-use std::future::Future;
-use std::pin::Pin;
-use std::task::{Context, Poll};
 use chrono::Utc;
-use crate::models::response_model::ResponseMetadata;
+use futures::future::{LocalBoxFuture, Ready, ready};
+use serde_json::Value;
+
+const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
 
 pub struct ProcessingTimeMiddleware;
 
@@ -15,9 +18,9 @@ impl<S, B> Transform<S, ServiceRequest> for ProcessingTimeMiddleware
 where
     S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
     S::Future: 'static,
-    B: 'static,
+    B: MessageBody + 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<BoxBody>;
     type Error = Error;
     type Transform = ProcessingTimeMiddlewareService<S>;
     type InitError = ();
@@ -36,9 +39,9 @@ impl<S, B> Service<ServiceRequest> for ProcessingTimeMiddlewareService<S>
 where
     S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
     S::Future: 'static,
-    B: 'static,
+    B: MessageBody + 'static,
 {
-    type Response = ServiceResponse<B>;
+    type Response = ServiceResponse<BoxBody>;
     type Error = Error;
     type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
@@ -49,14 +52,62 @@ where
         let fut = self.service.call(req);
 
         Box::pin(async move {
-            let mut res = fut.await?;
-            
-            // レスポンスボディを取得して処理時間を設定
-            if let Some(metadata) = res.extensions_mut().get_mut::<ResponseMetadata>() {
-                metadata.set_processing_end(Utc::now().timestamp_millis());
+            let res = fut.await?;
+
+            let is_json = res
+                .headers()
+                .get(CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .is_some_and(|value| value.starts_with("application/json"));
+            let (req, res) = res.into_parts();
+            let (mut res, body) = res.into_parts();
+
+            // JSONレスポンスのみボディを読み込み、metadataに処理時間を設定する
+            let (body, end_time) = if is_json {
+                let bytes = to_bytes(body)
+                    .await
+                    .map_err(|e| actix_web::error::ErrorInternalServerError(e.into()))?;
+                let end_time = Utc::now().timestamp_millis();
+                let body = BoxBody::new(set_processing_time(bytes, start_time, end_time));
+                (body, end_time)
+            } else {
+                (BoxBody::new(body), Utc::now().timestamp_millis())
+            };
+
+            if let Ok(value) = HeaderValue::from_str(&format!("app;dur={}", end_time - start_time))
+            {
+                res.headers_mut().insert(SERVER_TIMING, value);
             }
-            
-            Ok(res)
+
+            Ok(ServiceResponse::new(req, res.set_body(body)))
         })
     }
-} 
\ No newline at end of file
+}
+
+// metadataを持たないレスポンスはそのまま返す
+fn set_processing_time(bytes: Bytes, start_time: i64, end_time: i64) -> Bytes {
+    let mut value = match serde_json::from_slice::<Value>(&bytes) {
+        Ok(value) => value,
+        Err(_) => return bytes,
+    };
+
+    let metadata = match value.get_mut("metadata") {
+        Some(metadata) if metadata.is_object() => metadata,
+        _ => return bytes,
+    };
+    let mut response_metadata = match serde_json::from_value::<ResponseMetadata>(metadata.take()) {
+        Ok(response_metadata) => response_metadata,
+        Err(_) => return bytes,
+    };
+    response_metadata.set_processing_start(start_time);
+    response_metadata.set_processing_end(end_time);
+
+    match serde_json::to_value(&response_metadata) {
+        Ok(response_metadata) => *metadata = response_metadata,
+        Err(_) => return bytes,
+    }
+    match serde_json::to_vec(&value) {
+        Ok(body) => Bytes::from(body),
+        Err(_) => bytes,
+    }
+}
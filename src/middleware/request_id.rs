@@ -0,0 +1,93 @@
+use crate::constants::{MAX_REQUEST_ID_LENGTH, REQUEST_ID_HEADER};
+use actix_web::{
+    Error, HttpMessage,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    error::InternalError,
+    http::header::{HeaderMap, HeaderName, HeaderValue},
+};
+use futures::future::{LocalBoxFuture, Ready, ready};
+use uuid::Uuid;
+
+// リクエストの拡張に格納するリクエストID
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+pub struct RequestIdMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestIdMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestIdMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestIdMiddlewareService { service }))
+    }
+}
+
+pub struct RequestIdMiddlewareService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestIdMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let request_id = req
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|request_id| is_valid_request_id(request_id))
+            .map(|request_id| request_id.to_string())
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+        req.extensions_mut().insert(RequestId(request_id.clone()));
+
+        let fut = self.service.call(req);
+
+        Box::pin(async move {
+            match fut.await {
+                Ok(mut res) => {
+                    insert_request_id(res.headers_mut(), &request_id);
+                    Ok(res)
+                }
+                // エラーのレスポンスにもヘッダーを付与する
+                Err(e) => {
+                    let mut res = e.error_response();
+                    insert_request_id(res.headers_mut(), &request_id);
+                    Err(InternalError::from_response(e, res).into())
+                }
+            }
+        })
+    }
+}
+
+fn insert_request_id(headers: &mut HeaderMap, request_id: &str) {
+    // from_bytesは名前を小文字に正規化する
+    if let Ok(name) = HeaderName::from_bytes(REQUEST_ID_HEADER.as_bytes())
+        && let Ok(value) = HeaderValue::from_str(request_id)
+    {
+        headers.insert(name, value);
+    }
+}
+
+// ログへの不正な文字の混入を防ぐため、長さと文字種を制限する
+pub fn is_valid_request_id(request_id: &str) -> bool {
+    !request_id.is_empty()
+        && request_id.len() <= MAX_REQUEST_ID_LENGTH
+        && request_id.chars().all(|c| c.is_ascii_graphic())
+}
@@ -0,0 +1,83 @@
+use crate::handlers::utils::get_request_id;
+use crate::metrics::UNMATCHED_ROUTE;
+use actix_web::{
+    Error,
+    dev::{Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+};
+use futures::future::{LocalBoxFuture, Ready, ready};
+use std::time::Instant;
+use tracing::Instrument;
+
+pub struct RequestLogMiddleware;
+
+impl<S, B> Transform<S, ServiceRequest> for RequestLogMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Transform = RequestLogMiddlewareService<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequestLogMiddlewareService { service }))
+    }
+}
+
+pub struct RequestLogMiddlewareService<S> {
+    service: S,
+}
+
+impl<S, B> Service<ServiceRequest> for RequestLogMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let start = Instant::now();
+
+        // RequestIdMiddlewareが確定したIDをハンドラーと同じ方法で取得する
+        let request_id = get_request_id(req.request());
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            method = %req.method(),
+            route = %route,
+            status = tracing::field::Empty,
+            duration_ms = tracing::field::Empty,
+        );
+        let fut = span.in_scope(|| self.service.call(req));
+
+        Box::pin(
+            async move {
+                let res = fut.await;
+                let status = match &res {
+                    Ok(res) => res.status(),
+                    Err(e) => e.as_response_error().status_code(),
+                };
+
+                let span = tracing::Span::current();
+                span.record("status", status.as_u16());
+                span.record("duration_ms", start.elapsed().as_millis() as u64);
+                tracing::info!(target: "access", "request completed");
+
+                res
+            }
+            .instrument(span),
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct ApiToken {
+    pub api_token_id: Option<i64>,
+    pub user_id: i64,
+    pub name: String,
+    pub token_hash: String,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+// レスポンスに含めるため、トークンのハッシュを除いたもの
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct ApiTokenNoHash {
+    pub api_token_id: Option<i64>,
+    pub user_id: i64,
+    pub name: String,
+    pub created_at: i64,
+    pub expires_at: Option<i64>,
+    pub last_used_at: Option<i64>,
+    pub revoked_at: Option<i64>,
+}
+
+impl ApiToken {
+    pub fn new(user_id: i64, name: String, token_hash: String, expires_at: Option<i64>) -> Self {
+        Self {
+            api_token_id: None,
+            user_id,
+            name,
+            token_hash,
+            created_at: 0,
+            expires_at,
+            last_used_at: None,
+            revoked_at: None,
+        }
+    }
+
+    pub fn to_api_token_no_hash(&self) -> ApiTokenNoHash {
+        ApiTokenNoHash {
+            api_token_id: self.api_token_id,
+            user_id: self.user_id,
+            name: self.name.clone(),
+            created_at: self.created_at,
+            expires_at: self.expires_at,
+            last_used_at: self.last_used_at,
+            revoked_at: self.revoked_at,
+        }
+    }
+}
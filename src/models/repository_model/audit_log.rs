@@ -0,0 +1,83 @@
+use crate::enums::{AuditAction, AuditEntityType};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct AuditLog {
+    pub audit_log_id: Option<i64>,
+    pub actor_user_id: Option<i64>,
+    pub entity_type: String,
+    pub entity_id: i64,
+    pub action: String,
+    pub before_json: Option<String>,
+    pub after_json: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at: i64,
+}
+
+impl AuditLog {
+    pub fn new(
+        context: &AuditContext,
+        entity_type: AuditEntityType,
+        entity_id: i64,
+        action: AuditAction,
+        before_json: Option<String>,
+        after_json: Option<String>,
+    ) -> Self {
+        Self {
+            audit_log_id: None,
+            actor_user_id: context.actor_user_id,
+            entity_type: entity_type.to_str().to_string(),
+            entity_id,
+            action: action.to_str().to_string(),
+            before_json,
+            after_json,
+            request_id: context.request_id.clone(),
+            created_at: 0,
+        }
+    }
+}
+
+// 監査ログに記録する操作者とリクエストID。
+// ハンドラ以外（CLIやテスト）から呼ばれた場合はどちらもNoneになる
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditContext {
+    pub actor_user_id: Option<i64>,
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    pub fn new(actor_user_id: Option<i64>, request_id: Option<String>) -> Self {
+        Self {
+            actor_user_id,
+            request_id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct AuditLogFilter {
+    pub actor_user_id: Option<i64>,
+    pub entity_type: Option<String>,
+    pub entity_id: Option<i64>,
+    pub action: Option<String>,
+    pub request_id: Option<String>,
+    pub created_at_from: Option<i64>,
+    pub created_at_to: Option<i64>,
+}
+
+impl AuditLogFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.actor_user_id.is_none()
+            && self.entity_type.is_none()
+            && self.entity_id.is_none()
+            && self.action.is_none()
+            && self.request_id.is_none()
+            && self.created_at_from.is_none()
+            && self.created_at_to.is_none()
+    }
+}
@@ -0,0 +1,78 @@
+use crate::models::{Task, UserAssign};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// バッチで実行するタスクの操作。temp_idを付けて作成したタスクは、
+// 後続の操作からparent_temp_idで親として参照できる
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum TaskBatchOperation {
+    Create {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        temp_id: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_temp_id: Option<String>,
+        task: Task,
+    },
+    Update {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        parent_temp_id: Option<String>,
+        // 指定した場合は現在のバージョンと一致しなければ失敗する
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<i64>,
+        task: Task,
+    },
+    Delete {
+        task_id: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<i64>,
+        // restrict、cascade、reparentのいずれか。未指定の場合はrestrict
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        policy: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct TaskBatchRequest {
+    pub operations: Vec<TaskBatchOperation>,
+}
+
+// 操作ごとの結果。削除の場合は削除したタスク
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct TaskBatchResult {
+    pub index: usize,
+    pub op: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temp_id: Option<String>,
+    pub task: Task,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+#[serde(tag = "op", rename_all = "snake_case")]
+pub enum UserAssignBatchOperation {
+    Create {
+        user_assign: UserAssign,
+    },
+    Update {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<i64>,
+        user_assign: UserAssign,
+    },
+    Delete {
+        user_assign_id: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        version: Option<i64>,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct UserAssignBatchRequest {
+    pub operations: Vec<UserAssignBatchOperation>,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
+pub struct UserAssignBatchResult {
+    pub index: usize,
+    pub op: String,
+    pub user_assign: UserAssign,
+}
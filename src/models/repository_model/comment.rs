@@ -1,7 +1,9 @@
+use crate::models::repository_model::patch::deserialize_present;
 use crate::models::repository_model::user::UserNoPassword;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct Comment {
     pub comment_id: Option<i64>,
     pub user_id: i64,
@@ -11,7 +13,7 @@ pub struct Comment {
     pub updated_at: Option<i64>,
 }
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct CommentWithUser {
     pub comment_id: Option<i64>,
     pub user_id: i64,
@@ -19,9 +21,23 @@ pub struct CommentWithUser {
     pub content: String,
     pub created_at: i64,
     pub updated_at: Option<i64>,
+    #[schema(value_type = UserNoPassword)]
     pub user: sqlx::types::Json<UserNoPassword>,
 }
 
+// PATCHで変更するフィールド。省略したフィールドは変更しない
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CommentPatch {
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub content: Option<String>,
+}
+
 impl Comment {
     pub fn new(user_id: i64, task_id: i64, content: String) -> Self {
         Self {
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+// PRAGMA foreign_key_check の1行
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct ForeignKeyViolation {
+    pub table: String,
+    pub rowid: Option<i64>,
+    pub parent: String,
+    pub fkid: i64,
+}
+
+// コネクションプールの使用状況
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct PoolStatus {
+    pub size: u32,
+    pub idle: u32,
+    pub max_connections: u32,
+}
+
+impl PoolStatus {
+    pub fn in_use(&self) -> u32 {
+        self.size.saturating_sub(self.idle)
+    }
+}
@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// 削除した件数。プロジェクト・タスク・割り当て・コメントはゴミ箱への移動を含む
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+pub struct DeleteSummary {
+    pub policy: String,
+    pub users: i64,
+    pub projects: i64,
+    pub tasks: i64,
+    pub user_assigns: i64,
+    pub comments: i64,
+    // reparentで親を付け替えたタスク（階層を上げた子孫を含む）
+    pub reparented_tasks: i64,
+}
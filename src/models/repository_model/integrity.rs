@@ -0,0 +1,58 @@
+use crate::enums::{AuditEntityType, IntegrityCheck};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// データ整合性チェックで見つかった問題。fixは自動で行う修正で、Noneの場合は手動で直す
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct IntegrityIssue {
+    pub check: String,
+    pub entity_type: String,
+    pub id: i64,
+    pub detail: String,
+    pub fix: Option<String>,
+    pub fixed: bool,
+}
+
+impl IntegrityIssue {
+    pub fn new(
+        check: IntegrityCheck,
+        entity_type: AuditEntityType,
+        id: i64,
+        detail: String,
+        fix: Option<String>,
+    ) -> Self {
+        Self {
+            check: check.to_str().to_string(),
+            entity_type: entity_type.to_str().to_string(),
+            id,
+            detail,
+            fix,
+            fixed: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct IntegrityReport {
+    pub issues: Vec<IntegrityIssue>,
+    pub fixable: i64,
+    pub fixed: i64,
+    // trueの場合は修正内容だけを返し、データベースは変更していない
+    pub dry_run: bool,
+}
+
+impl IntegrityReport {
+    pub fn new(issues: Vec<IntegrityIssue>, dry_run: bool) -> Self {
+        Self {
+            fixable: issues.iter().filter(|issue| issue.fix.is_some()).count() as i64,
+            fixed: issues.iter().filter(|issue| issue.fixed).count() as i64,
+            issues,
+            dry_run,
+        }
+    }
+
+    // 修正後も残っている問題の件数
+    pub fn remaining(&self) -> i64 {
+        self.issues.len() as i64 - self.fixed
+    }
+}
@@ -0,0 +1,11 @@
+use serde::{Deserialize, Serialize};
+
+// 組み込まれたマイグレーションごとの適用状況
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct MigrationStatus {
+    pub version: i64,
+    pub description: String,
+    pub applied: bool,
+    // 適用済みのSQLと組み込まれたSQLが異なる
+    pub checksum_mismatch: bool,
+}
@@ -1,20 +1,61 @@
+pub mod api_token;
+pub mod audit_log;
+pub mod batch;
 pub mod comment;
+pub mod database_check;
+pub mod delete_summary;
+pub mod integrity;
+pub mod migration;
+pub mod patch;
 pub mod project;
+pub mod project_member;
+pub mod session;
 pub mod task;
+pub mod task_history;
+pub mod task_tree;
 pub mod taskwithuser;
+pub mod trash;
 pub mod user;
 pub mod user_assign;
 
+pub use api_token::ApiToken;
+pub use api_token::ApiTokenNoHash;
+pub use audit_log::AuditContext;
+pub use audit_log::AuditLog;
+pub use audit_log::AuditLogFilter;
+pub use batch::TaskBatchOperation;
+pub use batch::TaskBatchRequest;
+pub use batch::TaskBatchResult;
+pub use batch::UserAssignBatchOperation;
+pub use batch::UserAssignBatchRequest;
+pub use batch::UserAssignBatchResult;
 pub use comment::Comment;
+pub use comment::CommentPatch;
 pub use comment::CommentWithUser;
+pub use database_check::ForeignKeyViolation;
+pub use database_check::PoolStatus;
+pub use delete_summary::DeleteSummary;
+pub use integrity::IntegrityIssue;
+pub use integrity::IntegrityReport;
+pub use migration::MigrationStatus;
 pub use project::Project;
+pub use project::ProjectPatch;
+pub use project_member::ProjectMember;
+pub use session::Session;
 pub use task::Task;
 pub use task::TaskFilter;
+pub use task::TaskPatch;
+pub use task_history::TaskHistory;
+pub use task_tree::TaskTreeNode;
 pub use taskwithuser::FixedTaskWithUser;
 pub use taskwithuser::FixedUserWithTask;
 pub use taskwithuser::TaskWithUser;
+pub use trash::TrashFilter;
+pub use trash::TrashItem;
+pub use trash::TrashPurgeSummary;
 pub use user::User;
 pub use user::UserFilter;
 pub use user::UserNoPassword;
+pub use user::UserPatch;
 pub use user_assign::UserAssign;
 pub use user_assign::UserAssignFilter;
@@ -0,0 +1,22 @@
+use serde::{Deserialize, Deserializer};
+
+// PATCHのボディでは「省略」と「null」を区別する。
+// 省略したフィールドは#[serde(default)]でNoneになり、以下の関数は値がある場合のみ呼ばれる
+
+// nullを受け付けないフィールド。nullの場合はデシリアライズエラーにする
+pub fn deserialize_present<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+// nullで値を消せるフィールド。nullの場合はSome(None)になる
+pub fn deserialize_nullable<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
@@ -1,6 +1,8 @@
+use crate::models::repository_model::patch::deserialize_present;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
 pub struct Project {
     pub project_id: Option<i64>,
     pub name: String,
@@ -14,3 +16,16 @@ impl Project {
         }
     }
 }
+
+// PATCHで変更するフィールド。省略したフィールドは変更しない
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ProjectPatch {
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub name: Option<String>,
+}
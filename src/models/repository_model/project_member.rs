@@ -0,0 +1,22 @@
+use crate::enums::ProjectRole;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct ProjectMember {
+    pub project_member_id: Option<i64>,
+    pub project_id: i64,
+    pub user_id: i64,
+    pub role: i64,
+}
+
+impl ProjectMember {
+    pub fn new(project_id: i64, user_id: i64, role: ProjectRole) -> Self {
+        Self {
+            project_member_id: None,
+            project_id,
+            user_id,
+            role: role.to_int(),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Session {
+    pub session_id: Option<i64>,
+    pub user_id: i64,
+    pub token_hash: String,
+    pub created_at: i64,
+    pub expires_at: i64,
+    pub revoked_at: Option<i64>,
+}
+
+impl Session {
+    pub fn new(user_id: i64, token_hash: String, expires_at: i64) -> Self {
+        Self {
+            session_id: None,
+            user_id,
+            token_hash,
+            created_at: 0,
+            expires_at,
+            revoked_at: None,
+        }
+    }
+}
@@ -1,6 +1,8 @@
+use crate::models::repository_model::patch::{deserialize_nullable, deserialize_present};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
 pub struct Task {
     pub task_id: Option<i64>,
     pub project_id: i64,
@@ -39,6 +41,81 @@ impl Task {
     }
 }
 
+// PATCHで変更するフィールド。省略したフィールドは変更しない。
+// parent_id, description, deadlineはnullを指定すると値を消す
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TaskPatch {
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<i64>)]
+    pub parent_id: Option<Option<i64>>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub level: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub name: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<String>)]
+    pub description: Option<Option<String>>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub status: Option<i64>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_nullable",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(value_type = Option<i64>)]
+    pub deadline: Option<Option<i64>>,
+}
+
+impl TaskPatch {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    // 親子関係の検証が必要な変更か
+    pub fn changes_parent_relation(&self) -> bool {
+        self.parent_id.is_some() || self.level.is_some()
+    }
+
+    pub fn apply(&self, task: &Task) -> Task {
+        Task {
+            parent_id: self.parent_id.unwrap_or(task.parent_id),
+            level: self.level.unwrap_or(task.level),
+            name: self.name.clone().unwrap_or_else(|| task.name.clone()),
+            description: self
+                .description
+                .clone()
+                .unwrap_or_else(|| task.description.clone()),
+            status: self.status.unwrap_or(task.status),
+            deadline: self.deadline.unwrap_or(task.deadline),
+            ..task.clone()
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TaskFilter {
     pub project_id: Option<i64>,
@@ -54,6 +131,8 @@ pub struct TaskFilter {
     pub updated_at_from: Option<i64>,
     pub updated_at_to: Option<i64>,
     pub assignee_id: Option<i64>,
+    // 指定したユーザーがメンバーのプロジェクトのタスクに絞り込む
+    pub member_id: Option<i64>,
 }
 
 impl TaskFilter {
@@ -72,6 +151,7 @@ impl TaskFilter {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None, // 現在未使用
+            member_id: None,
         }
     }
 
@@ -141,5 +221,6 @@ impl TaskFilter {
             && self.updated_at_from.is_none()
             && self.updated_at_to.is_none()
             && self.assignee_id.is_none()
+            && self.member_id.is_none()
     }
 }
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// タスク更新時のフィールド単位の変更履歴。
+// 値はすべて文字列で保存し、NULLはNoneとする
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct TaskHistory {
+    pub task_history_id: Option<i64>,
+    pub task_id: i64,
+    pub field_name: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+    pub changed_by: Option<i64>,
+    pub changed_at: i64,
+}
+
+impl TaskHistory {
+    pub fn new(
+        task_id: i64,
+        field_name: String,
+        old_value: Option<String>,
+        new_value: Option<String>,
+        changed_by: Option<i64>,
+    ) -> Self {
+        Self {
+            task_history_id: None,
+            task_id,
+            field_name,
+            old_value,
+            new_value,
+            changed_by,
+            changed_at: 0,
+        }
+    }
+}
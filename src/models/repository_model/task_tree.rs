@@ -0,0 +1,15 @@
+use crate::models::repository_model::taskwithuser::TaskWithUser;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct TaskTreeNode {
+    #[serde(flatten)]
+    pub task: TaskWithUser,
+    // ツリーのルートからの深さ
+    pub depth: i64,
+    // falseの場合はフィルタに一致した子孫の祖先として含めている
+    pub matched: bool,
+    #[schema(no_recursion)]
+    pub children: Vec<TaskTreeNode>,
+}
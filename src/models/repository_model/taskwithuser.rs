@@ -1,8 +1,9 @@
 use crate::models::repository_model::task::Task;
 use crate::models::repository_model::user::UserNoPassword;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
 pub struct TaskWithUser {
     pub task_id: i64,
     pub project_id: i64,
@@ -14,6 +15,7 @@ pub struct TaskWithUser {
     pub deadline: Option<i64>,
     pub created_at: i64,
     pub updated_at: Option<i64>,
+    #[schema(value_type = Vec<UserNoPassword>)]
     pub users: sqlx::types::Json<Vec<UserNoPassword>>,
 }
 
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// ゴミ箱にあるプロジェクト・タスク・コメント。コメントのnameには本文が入る
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
+pub struct TrashItem {
+    pub entity_type: String,
+    pub id: i64,
+    pub project_id: i64,
+    pub task_id: Option<i64>,
+    pub name: String,
+    pub deleted_at: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct TrashFilter {
+    pub entity_type: Option<String>,
+    pub project_id: Option<i64>,
+    // 指定したユーザーがメンバーのプロジェクトのものに絞り込む
+    pub member_id: Option<i64>,
+}
+
+// 完全削除した件数。deleted_beforeより前にゴミ箱に移動したものが対象
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+pub struct TrashPurgeSummary {
+    pub deleted_before: i64,
+    pub projects: i64,
+    pub tasks: i64,
+    pub user_assigns: i64,
+    pub comments: i64,
+}
@@ -1,8 +1,11 @@
+use crate::errors::db_error::DBAccessError;
+use crate::models::repository_model::patch::deserialize_present;
+use crate::repository::password::hash_password;
 use crate::repository::validations::validate_user_password;
 use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
 pub struct User {
     pub user_id: Option<i64>,
     pub username: String,
@@ -10,28 +13,61 @@ pub struct User {
     pub password_hash: String,
 }
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, Clone, ToSchema)]
 pub struct UserNoPassword {
     pub user_id: Option<i64>,
     pub username: String,
     pub email: String,
 }
 
+// PATCHで変更するフィールド。省略したフィールドは変更しない。
+// passwordは平文で受け取り、ハンドラーでハッシュ化する
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct UserPatch {
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub username: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub email: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "deserialize_present",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[schema(nullable = false)]
+    pub password: Option<String>,
+}
+
+impl UserPatch {
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 impl User {
-    pub fn new(username: String, email: String, password: String) -> Self {
+    // 平文のパスワードを受け取った場合はハッシュ化する
+    pub fn new(username: String, email: String, password: String) -> Result<Self, DBAccessError> {
         let password_hash = match validate_user_password(&password) {
             Ok(()) => password,
-            _ => {
-                format!("{:x}", Sha256::digest(password.as_bytes()))
-            }
+            _ => hash_password(&password)?,
         };
 
-        Self {
+        Ok(Self {
             user_id: None,
             username,
             email,
             password_hash,
-        }
+        })
     }
 
     pub fn to_user_no_password(&self) -> UserNoPassword {
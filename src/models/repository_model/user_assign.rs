@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(sqlx::FromRow, Debug, Serialize, Deserialize, PartialEq, Eq, ToSchema)]
 pub struct UserAssign {
     pub user_assign_id: Option<i64>,
     pub user_id: i64,
@@ -21,6 +22,8 @@ impl UserAssign {
 pub struct UserAssignFilter {
     pub user_id: Option<i64>,
     pub task_id: Option<i64>,
+    // 指定したユーザーがメンバーのプロジェクトの割り当てに絞り込む
+    pub member_id: Option<i64>,
 }
 
 impl UserAssignFilter {
@@ -28,6 +31,7 @@ impl UserAssignFilter {
         Self {
             user_id: None,
             task_id: None,
+            member_id: None,
         }
     }
 
@@ -40,6 +44,6 @@ impl UserAssignFilter {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.user_id.is_none() && self.task_id.is_none()
+        self.user_id.is_none() && self.task_id.is_none() && self.member_id.is_none()
     }
 }
@@ -0,0 +1,72 @@
+use super::common_models::ResponseMetadata;
+use crate::models::ApiTokenNoHash;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// 発行時のみ平文のトークンを返す
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct IssuedApiToken {
+    pub token: String,
+    pub token_type: String,
+    pub api_token: ApiTokenNoHash,
+}
+
+impl IssuedApiToken {
+    pub fn new(token: String, api_token: ApiTokenNoHash) -> Self {
+        Self {
+            token,
+            token_type: "Bearer".to_string(),
+            api_token,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct IssuedApiTokenResponse {
+    pub results: Vec<IssuedApiToken>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl IssuedApiTokenResponse {
+    pub fn new(
+        results: Vec<IssuedApiToken>,
+        count: i64,
+        metadata: Option<ResponseMetadata>,
+    ) -> Self {
+        Self {
+            results,
+            count,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct ApiTokenResponse {
+    pub results: Vec<ApiTokenNoHash>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl ApiTokenResponse {
+    pub fn new(
+        results: Vec<ApiTokenNoHash>,
+        count: i64,
+        metadata: Option<ResponseMetadata>,
+    ) -> Self {
+        Self {
+            results,
+            count,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
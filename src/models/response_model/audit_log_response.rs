@@ -0,0 +1,33 @@
+use crate::models::AuditLog;
+use crate::models::response_model::Pagination;
+use crate::models::response_model::ResponseMetadata;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct AuditLogResponse {
+    pub results: Vec<AuditLog>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub pagination: Option<Pagination>,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl AuditLogResponse {
+    pub fn new(
+        results: Vec<AuditLog>,
+        count: i64,
+        pagination: Option<Pagination>,
+        metadata: Option<ResponseMetadata>,
+    ) -> Self {
+        Self {
+            results,
+            count,
+            rc: 0,
+            message: "OK".to_string(),
+            pagination,
+            metadata,
+        }
+    }
+}
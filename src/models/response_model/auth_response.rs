@@ -0,0 +1,44 @@
+use super::common_models::ResponseMetadata;
+use crate::models::UserNoPassword;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, Clone, ToSchema)]
+pub struct AuthToken {
+    pub token: String,
+    pub token_type: String,
+    pub expires_at: i64,
+    pub user: UserNoPassword,
+}
+
+impl AuthToken {
+    pub fn new(token: String, expires_at: i64, user: UserNoPassword) -> Self {
+        Self {
+            token,
+            token_type: "Bearer".to_string(),
+            expires_at,
+            user,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct AuthResponse {
+    pub results: Vec<AuthToken>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl AuthResponse {
+    pub fn new(results: Vec<AuthToken>, count: i64, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            results,
+            count,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
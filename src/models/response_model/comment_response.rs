@@ -1,8 +1,9 @@
 use super::common_models::{Pagination, ResponseMetadata};
 use crate::models::{Comment, CommentWithUser};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct CommentResponse {
     pub results: Vec<Comment>,
     pub count: i64,
@@ -30,7 +31,7 @@ impl CommentResponse {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct CommentUserResponse {
     pub results: Vec<CommentWithUser>,
     pub count: i64,
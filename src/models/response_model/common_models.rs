@@ -1,6 +1,12 @@
+use crate::errors::HandlerError;
+use crate::settings::server_config;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct RootResponse {
     pub message: String,
     pub rc: i32,
@@ -17,33 +23,77 @@ impl RootResponse {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ErrorResponse {
+    // ErrorKeyの表示名。ErrorKeyのないエラーはエラーの種類（例: InternalServerError）
+    pub code: String,
     pub message: String,
+    // 原因となったリクエストのフィールド
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
     pub rc: i32,
     pub metadata: Option<ResponseMetadata>,
 }
 
+// serdeのエラー（例: missing field `name`）に含まれるフィールド名。
+// serde_jsonのエラーはフィールド名を型で持たないため、メッセージから取り出す
+static SERDE_FIELD: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:missing|unknown|duplicate) field `(\w+)`").unwrap());
+
 impl ErrorResponse {
-    pub fn new(message: String, rc: i32, metadata: Option<ResponseMetadata>) -> Self {
+    pub fn from_error(error: &HandlerError, metadata: Option<ResponseMetadata>) -> Self {
+        let message = error.message();
+        let details = message.details.to_value();
+
+        // 複合キーの一意制約違反など、複数のフィールドの場合は設定しない
+        let field = message
+            .details
+            .field("field")
+            .and_then(|field| field.as_str())
+            .filter(|field| !field.contains(','))
+            .map(|field| field.to_string())
+            .or_else(|| {
+                SERDE_FIELD
+                    .captures(&message.to_string())
+                    .map(|captures| captures[1].to_string())
+            })
+            .or_else(|| {
+                message
+                    .key
+                    .and_then(|key| key.field())
+                    .map(|field| field.to_string())
+            });
+
         Self {
-            message,
-            rc,
+            code: message.code().unwrap_or_else(|| error.kind().to_string()),
+            message: message.message.clone(),
+            field,
+            details,
+            rc: error.rc(),
             metadata,
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct Pagination {
     pub current_page: i32,
     pub page_size: i32,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct ResponseMetadata {
     pub request_id: String,
     pub api_version: String,
+    // 以下はProcessingTimeMiddlewareが設定する（UNIX時間のミリ秒）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<i64>,
 }
 
 impl ResponseMetadata {
@@ -51,8 +101,20 @@ impl ResponseMetadata {
         Self {
             request_id,
             api_version: env!("CARGO_PKG_VERSION").to_string(),
+            start_time: None,
+            end_time: None,
+            duration_ms: None,
         }
     }
+
+    pub fn set_processing_start(&mut self, start_time: i64) {
+        self.start_time = Some(start_time);
+    }
+
+    pub fn set_processing_end(&mut self, end_time: i64) {
+        self.end_time = Some(end_time);
+        self.duration_ms = self.start_time.map(|start_time| end_time - start_time);
+    }
 }
 
 #[derive(Debug)]
@@ -84,7 +146,9 @@ impl PaginationParams {
         } else if self.page.is_some() && self.page_size.is_some() {
             if self.page.unwrap() <= 0 {
                 self.status = PaginationStatus::Error;
-            } else if self.page_size.unwrap() <= 0 || self.page_size.unwrap() > 101 {
+            } else if self.page_size.unwrap() <= 0
+                || self.page_size.unwrap() > server_config().pagination.max_page_size
+            {
                 self.status = PaginationStatus::Error;
             } else {
                 self.status = PaginationStatus::Active;
@@ -0,0 +1,25 @@
+use super::common_models::ResponseMetadata;
+use crate::models::DeleteSummary;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct DeleteResponse {
+    pub results: Vec<DeleteSummary>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl DeleteResponse {
+    pub fn new(summary: DeleteSummary, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            results: vec![summary],
+            count: 1,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
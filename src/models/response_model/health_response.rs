@@ -0,0 +1,111 @@
+use crate::models::response_model::ResponseMetadata;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+pub const HEALTH_STATUS_OK: &str = "ok";
+pub const HEALTH_STATUS_ERROR: &str = "error";
+
+#[derive(Debug, Serialize, Deserialize, Clone, ToSchema)]
+pub struct HealthCheck {
+    pub status: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl HealthCheck {
+    pub fn ok() -> Self {
+        Self {
+            status: HEALTH_STATUS_OK.to_string(),
+            message: None,
+        }
+    }
+
+    pub fn error(message: String) -> Self {
+        Self {
+            status: HEALTH_STATUS_ERROR.to_string(),
+            message: Some(message),
+        }
+    }
+
+    pub fn is_ok(&self) -> bool {
+        self.status == HEALTH_STATUS_OK
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct MigrationHealth {
+    #[serde(flatten)]
+    pub check: HealthCheck,
+    pub current_version: Option<i64>,
+    pub expected_version: Option<i64>,
+}
+
+// 使用状況の報告のみで、レディネスの判定には使わない。
+// saturationは使用中の接続数 / 最大接続数
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct PoolHealth {
+    pub size: u32,
+    pub idle: u32,
+    pub in_use: u32,
+    pub max_connections: u32,
+    pub saturation: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct StorageHealth {
+    #[serde(flatten)]
+    pub check: HealthCheck,
+    pub size_bytes: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessChecks {
+    pub database: HealthCheck,
+    pub migrations: MigrationHealth,
+    pub pool: PoolHealth,
+    pub storage: StorageHealth,
+}
+
+impl ReadinessChecks {
+    // 失敗した項目名を返す
+    pub fn failed(&self) -> Vec<&'static str> {
+        [
+            ("database", &self.database),
+            ("migrations", &self.migrations.check),
+            ("storage", &self.storage.check),
+        ]
+        .into_iter()
+        .filter(|(_, check)| !check.is_ok())
+        .map(|(name, _)| name)
+        .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReadinessResponse {
+    pub status: String,
+    pub checks: ReadinessChecks,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl ReadinessResponse {
+    pub fn new(
+        checks: ReadinessChecks,
+        message: String,
+        metadata: Option<ResponseMetadata>,
+    ) -> Self {
+        let ready = checks.failed().is_empty();
+        Self {
+            status: match ready {
+                true => HEALTH_STATUS_OK.to_string(),
+                false => HEALTH_STATUS_ERROR.to_string(),
+            },
+            checks,
+            rc: if ready { 0 } else { 1 },
+            message,
+            metadata,
+        }
+    }
+}
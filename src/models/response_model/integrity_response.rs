@@ -0,0 +1,25 @@
+use super::common_models::ResponseMetadata;
+use crate::models::IntegrityReport;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct IntegrityResponse {
+    pub results: Vec<IntegrityReport>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl IntegrityResponse {
+    pub fn new(report: IntegrityReport, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            results: vec![report],
+            count: 1,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
@@ -1,13 +1,31 @@
+mod api_token_response;
+mod audit_log_response;
+mod auth_response;
 mod comment_response;
 mod common_models;
+mod delete_response;
+mod health_response;
+mod integrity_response;
+mod project_member_response;
 mod project_response;
+mod task_history_response;
 mod task_response;
+mod trash_response;
 mod user_assign_response;
 mod user_response;
 
+pub use api_token_response::*;
+pub use audit_log_response::*;
+pub use auth_response::*;
 pub use comment_response::*;
 pub use common_models::*;
+pub use delete_response::*;
+pub use health_response::*;
+pub use integrity_response::*;
+pub use project_member_response::*;
 pub use project_response::*;
+pub use task_history_response::*;
 pub use task_response::*;
+pub use trash_response::*;
 pub use user_assign_response::*;
 pub use user_response::*;
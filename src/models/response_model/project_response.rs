@@ -2,8 +2,9 @@ use crate::models::project::Project;
 use crate::models::response_model::Pagination;
 use crate::models::response_model::ResponseMetadata;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
 pub struct ProjectResponse {
     pub results: Vec<Project>,
     pub count: i64,
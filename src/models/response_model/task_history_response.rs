@@ -0,0 +1,33 @@
+use crate::models::TaskHistory;
+use crate::models::response_model::Pagination;
+use crate::models::response_model::ResponseMetadata;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct TaskHistoryResponse {
+    pub results: Vec<TaskHistory>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub pagination: Option<Pagination>,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl TaskHistoryResponse {
+    pub fn new(
+        results: Vec<TaskHistory>,
+        count: i64,
+        pagination: Option<Pagination>,
+        metadata: Option<ResponseMetadata>,
+    ) -> Self {
+        Self {
+            results,
+            count,
+            rc: 0,
+            message: "OK".to_string(),
+            pagination,
+            metadata,
+        }
+    }
+}
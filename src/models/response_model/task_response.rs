@@ -1,8 +1,9 @@
 use super::common_models::{Pagination, ResponseMetadata};
-use crate::models::{Task, TaskWithUser};
+use crate::models::{Task, TaskBatchResult, TaskTreeNode, TaskWithUser};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct TaskResponse {
     pub results: Vec<Task>,
     pub count: i64,
@@ -30,7 +31,7 @@ impl TaskResponse {
     }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct TaskUserResponse {
     pub results: Vec<TaskWithUser>,
     pub count: i64,
@@ -57,3 +58,54 @@ impl TaskUserResponse {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TaskBatchResponse {
+    pub results: Vec<TaskBatchResult>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl TaskBatchResponse {
+    pub fn new(results: Vec<TaskBatchResult>, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            count: results.len() as i64,
+            results,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
+
+// countはルートの数。子はresultsの各要素に入れ子で含まれる
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TaskTreeResponse {
+    pub results: Vec<TaskTreeNode>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl TaskTreeResponse {
+    pub fn new(results: Vec<TaskTreeNode>, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            count: results.len() as i64,
+            results,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
+
+// GET /tasksのレスポンス。with_user=trueの場合はTaskUserResponseになる
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+#[serde(untagged)]
+pub enum GetTasksResponse {
+    Tasks(TaskResponse),
+    TasksWithUser(TaskUserResponse),
+}
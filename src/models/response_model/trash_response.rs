@@ -0,0 +1,46 @@
+use super::common_models::ResponseMetadata;
+use crate::models::{TrashItem, TrashPurgeSummary};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TrashResponse {
+    pub results: Vec<TrashItem>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl TrashResponse {
+    pub fn new(results: Vec<TrashItem>, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            count: results.len() as i64,
+            results,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct TrashPurgeResponse {
+    pub results: Vec<TrashPurgeSummary>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl TrashPurgeResponse {
+    pub fn new(summary: TrashPurgeSummary, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            results: vec![summary],
+            count: 1,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use super::common_models::{Pagination, ResponseMetadata};
-use crate::models::UserAssign;
+use crate::models::{UserAssign, UserAssignBatchResult};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct UserAssignResponse {
     pub results: Vec<UserAssign>,
     pub count: i64,
@@ -29,3 +30,24 @@ impl UserAssignResponse {
         }
     }
 }
+
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
+pub struct UserAssignBatchResponse {
+    pub results: Vec<UserAssignBatchResult>,
+    pub count: i64,
+    pub rc: i32,
+    pub message: String,
+    pub metadata: Option<ResponseMetadata>,
+}
+
+impl UserAssignBatchResponse {
+    pub fn new(results: Vec<UserAssignBatchResult>, metadata: Option<ResponseMetadata>) -> Self {
+        Self {
+            count: results.len() as i64,
+            results,
+            rc: 0,
+            message: "OK".to_string(),
+            metadata,
+        }
+    }
+}
@@ -1,8 +1,9 @@
 use super::common_models::{Pagination, ResponseMetadata};
 use crate::models::UserNoPassword;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, ToSchema)]
 pub struct UserResponse {
     pub results: Vec<UserNoPassword>,
     pub count: i64,
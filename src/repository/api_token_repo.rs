@@ -0,0 +1,224 @@
+use crate::constants::API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS;
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::ApiToken;
+use crate::repository::user_repo::get_user_by_id_with_transaction;
+use crate::repository::validations::{
+    validate_api_token_expires_at, validate_api_token_id, validate_api_token_id_is_none,
+    validate_api_token_name, validate_api_token_token_hash, validate_api_token_user_id,
+};
+use chrono::Utc;
+use serde_json::json;
+use sqlx::{Pool, Sqlite};
+
+pub struct ApiTokenRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ApiTokenRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_api_token(&self, api_token: ApiToken) -> Result<ApiToken, DBAccessError> {
+        let _timer = QueryTimer::start("api_token", "create_api_token");
+        let now = Utc::now().timestamp();
+
+        validate_api_token_id_is_none(api_token.api_token_id)?;
+        validate_api_token_user_id(api_token.user_id)?;
+        validate_api_token_name(&api_token.name)?;
+        validate_api_token_token_hash(&api_token.token_hash)?;
+        validate_api_token_expires_at(api_token.expires_at, now)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        get_user_by_id_with_transaction(&api_token.user_id, &mut tx).await?;
+
+        let result = sqlx::query_as!(
+            ApiToken,
+            r#"
+                INSERT INTO api_tokens (user_id, name, token_hash, created_at, expires_at)
+                VALUES ($1, $2, $3, $4, $5)
+                RETURNING api_token_id, user_id, name, token_hash, created_at, expires_at,
+                    last_used_at, revoked_at
+            "#,
+            api_token.user_id,
+            api_token.name,
+            api_token.token_hash,
+            now,
+            api_token.expires_at,
+        )
+        .fetch_one(&mut *tx)
+        .await;
+
+        match result {
+            Ok(api_token) => {
+                tx.commit().await.map_err(|e| {
+                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                        ErrorKey::ApiTokenCreateFailed,
+                        e.to_string()
+                    )))
+                })?;
+                log::info!(
+                    "Created API token: ID = {:?}, User ID = {}, Name = {}",
+                    api_token.api_token_id,
+                    api_token.user_id,
+                    api_token.name
+                );
+                Ok(api_token)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(DBAccessError::QueryError(anyhow::anyhow!(
+                    get_error_message(ErrorKey::ApiTokenCreateFailed, e.to_string())
+                )))
+            }
+        }
+    }
+
+    // 無効化・期限切れのトークンも含めて返す
+    pub async fn get_api_tokens_by_user_id(
+        &self,
+        user_id: i64,
+    ) -> Result<Vec<ApiToken>, DBAccessError> {
+        let _timer = QueryTimer::start("api_token", "get_api_tokens_by_user_id");
+        validate_api_token_user_id(user_id)?;
+
+        let result = sqlx::query_as!(
+            ApiToken,
+            r#"
+                SELECT api_token_id, user_id, name, token_hash, created_at, expires_at,
+                    last_used_at, revoked_at
+                FROM api_tokens
+                WHERE user_id = $1
+                ORDER BY api_token_id
+            "#,
+            user_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ApiTokenGetByUserIdFailed,
+                e.to_string()
+            )))
+        })?;
+        log::debug!("Get API tokens by user id: {:?}", result.len());
+
+        Ok(result)
+    }
+
+    // 有効なトークンを返す。最終使用日時は認証のたびに書き込まないよう、
+    // 前回から一定時間が経過している場合のみ更新する。
+    // 無効化・期限切れ・存在しない場合はNotFoundErrorを返す
+    pub async fn use_api_token(&self, token_hash: &str) -> Result<ApiToken, DBAccessError> {
+        let _timer = QueryTimer::start("api_token", "use_api_token");
+        validate_api_token_token_hash(token_hash)?;
+
+        let use_error = |e: sqlx::Error| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ApiTokenUseFailed,
+                e.to_string()
+            )))
+        };
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query_as!(
+            ApiToken,
+            r#"
+                SELECT api_token_id, user_id, name, token_hash, created_at, expires_at,
+                    last_used_at, revoked_at
+                FROM api_tokens
+                WHERE token_hash = $1
+                    AND revoked_at IS NULL
+                    AND (expires_at IS NULL OR expires_at > $2)
+            "#,
+            token_hash,
+            now,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(use_error)?;
+
+        let Some(mut api_token) = result else {
+            return Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::ApiTokenUseFailedByTokenHashNotFound,
+                "".to_string(),
+            )));
+        };
+
+        let threshold = now - API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS;
+        if api_token
+            .last_used_at
+            .is_none_or(|last_used_at| last_used_at < threshold)
+        {
+            sqlx::query!(
+                r#"
+                    UPDATE api_tokens
+                    SET last_used_at = $1
+                    WHERE api_token_id = $2
+                        AND (last_used_at IS NULL OR last_used_at < $3)
+                "#,
+                now,
+                api_token.api_token_id,
+                threshold,
+            )
+            .execute(&self.pool)
+            .await
+            .map_err(use_error)?;
+            api_token.last_used_at = Some(now);
+        }
+
+        Ok(api_token)
+    }
+
+    // 他のユーザーのトークンは無効化できない
+    pub async fn revoke_api_token(
+        &self,
+        api_token_id: i64,
+        user_id: i64,
+    ) -> Result<ApiToken, DBAccessError> {
+        let _timer = QueryTimer::start("api_token", "revoke_api_token");
+        validate_api_token_id(api_token_id)?;
+        validate_api_token_user_id(user_id)?;
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query_as!(
+            ApiToken,
+            r#"
+                UPDATE api_tokens
+                SET revoked_at = $1
+                WHERE api_token_id = $2 AND user_id = $3 AND revoked_at IS NULL
+                RETURNING api_token_id, user_id, name, token_hash, created_at, expires_at,
+                    last_used_at, revoked_at
+            "#,
+            now,
+            api_token_id,
+            user_id,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ApiTokenRevokeFailed,
+                e.to_string()
+            )))
+        })?;
+
+        match result {
+            Some(api_token) => {
+                log::info!(
+                    "Revoked API token: ID = {}, User ID = {}",
+                    api_token_id,
+                    user_id
+                );
+                Ok(api_token)
+            }
+            None => Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::ApiTokenRevokeFailedByIdNotFound,
+                [("id", json!(api_token_id)), ("user_id", json!(user_id))],
+            ))),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::enums::{AuditAction, AuditEntityType};
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{AuditContext, AuditLog, AuditLogFilter};
+use crate::repository::validations::validate_pagination;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+
+pub struct AuditLogRepository {
+    pool: Pool<Sqlite>,
+}
+
+enum AuditLogFilterValue {
+    I64(i64),
+    String(String),
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    // 新しい順に返す
+    pub async fn get_audit_logs_by_filter(
+        &self,
+        filter: Option<&AuditLogFilter>,
+        page: Option<&i32>,
+        page_size: Option<&i32>,
+    ) -> Result<Vec<AuditLog>, DBAccessError> {
+        let _timer = QueryTimer::start("audit_log", "get_audit_logs_by_filter");
+        if let Some(filter) = filter {
+            validate_audit_log_filter(filter)?;
+        }
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditLogGetByFilterFailed,
+                e.to_string()
+            )))
+        })?;
+
+        let (where_clause, bind_values) = match filter {
+            Some(filter) => build_audit_log_where_clause(filter),
+            None => (String::new(), Vec::new()),
+        };
+
+        let count_query = format!("SELECT COUNT(*) FROM audit_log {}", where_clause);
+        let mut count_builder = sqlx::query_scalar::<_, i64>(&count_query);
+        for value in bind_values.iter() {
+            match value {
+                AuditLogFilterValue::I64(v) => count_builder = count_builder.bind(v),
+                AuditLogFilterValue::String(v) => count_builder = count_builder.bind(v),
+            }
+        }
+        let count = count_builder.fetch_one(&mut *tx).await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditLogGetCountFailed,
+                e.to_string()
+            )))
+        })?;
+        validate_pagination(page, page_size, &count)?;
+
+        let mut query = format!(
+            r#"
+                SELECT audit_log_id, actor_user_id, entity_type, entity_id, action,
+                    before_json, after_json, request_id, created_at
+                FROM audit_log {}
+                ORDER BY audit_log_id DESC
+            "#,
+            where_clause
+        );
+        let index = bind_values.len() + 1;
+        if page.is_some() && page_size.is_some() {
+            query.push_str(&format!(" LIMIT ${} OFFSET ${}", index, index + 1));
+        }
+
+        let mut query_builder = sqlx::query_as::<_, AuditLog>(&query);
+        for value in bind_values.iter() {
+            match value {
+                AuditLogFilterValue::I64(v) => query_builder = query_builder.bind(v),
+                AuditLogFilterValue::String(v) => query_builder = query_builder.bind(v),
+            }
+        }
+        if let (Some(page), Some(page_size)) = (page, page_size) {
+            let offset = (*page - 1) * *page_size;
+            query_builder = query_builder.bind(*page_size).bind(offset);
+        }
+
+        let result = query_builder.fetch_all(&mut *tx).await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditLogGetByFilterFailed,
+                e.to_string()
+            )))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::AuditLogGetByFilterFailed,
+                e.to_string()
+            )))
+        })?;
+        log::debug!("Get audit logs by filter: {:?}", result.len());
+
+        Ok(result)
+    }
+}
+
+fn validate_audit_log_filter(filter: &AuditLogFilter) -> Result<(), DBAccessError> {
+    if let Some(actor_user_id) = filter.actor_user_id
+        && actor_user_id < 0
+    {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::AuditLogActorUserIdInvalid,
+            [("id", json!(actor_user_id))],
+        )));
+    }
+    if let Some(entity_id) = filter.entity_id
+        && entity_id < 0
+    {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::AuditLogEntityIdInvalid,
+            [("id", json!(entity_id))],
+        )));
+    }
+    if let Some(entity_type) = &filter.entity_type {
+        AuditEntityType::from_string(entity_type)
+            .map_err(|e| DBAccessError::ValidationError(e.into()))?;
+    }
+    if let Some(action) = &filter.action {
+        AuditAction::from_string(action).map_err(|e| DBAccessError::ValidationError(e.into()))?;
+    }
+    Ok(())
+}
+
+fn build_audit_log_where_clause(filter: &AuditLogFilter) -> (String, Vec<AuditLogFilterValue>) {
+    let mut where_clauses = Vec::new();
+    let mut bind_values = Vec::new();
+
+    if let Some(actor_user_id) = filter.actor_user_id {
+        bind_values.push(AuditLogFilterValue::I64(actor_user_id));
+        where_clauses.push(format!("actor_user_id = ${}", bind_values.len()));
+    }
+    if let Some(entity_type) = &filter.entity_type {
+        bind_values.push(AuditLogFilterValue::String(entity_type.clone()));
+        where_clauses.push(format!("entity_type = ${}", bind_values.len()));
+    }
+    if let Some(entity_id) = filter.entity_id {
+        bind_values.push(AuditLogFilterValue::I64(entity_id));
+        where_clauses.push(format!("entity_id = ${}", bind_values.len()));
+    }
+    if let Some(action) = &filter.action {
+        bind_values.push(AuditLogFilterValue::String(action.clone()));
+        where_clauses.push(format!("action = ${}", bind_values.len()));
+    }
+    if let Some(request_id) = &filter.request_id {
+        bind_values.push(AuditLogFilterValue::String(request_id.clone()));
+        where_clauses.push(format!("request_id = ${}", bind_values.len()));
+    }
+    if let Some(created_at_from) = filter.created_at_from {
+        bind_values.push(AuditLogFilterValue::I64(created_at_from));
+        where_clauses.push(format!("created_at >= ${}", bind_values.len()));
+    }
+    if let Some(created_at_to) = filter.created_at_to {
+        bind_values.push(AuditLogFilterValue::I64(created_at_to));
+        where_clauses.push(format!("created_at <= ${}", bind_values.len()));
+    }
+
+    if where_clauses.is_empty() {
+        (String::new(), bind_values)
+    } else {
+        (
+            format!(" WHERE {}", where_clauses.join(" AND ")),
+            bind_values,
+        )
+    }
+}
+
+// 監査ログに保存するJSON文字列に変換する
+pub fn to_audit_json<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_string(value) {
+        Ok(json) => Some(json),
+        Err(e) => {
+            log::warn!("Failed to serialize audit log value: {:?}", e);
+            None
+        }
+    }
+}
+
+// 変更と同じトランザクション内で呼び出すこと
+pub async fn create_audit_log_with_transaction(
+    audit_log: &AuditLog,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<AuditLog, DBAccessError> {
+    let now = Utc::now().timestamp();
+    let result = sqlx::query_as!(
+        AuditLog,
+        r#"
+            INSERT INTO audit_log (actor_user_id, entity_type, entity_id, action, before_json,
+                after_json, request_id, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING audit_log_id, actor_user_id, entity_type, entity_id, action, before_json,
+                after_json, request_id, created_at
+        "#,
+        audit_log.actor_user_id,
+        audit_log.entity_type,
+        audit_log.entity_id,
+        audit_log.action,
+        audit_log.before_json,
+        audit_log.after_json,
+        audit_log.request_id,
+        now,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::AuditLogCreateFailed,
+            e.to_string()
+        )))
+    })?;
+
+    log::debug!(
+        "Created audit log: {} {} ID = {}",
+        result.action,
+        result.entity_type,
+        result.entity_id
+    );
+
+    Ok(result)
+}
+
+// 同じ操作でまとめて変更した行を1行ずつ記録する。変更前後の内容は記録しない
+pub async fn create_audit_logs_with_transaction(
+    audit_context: &AuditContext,
+    entity_type: AuditEntityType,
+    ids: &[i64],
+    action: AuditAction,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    for id in ids.iter() {
+        let audit_log = AuditLog::new(audit_context, entity_type, *id, action, None, None);
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+    }
+    Ok(())
+}
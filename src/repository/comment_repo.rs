@@ -1,20 +1,31 @@
-use crate::enums::TaskLevel;
+use crate::enums::{AuditAction, AuditEntityType, TaskLevel};
 use crate::errors::db_error::DBAccessError;
 use crate::errors::messages::{ErrorKey, get_error_message};
-use crate::models::Comment;
+use crate::metrics::QueryTimer;
 use crate::models::repository_model::comment::CommentWithUser;
+use crate::models::{AuditContext, AuditLog, Comment, CommentPatch};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
 use crate::repository::task_repo::get_task_by_id_with_transaction;
+use crate::repository::trash_repo::get_deleted_at_with_transaction;
 use crate::repository::user_repo::get_user_by_id_with_transaction;
 use crate::repository::validations::{
     validate_comment_content, validate_comment_id, validate_comment_id_is_none,
     validate_comment_task_id, validate_comment_user_id, validate_pagination,
 };
+use crate::repository::version_repo::{
+    Versioned, bump_version_with_transaction, check_version_with_transaction,
+    get_version_with_transaction,
+};
 use anyhow::Result;
 use chrono::Utc;
+use serde_json::json;
 use sqlx::{Pool, Sqlite, Transaction};
 
 pub struct CommentRepository {
     pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+    expected_versions: Option<Vec<i64>>,
+    member_id: Option<i64>,
 }
 
 pub enum CommentFilterValue {
@@ -24,7 +35,47 @@ pub enum CommentFilterValue {
 
 impl CommentRepository {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+            expected_versions: None,
+            member_id: None,
+        }
+    }
+
+    // 一覧の取得を指定したユーザーがメンバーのプロジェクトのコメントに絞り込む。Noneの場合はすべて
+    pub fn with_member_scope(mut self, member_id: Option<i64>) -> Self {
+        self.member_id = member_id;
+        self
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // If-Matchで指定されたバージョン。更新・削除の前にトランザクション内で確認する
+    pub fn with_expected_versions(mut self, expected_versions: Option<Vec<i64>>) -> Self {
+        self.expected_versions = expected_versions;
+        self
+    }
+
+    fn comment_audit_log(
+        &self,
+        comment_id: i64,
+        action: AuditAction,
+        before: Option<&Comment>,
+        after: Option<&Comment>,
+    ) -> AuditLog {
+        AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::Comment,
+            comment_id,
+            action,
+            before.and_then(to_audit_json),
+            after.and_then(to_audit_json),
+        )
     }
 
     async fn validate_target_user_and_task(
@@ -38,7 +89,7 @@ impl CommentRepository {
         if task.level != TaskLevel::max_level() as i64 {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::CommentToNotMaxLevelTask,
-                format!("ID = {}", comment.task_id),
+                [("id", json!(comment.task_id))],
             )));
         }
         Ok(())
@@ -55,12 +106,13 @@ impl CommentRepository {
             Some(_) => Ok(()),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::CommentIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
 
     pub async fn create_comment(&self, comment: Comment) -> Result<Comment, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "create_comment");
         validate_comment_id_is_none(comment.comment_id)?;
         validate_comment_user_id(comment.user_id)?;
         validate_comment_task_id(comment.task_id)?;
@@ -89,6 +141,14 @@ impl CommentRepository {
 
         match result {
             Ok(comment) => {
+                let audit_log = self.comment_audit_log(
+                    comment.comment_id.unwrap_or_default(),
+                    AuditAction::Create,
+                    None,
+                    Some(&comment),
+                );
+                create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
                 tx.commit().await.map_err(|e| {
                     DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                         ErrorKey::CommentCreateFailed,
@@ -107,6 +167,7 @@ impl CommentRepository {
     }
 
     pub async fn get_all_comments(&self) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_all_comments");
         let result = sqlx::query_as::<_, CommentWithUser>(
             r#"
                 SELECT comments.comment_id, comments.user_id, comments.task_id, comments.content, comments.created_at, comments.updated_at,
@@ -119,9 +180,16 @@ impl CommentRepository {
                 ) AS user
             FROM comments
             INNER JOIN users ON comments.user_id = users.user_id
+            WHERE comments.deleted_at IS NULL
+                AND ($1 IS NULL OR comments.task_id IN (
+                    SELECT tasks.task_id FROM tasks
+                    INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                    WHERE project_members.user_id = $1
+                ))
             ORDER BY comments.comment_id
             "#
         )
+        .bind(self.member_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
@@ -135,11 +203,33 @@ impl CommentRepository {
         Ok(result)
     }
 
+    pub async fn get_comments_count(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comments_count");
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::CommentGetCountFailed,
+                e.to_string()
+            )))
+        })?;
+
+        let result = get_comment_count_with_transaction(self.member_id, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::CommentGetCountFailed,
+                e.to_string()
+            )))
+        })?;
+
+        Ok(result)
+    }
+
     pub async fn get_comments_with_pagination(
         &self,
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comments_with_pagination");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::CommentGetAllFailed,
@@ -147,7 +237,7 @@ impl CommentRepository {
             )))
         })?;
 
-        let count = get_comment_count_with_transaction(&mut tx).await?;
+        let count = get_comment_count_with_transaction(self.member_id, &mut tx).await?;
         validate_pagination(Some(page), Some(page_size), &count)?;
 
         let offset = (*page - 1) * *page_size;
@@ -170,12 +260,19 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
+                WHERE comments.deleted_at IS NULL
+                    AND ($3 IS NULL OR comments.task_id IN (
+                        SELECT tasks.task_id FROM tasks
+                        INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                        WHERE project_members.user_id = $3
+                    ))
                 ORDER BY comments.comment_id
                 LIMIT $1 OFFSET $2
             "#
         )
         .bind(limit)
         .bind(offset)
+        .bind(self.member_id)
         .fetch_all(&mut *tx)
         .await
         .map_err(|e| {
@@ -196,6 +293,7 @@ impl CommentRepository {
     }
 
     pub async fn get_comment_by_id(&self, id: i64) -> Result<CommentWithUser, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comment_by_id");
         validate_comment_id(Some(id))?;
 
         let result = sqlx::query_as::<_, CommentWithUser>(
@@ -210,7 +308,7 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
-                WHERE comment_id = $1
+                WHERE comment_id = $1 AND comments.deleted_at IS NULL
             "#
         )
         .bind(id)
@@ -227,7 +325,7 @@ impl CommentRepository {
             Some(comment) => Ok(comment),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::CommentGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
@@ -238,6 +336,7 @@ impl CommentRepository {
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comments_with_pagination_by_task_id");
         validate_comment_task_id(task_id)?;
 
         let mut tx = self.pool.begin().await.map_err(|e| {
@@ -247,7 +346,8 @@ impl CommentRepository {
             )))
         })?;
 
-        let count = get_comment_count_by_task_id_with_transaction(task_id, &mut tx).await?;
+        let count =
+            get_comment_count_by_task_id_with_transaction(task_id, self.member_id, &mut tx).await?;
         validate_pagination(Some(page), Some(page_size), &count)?;
 
         let offset = (*page - 1) * *page_size;
@@ -270,7 +370,12 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
-                WHERE comments.task_id = $1
+                WHERE comments.task_id = $1 AND comments.deleted_at IS NULL
+                    AND ($4 IS NULL OR comments.task_id IN (
+                        SELECT tasks.task_id FROM tasks
+                        INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                        WHERE project_members.user_id = $4
+                    ))
                 ORDER BY comments.comment_id
                 LIMIT $2 OFFSET $3
             "#
@@ -278,6 +383,7 @@ impl CommentRepository {
         .bind(task_id)
         .bind(limit)
         .bind(offset)
+        .bind(self.member_id)
         .fetch_all(&mut *tx)
         .await
         .map_err(|e| {
@@ -301,6 +407,7 @@ impl CommentRepository {
         &self,
         task_id: i64,
     ) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comment_by_task_id");
         validate_comment_task_id(task_id)?;
 
         sqlx::query_as::<_, CommentWithUser>(
@@ -315,10 +422,16 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
-                WHERE task_id = $1
+                WHERE task_id = $1 AND comments.deleted_at IS NULL
+                    AND ($2 IS NULL OR comments.task_id IN (
+                        SELECT tasks.task_id FROM tasks
+                        INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                        WHERE project_members.user_id = $2
+                    ))
             "#
         )
         .bind(task_id)
+        .bind(self.member_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
@@ -335,6 +448,7 @@ impl CommentRepository {
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comments_with_pagination_by_user_id");
         validate_comment_user_id(user_id)?;
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
@@ -343,7 +457,8 @@ impl CommentRepository {
             )))
         })?;
 
-        let count = get_comment_count_by_user_id_with_transaction(user_id, &mut tx).await?;
+        let count =
+            get_comment_count_by_user_id_with_transaction(user_id, self.member_id, &mut tx).await?;
         validate_pagination(Some(page), Some(page_size), &count)?;
 
         let offset = (*page - 1) * *page_size;
@@ -366,7 +481,12 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
-                WHERE comments.user_id = $1
+                WHERE comments.user_id = $1 AND comments.deleted_at IS NULL
+                    AND ($4 IS NULL OR comments.task_id IN (
+                        SELECT tasks.task_id FROM tasks
+                        INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                        WHERE project_members.user_id = $4
+                    ))
                 ORDER BY comments.comment_id
                 LIMIT $2 OFFSET $3
             "#
@@ -374,6 +494,7 @@ impl CommentRepository {
         .bind(user_id)
         .bind(limit)
         .bind(offset)
+        .bind(self.member_id)
         .fetch_all(&mut *tx)
         .await
         .map_err(|e| {
@@ -397,6 +518,7 @@ impl CommentRepository {
         &self,
         user_id: i64,
     ) -> Result<Vec<CommentWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "get_comment_by_user_id");
         validate_comment_user_id(user_id)?;
         sqlx::query_as::<_, CommentWithUser>(
             r#"
@@ -410,10 +532,16 @@ impl CommentRepository {
                 ) AS user
                 FROM comments
                 INNER JOIN users ON comments.user_id = users.user_id
-                WHERE comments.user_id = $1
+                WHERE comments.user_id = $1 AND comments.deleted_at IS NULL
+                    AND ($2 IS NULL OR comments.task_id IN (
+                        SELECT tasks.task_id FROM tasks
+                        INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                        WHERE project_members.user_id = $2
+                    ))
             "#
         )
         .bind(user_id)
+        .bind(self.member_id)
         .fetch_all(&self.pool)
         .await
         .map_err(|e| {
@@ -424,7 +552,11 @@ impl CommentRepository {
         })
     }
 
-    pub async fn update_comment(&self, comment: Comment) -> Result<Comment, DBAccessError> {
+    pub async fn update_comment(
+        &self,
+        comment: Comment,
+    ) -> Result<Versioned<Comment>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "update_comment");
         validate_comment_id(comment.comment_id)?;
         validate_comment_user_id(comment.user_id)?;
         validate_comment_task_id(comment.task_id)?;
@@ -432,6 +564,14 @@ impl CommentRepository {
 
         let mut tx = self.pool.begin().await?;
 
+        let version = bump_version_with_transaction(
+            AuditEntityType::Comment,
+            comment.comment_id.unwrap_or_default(),
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
         self.validate_target_user_and_task(&comment, &mut tx)
             .await?;
 
@@ -440,12 +580,13 @@ impl CommentRepository {
             None => {
                 return Err(DBAccessError::ValidationError(get_error_message(
                     ErrorKey::CommentIdInvalid,
-                    format!("ID = {:?}", comment.comment_id),
+                    [("id", json!(comment.comment_id))],
                 )));
             }
         };
         self.validate_comment_id_is_exist(comment_id, &mut tx)
             .await?;
+        let before = get_comment_by_id_with_transaction(comment_id, &mut tx).await?;
 
         let now = Utc::now().timestamp();
         let result = sqlx::query_as!(
@@ -453,7 +594,7 @@ impl CommentRepository {
             r#"
                 UPDATE comments
                 SET content = $1, user_id = $2, task_id = $3, updated_at = $4
-                WHERE comment_id = $5
+                WHERE comment_id = $5 AND deleted_at IS NULL
                 RETURNING comment_id, user_id, task_id, content, created_at, updated_at
             "#,
             comment.content,
@@ -467,13 +608,121 @@ impl CommentRepository {
 
         match result {
             Ok(comment) => {
+                let audit_log = self.comment_audit_log(
+                    comment_id,
+                    AuditAction::Update,
+                    before.as_ref(),
+                    Some(&comment),
+                );
+                create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
                 tx.commit().await.map_err(|e| {
                     DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                         ErrorKey::CommentUpdateFailed,
                         e.to_string()
                     )))
                 })?;
-                Ok(comment)
+                Ok(Versioned {
+                    value: comment,
+                    version: version.unwrap_or_default(),
+                })
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(DBAccessError::QueryError(anyhow::anyhow!(
+                    get_error_message(ErrorKey::CommentUpdateFailed, e.to_string())
+                )))
+            }
+        }
+    }
+
+    // 指定されたフィールドのみ検証して更新する
+    pub async fn patch_comment(
+        &self,
+        id: i64,
+        patch: CommentPatch,
+    ) -> Result<Versioned<Comment>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "patch_comment");
+        validate_comment_id(Some(id))?;
+        if let Some(content) = &patch.content {
+            validate_comment_content(content)?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        // 変更がない場合はバージョンを進めない
+        let version = match &patch.content {
+            Some(_) => {
+                bump_version_with_transaction(
+                    AuditEntityType::Comment,
+                    id,
+                    self.expected_versions.as_deref(),
+                    &mut tx,
+                )
+                .await?
+            }
+            None => {
+                check_version_with_transaction(
+                    AuditEntityType::Comment,
+                    id,
+                    self.expected_versions.as_deref(),
+                    &mut tx,
+                )
+                .await?
+            }
+        };
+
+        let before = match get_comment_by_id_with_transaction(id, &mut tx).await? {
+            Some(before) => before,
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::CommentIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+        let content = match &patch.content {
+            Some(content) => content,
+            None => {
+                return Ok(Versioned {
+                    value: before,
+                    version: version.unwrap_or_default(),
+                });
+            }
+        };
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query_as!(
+            Comment,
+            r#"
+                UPDATE comments
+                SET content = $1, updated_at = $2
+                WHERE comment_id = $3 AND deleted_at IS NULL
+                RETURNING comment_id, user_id, task_id, content, created_at, updated_at
+            "#,
+            content,
+            now,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await;
+
+        match result {
+            Ok(comment) => {
+                let audit_log =
+                    self.comment_audit_log(id, AuditAction::Update, Some(&before), Some(&comment));
+                create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+                tx.commit().await.map_err(|e| {
+                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                        ErrorKey::CommentUpdateFailed,
+                        e.to_string()
+                    )))
+                })?;
+                Ok(Versioned {
+                    value: comment,
+                    version: version.unwrap_or_default(),
+                })
             }
             Err(e) => {
                 let _ = tx.rollback().await;
@@ -485,16 +734,33 @@ impl CommentRepository {
     }
 
     pub async fn delete_comment(&self, id: i64) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("comment", "delete_comment");
         validate_comment_id(Some(id))?;
 
-        let result = sqlx::query!(
+        let mut tx = self.pool.begin().await?;
+
+        bump_version_with_transaction(
+            AuditEntityType::Comment,
+            id,
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        // ゴミ箱に移動する
+        let now = Utc::now().timestamp();
+        let result = sqlx::query_as!(
+            Comment,
             r#"
-                DELETE FROM comments
-                WHERE comment_id = $1
+                UPDATE comments
+                SET deleted_at = $2
+                WHERE comment_id = $1 AND deleted_at IS NULL
+                RETURNING comment_id, user_id, task_id, content, created_at, updated_at
             "#,
             id,
+            now,
         )
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
@@ -503,15 +769,86 @@ impl CommentRepository {
             )))
         })?;
 
-        if result.rows_affected() == 0 {
-            return Err(DBAccessError::NotFoundError(get_error_message(
-                ErrorKey::CommentDeleteFailedByIdNotFound,
-                format!("ID = {}", id),
-            )));
-        }
+        let deleted = match result {
+            Some(comment) => comment,
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::CommentDeleteFailedByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+
+        let audit_log = self.comment_audit_log(id, AuditAction::Delete, Some(&deleted), None);
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::CommentDeleteFailed,
+                e.to_string()
+            )))
+        })?;
 
         Ok(())
     }
+
+    // ゴミ箱から戻す。対象のタスクがゴミ箱にある場合は戻せない
+    pub async fn restore_comment(&self, id: i64) -> Result<Versioned<Comment>, DBAccessError> {
+        let _timer = QueryTimer::start("comment", "restore_comment");
+        validate_comment_id(Some(id))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        get_deleted_at_with_transaction(AuditEntityType::Comment, id, &mut tx).await?;
+        let restored = sqlx::query_as!(
+            Comment,
+            r#"
+                UPDATE comments
+                SET deleted_at = NULL
+                WHERE comment_id = $1
+                RETURNING comment_id, user_id, task_id, content, created_at, updated_at
+            "#,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TrashRestoreFailed,
+                e.to_string()
+            )))
+        })?;
+
+        match get_task_by_id_with_transaction(restored.task_id, &mut tx).await {
+            Ok(_) => {}
+            Err(DBAccessError::NotFoundError(_)) => {
+                return Err(DBAccessError::ConflictError(get_error_message(
+                    ErrorKey::TrashParentDeleted,
+                    [
+                        ("parent_type", json!("task")),
+                        ("parent_id", json!(restored.task_id)),
+                    ],
+                )));
+            }
+            Err(e) => return Err(e),
+        }
+
+        let audit_log = self.comment_audit_log(id, AuditAction::Restore, None, Some(&restored));
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+        let version = get_version_with_transaction(&AuditEntityType::Comment, id, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TrashRestoreFailed,
+                e.to_string()
+            )))
+        })?;
+
+        Ok(Versioned {
+            value: restored,
+            version: version.unwrap_or_default(),
+        })
+    }
 }
 
 pub async fn get_comment_by_id_with_transaction(
@@ -524,7 +861,7 @@ pub async fn get_comment_by_id_with_transaction(
         r#"
             SELECT comment_id, user_id, task_id, content, created_at, updated_at
             FROM comments
-            WHERE comment_id = $1
+            WHERE comment_id = $1 AND deleted_at IS NULL
         "#,
         id,
     )
@@ -538,13 +875,22 @@ pub async fn get_comment_by_id_with_transaction(
     })
 }
 
+// member_idを指定した場合はそのユーザーがメンバーのプロジェクトのコメントだけを数える
 pub async fn get_comment_count_with_transaction(
+    member_id: Option<i64>,
     transaction: &mut Transaction<'_, Sqlite>,
 ) -> Result<i64, DBAccessError> {
     let result = sqlx::query_scalar!(
         r#"
             SELECT COUNT(*) FROM comments
+            WHERE deleted_at IS NULL
+                AND ($1 IS NULL OR comments.task_id IN (
+                    SELECT tasks.task_id FROM tasks
+                    INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                    WHERE project_members.user_id = $1
+                ))
         "#,
+        member_id,
     )
     .fetch_one(&mut **transaction)
     .await
@@ -561,14 +907,22 @@ pub async fn get_comment_count_with_transaction(
 
 pub async fn get_comment_count_by_task_id_with_transaction(
     task_id: i64,
+    member_id: Option<i64>,
     transaction: &mut Transaction<'_, Sqlite>,
 ) -> Result<i64, DBAccessError> {
     validate_comment_task_id(task_id)?;
     let result = sqlx::query_scalar!(
         r#"
-            SELECT COUNT(*) FROM comments WHERE task_id = $1
-            "#,
+            SELECT COUNT(*) FROM comments
+            WHERE task_id = $1 AND deleted_at IS NULL
+                AND ($2 IS NULL OR comments.task_id IN (
+                    SELECT tasks.task_id FROM tasks
+                    INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                    WHERE project_members.user_id = $2
+                ))
+        "#,
         task_id,
+        member_id,
     )
     .fetch_one(&mut **transaction)
     .await
@@ -585,14 +939,22 @@ pub async fn get_comment_count_by_task_id_with_transaction(
 
 pub async fn get_comment_count_by_user_id_with_transaction(
     user_id: i64,
+    member_id: Option<i64>,
     transaction: &mut Transaction<'_, Sqlite>,
 ) -> Result<i64, DBAccessError> {
     validate_comment_user_id(user_id)?;
     let result = sqlx::query_scalar!(
         r#"
-            SELECT COUNT(*) FROM comments WHERE user_id = $1
-            "#,
+            SELECT COUNT(*) FROM comments
+            WHERE user_id = $1 AND deleted_at IS NULL
+                AND ($2 IS NULL OR comments.task_id IN (
+                    SELECT tasks.task_id FROM tasks
+                    INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                    WHERE project_members.user_id = $2
+                ))
+        "#,
         user_id,
+        member_id,
     )
     .fetch_one(&mut **transaction)
     .await
@@ -0,0 +1,88 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{ForeignKeyViolation, PoolStatus};
+use sqlx::{Pool, Sqlite};
+
+pub struct DatabaseRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl DatabaseRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    // 問題がなければ空のVecを返す
+    pub async fn check_integrity(&self) -> Result<Vec<String>, DBAccessError> {
+        let _timer = QueryTimer::start("database", "check_integrity");
+        let result = sqlx::query_scalar::<_, String>("PRAGMA integrity_check")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                    ErrorKey::DatabaseIntegrityCheckFailed,
+                    e.to_string()
+                )))
+            })?;
+
+        Ok(result.into_iter().filter(|row| row != "ok").collect())
+    }
+
+    pub async fn check_foreign_keys(&self) -> Result<Vec<ForeignKeyViolation>, DBAccessError> {
+        let _timer = QueryTimer::start("database", "check_foreign_keys");
+        let result = sqlx::query_as::<_, ForeignKeyViolation>("PRAGMA foreign_key_check")
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| {
+                DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                    ErrorKey::DatabaseForeignKeyCheckFailed,
+                    e.to_string()
+                )))
+            })?;
+
+        Ok(result)
+    }
+
+    // 共有ロックが必要なクエリでロック中のデータベースも検出する
+    pub async fn ping(&self) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("database", "ping");
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM sqlite_master")
+            .fetch_one(&self.pool)
+            .await
+            .map_err(|e| {
+                DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                    ErrorKey::HealthDatabasePingFailed,
+                    e.to_string()
+                )))
+            })?;
+
+        Ok(())
+    }
+
+    // バイト単位で返す
+    pub async fn get_database_size(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("database", "get_database_size");
+        let result = sqlx::query_scalar::<_, i64>(
+            "SELECT page_count * page_size FROM pragma_page_count(), pragma_page_size()",
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::HealthDatabaseSizeFailed,
+                e.to_string()
+            )))
+        })?;
+
+        Ok(result)
+    }
+
+    pub fn get_pool_status(&self) -> PoolStatus {
+        PoolStatus {
+            size: self.pool.size(),
+            idle: self.pool.num_idle() as u32,
+            max_connections: self.pool.options().get_max_connections(),
+        }
+    }
+}
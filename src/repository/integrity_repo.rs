@@ -0,0 +1,523 @@
+use crate::enums::{AuditAction, AuditEntityType, IntegrityCheck, TaskLevel, TaskStatus};
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{AuditContext, AuditLog, IntegrityIssue, IntegrityReport, Task};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
+use crate::repository::task_history_repo::{
+    build_task_histories, create_task_history_with_transaction,
+};
+use chrono::Utc;
+use sqlx::{Pool, Sqlite, Transaction};
+use std::collections::{HashMap, HashSet};
+
+// 自動で行う修正。同じタスクに複数の修正がある場合も、それぞれ対象の列だけを更新する
+enum IntegrityFix {
+    DeleteUserAssign(i64),
+    DeleteComment(i64),
+    SetTaskProjectId(i64, i64),
+    SetTaskStatus(i64, i64),
+}
+
+impl IntegrityFix {
+    fn describe(&self) -> String {
+        match self {
+            IntegrityFix::DeleteUserAssign(_) | IntegrityFix::DeleteComment(_) => {
+                "Delete".to_string()
+            }
+            IntegrityFix::SetTaskProjectId(_, project_id) => {
+                format!("Set Project ID = {}", project_id)
+            }
+            IntegrityFix::SetTaskStatus(_, status) => format!("Set Status = {}", status),
+        }
+    }
+}
+
+// タスクかユーザーが存在しない割り当て・コメントの行
+struct OrphanedRow {
+    id: i64,
+    user_id: i64,
+    task_id: i64,
+    task_missing: bool,
+    user_missing: bool,
+}
+
+impl OrphanedRow {
+    fn detail(&self) -> String {
+        let missing: Vec<&str> = [("task", self.task_missing), ("user", self.user_missing)]
+            .into_iter()
+            .filter(|(_, missing)| *missing)
+            .map(|(name, _)| name)
+            .collect();
+        format!(
+            "Task ID = {}, User ID = {}, Missing = {}",
+            self.task_id,
+            self.user_id,
+            missing.join(" and ")
+        )
+    }
+}
+
+struct DuplicateRow {
+    id: i64,
+    user_id: i64,
+    task_id: i64,
+    kept_id: i64,
+}
+
+pub struct IntegrityRepository {
+    pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+}
+
+impl IntegrityRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // 問題を探すだけで、データベースは変更しない
+    pub async fn check_integrity_issues(&self) -> Result<IntegrityReport, DBAccessError> {
+        let _timer = QueryTimer::start("integrity", "check_integrity_issues");
+        let mut tx = self.pool.begin().await?;
+
+        let issues = find_integrity_issues_with_transaction(&mut tx)
+            .await?
+            .into_iter()
+            .map(|(issue, _)| issue)
+            .collect();
+
+        Ok(IntegrityReport::new(issues, false))
+    }
+
+    // 自動で直せる問題を修正する。dry_runの場合も同じ修正を実行してからロールバックするため、
+    // 修正できない場合はどちらもエラーになる
+    pub async fn repair_integrity_issues(
+        &self,
+        dry_run: bool,
+    ) -> Result<IntegrityReport, DBAccessError> {
+        let _timer = QueryTimer::start("integrity", "repair_integrity_issues");
+        let mut tx = self.pool.begin().await?;
+
+        let mut issues = Vec::new();
+        for (mut issue, fix) in find_integrity_issues_with_transaction(&mut tx).await? {
+            if let Some(fix) = fix {
+                self.apply_fix_with_transaction(&fix, &mut tx).await?;
+                issue.fixed = !dry_run;
+            }
+            issues.push(issue);
+        }
+
+        match dry_run {
+            true => tx.rollback().await.map_err(repair_error)?,
+            false => tx.commit().await.map_err(repair_error)?,
+        }
+
+        let report = IntegrityReport::new(issues, dry_run);
+        log::info!(
+            "Repaired integrity issues: Issues = {}, Fixed = {}, Dry Run = {}",
+            report.issues.len(),
+            report.fixed,
+            dry_run
+        );
+
+        Ok(report)
+    }
+
+    async fn apply_fix_with_transaction(
+        &self,
+        fix: &IntegrityFix,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), DBAccessError> {
+        match fix {
+            IntegrityFix::DeleteUserAssign(id) => {
+                sqlx::query!("DELETE FROM user_assign WHERE user_assign_id = $1", id)
+                    .execute(&mut **tx)
+                    .await
+                    .map_err(repair_error)?;
+                self.write_purge_audit_log(AuditEntityType::UserAssign, *id, tx)
+                    .await
+            }
+            IntegrityFix::DeleteComment(id) => {
+                sqlx::query!("DELETE FROM comments WHERE comment_id = $1", id)
+                    .execute(&mut **tx)
+                    .await
+                    .map_err(repair_error)?;
+                self.write_purge_audit_log(AuditEntityType::Comment, *id, tx)
+                    .await
+            }
+            IntegrityFix::SetTaskProjectId(task_id, project_id) => {
+                let before = get_any_task_with_transaction(*task_id, tx).await?;
+                let mut after = before.clone();
+                after.project_id = *project_id;
+                self.write_task_fix(&before, &after, tx).await
+            }
+            IntegrityFix::SetTaskStatus(task_id, status) => {
+                let before = get_any_task_with_transaction(*task_id, tx).await?;
+                let mut after = before.clone();
+                after.status = *status;
+                self.write_task_fix(&before, &after, tx).await
+            }
+        }
+    }
+
+    async fn write_purge_audit_log(
+        &self,
+        entity_type: AuditEntityType,
+        id: i64,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), DBAccessError> {
+        let audit_log = AuditLog::new(
+            &self.audit_context,
+            entity_type,
+            id,
+            AuditAction::Purge,
+            None,
+            None,
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+        Ok(())
+    }
+
+    async fn write_task_fix(
+        &self,
+        before: &Task,
+        after: &Task,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(), DBAccessError> {
+        let now = Utc::now().timestamp();
+        let task_id = before.task_id.unwrap_or_default();
+        let after = sqlx::query_as!(
+            Task,
+            r#"
+                UPDATE tasks
+                SET project_id = $1, status = $2, updated_at = $3, version = version + 1
+                WHERE task_id = $4
+                RETURNING task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
+            "#,
+            after.project_id,
+            after.status,
+            now,
+            task_id,
+        )
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(repair_error)?;
+
+        let audit_log = AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::Task,
+            task_id,
+            AuditAction::Update,
+            to_audit_json(before),
+            to_audit_json(&after),
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        let histories = build_task_histories(before, &after, self.audit_context.actor_user_id);
+        for history in histories.iter() {
+            create_task_history_with_transaction(history, tx).await?;
+        }
+
+        Ok(())
+    }
+}
+
+fn check_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::IntegrityCheckFailed,
+        e.to_string()
+    )))
+}
+
+fn repair_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::IntegrityRepairFailed,
+        e.to_string()
+    )))
+}
+
+// ゴミ箱にあるものも含めて調べる。ゴミ箱から戻すと同じ問題が現れるため
+async fn find_integrity_issues_with_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<(IntegrityIssue, Option<IntegrityFix>)>, DBAccessError> {
+    let mut issues = find_task_issues_with_transaction(tx).await?;
+
+    for row in get_orphaned_user_assigns_with_transaction(tx).await? {
+        let fix = IntegrityFix::DeleteUserAssign(row.id);
+        let issue = IntegrityIssue::new(
+            IntegrityCheck::OrphanedUserAssign,
+            AuditEntityType::UserAssign,
+            row.id,
+            row.detail(),
+            Some(fix.describe()),
+        );
+        issues.push((issue, Some(fix)));
+    }
+
+    for row in get_orphaned_comments_with_transaction(tx).await? {
+        let fix = IntegrityFix::DeleteComment(row.id);
+        let issue = IntegrityIssue::new(
+            IntegrityCheck::OrphanedComment,
+            AuditEntityType::Comment,
+            row.id,
+            row.detail(),
+            Some(fix.describe()),
+        );
+        issues.push((issue, Some(fix)));
+    }
+
+    // 最も古い割り当てを残す
+    for row in get_duplicate_user_assigns_with_transaction(tx).await? {
+        let fix = IntegrityFix::DeleteUserAssign(row.id);
+        let issue = IntegrityIssue::new(
+            IntegrityCheck::DuplicateUserAssign,
+            AuditEntityType::UserAssign,
+            row.id,
+            format!(
+                "User ID = {}, Task ID = {}, Kept ID = {}",
+                row.user_id, row.task_id, row.kept_id
+            ),
+            Some(fix.describe()),
+        );
+        issues.push((issue, Some(fix)));
+    }
+
+    Ok(issues)
+}
+
+// 親子関係の問題は正しい親を決められないため自動では直さない
+async fn find_task_issues_with_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<(IntegrityIssue, Option<IntegrityFix>)>, DBAccessError> {
+    let tasks = sqlx::query_as!(
+        Task,
+        r#"
+            SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
+            FROM tasks
+            ORDER BY task_id
+        "#,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(check_error)?;
+
+    let project_ids: HashSet<i64> =
+        sqlx::query_scalar!(r#"SELECT project_id AS "project_id!" FROM projects"#)
+            .fetch_all(&mut **tx)
+            .await
+            .map_err(check_error)?
+            .into_iter()
+            .collect();
+
+    let tasks_by_id: HashMap<i64, &Task> = tasks
+        .iter()
+        .map(|task| (task.task_id.unwrap_or_default(), task))
+        .collect();
+
+    let mut issues = Vec::new();
+    let mut push =
+        |check: IntegrityCheck, task_id: i64, detail: String, fix: Option<IntegrityFix>| {
+            let issue = IntegrityIssue::new(
+                check,
+                AuditEntityType::Task,
+                task_id,
+                detail,
+                fix.as_ref().map(IntegrityFix::describe),
+            );
+            issues.push((issue, fix));
+        };
+
+    for task in tasks.iter() {
+        let task_id = task.task_id.unwrap_or_default();
+
+        if TaskStatus::from_int(task.status).is_err() {
+            push(
+                IntegrityCheck::InvalidStatus,
+                task_id,
+                format!("Status = {}", task.status),
+                Some(IntegrityFix::SetTaskStatus(
+                    task_id,
+                    TaskStatus::NotStarted.to_int(),
+                )),
+            );
+        }
+
+        if TaskLevel::from_int(task.level).is_err() {
+            push(
+                IntegrityCheck::InvalidLevel,
+                task_id,
+                format!("Level = {}", task.level),
+                None,
+            );
+            continue;
+        }
+
+        let Some(parent_id) = task.parent_id else {
+            if task.level != TaskLevel::Major.to_int() {
+                push(
+                    IntegrityCheck::ParentLevel,
+                    task_id,
+                    format!("Level = {}, Parent ID = None", task.level),
+                    None,
+                );
+            }
+            continue;
+        };
+
+        if parent_id == task_id {
+            push(
+                IntegrityCheck::ParentLevel,
+                task_id,
+                format!("Level = {}, Parent ID = {} (self)", task.level, parent_id),
+                None,
+            );
+            continue;
+        }
+
+        let Some(parent) = tasks_by_id.get(&parent_id) else {
+            push(
+                IntegrityCheck::ParentLevel,
+                task_id,
+                format!(
+                    "Level = {}, Parent ID = {} (missing)",
+                    task.level, parent_id
+                ),
+                None,
+            );
+            continue;
+        };
+
+        let level_matches = parent.level == task.level - 1;
+        if !level_matches {
+            push(
+                IntegrityCheck::ParentLevel,
+                task_id,
+                format!(
+                    "Level = {}, Parent ID = {}, Parent Level = {}",
+                    task.level, parent_id, parent.level
+                ),
+                None,
+            );
+        }
+
+        if parent.project_id != task.project_id {
+            // 親が正しくない場合や、親のプロジェクトが存在しない場合は親に合わせない
+            let fix = (level_matches && project_ids.contains(&parent.project_id))
+                .then_some(IntegrityFix::SetTaskProjectId(task_id, parent.project_id));
+            push(
+                IntegrityCheck::ProjectMismatch,
+                task_id,
+                format!(
+                    "Project ID = {}, Parent ID = {}, Parent Project ID = {}",
+                    task.project_id, parent_id, parent.project_id
+                ),
+                fix,
+            );
+        }
+    }
+
+    Ok(issues)
+}
+
+async fn get_orphaned_user_assigns_with_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<OrphanedRow>, DBAccessError> {
+    sqlx::query_as!(
+        OrphanedRow,
+        r#"
+            SELECT
+                user_assign.user_assign_id AS "id!",
+                user_assign.user_id AS "user_id!",
+                user_assign.task_id AS "task_id!",
+                tasks.task_id IS NULL AS "task_missing!: bool",
+                users.user_id IS NULL AS "user_missing!: bool"
+            FROM user_assign
+            LEFT JOIN tasks ON user_assign.task_id = tasks.task_id
+            LEFT JOIN users ON user_assign.user_id = users.user_id
+            WHERE tasks.task_id IS NULL OR users.user_id IS NULL
+            ORDER BY user_assign.user_assign_id
+        "#,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(check_error)
+}
+
+async fn get_orphaned_comments_with_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<OrphanedRow>, DBAccessError> {
+    sqlx::query_as!(
+        OrphanedRow,
+        r#"
+            SELECT
+                comments.comment_id AS "id!",
+                comments.user_id AS "user_id!",
+                comments.task_id AS "task_id!",
+                tasks.task_id IS NULL AS "task_missing!: bool",
+                users.user_id IS NULL AS "user_missing!: bool"
+            FROM comments
+            LEFT JOIN tasks ON comments.task_id = tasks.task_id
+            LEFT JOIN users ON comments.user_id = users.user_id
+            WHERE tasks.task_id IS NULL OR users.user_id IS NULL
+            ORDER BY comments.comment_id
+        "#,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(check_error)
+}
+
+// ゴミ箱にない割り当てのみ。削除した割り当てを作り直すのは正しい操作のため
+async fn get_duplicate_user_assigns_with_transaction(
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<DuplicateRow>, DBAccessError> {
+    sqlx::query_as!(
+        DuplicateRow,
+        r#"
+            SELECT
+                duplicate.user_assign_id AS "id!",
+                duplicate.user_id AS "user_id!",
+                duplicate.task_id AS "task_id!",
+                MIN(kept.user_assign_id) AS "kept_id!: i64"
+            FROM user_assign AS duplicate
+            INNER JOIN user_assign AS kept
+                ON duplicate.user_id = kept.user_id
+                AND duplicate.task_id = kept.task_id
+                AND kept.user_assign_id < duplicate.user_assign_id
+                AND kept.deleted_at IS NULL
+            WHERE duplicate.deleted_at IS NULL
+            GROUP BY duplicate.user_assign_id
+            ORDER BY duplicate.user_assign_id
+        "#,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(check_error)
+}
+
+// ゴミ箱にあるタスクも取得する
+async fn get_any_task_with_transaction(
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Task, DBAccessError> {
+    sqlx::query_as!(
+        Task,
+        r#"
+            SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
+            FROM tasks
+            WHERE task_id = $1
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(repair_error)
+}
@@ -0,0 +1,165 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::MigrationStatus;
+use serde_json::json;
+use sqlx::migrate::{Migrate, Migrator};
+use sqlx::{Pool, Sqlite};
+use std::collections::HashMap;
+
+// migrationsディレクトリをバイナリに組み込む
+pub static MIGRATOR: Migrator = sqlx::migrate!("./migrations");
+
+pub struct MigrationRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl MigrationRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    // バージョンの昇順に返す
+    pub async fn get_migration_status(&self) -> Result<Vec<MigrationStatus>, DBAccessError> {
+        let _timer = QueryTimer::start("migration", "get_migration_status");
+        let status_error = |e: sqlx::migrate::MigrateError| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::MigrationStatusFailed,
+                e.to_string()
+            )))
+        };
+
+        let mut conn = self.pool.acquire().await?;
+        // ヘルスチェックからも呼ばれるため管理テーブルは作成しない。
+        // テーブルがなければすべて未適用として扱う
+        let table_exists = sqlx::query_scalar::<_, bool>(
+            "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = '_sqlx_migrations')",
+        )
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::MigrationStatusFailed,
+                e.to_string()
+            )))
+        })?;
+
+        let mut applied_migrations = HashMap::new();
+        if table_exists {
+            if let Some(version) = conn.dirty_version().await.map_err(status_error)? {
+                return Err(DBAccessError::QueryError(anyhow::anyhow!(
+                    get_error_message(ErrorKey::MigrationDirty, [("version", json!(version))])
+                )));
+            }
+
+            applied_migrations = conn
+                .list_applied_migrations()
+                .await
+                .map_err(status_error)?
+                .into_iter()
+                .map(|migration| (migration.version, migration.checksum))
+                .collect::<HashMap<_, _>>();
+        }
+
+        let result = MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| {
+                let checksum = applied_migrations.get(&migration.version);
+                MigrationStatus {
+                    version: migration.version,
+                    description: migration.description.to_string(),
+                    applied: checksum.is_some(),
+                    checksum_mismatch: checksum
+                        .is_some_and(|checksum| *checksum != migration.checksum),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        log::debug!("Get migration status: {:?}", result.len());
+
+        Ok(result)
+    }
+
+    // 適用済みのマイグレーションがない場合はNoneを返す
+    pub async fn get_current_version(&self) -> Result<Option<i64>, DBAccessError> {
+        let _timer = QueryTimer::start("migration", "get_current_version");
+        let status = self.get_migration_status().await?;
+        Ok(current_version(&status))
+    }
+
+    // 新たに適用したバージョンを返す
+    pub async fn run_pending_migrations(&self) -> Result<Vec<i64>, DBAccessError> {
+        let _timer = QueryTimer::start("migration", "run_pending_migrations");
+        let pending = self
+            .get_migration_status()
+            .await?
+            .into_iter()
+            .filter(|migration| !migration.applied)
+            .map(|migration| migration.version)
+            .collect::<Vec<_>>();
+
+        MIGRATOR.run(&self.pool).await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::MigrationRunFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!("Applied migrations: {:?}", pending);
+
+        Ok(pending)
+    }
+
+    // targetより新しいマイグレーションを取り消す。
+    // targetを省略した場合は最新の1件のみ取り消す
+    pub async fn revert_migrations(&self, target: Option<i64>) -> Result<Vec<i64>, DBAccessError> {
+        let _timer = QueryTimer::start("migration", "revert_migrations");
+        let status = self.get_migration_status().await?;
+        let applied = status
+            .iter()
+            .filter(|migration| migration.applied)
+            .map(|migration| migration.version)
+            .collect::<Vec<_>>();
+
+        let target = match target {
+            Some(target) => target,
+            None => applied.iter().rev().nth(1).copied().unwrap_or(0),
+        };
+        if target != 0 && !applied.contains(&target) {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::MigrationTargetInvalid,
+                [("target", json!(target))],
+            )));
+        }
+
+        MIGRATOR.undo(&self.pool, target).await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::MigrationRevertFailed,
+                e.to_string()
+            )))
+        })?;
+
+        let reverted = applied
+            .into_iter()
+            .rev()
+            .filter(|version| *version > target)
+            .collect::<Vec<_>>();
+        log::info!("Reverted migrations: {:?}", reverted);
+
+        Ok(reverted)
+    }
+}
+
+pub fn current_version(status: &[MigrationStatus]) -> Option<i64> {
+    status
+        .iter()
+        .filter(|migration| migration.applied)
+        .map(|migration| migration.version)
+        .max()
+}
+
+// バイナリに組み込まれた最新のバージョン
+pub fn latest_version(status: &[MigrationStatus]) -> Option<i64> {
+    status.iter().map(|migration| migration.version).max()
+}
@@ -1,10 +1,22 @@
+pub mod api_token_repo;
+pub mod audit_log_repo;
 pub mod comment_repo;
+pub mod database_repo;
+pub mod integrity_repo;
+pub mod migration_repo;
+pub mod password;
+pub mod project_member_repo;
 pub mod project_repo;
+pub mod session_repo;
+pub mod task_history_repo;
 pub mod task_repo;
+pub mod task_tree_repo;
 pub mod task_user_repo;
+pub mod trash_repo;
 pub mod user_assign_repo;
 pub mod user_repo;
 pub mod validations;
+pub mod version_repo;
 
 #[cfg(test)]
 mod tests;
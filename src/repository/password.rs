@@ -0,0 +1,167 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::settings::server_config;
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::{Algorithm, Argon2, Params, Version};
+use once_cell::sync::{Lazy, OnceCell};
+use regex::Regex;
+use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
+
+// ハッシュ文字列はPHC形式（$argon2id$v=19$m=...,t=...,p=...$salt$hash）で保存する。
+// 以前のバージョンはソルトなしのSHA-256（16進数64文字）を保存していたため、
+// 検証時はどちらの形式も受け付ける。
+
+// 存在しないユーザーの検証に使うハッシュ。最初に使うときに現在の設定で作成する
+static DUMMY_PASSWORD_HASH: OnceCell<String> = OnceCell::new();
+
+// 以前のバージョンが保存していたSHA-256のハッシュ
+static LEGACY_HASH: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-f0-9]{64}$").unwrap());
+
+fn argon2() -> Result<Argon2<'static>, DBAccessError> {
+    let config = &server_config().password_hash;
+    let params = Params::new(
+        config.memory_kib,
+        config.iterations,
+        config.parallelism,
+        None,
+    )
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::PasswordHashParamsInvalid,
+            e.to_string()
+        )))
+    })?;
+
+    Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+}
+
+pub fn hash_password(password: &str) -> Result<String, DBAccessError> {
+    if password.is_empty() {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::UserPasswordEmpty,
+            "".to_string(),
+        )));
+    }
+
+    let salt = SaltString::generate(&mut OsRng);
+    let hash = argon2()?
+        .hash_password(password.as_bytes(), &salt)
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::PasswordHashFailed,
+                e.to_string()
+            )))
+        })?;
+
+    Ok(hash.to_string())
+}
+
+pub fn is_legacy_hash(password_hash: &str) -> bool {
+    LEGACY_HASH.is_match(password_hash)
+}
+
+pub fn is_phc_hash(password_hash: &str) -> bool {
+    match PasswordHash::new(password_hash) {
+        Ok(hash) => Algorithm::try_from(hash.algorithm).is_ok(),
+        Err(_) => false,
+    }
+}
+
+pub fn verify_password(password: &str, password_hash: &str) -> Result<bool, DBAccessError> {
+    if is_legacy_hash(password_hash) {
+        // 一致しない位置によって比較にかかる時間が変わらないようにする
+        let legacy = format!("{:x}", Sha256::digest(password.as_bytes()));
+        return Ok(legacy.as_bytes().ct_eq(password_hash.as_bytes()).into());
+    }
+
+    // 形式が不正なハッシュは一致しないものとして扱う
+    let parsed = match PasswordHash::new(password_hash) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            log::warn!(
+                "{}",
+                get_error_message(ErrorKey::PasswordHashFormatInvalid, e.to_string())
+            );
+            return Ok(false);
+        }
+    };
+
+    // パラメータはハッシュ文字列から読み取られるため、作業量を変更しても古いハッシュを検証できる
+    match argon2()?.verify_password(password.as_bytes(), &parsed) {
+        Ok(()) => Ok(true),
+        Err(argon2::password_hash::Error::Password) => Ok(false),
+        Err(e) => Err(DBAccessError::QueryError(anyhow::anyhow!(
+            get_error_message(ErrorKey::PasswordVerifyFailed, e.to_string())
+        ))),
+    }
+}
+
+// SHA-256のハッシュ、または現在の設定と異なるパラメータのハッシュは再ハッシュが必要
+pub fn needs_rehash(password_hash: &str) -> bool {
+    if is_legacy_hash(password_hash) {
+        return true;
+    }
+
+    let parsed = match PasswordHash::new(password_hash) {
+        Ok(parsed) => parsed,
+        Err(_) => return true,
+    };
+
+    if Algorithm::try_from(parsed.algorithm) != Ok(Algorithm::Argon2id) {
+        return true;
+    }
+
+    let config = &server_config().password_hash;
+    match Params::try_from(&parsed) {
+        Ok(params) => {
+            params.m_cost() != config.memory_kib
+                || params.t_cost() != config.iterations
+                || params.p_cost() != config.parallelism
+        }
+        Err(_) => true,
+    }
+}
+
+// ユーザーが存在しない場合も、存在する場合と同じだけ時間がかかるようにダミーのハッシュで検証する
+pub fn verify_dummy_password(password: &str) -> Result<(), DBAccessError> {
+    let dummy_hash = DUMMY_PASSWORD_HASH.get_or_try_init(|| hash_password("dummy password"))?;
+    verify_password(password, dummy_hash)?;
+    Ok(())
+}
+
+// Argon2は意図的に重い処理のため、非同期のワーカーを止めないよう専用のスレッドで実行する
+async fn run_blocking<T, F>(error_key: ErrorKey, f: F) -> Result<T, DBAccessError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, DBAccessError> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f).await.map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(error_key, e.to_string())))
+    })?
+}
+
+pub async fn hash_password_async(password: String) -> Result<String, DBAccessError> {
+    run_blocking(ErrorKey::PasswordHashFailed, move || {
+        hash_password(&password)
+    })
+    .await
+}
+
+pub async fn verify_password_async(
+    password: String,
+    password_hash: String,
+) -> Result<bool, DBAccessError> {
+    run_blocking(ErrorKey::PasswordVerifyFailed, move || {
+        verify_password(&password, &password_hash)
+    })
+    .await
+}
+
+pub async fn verify_dummy_password_async(password: String) -> Result<(), DBAccessError> {
+    run_blocking(ErrorKey::PasswordVerifyFailed, move || {
+        verify_dummy_password(&password)
+    })
+    .await
+}
@@ -0,0 +1,342 @@
+use crate::enums::ProjectRole;
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::ProjectMember;
+use crate::repository::project_repo::get_project_by_id_with_transaction;
+use crate::repository::user_repo::get_user_by_id_with_transaction;
+use crate::repository::validations::{
+    validate_project_member_id_is_none, validate_project_member_project_id,
+    validate_project_member_user_id, validate_project_role,
+};
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+
+pub struct ProjectMemberRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl ProjectMemberRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_project_member(
+        &self,
+        project_member: ProjectMember,
+    ) -> Result<ProjectMember, DBAccessError> {
+        let _timer = QueryTimer::start("project_member", "create_project_member");
+        let mut tx = self.pool.begin().await?;
+
+        let result = create_project_member_with_transaction(&project_member, &mut tx).await;
+
+        match result {
+            Ok(project_member) => {
+                tx.commit().await.map_err(|e| {
+                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                        ErrorKey::ProjectMemberCreateFailed,
+                        e.to_string()
+                    )))
+                })?;
+                Ok(project_member)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    pub async fn get_project_members_by_project_id(
+        &self,
+        project_id: i64,
+    ) -> Result<Vec<ProjectMember>, DBAccessError> {
+        let _timer = QueryTimer::start("project_member", "get_project_members_by_project_id");
+        validate_project_member_project_id(project_id)?;
+
+        let result = sqlx::query_as!(
+            ProjectMember,
+            r#"
+                SELECT project_member_id, project_id, user_id, role
+                FROM project_members
+                WHERE project_id = $1
+                ORDER BY project_member_id
+            "#,
+            project_id,
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectMemberGetByProjectIdFailed,
+                e.to_string()
+            )))
+        })?;
+        log::debug!("Get project members by project id: {:?}", result);
+
+        Ok(result)
+    }
+
+    // メンバーでない場合はNoneを返す
+    pub async fn get_project_role(
+        &self,
+        project_id: i64,
+        user_id: i64,
+    ) -> Result<Option<ProjectRole>, DBAccessError> {
+        let _timer = QueryTimer::start("project_member", "get_project_role");
+        let mut tx = self.pool.begin().await?;
+        let result = get_project_member_with_transaction(project_id, user_id, &mut tx).await?;
+        tx.commit().await?;
+
+        match result {
+            Some(project_member) => ProjectRole::from_int(project_member.role)
+                .map(Some)
+                .map_err(DBAccessError::QueryError),
+            None => Ok(None),
+        }
+    }
+
+    pub async fn update_project_member_role(
+        &self,
+        project_id: i64,
+        user_id: i64,
+        role: i64,
+    ) -> Result<ProjectMember, DBAccessError> {
+        let _timer = QueryTimer::start("project_member", "update_project_member_role");
+        validate_project_member_project_id(project_id)?;
+        validate_project_member_user_id(user_id)?;
+        validate_project_role(role)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let current = get_project_member_with_transaction(project_id, user_id, &mut tx).await?;
+        let current = match current {
+            Some(current) => current,
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::ProjectMemberGetByProjectIdAndUserIdNotFound,
+                    [
+                        ("project_id", json!(project_id)),
+                        ("user_id", json!(user_id)),
+                    ],
+                )));
+            }
+        };
+
+        if current.role == ProjectRole::Owner.to_int() && role != ProjectRole::Owner.to_int() {
+            validate_not_last_owner(project_id, &mut tx).await?;
+        }
+
+        let result = sqlx::query_as!(
+            ProjectMember,
+            r#"
+                UPDATE project_members
+                SET role = $1
+                WHERE project_id = $2 AND user_id = $3
+                RETURNING project_member_id, project_id, user_id, role
+            "#,
+            role,
+            project_id,
+            user_id,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectMemberUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectMemberUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!("Updated project member: {:?}", result);
+
+        Ok(result)
+    }
+
+    pub async fn delete_project_member(
+        &self,
+        project_id: i64,
+        user_id: i64,
+    ) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("project_member", "delete_project_member");
+        validate_project_member_project_id(project_id)?;
+        validate_project_member_user_id(user_id)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let current = get_project_member_with_transaction(project_id, user_id, &mut tx).await?;
+        let current = match current {
+            Some(current) => current,
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::ProjectMemberGetByProjectIdAndUserIdNotFound,
+                    [
+                        ("project_id", json!(project_id)),
+                        ("user_id", json!(user_id)),
+                    ],
+                )));
+            }
+        };
+
+        if current.role == ProjectRole::Owner.to_int() {
+            validate_not_last_owner(project_id, &mut tx).await?;
+        }
+
+        sqlx::query!(
+            r#"
+                DELETE FROM project_members
+                WHERE project_id = $1 AND user_id = $2
+            "#,
+            project_id,
+            user_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectMemberDeleteFailed,
+                e.to_string()
+            )))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectMemberDeleteFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!(
+            "Deleted project member: Project ID = {}, User ID = {}",
+            project_id,
+            user_id
+        );
+
+        Ok(())
+    }
+}
+
+async fn validate_not_last_owner(
+    project_id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    let owner_role = ProjectRole::Owner.to_int();
+    let owners = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) FROM project_members
+            WHERE project_id = $1 AND role = $2
+        "#,
+        project_id,
+        owner_role,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::ProjectMemberGetByProjectIdFailed,
+            e.to_string()
+        )))
+    })?;
+
+    if owners <= 1 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ProjectMemberLastOwner,
+            [("project_id", json!(project_id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub async fn create_project_member_with_transaction(
+    project_member: &ProjectMember,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<ProjectMember, DBAccessError> {
+    validate_project_member_id_is_none(project_member.project_member_id)?;
+    validate_project_member_project_id(project_member.project_id)?;
+    validate_project_member_user_id(project_member.user_id)?;
+    validate_project_role(project_member.role)?;
+
+    if get_project_by_id_with_transaction(project_member.project_id, tx)
+        .await?
+        .is_none()
+    {
+        return Err(DBAccessError::NotFoundError(get_error_message(
+            ErrorKey::ProjectMemberProjectIdNotFound,
+            [("id", json!(project_member.project_id))],
+        )));
+    }
+    get_user_by_id_with_transaction(&project_member.user_id, tx).await?;
+
+    if get_project_member_with_transaction(project_member.project_id, project_member.user_id, tx)
+        .await?
+        .is_some()
+    {
+        return Err(DBAccessError::ConflictError(get_error_message(
+            ErrorKey::ProjectMemberAlreadyExists,
+            [
+                ("project_id", json!(project_member.project_id)),
+                ("user_id", json!(project_member.user_id)),
+            ],
+        )));
+    }
+
+    let result = sqlx::query_as!(
+        ProjectMember,
+        r#"
+            INSERT INTO project_members (project_id, user_id, role)
+            VALUES ($1, $2, $3)
+            RETURNING project_member_id, project_id, user_id, role
+        "#,
+        project_member.project_id,
+        project_member.user_id,
+        project_member.role,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::ProjectMemberCreateFailed,
+            e.to_string()
+        )))
+    })?;
+
+    log::info!("Created project member: {:?}", result);
+
+    Ok(result)
+}
+
+pub async fn get_project_member_with_transaction(
+    project_id: i64,
+    user_id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Option<ProjectMember>, DBAccessError> {
+    let result = sqlx::query_as!(
+        ProjectMember,
+        r#"
+            SELECT project_member_id, project_id, user_id, role
+            FROM project_members
+            WHERE project_id = $1 AND user_id = $2
+        "#,
+        project_id,
+        user_id,
+    )
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::ProjectMemberGetByProjectIdAndUserIdFailed,
+            e.to_string()
+        )))
+    })?;
+    log::debug!("Get project member with transaction: {:?}", result);
+
+    Ok(result)
+}
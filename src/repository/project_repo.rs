@@ -1,24 +1,85 @@
+use crate::enums::{AuditAction, AuditEntityType, DeletePolicy, ProjectRole};
 use crate::errors::db_error::DBAccessError;
 use crate::errors::messages::{ErrorKey, get_error_message};
-use crate::models::Project;
+use crate::metrics::QueryTimer;
+use crate::models::{AuditContext, AuditLog, DeleteSummary, Project, ProjectMember, ProjectPatch};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
+use crate::repository::project_member_repo::create_project_member_with_transaction;
+use crate::repository::trash_repo::{
+    get_deleted_at_with_transaction, restore_project_with_transaction,
+    trash_project_with_transaction,
+};
 use crate::repository::validations::{
     validate_pagination, validate_project_id, validate_project_name,
 };
+use crate::repository::version_repo::{
+    Versioned, bump_version_with_transaction, check_version_with_transaction,
+    get_version_with_transaction,
+};
 use anyhow::Result;
+use chrono::Utc;
+use serde_json::json;
 use sqlx::{Pool, Sqlite, Transaction};
 
 pub struct ProjectRepository {
     pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+    expected_versions: Option<Vec<i64>>,
+    member_id: Option<i64>,
 }
 
 impl ProjectRepository {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+            expected_versions: None,
+            member_id: None,
+        }
+    }
+
+    // 一覧の取得を指定したユーザーがメンバーのプロジェクトに絞り込む。Noneの場合はすべて
+    pub fn with_member_scope(mut self, member_id: Option<i64>) -> Self {
+        self.member_id = member_id;
+        self
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // If-Matchで指定されたバージョン。更新・削除の前にトランザクション内で確認する
+    pub fn with_expected_versions(mut self, expected_versions: Option<Vec<i64>>) -> Self {
+        self.expected_versions = expected_versions;
+        self
+    }
+
+    fn project_audit_log(
+        &self,
+        project_id: i64,
+        action: AuditAction,
+        before: Option<&Project>,
+        after: Option<&Project>,
+    ) -> AuditLog {
+        AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::Project,
+            project_id,
+            action,
+            before.and_then(to_audit_json),
+            after.and_then(to_audit_json),
+        )
     }
 
     pub async fn create_project(&self, project: Project) -> Result<Project, DBAccessError> {
+        let _timer = QueryTimer::start("project", "create_project");
         validate_project_id(project.project_id)?;
         validate_project_name(&project.name)?;
+
+        let mut tx = self.pool.begin().await?;
+
         let result = sqlx::query_as!(
             Project,
             r#"
@@ -28,9 +89,25 @@ impl ProjectRepository {
             "#,
             project.name,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| {
+            DBAccessError::from_write_error(
+                e,
+                ErrorKey::ProjectCreateFailed,
+                ErrorKey::ProjectAlreadyExists,
+            )
+        })?;
+
+        let audit_log = self.project_audit_log(
+            result.project_id.unwrap_or_default(),
+            AuditAction::Create,
+            None,
+            Some(&result),
+        );
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::ProjectCreateFailed,
                 e.to_string()
@@ -42,13 +119,90 @@ impl ProjectRepository {
         Ok(result)
     }
 
+    // プロジェクトを作成し、作成したユーザーをオーナーとして登録する
+    pub async fn create_project_with_owner(
+        &self,
+        project: Project,
+        owner_user_id: i64,
+    ) -> Result<Project, DBAccessError> {
+        let _timer = QueryTimer::start("project", "create_project_with_owner");
+        validate_project_id(project.project_id)?;
+        validate_project_name(&project.name)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let result = sqlx::query_as!(
+            Project,
+            r#"
+                INSERT INTO projects (name)
+                VALUES ($1)
+                RETURNING project_id, name
+            "#,
+            project.name,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::from_write_error(
+                e,
+                ErrorKey::ProjectCreateFailed,
+                ErrorKey::ProjectAlreadyExists,
+            )
+        });
+
+        let project = match result {
+            Ok(project) => project,
+            Err(e) => {
+                let _ = tx.rollback().await;
+                return Err(e);
+            }
+        };
+
+        let audit_log = self.project_audit_log(
+            project.project_id.unwrap_or_default(),
+            AuditAction::Create,
+            None,
+            Some(&project),
+        );
+        if let Err(e) = create_audit_log_with_transaction(&audit_log, &mut tx).await {
+            let _ = tx.rollback().await;
+            return Err(e);
+        }
+
+        let owner = ProjectMember::new(
+            project.project_id.unwrap(),
+            owner_user_id,
+            ProjectRole::Owner,
+        );
+        if let Err(e) = create_project_member_with_transaction(&owner, &mut tx).await {
+            let _ = tx.rollback().await;
+            return Err(e);
+        }
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::ProjectCreateFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!(
+            "Created project: {:?}, Owner User ID = {}",
+            project,
+            owner_user_id
+        );
+
+        Ok(project)
+    }
+
     pub async fn get_project_by_id(&self, id: i64) -> Result<Project, DBAccessError> {
+        let _timer = QueryTimer::start("project", "get_project_by_id");
         let result = sqlx::query_as!(
             Project,
             r#"
                 SELECT project_id, name
                 FROM projects
-                WHERE project_id = $1
+                WHERE project_id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -67,18 +221,19 @@ impl ProjectRepository {
             Some(project) => Ok(project),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::ProjectGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
 
     pub async fn get_project_by_name(&self, name: &str) -> Result<Project, DBAccessError> {
+        let _timer = QueryTimer::start("project", "get_project_by_name");
         let result = sqlx::query_as!(
             Project,
             r#"
                 SELECT project_id, name
                 FROM projects
-                WHERE name = $1
+                WHERE name = $1 AND deleted_at IS NULL
             "#,
             name,
         )
@@ -97,18 +252,24 @@ impl ProjectRepository {
             Some(project) => Ok(project),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::ProjectGetByNameNotFound,
-                format!("Name = {}", name),
+                [("name", json!(name))],
             ))),
         }
     }
 
     pub async fn get_all_projects(&self) -> Result<Vec<Project>, DBAccessError> {
+        let _timer = QueryTimer::start("project", "get_all_projects");
         let result = sqlx::query_as!(
             Project,
             r#"
                 SELECT project_id, name
                 FROM projects
+                WHERE deleted_at IS NULL
+                    AND ($1 IS NULL OR project_id IN (
+                        SELECT project_id FROM project_members WHERE user_id = $1
+                    ))
             "#,
+            self.member_id,
         )
         .fetch_all(&self.pool)
         .await
@@ -125,9 +286,10 @@ impl ProjectRepository {
     }
 
     pub async fn get_projects_count(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("project", "get_projects_count");
         let result = sqlx::query_scalar!(
             r#"
-                SELECT COUNT(*) FROM projects
+                SELECT COUNT(*) FROM projects WHERE deleted_at IS NULL
             "#,
         )
         .fetch_one(&self.pool)
@@ -148,6 +310,7 @@ impl ProjectRepository {
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<Project>, DBAccessError> {
+        let _timer = QueryTimer::start("project", "get_projects_with_pagination");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::ProjectGetAllFailed,
@@ -155,7 +318,7 @@ impl ProjectRepository {
             )))
         })?;
 
-        let count = get_projects_count_with_transaction(&mut tx).await?;
+        let count = get_projects_count_with_transaction(self.member_id, &mut tx).await?;
         validate_pagination(Some(page), Some(page_size), &count)?;
 
         let offset = (*page - 1) * *page_size;
@@ -171,11 +334,16 @@ impl ProjectRepository {
             r#"
                 SELECT project_id, name
                 FROM projects
+                WHERE deleted_at IS NULL
+                    AND ($3 IS NULL OR project_id IN (
+                        SELECT project_id FROM project_members WHERE user_id = $3
+                    ))
                 ORDER BY project_id
                 LIMIT $1 OFFSET $2
             "#,
             limit,
             offset,
+            self.member_id,
         )
         .fetch_all(&mut *tx)
         .await;
@@ -208,24 +376,61 @@ impl ProjectRepository {
         }
     }
 
-    pub async fn update_project(&self, project: Project) -> Result<Project, DBAccessError> {
+    pub async fn update_project(
+        &self,
+        project: Project,
+    ) -> Result<Versioned<Project>, DBAccessError> {
+        let _timer = QueryTimer::start("project", "update_project");
         validate_project_id(project.project_id)?;
         validate_project_name(&project.name)?;
 
+        let mut tx = self.pool.begin().await?;
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::Project,
+            project.project_id.unwrap_or_default(),
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        let before = match project.project_id {
+            Some(id) => get_project_by_id_with_transaction(id, &mut tx).await?,
+            None => None,
+        };
+
         let result = sqlx::query_as!(
             Project,
             r#"
                 UPDATE projects
                 SET name = $1
-                WHERE project_id = $2
+                WHERE project_id = $2 AND deleted_at IS NULL
                 RETURNING project_id, name
             "#,
             project.name,
             project.project_id,
         )
-        .fetch_optional(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
+            DBAccessError::from_write_error(
+                e,
+                ErrorKey::ProjectUpdateFailed,
+                ErrorKey::ProjectAlreadyExists,
+            )
+        })?;
+
+        if let Some(updated) = &result {
+            let audit_log = self.project_audit_log(
+                updated.project_id.unwrap_or_default(),
+                AuditAction::Update,
+                before.as_ref(),
+                Some(updated),
+            );
+            create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+        }
+
+        tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::ProjectUpdateFailed,
                 e.to_string()
@@ -234,44 +439,166 @@ impl ProjectRepository {
 
         log::info!("Updated project: {:?}", result);
 
-        match result {
-            Some(project) => Ok(project),
+        match result.zip(version) {
+            Some((project, version)) => Ok(Versioned {
+                value: project,
+                version,
+            }),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::ProjectUpdateFailed,
-                format!("ID = {:?}", project.project_id),
+                [("id", json!(project.project_id))],
             ))),
         }
     }
 
-    pub async fn delete_project(&self, id: i64) -> Result<(), DBAccessError> {
+    // 指定されたフィールドのみ更新する
+    pub async fn patch_project(
+        &self,
+        id: i64,
+        patch: ProjectPatch,
+    ) -> Result<Versioned<Project>, DBAccessError> {
         validate_project_id(Some(id))?;
+        match patch.name {
+            Some(name) => {
+                self.update_project(Project {
+                    project_id: Some(id),
+                    name,
+                })
+                .await
+            }
+            None => {
+                let mut tx = self.pool.begin().await?;
+                let version = check_version_with_transaction(
+                    AuditEntityType::Project,
+                    id,
+                    self.expected_versions.as_deref(),
+                    &mut tx,
+                )
+                .await?;
+                match get_project_by_id_with_transaction(id, &mut tx)
+                    .await?
+                    .zip(version)
+                {
+                    Some((project, version)) => Ok(Versioned {
+                        value: project,
+                        version,
+                    }),
+                    None => Err(DBAccessError::NotFoundError(get_error_message(
+                        ErrorKey::ProjectGetByIdNotFound,
+                        [("id", json!(id))],
+                    ))),
+                }
+            }
+        }
+    }
 
-        let result = sqlx::query!(
-            r#"
-                DELETE FROM projects
-                WHERE project_id = $1
-            "#,
+    // restrictはゴミ箱にないタスクがあれば失敗し、cascadeはタスクと、その割り当て・コメントも
+    // 一緒にゴミ箱に移動する。reparentは使えない
+    pub async fn delete_project(
+        &self,
+        id: i64,
+        policy: DeletePolicy,
+    ) -> Result<DeleteSummary, DBAccessError> {
+        let _timer = QueryTimer::start("project", "delete_project");
+        validate_project_id(Some(id))?;
+        if policy == DeletePolicy::Reparent {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::DeletePolicyNotSupported,
+                [
+                    ("entity", json!("project")),
+                    ("policy", json!(policy.to_str())),
+                ],
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        bump_version_with_transaction(
+            AuditEntityType::Project,
             id,
+            self.expected_versions.as_deref(),
+            &mut tx,
         )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| {
+        .await?;
+
+        let deleted = match get_project_by_id_with_transaction(id, &mut tx).await? {
+            Some(project) => project,
+            None => {
+                return Err(DBAccessError::ValidationError(get_error_message(
+                    ErrorKey::ProjectDeleteFailedByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+
+        if policy == DeletePolicy::Restrict {
+            let tasks = count_project_tasks_with_transaction(id, &mut tx).await?;
+            if tasks > 0 {
+                return Err(DBAccessError::ConflictError(get_error_message(
+                    ErrorKey::DeletePolicyRestricted,
+                    [("id", json!(id)), ("tasks", json!(tasks))],
+                )));
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let mut summary =
+            trash_project_with_transaction(id, now, &self.audit_context, &mut tx).await?;
+        summary.policy = policy.to_str().to_string();
+
+        let audit_log = self.project_audit_log(id, AuditAction::Delete, Some(&deleted), None);
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::ProjectDeleteFailedByIdNotFound,
                 e.to_string()
             )))
         })?;
 
-        if result.rows_affected() == 0 {
-            return Err(DBAccessError::ValidationError(get_error_message(
-                ErrorKey::ProjectDeleteFailedByIdNotFound,
-                format!("ID = {}", id),
-            )));
-        }
+        log::info!("Deleted project: ID = {}, Summary = {:?}", id, summary);
+
+        Ok(summary)
+    }
+
+    // ゴミ箱から戻す。同時にゴミ箱に移動したタスクと、その割り当て・コメントも戻す
+    pub async fn restore_project(&self, id: i64) -> Result<Versioned<Project>, DBAccessError> {
+        let _timer = QueryTimer::start("project", "restore_project");
+        validate_project_id(Some(id))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let deleted_at =
+            get_deleted_at_with_transaction(AuditEntityType::Project, id, &mut tx).await?;
+        restore_project_with_transaction(id, deleted_at, &self.audit_context, &mut tx).await?;
+
+        let project = match get_project_by_id_with_transaction(id, &mut tx).await? {
+            Some(project) => project,
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::ProjectGetByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+        let version = get_version_with_transaction(&AuditEntityType::Project, id, &mut tx).await?;
+
+        let audit_log = self.project_audit_log(id, AuditAction::Restore, None, Some(&project));
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TrashRestoreFailed,
+                e.to_string()
+            )))
+        })?;
 
-        log::info!("Deleted project: ID = {}", id);
+        log::info!("Restored project: ID = {}", id);
 
-        Ok(())
+        Ok(Versioned {
+            value: project,
+            version: version.unwrap_or_default(),
+        })
     }
 }
 
@@ -284,7 +611,7 @@ pub async fn get_project_by_id_with_transaction(
         r#"
             SELECT project_id, name
             FROM projects
-            WHERE project_id = $1
+            WHERE project_id = $1 AND deleted_at IS NULL
         "#,
         id,
     )
@@ -298,13 +625,20 @@ pub async fn get_project_by_id_with_transaction(
     })
 }
 
+// member_idを指定した場合はそのユーザーがメンバーのプロジェクトだけを数える
 pub async fn get_projects_count_with_transaction(
+    member_id: Option<i64>,
     transaction: &mut Transaction<'_, Sqlite>,
 ) -> Result<i64, DBAccessError> {
     let result = sqlx::query_scalar!(
         r#"
             SELECT COUNT(*) FROM projects
+            WHERE deleted_at IS NULL
+                AND ($1 IS NULL OR project_id IN (
+                    SELECT project_id FROM project_members WHERE user_id = $1
+                ))
         "#,
+        member_id,
     )
     .fetch_one(&mut **transaction)
     .await
@@ -319,3 +653,24 @@ pub async fn get_projects_count_with_transaction(
 
     Ok(result)
 }
+
+// プロジェクトにある、ゴミ箱にないタスクの件数
+async fn count_project_tasks_with_transaction(
+    id: i64,
+    transaction: &mut Transaction<'_, Sqlite>,
+) -> Result<i64, DBAccessError> {
+    sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) FROM tasks WHERE project_id = $1 AND deleted_at IS NULL
+        "#,
+        id,
+    )
+    .fetch_one(&mut **transaction)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::ProjectDeleteFailed,
+            e.to_string()
+        )))
+    })
+}
@@ -0,0 +1,177 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::Session;
+use crate::repository::user_repo::get_user_by_id_with_transaction;
+use crate::repository::validations::{
+    validate_session_expires_at, validate_session_id_is_none, validate_session_token_hash,
+    validate_session_user_id,
+};
+use chrono::Utc;
+use sqlx::{Pool, Sqlite};
+
+pub struct SessionRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl SessionRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    pub async fn create_session(&self, session: Session) -> Result<Session, DBAccessError> {
+        let _timer = QueryTimer::start("session", "create_session");
+        let now = Utc::now().timestamp();
+
+        validate_session_id_is_none(session.session_id)?;
+        validate_session_user_id(session.user_id)?;
+        validate_session_token_hash(&session.token_hash)?;
+        validate_session_expires_at(session.expires_at, now)?;
+
+        let mut tx = self.pool.begin().await?;
+
+        get_user_by_id_with_transaction(&session.user_id, &mut tx).await?;
+
+        let result = sqlx::query_as!(
+            Session,
+            r#"
+                INSERT INTO sessions (user_id, token_hash, created_at, expires_at)
+                VALUES ($1, $2, $3, $4)
+                RETURNING session_id, user_id, token_hash, created_at, expires_at, revoked_at
+            "#,
+            session.user_id,
+            session.token_hash,
+            now,
+            session.expires_at,
+        )
+        .fetch_one(&mut *tx)
+        .await;
+
+        match result {
+            Ok(session) => {
+                tx.commit().await.map_err(|e| {
+                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                        ErrorKey::SessionCreateFailed,
+                        e.to_string()
+                    )))
+                })?;
+                log::info!(
+                    "Created session: ID = {:?}, User ID = {}",
+                    session.session_id,
+                    session.user_id
+                );
+                Ok(session)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(DBAccessError::QueryError(anyhow::anyhow!(
+                    get_error_message(ErrorKey::SessionCreateFailed, e.to_string())
+                )))
+            }
+        }
+    }
+
+    // 有効期限内かつ無効化されていないセッションのみを返す
+    pub async fn get_active_session_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Session, DBAccessError> {
+        let _timer = QueryTimer::start("session", "get_active_session_by_token_hash");
+        validate_session_token_hash(token_hash)?;
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query_as!(
+            Session,
+            r#"
+                SELECT session_id, user_id, token_hash, created_at, expires_at, revoked_at
+                FROM sessions
+                WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > $2
+            "#,
+            token_hash,
+            now,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::SessionGetByTokenHashFailed,
+                e.to_string()
+            )))
+        })?;
+
+        match result {
+            Some(session) => Ok(session),
+            None => Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::SessionGetByTokenHashNotFound,
+                "".to_string(),
+            ))),
+        }
+    }
+
+    pub async fn revoke_session(&self, token_hash: &str) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("session", "revoke_session");
+        validate_session_token_hash(token_hash)?;
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query!(
+            r#"
+                UPDATE sessions
+                SET revoked_at = $1
+                WHERE token_hash = $2 AND revoked_at IS NULL
+            "#,
+            now,
+            token_hash,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::SessionRevokeFailed,
+                e.to_string()
+            )))
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::SessionRevokeFailedByTokenHashNotFound,
+                "".to_string(),
+            )));
+        }
+
+        log::info!("Revoked session");
+
+        Ok(())
+    }
+
+    pub async fn revoke_sessions_by_user_id(&self, user_id: i64) -> Result<u64, DBAccessError> {
+        let _timer = QueryTimer::start("session", "revoke_sessions_by_user_id");
+        validate_session_user_id(user_id)?;
+
+        let now = Utc::now().timestamp();
+        let result = sqlx::query!(
+            r#"
+                UPDATE sessions
+                SET revoked_at = $1
+                WHERE user_id = $2 AND revoked_at IS NULL
+            "#,
+            now,
+            user_id,
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::SessionRevokeFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!(
+            "Revoked sessions: User ID = {}, Count = {}",
+            user_id,
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+}
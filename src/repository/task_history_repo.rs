@@ -0,0 +1,190 @@
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{Task, TaskHistory};
+use crate::repository::task_repo::get_task_by_id_with_transaction;
+use crate::repository::validations::validate_pagination;
+use chrono::Utc;
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+
+pub struct TaskHistoryRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl TaskHistoryRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    // 古い順に返す
+    pub async fn get_task_history_by_task_id(
+        &self,
+        task_id: i64,
+        page: Option<&i32>,
+        page_size: Option<&i32>,
+    ) -> Result<Vec<TaskHistory>, DBAccessError> {
+        let _timer = QueryTimer::start("task_history", "get_task_history_by_task_id");
+        if task_id < 0 {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::TaskHistoryTaskIdInvalid,
+                [("id", json!(task_id))],
+            )));
+        }
+
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskHistoryGetByTaskIdFailed,
+                e.to_string()
+            )))
+        })?;
+
+        get_task_by_id_with_transaction(task_id, &mut tx).await?;
+
+        let count = sqlx::query_scalar!(
+            r#"
+                SELECT COUNT(*) FROM task_history WHERE task_id = $1
+            "#,
+            task_id,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskHistoryGetCountFailed,
+                e.to_string()
+            )))
+        })?;
+        validate_pagination(page, page_size, &count)?;
+
+        let mut query = r#"
+                SELECT task_history_id, task_id, field_name, old_value, new_value, changed_by,
+                    changed_at
+                FROM task_history
+                WHERE task_id = $1
+                ORDER BY changed_at ASC, task_history_id ASC
+            "#
+        .to_string();
+        if page.is_some() && page_size.is_some() {
+            query.push_str(" LIMIT $2 OFFSET $3");
+        }
+
+        let mut query_builder = sqlx::query_as::<_, TaskHistory>(&query).bind(task_id);
+        if let (Some(page), Some(page_size)) = (page, page_size) {
+            let offset = (*page - 1) * *page_size;
+            query_builder = query_builder.bind(*page_size).bind(offset);
+        }
+
+        let result = query_builder.fetch_all(&mut *tx).await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskHistoryGetByTaskIdFailed,
+                e.to_string()
+            )))
+        })?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskHistoryGetByTaskIdFailed,
+                e.to_string()
+            )))
+        })?;
+        log::debug!("Get task history by task id: {:?}", result.len());
+
+        Ok(result)
+    }
+}
+
+// 更新前後のタスクを比較し、変更されたフィールドの履歴を作成する
+pub fn build_task_histories(
+    before: &Task,
+    after: &Task,
+    changed_by: Option<i64>,
+) -> Vec<TaskHistory> {
+    fn to_value<T: ToString>(value: &Option<T>) -> Option<String> {
+        value.as_ref().map(|v| v.to_string())
+    }
+
+    let task_id = after.task_id.unwrap_or_default();
+    let fields = [
+        (
+            "project_id",
+            Some(before.project_id.to_string()),
+            Some(after.project_id.to_string()),
+        ),
+        (
+            "parent_id",
+            to_value(&before.parent_id),
+            to_value(&after.parent_id),
+        ),
+        (
+            "level",
+            Some(before.level.to_string()),
+            Some(after.level.to_string()),
+        ),
+        ("name", Some(before.name.clone()), Some(after.name.clone())),
+        (
+            "description",
+            before.description.clone(),
+            after.description.clone(),
+        ),
+        (
+            "status",
+            Some(before.status.to_string()),
+            Some(after.status.to_string()),
+        ),
+        (
+            "deadline",
+            to_value(&before.deadline),
+            to_value(&after.deadline),
+        ),
+    ];
+
+    fields
+        .into_iter()
+        .filter(|(_, old_value, new_value)| old_value != new_value)
+        .map(|(field_name, old_value, new_value)| {
+            TaskHistory::new(
+                task_id,
+                field_name.to_string(),
+                old_value,
+                new_value,
+                changed_by,
+            )
+        })
+        .collect()
+}
+
+pub async fn create_task_history_with_transaction(
+    task_history: &TaskHistory,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<TaskHistory, DBAccessError> {
+    let now = Utc::now().timestamp();
+    let result = sqlx::query_as!(
+        TaskHistory,
+        r#"
+            INSERT INTO task_history (task_id, field_name, old_value, new_value, changed_by,
+                changed_at)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING task_history_id, task_id, field_name, old_value, new_value, changed_by,
+                changed_at
+        "#,
+        task_history.task_id,
+        task_history.field_name,
+        task_history.old_value,
+        task_history.new_value,
+        task_history.changed_by,
+        now,
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::TaskHistoryCreateFailed,
+            e.to_string()
+        )))
+    })?;
+
+    log::debug!("Created task history: {:?}", result);
+
+    Ok(result)
+}
@@ -1,19 +1,40 @@
-use crate::enums::TaskFilterValue;
-use crate::errors::db_error::DBAccessError;
+use crate::enums::{AuditAction, AuditEntityType, DeletePolicy, TaskFilterValue, TaskLevel};
+use crate::errors::db_error::{BatchError, DBAccessError};
 use crate::errors::messages::{ErrorKey, get_error_message};
-use crate::models::{Task, task::TaskFilter};
+use crate::metrics::QueryTimer;
+use crate::models::{
+    AuditContext, AuditLog, DeleteSummary, Task, TaskBatchOperation, TaskBatchResult, TaskPatch,
+    task::TaskFilter,
+};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
 use crate::repository::project_repo::get_project_by_id_with_transaction;
+use crate::repository::task_history_repo::{
+    build_task_histories, create_task_history_with_transaction,
+};
+use crate::repository::trash_repo::{
+    get_deleted_at_with_transaction, restore_task_subtree_with_transaction,
+    trash_task_subtree_with_transaction,
+};
 use crate::repository::validations::{
-    validate_pagination, validate_task_description, validate_task_id, validate_task_id_is_none,
-    validate_task_level, validate_task_name, validate_task_parent_id, validate_task_project_id,
-    validate_task_status, validate_task_unix_timestamp, validate_task_unix_timestamp_or_none,
+    validate_batch_operations_count, validate_pagination, validate_task_description,
+    validate_task_id, validate_task_id_is_none, validate_task_level, validate_task_name,
+    validate_task_parent_id, validate_task_project_id, validate_task_status,
+    validate_task_unix_timestamp, validate_task_unix_timestamp_or_none,
+};
+use crate::repository::version_repo::{
+    Versioned, bump_version_with_transaction, check_version_with_transaction,
+    get_version_with_transaction,
 };
 use anyhow::Result;
 use chrono::Utc;
+use serde_json::json;
 use sqlx::{Pool, Sqlite, Transaction};
+use std::collections::HashMap;
 
 pub struct TaskRepository {
     pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+    expected_versions: Option<Vec<i64>>,
 }
 
 fn deduplicate(tasks: Vec<Task>) -> Vec<Task> {
@@ -28,7 +49,40 @@ fn deduplicate(tasks: Vec<Task>) -> Vec<Task> {
 
 impl TaskRepository {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+            expected_versions: None,
+        }
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // If-Matchで指定されたバージョン。更新・削除の前にトランザクション内で確認する
+    pub fn with_expected_versions(mut self, expected_versions: Option<Vec<i64>>) -> Self {
+        self.expected_versions = expected_versions;
+        self
+    }
+
+    fn task_audit_log(
+        &self,
+        task_id: i64,
+        action: AuditAction,
+        before: Option<&Task>,
+        after: Option<&Task>,
+    ) -> AuditLog {
+        AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::Task,
+            task_id,
+            action,
+            before.and_then(to_audit_json),
+            after.and_then(to_audit_json),
+        )
     }
 
     async fn validate_project_id_is_exist(
@@ -40,7 +94,7 @@ impl TaskRepository {
         if project.is_none() {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::TaskProjectIdNotFound,
-                format!("ID = {}", project_id),
+                [("id", json!(project_id))],
             )));
         }
         Ok(())
@@ -57,7 +111,7 @@ impl TaskRepository {
             if level != 0 {
                 return Err(DBAccessError::ValidationError(get_error_message(
                     ErrorKey::TaskNoParentIdOnNonMajorTask,
-                    format!("Level = {}", level),
+                    [("level", json!(level))],
                 )));
             }
             return Ok(());
@@ -66,7 +120,10 @@ impl TaskRepository {
             if parent_task.level != level - 1 {
                 return Err(DBAccessError::ValidationError(get_error_message(
                     ErrorKey::TaskParentLevelInvalid,
-                    format!("Level = {}, Parent Level = {}", level, parent_task.level),
+                    [
+                        ("level", json!(level)),
+                        ("parent_level", json!(parent_task.level)),
+                    ],
                 )));
             }
         }
@@ -74,7 +131,7 @@ impl TaskRepository {
         if self_id.is_some() && parent_id.is_some() && parent_id.unwrap() == self_id.unwrap() {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::TaskParentIdCannotBeSameAsTaskId,
-                format!("ID = {}", parent_id.unwrap()),
+                [("id", json!(parent_id.unwrap()))],
             )));
         }
 
@@ -82,6 +139,27 @@ impl TaskRepository {
     }
 
     pub async fn create_task(&self, task: Task) -> Result<Task, DBAccessError> {
+        let _timer = QueryTimer::start("task", "create_task");
+        let mut tx = self.pool.begin().await?;
+
+        let task = self.create_task_with_transaction(&task, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskCreateFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("Created task: {:?}", task);
+        Ok(task)
+    }
+
+    // 呼び出し元のトランザクションで作成する。コミットは呼び出し元で行う
+    pub async fn create_task_with_transaction(
+        &self,
+        task: &Task,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Task, DBAccessError> {
         validate_task_id_is_none(task.task_id)?;
         validate_task_project_id(task.project_id)?;
         validate_task_parent_id(task.parent_id)?;
@@ -91,15 +169,13 @@ impl TaskRepository {
         validate_task_description(task.description.as_ref())?;
         validate_task_unix_timestamp_or_none(task.deadline)?;
 
-        let mut tx = self.pool.begin().await?;
-
-        self.validate_project_id_is_exist(task.project_id, &mut tx)
+        self.validate_project_id_is_exist(task.project_id, tx)
             .await?;
-        self.validate_parent_relation(task.parent_id, task.level, task.task_id, &mut tx)
+        self.validate_parent_relation(task.parent_id, task.level, task.task_id, tx)
             .await?;
 
         let now = Utc::now().timestamp();
-        let result = sqlx::query_as!(
+        let task = sqlx::query_as!(
             Task,
             r#"
                 INSERT INTO tasks (project_id, parent_id, level, name, description, status, deadline, created_at, updated_at)
@@ -116,30 +192,28 @@ impl TaskRepository {
             now,
             now,
         )
-        .fetch_one(&mut *tx)
-        .await;
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskCreateFailed,
+                e.to_string()
+            )))
+        })?;
 
-        match result {
-            Ok(task) => {
-                tx.commit().await.map_err(|e| {
-                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
-                        ErrorKey::TaskCreateFailed,
-                        e.to_string()
-                    )))
-                })?;
-                log::info!("Created task: {:?}", task);
-                Ok(task)
-            }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(DBAccessError::QueryError(anyhow::anyhow!(
-                    get_error_message(ErrorKey::TaskCreateFailed, e.to_string())
-                )))
-            }
-        }
+        let audit_log = self.task_audit_log(
+            task.task_id.unwrap_or_default(),
+            AuditAction::Create,
+            None,
+            Some(&task),
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        Ok(task)
     }
 
     pub async fn get_task_by_id(&self, id: i64) -> Result<Task, DBAccessError> {
+        let _timer = QueryTimer::start("task", "get_task_by_id");
         validate_task_id(Some(id))?;
 
         let result = sqlx::query_as!(
@@ -147,7 +221,7 @@ impl TaskRepository {
             r#"
                 SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
                 FROM tasks
-                WHERE task_id = $1
+                WHERE task_id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -161,17 +235,19 @@ impl TaskRepository {
             Some(task) => Ok(task),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::TaskGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
 
     pub async fn get_all_tasks(&self) -> Result<Vec<Task>, DBAccessError> {
+        let _timer = QueryTimer::start("task", "get_all_tasks");
         let result = sqlx::query_as!(
             Task,
             r#"
                 SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
                 FROM tasks
+                WHERE deleted_at IS NULL
             "#,
         )
         .fetch_all(&self.pool)
@@ -184,9 +260,10 @@ impl TaskRepository {
     }
 
     pub async fn get_tasks_count(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("task", "get_tasks_count");
         let result = sqlx::query_scalar::<_, i64>(
             r#"
-            SELECT COUNT(*) FROM tasks
+            SELECT COUNT(*) FROM tasks WHERE deleted_at IS NULL
         "#,
         )
         .fetch_one(&self.pool)
@@ -209,6 +286,7 @@ impl TaskRepository {
         page: Option<&i32>,
         page_size: Option<&i32>,
     ) -> Result<Vec<Task>, DBAccessError> {
+        let _timer = QueryTimer::start("task", "get_tasks_by_filter");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskGetByFilterFailed,
@@ -231,14 +309,46 @@ impl TaskRepository {
         Ok(result)
     }
 
-    pub async fn update_task(&self, task: Task) -> Result<Task, DBAccessError> {
+    pub async fn update_task(&self, task: Task) -> Result<Versioned<Task>, DBAccessError> {
+        let _timer = QueryTimer::start("task", "update_task");
         if task.task_id.is_none() {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::TaskIdInvalid,
-                format!("ID = {}", task.task_id.unwrap()),
+                [("id", json!(task.task_id))],
             )));
         }
 
+        let mut tx = self.pool.begin().await?;
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::Task,
+            task.task_id.unwrap_or_default(),
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        let task = self.update_task_with_transaction(&task, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("Updated task: {:?}", task);
+        Ok(Versioned {
+            value: task,
+            version: version.unwrap_or_default(),
+        })
+    }
+
+    // 呼び出し元のトランザクションで更新する。バージョンの更新とコミットは呼び出し元で行う
+    pub async fn update_task_with_transaction(
+        &self,
+        task: &Task,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Task, DBAccessError> {
         validate_task_id(task.task_id)?;
         validate_task_project_id(task.project_id)?;
         validate_task_parent_id(task.parent_id)?;
@@ -248,17 +358,115 @@ impl TaskRepository {
         validate_task_description(task.description.as_ref())?;
         validate_task_unix_timestamp_or_none(task.deadline)?;
 
-        let mut tx = self.pool.begin().await?;
+        let Some(task_id) = task.task_id else {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::TaskIdInvalid,
+                [("id", json!(task.task_id))],
+            )));
+        };
+        let before = get_task_by_id_with_transaction(task_id, tx).await?;
 
-        let _ = get_task_by_id_with_transaction(task.task_id.unwrap(), &mut tx).await?;
+        // プロジェクトの変更は更新の対象外のため、異なる値は受け付けない
+        if task.project_id != before.project_id {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::TaskProjectIdChanged,
+                [
+                    ("project_id", json!(task.project_id)),
+                    ("current_project_id", json!(before.project_id)),
+                ],
+            )));
+        }
 
-        self.validate_project_id_is_exist(task.project_id, &mut tx)
+        self.validate_project_id_is_exist(task.project_id, tx)
+            .await?;
+        self.validate_parent_relation(task.parent_id, task.level, task.task_id, tx)
             .await?;
-        self.validate_parent_relation(task.parent_id, task.level, task.task_id, &mut tx)
+
+        self.write_task_update(&before, task, tx).await
+    }
+
+    // 指定されたフィールドのみ検証して更新する
+    pub async fn patch_task(
+        &self,
+        id: i64,
+        patch: TaskPatch,
+    ) -> Result<Versioned<Task>, DBAccessError> {
+        let _timer = QueryTimer::start("task", "patch_task");
+        validate_task_id(Some(id))?;
+        if let Some(parent_id) = patch.parent_id {
+            validate_task_parent_id(parent_id)?;
+        }
+        if let Some(level) = patch.level {
+            validate_task_level(level)?;
+        }
+        if let Some(status) = patch.status {
+            validate_task_status(status)?;
+        }
+        if let Some(name) = &patch.name {
+            validate_task_name(name)?;
+        }
+        if let Some(description) = &patch.description {
+            validate_task_description(description.as_ref())?;
+        }
+        if let Some(deadline) = patch.deadline {
+            validate_task_unix_timestamp_or_none(deadline)?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        if patch.is_empty() {
+            let version = check_version_with_transaction(
+                AuditEntityType::Task,
+                id,
+                self.expected_versions.as_deref(),
+                &mut tx,
+            )
             .await?;
+            let before = get_task_by_id_with_transaction(id, &mut tx).await?;
+            return Ok(Versioned {
+                value: before,
+                version: version.unwrap_or_default(),
+            });
+        }
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::Task,
+            id,
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+        let before = get_task_by_id_with_transaction(id, &mut tx).await?;
+
+        let task = patch.apply(&before);
+        if patch.changes_parent_relation() {
+            self.validate_parent_relation(task.parent_id, task.level, task.task_id, &mut tx)
+                .await?;
+        }
+
+        let task = self.write_task_update(&before, &task, &mut tx).await?;
 
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("Patched task: {:?}", task);
+        Ok(Versioned {
+            value: task,
+            version: version.unwrap_or_default(),
+        })
+    }
+
+    async fn write_task_update(
+        &self,
+        before: &Task,
+        task: &Task,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<Task, DBAccessError> {
         let now = Utc::now().timestamp();
-        let result = sqlx::query_as!(
+        let task = sqlx::query_as!(
             Task,
             r#"
                 UPDATE tasks 
@@ -275,56 +483,379 @@ impl TaskRepository {
             now,
             task.task_id,
         )
-        .fetch_one(&mut *tx)
-        .await;
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TaskUpdateFailed,
+                e.to_string()
+            )))
+        })?;
 
-        match result {
-            Ok(task) => {
-                tx.commit().await.map_err(|e| {
-                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
-                        ErrorKey::TaskUpdateFailed,
-                        e.to_string()
-                    )))
-                })?;
-                log::info!("Updated task: {:?}", task);
-                Ok(task)
-            }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(DBAccessError::QueryError(anyhow::anyhow!(
-                    get_error_message(ErrorKey::TaskUpdateFailed, e.to_string())
-                )))
-            }
+        let audit_log = self.task_audit_log(
+            task.task_id.unwrap_or_default(),
+            AuditAction::Update,
+            Some(before),
+            Some(&task),
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        let histories = build_task_histories(before, &task, self.audit_context.actor_user_id);
+        for history in histories.iter() {
+            create_task_history_with_transaction(history, tx).await?;
         }
+
+        Ok(task)
     }
 
-    pub async fn delete_task(&self, id: i64) -> Result<(), DBAccessError> {
+    pub async fn delete_task(
+        &self,
+        id: i64,
+        policy: DeletePolicy,
+    ) -> Result<DeleteSummary, DBAccessError> {
+        let _timer = QueryTimer::start("task", "delete_task");
         validate_task_id(Some(id))?;
 
-        let result = sqlx::query!(
-            r#"
-                DELETE FROM tasks WHERE task_id = $1
-            "#,
+        let mut tx = self.pool.begin().await?;
+
+        bump_version_with_transaction(
+            AuditEntityType::Task,
             id,
+            self.expected_versions.as_deref(),
+            &mut tx,
         )
-        .execute(&self.pool)
-        .await
-        .map_err(|e| {
+        .await?;
+
+        let (_, summary) = self
+            .delete_task_with_transaction(id, policy, &mut tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskDeleteFailedByIdNotFound,
                 e.to_string()
             )))
         })?;
+        log::info!("Deleted task: ID = {}, Summary = {:?}", id, summary);
 
-        if result.rows_affected() == 0 {
-            return Err(DBAccessError::ValidationError(get_error_message(
-                ErrorKey::TaskDeleteFailedByIdNotFound,
-                format!("ID = {}", id),
+        Ok(summary)
+    }
+
+    // 呼び出し元のトランザクションで削除ポリシーに従ってゴミ箱に移動し、移動したタスクと件数を返す。
+    // restrictは子のタスク・割り当て・コメントがあれば失敗し、cascadeは子孫のタスクと、
+    // それらの割り当て・コメントも一緒に移動する。reparentは子のタスクを親の親に付け替えてから移動する。
+    // コミットは呼び出し元で行う
+    pub async fn delete_task_with_transaction(
+        &self,
+        id: i64,
+        policy: DeletePolicy,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<(Task, DeleteSummary), DBAccessError> {
+        validate_task_id(Some(id))?;
+
+        let deleted = match get_task_by_id_with_transaction(id, tx).await {
+            Ok(task) => task,
+            Err(DBAccessError::NotFoundError(_)) => {
+                return Err(DBAccessError::ValidationError(get_error_message(
+                    ErrorKey::TaskDeleteFailedByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+            Err(e) => return Err(e),
+        };
+
+        let mut reparented_tasks = 0;
+        match policy {
+            DeletePolicy::Restrict => {
+                let dependents = count_task_dependents_with_transaction(id, tx).await?;
+                if dependents.children > 0 || dependents.user_assigns > 0 || dependents.comments > 0
+                {
+                    return Err(DBAccessError::ConflictError(get_error_message(
+                        ErrorKey::DeletePolicyRestricted,
+                        [
+                            ("id", json!(id)),
+                            ("children", json!(dependents.children)),
+                            ("user_assigns", json!(dependents.user_assigns)),
+                            ("comments", json!(dependents.comments)),
+                        ],
+                    )));
+                }
+            }
+            DeletePolicy::Cascade => {}
+            DeletePolicy::Reparent => {
+                reparented_tasks = self
+                    .reparent_children_with_transaction(&deleted, tx)
+                    .await?;
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let mut summary =
+            trash_task_subtree_with_transaction(id, now, &self.audit_context, tx).await?;
+        summary.policy = policy.to_str().to_string();
+        summary.reparented_tasks = reparented_tasks;
+
+        let audit_log = self.task_audit_log(id, AuditAction::Delete, Some(&deleted), None);
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        Ok((deleted, summary))
+    }
+
+    // 子のタスクを親の親（ない場合は大タスク）に付け替え、子孫の階層を1つずつ上げる。
+    // 最下位の階層でなくなるタスクに割り当てかコメントがある場合は付け替えない
+    async fn reparent_children_with_transaction(
+        &self,
+        parent: &Task,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<i64, DBAccessError> {
+        let parent_id = parent.task_id.unwrap_or_default();
+        let descendants = get_descendant_tasks_with_transaction(parent_id, tx).await?;
+
+        for task in descendants.iter() {
+            if task.level != TaskLevel::max_level() {
+                continue;
+            }
+            let task_id = task.task_id.unwrap_or_default();
+            let dependents = count_task_dependents_with_transaction(task_id, tx).await?;
+            if dependents.user_assigns > 0 || dependents.comments > 0 {
+                return Err(DBAccessError::ConflictError(get_error_message(
+                    ErrorKey::DeletePolicyReparentLevelConflict,
+                    [
+                        ("id", json!(task_id)),
+                        ("user_assigns", json!(dependents.user_assigns)),
+                        ("comments", json!(dependents.comments)),
+                    ],
+                )));
+            }
+        }
+
+        for task in descendants.iter() {
+            let mut moved = task.clone();
+            if moved.parent_id == Some(parent_id) {
+                moved.parent_id = parent.parent_id;
+            }
+            moved.level -= 1;
+            bump_version_with_transaction(
+                AuditEntityType::Task,
+                task.task_id.unwrap_or_default(),
+                None,
+                tx,
+            )
+            .await?;
+            self.write_task_update(task, &moved, tx).await?;
+        }
+
+        Ok(descendants.len() as i64)
+    }
+
+    // ゴミ箱から戻す。同時にゴミ箱に移動した子孫のタスクと、割り当て・コメントも戻す。
+    // 親のタスクかプロジェクトがゴミ箱にある場合は戻せない
+    pub async fn restore_task(&self, id: i64) -> Result<Versioned<Task>, DBAccessError> {
+        let _timer = QueryTimer::start("task", "restore_task");
+        validate_task_id(Some(id))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let deleted_at =
+            get_deleted_at_with_transaction(AuditEntityType::Task, id, &mut tx).await?;
+        restore_task_subtree_with_transaction(id, deleted_at, &self.audit_context, &mut tx).await?;
+
+        let task = get_task_by_id_with_transaction(id, &mut tx).await?;
+        if let Some(parent_id) = task.parent_id
+            && parent_id != id
+        {
+            match get_task_by_id_with_transaction(parent_id, &mut tx).await {
+                Ok(_) => {}
+                Err(DBAccessError::NotFoundError(_)) => {
+                    return Err(DBAccessError::ConflictError(get_error_message(
+                        ErrorKey::TrashParentDeleted,
+                        [
+                            ("parent_type", json!("task")),
+                            ("parent_id", json!(parent_id)),
+                        ],
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        if get_project_by_id_with_transaction(task.project_id, &mut tx)
+            .await?
+            .is_none()
+        {
+            return Err(DBAccessError::ConflictError(get_error_message(
+                ErrorKey::TrashParentDeleted,
+                [
+                    ("parent_type", json!("project")),
+                    ("parent_id", json!(task.project_id)),
+                ],
             )));
         }
-        log::info!("Deleted task: {:?}", id);
 
-        Ok(())
+        let audit_log = self.task_audit_log(id, AuditAction::Restore, None, Some(&task));
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+        let version = get_version_with_transaction(&AuditEntityType::Task, id, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TrashRestoreFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("Restored task: {:?}", id);
+
+        Ok(Versioned {
+            value: task,
+            version: version.unwrap_or_default(),
+        })
+    }
+
+    // 全ての操作を1つのトランザクションで実行する。いずれかが失敗した場合は全てロールバックする
+    pub async fn run_task_batch(
+        &self,
+        operations: Vec<TaskBatchOperation>,
+    ) -> Result<Vec<TaskBatchResult>, BatchError> {
+        let _timer = QueryTimer::start("task", "run_task_batch");
+        validate_batch_operations_count(operations.len())?;
+
+        let mut tx = self.pool.begin().await.map_err(DBAccessError::from)?;
+
+        // 一時IDと作成したタスクのIDの対応
+        let mut temp_ids: HashMap<String, i64> = HashMap::new();
+        let mut results = Vec::new();
+        for (index, operation) in operations.into_iter().enumerate() {
+            let result = self
+                .run_task_batch_operation(index, operation, &mut temp_ids, &mut tx)
+                .await
+                .map_err(|e| BatchError::at(index, e))?;
+            results.push(result);
+        }
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::BatchCommitFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("Task batch: {} operations", results.len());
+        Ok(results)
+    }
+
+    async fn run_task_batch_operation(
+        &self,
+        index: usize,
+        operation: TaskBatchOperation,
+        temp_ids: &mut HashMap<String, i64>,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<TaskBatchResult, DBAccessError> {
+        match operation {
+            TaskBatchOperation::Create {
+                temp_id,
+                parent_temp_id,
+                mut task,
+            } => {
+                if let Some(temp_id) = &temp_id
+                    && temp_ids.contains_key(temp_id)
+                {
+                    return Err(DBAccessError::ValidationError(get_error_message(
+                        ErrorKey::BatchTempIdDuplicate,
+                        [("temp_id", json!(temp_id))],
+                    )));
+                }
+                resolve_parent_temp_id(&mut task, parent_temp_id.as_deref(), temp_ids)?;
+
+                let task = self.create_task_with_transaction(&task, tx).await?;
+                if let Some(temp_id) = &temp_id {
+                    temp_ids.insert(temp_id.clone(), task.task_id.unwrap_or_default());
+                }
+                Ok(TaskBatchResult {
+                    index,
+                    op: AuditAction::Create.to_str().to_string(),
+                    temp_id,
+                    task,
+                })
+            }
+            TaskBatchOperation::Update {
+                parent_temp_id,
+                version,
+                mut task,
+            } => {
+                resolve_parent_temp_id(&mut task, parent_temp_id.as_deref(), temp_ids)?;
+                bump_version_with_transaction(
+                    AuditEntityType::Task,
+                    task.task_id.unwrap_or_default(),
+                    version.as_ref().map(std::slice::from_ref),
+                    tx,
+                )
+                .await?;
+
+                let task = self.update_task_with_transaction(&task, tx).await?;
+                Ok(TaskBatchResult {
+                    index,
+                    op: AuditAction::Update.to_str().to_string(),
+                    temp_id: None,
+                    task,
+                })
+            }
+            TaskBatchOperation::Delete {
+                task_id,
+                version,
+                policy,
+            } => {
+                let policy = match policy {
+                    Some(policy) => DeletePolicy::from_string(&policy)
+                        .map_err(|e| DBAccessError::ValidationError(e.into()))?,
+                    None => DeletePolicy::default(),
+                };
+                bump_version_with_transaction(
+                    AuditEntityType::Task,
+                    task_id,
+                    version.as_ref().map(std::slice::from_ref),
+                    tx,
+                )
+                .await?;
+
+                let (task, _) = self
+                    .delete_task_with_transaction(task_id, policy, tx)
+                    .await?;
+                Ok(TaskBatchResult {
+                    index,
+                    op: AuditAction::Delete.to_str().to_string(),
+                    temp_id: None,
+                    task,
+                })
+            }
+        }
+    }
+}
+
+// parent_temp_idをバッチ内の前の操作で作成したタスクのIDに置き換える
+fn resolve_parent_temp_id(
+    task: &mut Task,
+    parent_temp_id: Option<&str>,
+    temp_ids: &HashMap<String, i64>,
+) -> Result<(), DBAccessError> {
+    let Some(parent_temp_id) = parent_temp_id else {
+        return Ok(());
+    };
+    if task.parent_id.is_some() {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::BatchParentIdConflict,
+            [
+                ("parent_id", json!(task.parent_id)),
+                ("parent_temp_id", json!(parent_temp_id)),
+            ],
+        )));
+    }
+
+    match temp_ids.get(parent_temp_id) {
+        Some(id) => {
+            task.parent_id = Some(*id);
+            Ok(())
+        }
+        None => Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::BatchTempIdNotFound,
+            [("temp_id", json!(parent_temp_id))],
+        ))),
     }
 }
 
@@ -332,7 +863,8 @@ pub fn build_task_where_clause(
     filter: &TaskFilter,
     user_ids: Option<&Vec<i64>>,
 ) -> (String, Vec<TaskFilterValue>) {
-    let mut where_calses = Vec::new();
+    // ゴミ箱のタスクは常に除外する
+    let mut where_calses = vec!["tasks.deleted_at IS NULL".to_string()];
     let mut bind_values: Vec<TaskFilterValue> = Vec::new();
 
     let mut index = 1;
@@ -428,6 +960,15 @@ pub fn build_task_where_clause(
         index += 1;
     }
 
+    if let Some(member_id) = filter.member_id {
+        where_calses.push(format!(
+            "tasks.project_id IN (SELECT project_id FROM project_members WHERE user_id = ${})",
+            index
+        ));
+        bind_values.push(TaskFilterValue::I64(member_id));
+        index += 1;
+    }
+
     if user_ids.is_some() {
         // バインド値の追加
         let mut id_idx = 0;
@@ -442,6 +983,7 @@ pub fn build_task_where_clause(
                 EXISTS (
                     SELECT 1 FROM user_assign
                     WHERE user_assign.task_id = tasks.task_id
+                      AND user_assign.deleted_at IS NULL
                       AND user_assign.user_id IN({})
                 )
             "#,
@@ -508,7 +1050,7 @@ pub async fn get_task_by_id_with_transaction(
         r#"
             SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
             FROM tasks
-            WHERE task_id = $1
+            WHERE task_id = $1 AND deleted_at IS NULL
         "#,
         id
     )
@@ -521,11 +1063,74 @@ pub async fn get_task_by_id_with_transaction(
         Some(task) => Ok(task),
         None => Err(DBAccessError::NotFoundError(get_error_message(
             ErrorKey::TaskGetByIdNotFound,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         ))),
     }
 }
 
+// タスクを参照している、ゴミ箱にない子のタスク・割り当て・コメントの件数
+struct TaskDependents {
+    children: i64,
+    user_assigns: i64,
+    comments: i64,
+}
+
+async fn count_task_dependents_with_transaction(
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<TaskDependents, DBAccessError> {
+    sqlx::query_as!(
+        TaskDependents,
+        r#"
+            SELECT
+                (SELECT COUNT(*) FROM tasks WHERE parent_id = $1 AND task_id != $1 AND deleted_at IS NULL) AS "children!: i64",
+                (SELECT COUNT(*) FROM user_assign WHERE task_id = $1 AND deleted_at IS NULL) AS "user_assigns!: i64",
+                (SELECT COUNT(*) FROM comments WHERE task_id = $1 AND deleted_at IS NULL) AS "comments!: i64"
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::TaskDeleteFailed,
+            e.to_string()
+        )))
+    })
+}
+
+// ゴミ箱にない子孫のタスク（自身を除く）を、階層の浅い順に返す
+async fn get_descendant_tasks_with_transaction(
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<Task>, DBAccessError> {
+    sqlx::query_as!(
+        Task,
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE parent_id = $1 AND task_id != $1 AND deleted_at IS NULL
+                UNION
+                SELECT tasks.task_id FROM tasks
+                INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+                WHERE tasks.deleted_at IS NULL
+            )
+            SELECT task_id, project_id, parent_id, level, name, description, status, deadline, created_at, updated_at
+            FROM tasks
+            WHERE task_id IN (SELECT task_id FROM subtree) AND task_id != $1
+            ORDER BY level, task_id
+        "#,
+        id
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::TaskDeleteFailed,
+            e.to_string()
+        )))
+    })
+}
+
 pub async fn get_tasks_count_with_transaction(
     tx: &mut Transaction<'_, Sqlite>,
     filter: Option<&TaskFilter>,
@@ -564,6 +1169,7 @@ pub async fn get_tasks_count_with_transaction(
             count
         }
         None => {
+            let query = format!("{} WHERE tasks.deleted_at IS NULL", query);
             let count = sqlx::query_scalar::<_, i64>(&query)
                 .fetch_one(&mut **tx)
                 .await
@@ -602,7 +1208,8 @@ pub async fn get_tasks_with_pagination_with_transaction(
     if user_ids.is_some() && user_ids.unwrap().len() > 0 {
         query.push_str(
             r#"
-            INNER JOIN user_assign ON tasks.task_id = user_assign.task_id
+            INNER JOIN user_assign
+                ON tasks.task_id = user_assign.task_id AND user_assign.deleted_at IS NULL
         "#,
         );
     }
@@ -625,6 +1232,8 @@ pub async fn get_tasks_with_pagination_with_transaction(
         // クエリのバインド値のインデックスを更新
         index = bind_values.len() + 1;
         filter_bind_values = bind_values;
+    } else {
+        query.push_str(" WHERE tasks.deleted_at IS NULL");
     }
 
     query.push_str(" ORDER BY tasks.task_id ASC");
@@ -669,9 +1278,7 @@ pub async fn get_tasks_with_pagination_with_transaction(
         )))
     })?;
 
-    log::debug!("Get tasks by filter: {:?}", result);
-
-    println!("result: {:?}", result);
+    log::debug!("Get tasks by filter: {:?}", result.len());
 
     Ok(deduplicate(result))
 }
@@ -0,0 +1,255 @@
+use crate::enums::{TaskFilterValue, TaskLevel};
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{TaskFilter, TaskTreeNode, TaskWithUser};
+use crate::repository::project_repo::get_project_by_id_with_transaction;
+use crate::repository::task_repo::{
+    build_task_where_clause, get_task_by_id_with_transaction, validate_task_filter,
+};
+use crate::repository::validations::{validate_task_id, validate_task_tree_max_depth};
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+use std::collections::HashMap;
+
+// ツリーの起点
+enum TaskTreeRoot {
+    Task(i64),
+    // プロジェクトの親のないタスクすべて
+    Project(i64),
+}
+
+#[derive(sqlx::FromRow)]
+struct TaskTreeRow {
+    #[sqlx(flatten)]
+    task: TaskWithUser,
+    depth: i64,
+    matched: bool,
+}
+
+pub struct TaskTreeRepository {
+    pool: Pool<Sqlite>,
+}
+
+impl TaskTreeRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+
+    // 結果はタスク自身をルートとする1件。フィルタに一致するタスクがなければ空になる
+    pub async fn get_task_tree(
+        &self,
+        task_id: i64,
+        filter: Option<&TaskFilter>,
+        user_ids: Option<&Vec<i64>>,
+        max_depth: Option<i64>,
+    ) -> Result<Vec<TaskTreeNode>, DBAccessError> {
+        let _timer = QueryTimer::start("task_tree", "get_task_tree");
+        validate_task_id(Some(task_id))?;
+
+        let mut tx = self.pool.begin().await.map_err(get_tree_error)?;
+        get_task_by_id_with_transaction(task_id, &mut tx).await?;
+        let nodes = get_task_tree_with_transaction(
+            TaskTreeRoot::Task(task_id),
+            filter,
+            user_ids,
+            max_depth,
+            &mut tx,
+        )
+        .await?;
+        tx.commit().await.map_err(get_tree_error)?;
+
+        log::debug!(
+            "Got task tree: Task ID = {}, Roots = {}",
+            task_id,
+            nodes.len()
+        );
+        Ok(nodes)
+    }
+
+    pub async fn get_project_tree(
+        &self,
+        project_id: i64,
+        filter: Option<&TaskFilter>,
+        user_ids: Option<&Vec<i64>>,
+        max_depth: Option<i64>,
+    ) -> Result<Vec<TaskTreeNode>, DBAccessError> {
+        let _timer = QueryTimer::start("task_tree", "get_project_tree");
+
+        let mut tx = self.pool.begin().await.map_err(get_tree_error)?;
+        if get_project_by_id_with_transaction(project_id, &mut tx)
+            .await?
+            .is_none()
+        {
+            return Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::ProjectGetByIdNotFound,
+                [("id", json!(project_id))],
+            )));
+        }
+        let nodes = get_task_tree_with_transaction(
+            TaskTreeRoot::Project(project_id),
+            filter,
+            user_ids,
+            max_depth,
+            &mut tx,
+        )
+        .await?;
+        tx.commit().await.map_err(get_tree_error)?;
+
+        log::debug!(
+            "Got project tree: Project ID = {}, Roots = {}",
+            project_id,
+            nodes.len()
+        );
+        Ok(nodes)
+    }
+}
+
+fn get_tree_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::TaskTreeGetFailed,
+        e.to_string()
+    )))
+}
+
+// 再帰CTEで子孫を1回のクエリで取得し、フィルタに一致したかどうかを行ごとに返す。
+// 権限はルートのプロジェクトでのみ確認するため、別のプロジェクトの子孫はたどらない。
+// 親子関係が循環している場合に備えて深さで打ち切り、最も浅い位置だけを使う
+async fn get_task_tree_with_transaction(
+    root: TaskTreeRoot,
+    filter: Option<&TaskFilter>,
+    user_ids: Option<&Vec<i64>>,
+    max_depth: Option<i64>,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Vec<TaskTreeNode>, DBAccessError> {
+    let max_depth = max_depth.unwrap_or(TaskLevel::max_level());
+    validate_task_tree_max_depth(max_depth)?;
+
+    let empty_filter = TaskFilter::new();
+    let filter = filter.unwrap_or(&empty_filter);
+    validate_task_filter(filter).map_err(|e| match e.downcast::<DBAccessError>() {
+        Ok(e) => e,
+        Err(e) => DBAccessError::QueryError(e),
+    })?;
+
+    let (where_clause, bind_values) = build_task_where_clause(filter, user_ids);
+    let root_index = bind_values.len() + 1;
+    let depth_index = root_index + 1;
+    let (root_id, anchor) = match root {
+        TaskTreeRoot::Task(task_id) => (task_id, format!("tasks.task_id = ${}", root_index)),
+        TaskTreeRoot::Project(project_id) => (
+            project_id,
+            format!(
+                "tasks.project_id = ${} AND tasks.parent_id IS NULL",
+                root_index
+            ),
+        ),
+    };
+
+    let query = format!(
+        r#"
+            WITH RECURSIVE subtree(task_id, project_id, depth) AS (
+                SELECT tasks.task_id, tasks.project_id, 0
+                FROM tasks
+                WHERE {anchor} AND tasks.deleted_at IS NULL
+                UNION ALL
+                SELECT tasks.task_id, tasks.project_id, subtree.depth + 1
+                FROM tasks
+                INNER JOIN subtree
+                    ON tasks.parent_id = subtree.task_id AND tasks.project_id = subtree.project_id
+                WHERE tasks.deleted_at IS NULL AND subtree.depth < ${depth_index}
+            ),
+            nodes AS (
+                SELECT task_id, MIN(depth) AS depth
+                FROM subtree
+                GROUP BY task_id
+            ),
+            matched AS (
+                SELECT tasks.task_id
+                FROM tasks
+                {where_clause}
+            )
+            SELECT
+                tasks.task_id,
+                tasks.project_id,
+                tasks.parent_id,
+                tasks.level,
+                tasks.name,
+                tasks.description,
+                tasks.status,
+                tasks.deadline,
+                tasks.created_at,
+                tasks.updated_at,
+                json_group_array(
+                    json_object(
+                        'user_id', users.user_id,
+                        'username', users.username,
+                        'email', users.email
+                    )
+                ) FILTER (WHERE users.user_id IS NOT NULL) AS users,
+                nodes.depth,
+                nodes.task_id IN (SELECT task_id FROM matched) AS matched
+            FROM nodes
+            INNER JOIN tasks ON tasks.task_id = nodes.task_id
+            LEFT JOIN user_assign
+                ON user_assign.task_id = tasks.task_id AND user_assign.deleted_at IS NULL
+            LEFT JOIN users ON users.user_id = user_assign.user_id
+            GROUP BY tasks.task_id
+            ORDER BY tasks.task_id ASC
+        "#
+    );
+
+    let mut query_builder = sqlx::query_as::<_, TaskTreeRow>(&query);
+    for value in bind_values.iter() {
+        match value {
+            TaskFilterValue::I64(v) => query_builder = query_builder.bind(v),
+            TaskFilterValue::String(v) => query_builder = query_builder.bind(v),
+        }
+    }
+    query_builder = query_builder.bind(root_id).bind(max_depth);
+
+    let rows = query_builder
+        .fetch_all(&mut **tx)
+        .await
+        .map_err(get_tree_error)?;
+
+    let (roots, descendants): (Vec<TaskTreeRow>, Vec<TaskTreeRow>) =
+        rows.into_iter().partition(|row| row.depth == 0);
+    let mut children_by_parent: HashMap<i64, Vec<TaskTreeRow>> = HashMap::new();
+    for row in descendants {
+        if let Some(parent_id) = row.task.parent_id {
+            children_by_parent.entry(parent_id).or_default().push(row);
+        }
+    }
+
+    Ok(roots
+        .into_iter()
+        .filter_map(|root| build_task_tree_node(root, &mut children_by_parent))
+        .collect())
+}
+
+// フィルタに一致せず、一致する子孫もないタスクは除く
+fn build_task_tree_node(
+    row: TaskTreeRow,
+    children_by_parent: &mut HashMap<i64, Vec<TaskTreeRow>>,
+) -> Option<TaskTreeNode> {
+    // 循環している場合は深さが1つ深い子だけをたどる
+    let children: Vec<TaskTreeNode> = children_by_parent
+        .remove(&row.task.task_id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|child| child.depth == row.depth + 1)
+        .filter_map(|child| build_task_tree_node(child, children_by_parent))
+        .collect();
+
+    if !row.matched && children.is_empty() {
+        return None;
+    }
+
+    Some(TaskTreeNode {
+        task: row.task,
+        depth: row.depth,
+        matched: row.matched,
+        children,
+    })
+}
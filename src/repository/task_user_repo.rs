@@ -1,12 +1,14 @@
 use crate::enums::TaskFilterValue;
 use crate::errors::db_error::DBAccessError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
 use crate::models::repository_model::task::TaskFilter;
 use crate::models::repository_model::taskwithuser::TaskWithUser;
 use crate::repository::task_repo::{
     build_task_where_clause, get_tasks_count_with_transaction, validate_task_filter,
 };
 use crate::repository::validations::{validate_pagination, validate_task_id};
+use serde_json::json;
 use sqlx::{Pool, Sqlite};
 
 pub struct TaskUserRepository {
@@ -39,6 +41,7 @@ impl TaskUserRepository {
         filter: Option<&TaskFilter>,
         user_ids: Option<&Vec<i64>>,
     ) -> Result<Vec<TaskWithUser>, DBAccessError> {
+        let _timer = QueryTimer::start("task_user", "get_tasks_and_users_by_filter");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::TaskUserGetByFilterFailed,
@@ -78,7 +81,8 @@ impl TaskUserRepository {
                         ), '[]'
                     ) AS users
                 FROM tasks
-                LEFT JOIN user_assign ON user_assign.task_id = tasks.task_id
+                LEFT JOIN user_assign
+                    ON user_assign.task_id = tasks.task_id AND user_assign.deleted_at IS NULL
                 LEFT JOIN users ON users.user_id = user_assign.user_id
             "#
         );
@@ -103,6 +107,8 @@ impl TaskUserRepository {
             query.push_str(&format!(" {}", where_clause));
             index = bind_values.len() + 1;
             filter_bind_values = bind_values;
+        } else {
+            query.push_str(" WHERE tasks.deleted_at IS NULL");
         }
 
         query.push_str(" GROUP BY tasks.task_id");
@@ -159,6 +165,7 @@ impl TaskUserRepository {
     }
 
     pub async fn get_task_by_id_with_user(&self, id: i64) -> Result<TaskWithUser, DBAccessError> {
+        let _timer = QueryTimer::start("task_user", "get_task_by_id_with_user");
         validate_task_id(Some(id))?;
 
         let mut tx = self.pool.begin().await.map_err(|e| {
@@ -200,9 +207,10 @@ impl TaskUserRepository {
                         ), '[]'
                     ) AS users
                 FROM tasks
-                LEFT JOIN user_assign ON user_assign.task_id = tasks.task_id
+                LEFT JOIN user_assign
+                    ON user_assign.task_id = tasks.task_id AND user_assign.deleted_at IS NULL
                 LEFT JOIN users ON users.user_id = user_assign.user_id
-                WHERE tasks.task_id = $1
+                WHERE tasks.task_id = $1 AND tasks.deleted_at IS NULL
                 GROUP BY tasks.task_id
             "#
         );
@@ -232,7 +240,7 @@ impl TaskUserRepository {
             }
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::TaskGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
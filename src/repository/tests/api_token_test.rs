@@ -0,0 +1,179 @@
+use crate::constants::API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS;
+use crate::errors::DBAccessError;
+use crate::models::ApiToken;
+use crate::repository::api_token_repo::ApiTokenRepository;
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod api_token_repo_test {
+    use super::*;
+
+    #[sqlx::test(fixtures("api_tokens"))]
+    async fn test_api_token_repo_create_api_token(pool: SqlitePool) {
+        let api_token_repo = ApiTokenRepository::new(pool);
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        let api_token = ApiToken::new(
+            2,
+            "deploy".to_string(),
+            "new_api_token_hash".to_string(),
+            Some(expires_at),
+        );
+        let created = api_token_repo.create_api_token(api_token).await.unwrap();
+        assert_ne!(created.api_token_id, None);
+        assert_eq!(created.user_id, 2);
+        assert_eq!(created.name, "deploy");
+        assert_eq!(created.expires_at, Some(expires_at));
+        assert_eq!(created.last_used_at, None);
+        assert_eq!(created.revoked_at, None);
+
+        // 有効期限なし
+        let api_token = ApiToken::new(
+            2,
+            "nightly".to_string(),
+            "new_api_token_hash_2".to_string(),
+            None,
+        );
+        let created = api_token_repo.create_api_token(api_token).await.unwrap();
+        assert_eq!(created.expires_at, None);
+
+        let api_tokens = api_token_repo.get_api_tokens_by_user_id(2).await.unwrap();
+        assert_eq!(api_tokens.len(), 3);
+    }
+
+    #[sqlx::test(fixtures("api_tokens"))]
+    async fn test_api_token_repo_create_api_token_invalid(pool: SqlitePool) {
+        let api_token_repo = ApiTokenRepository::new(pool);
+
+        // 存在しないユーザー
+        let api_token = ApiToken::new(100, "ci".to_string(), "hash".to_string(), None);
+        let result = api_token_repo.create_api_token(api_token).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 空の名前
+        let api_token = ApiToken::new(1, "".to_string(), "hash".to_string(), None);
+        let result = api_token_repo.create_api_token(api_token).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // 空のトークンハッシュ
+        let api_token = ApiToken::new(1, "ci".to_string(), "".to_string(), None);
+        let result = api_token_repo.create_api_token(api_token).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // 期限切れ
+        let api_token = ApiToken::new(1, "ci".to_string(), "hash".to_string(), Some(1));
+        let result = api_token_repo.create_api_token(api_token).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // 重複したトークンハッシュ
+        let api_token = ApiToken::new(
+            1,
+            "ci".to_string(),
+            "active_api_token_hash_0".to_string(),
+            None,
+        );
+        let result = api_token_repo.create_api_token(api_token).await;
+        assert!(matches!(result, Err(DBAccessError::QueryError(_))));
+    }
+
+    #[sqlx::test(fixtures("api_tokens"))]
+    async fn test_api_token_repo_use_api_token(pool: SqlitePool) {
+        let api_token_repo = ApiTokenRepository::new(pool);
+        let before = Utc::now().timestamp();
+
+        let api_token = api_token_repo
+            .use_api_token("active_api_token_hash_0")
+            .await
+            .unwrap();
+        assert_eq!(api_token.api_token_id, Some(1));
+        assert_eq!(api_token.user_id, 1);
+        assert!(api_token.last_used_at.unwrap() >= before);
+
+        let api_token = api_token_repo
+            .use_api_token("active_api_token_hash_1")
+            .await
+            .unwrap();
+        assert_eq!(api_token.api_token_id, Some(2));
+
+        for token_hash in [
+            "expired_api_token_hash",
+            "revoked_api_token_hash",
+            "unknown_api_token_hash",
+        ] {
+            let result = api_token_repo.use_api_token(token_hash).await;
+            assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+        }
+    }
+
+    #[sqlx::test(fixtures("api_tokens"))]
+    async fn test_api_token_repo_use_api_token_throttles_last_used_at(pool: SqlitePool) {
+        let api_token_repo = ApiTokenRepository::new(pool.clone());
+        let now = Utc::now().timestamp();
+
+        // 間隔内の使用では更新しない
+        let recent = now - API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS / 2;
+        sqlx::query("UPDATE api_tokens SET last_used_at = $1 WHERE api_token_id = 1")
+            .bind(recent)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let api_token = api_token_repo
+            .use_api_token("active_api_token_hash_0")
+            .await
+            .unwrap();
+        assert_eq!(api_token.last_used_at, Some(recent));
+        let stored = sqlx::query_scalar::<_, i64>(
+            "SELECT last_used_at FROM api_tokens WHERE api_token_id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(stored, recent);
+
+        // 間隔を過ぎていれば更新する
+        let stale = now - API_TOKEN_LAST_USED_UPDATE_INTERVAL_SECONDS - 1;
+        sqlx::query("UPDATE api_tokens SET last_used_at = $1 WHERE api_token_id = 1")
+            .bind(stale)
+            .execute(&pool)
+            .await
+            .unwrap();
+        let api_token = api_token_repo
+            .use_api_token("active_api_token_hash_0")
+            .await
+            .unwrap();
+        assert!(api_token.last_used_at.unwrap() >= now);
+        let stored = sqlx::query_scalar::<_, i64>(
+            "SELECT last_used_at FROM api_tokens WHERE api_token_id = 1",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(Some(stored), api_token.last_used_at);
+    }
+
+    #[sqlx::test(fixtures("api_tokens"))]
+    async fn test_api_token_repo_revoke_api_token(pool: SqlitePool) {
+        let api_token_repo = ApiTokenRepository::new(pool);
+
+        // 他のユーザーのトークンは無効化できない
+        let result = api_token_repo.revoke_api_token(1, 2).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        let revoked = api_token_repo.revoke_api_token(1, 1).await.unwrap();
+        assert_eq!(revoked.api_token_id, Some(1));
+        assert_ne!(revoked.revoked_at, None);
+
+        let result = api_token_repo
+            .use_api_token("active_api_token_hash_0")
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 無効化済み
+        let result = api_token_repo.revoke_api_token(1, 1).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        let result = api_token_repo.revoke_api_token(-1, 1).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+    }
+}
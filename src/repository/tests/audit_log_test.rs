@@ -0,0 +1,216 @@
+use crate::enums::{DeletePolicy, TaskLevel, TaskStatus};
+use crate::errors::DBAccessError;
+use crate::models::{AuditContext, AuditLogFilter, Comment, Project, Task, User};
+use crate::repository::audit_log_repo::AuditLogRepository;
+use crate::repository::comment_repo::CommentRepository;
+use crate::repository::project_repo::ProjectRepository;
+use crate::repository::task_repo::TaskRepository;
+use crate::repository::user_repo::UserRepository;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod audit_log_repo_test {
+    use super::*;
+
+    fn audit_context() -> AuditContext {
+        AuditContext::new(Some(1), Some("test-request-id".to_string()))
+    }
+
+    fn entity_filter(entity_type: &str, entity_id: i64) -> AuditLogFilter {
+        AuditLogFilter {
+            entity_type: Some(entity_type.to_string()),
+            entity_id: Some(entity_id),
+            ..AuditLogFilter::new()
+        }
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_audit_log_task_lifecycle(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone()).with_audit_context(audit_context());
+        let audit_log_repo = AuditLogRepository::new(pool);
+
+        let task = Task::new(
+            1,
+            None,
+            TaskLevel::Major.to_int(),
+            "Audited Task".to_string(),
+            None,
+            TaskStatus::NotStarted.to_int(),
+            None,
+        );
+        let created = task_repo.create_task(task).await.unwrap();
+        let task_id = created.task_id.unwrap();
+
+        let mut updated = created.clone();
+        updated.name = "Renamed Task".to_string();
+        task_repo.update_task(updated).await.unwrap();
+        task_repo
+            .delete_task(task_id, DeletePolicy::Restrict)
+            .await
+            .unwrap();
+
+        let filter = entity_filter("task", task_id);
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), None, None)
+            .await
+            .unwrap();
+        assert_eq!(audit_logs.len(), 3);
+
+        // 新しい順
+        let actions: Vec<&str> = audit_logs.iter().map(|log| log.action.as_str()).collect();
+        assert_eq!(actions, vec!["delete", "update", "create"]);
+        for audit_log in audit_logs.iter() {
+            assert_eq!(audit_log.actor_user_id, Some(1));
+            assert_eq!(audit_log.request_id.as_deref(), Some("test-request-id"));
+        }
+
+        let create_log = &audit_logs[2];
+        assert_eq!(create_log.before_json, None);
+        let after: Task = serde_json::from_str(create_log.after_json.as_ref().unwrap()).unwrap();
+        assert_eq!(after, created);
+
+        let update_log = &audit_logs[1];
+        let before: Task = serde_json::from_str(update_log.before_json.as_ref().unwrap()).unwrap();
+        let after: Task = serde_json::from_str(update_log.after_json.as_ref().unwrap()).unwrap();
+        assert_eq!(before.name, "Audited Task");
+        assert_eq!(after.name, "Renamed Task");
+
+        let delete_log = &audit_logs[0];
+        let before: Task = serde_json::from_str(delete_log.before_json.as_ref().unwrap()).unwrap();
+        assert_eq!(before.name, "Renamed Task");
+        assert_eq!(delete_log.after_json, None);
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_audit_log_not_written_on_failure(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone()).with_audit_context(audit_context());
+        let audit_log_repo = AuditLogRepository::new(pool);
+
+        let result = task_repo.delete_task(1000, DeletePolicy::Restrict).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // 存在しないプロジェクト
+        let task = Task::new(
+            1000,
+            None,
+            TaskLevel::Major.to_int(),
+            "Task".to_string(),
+            None,
+            TaskStatus::NotStarted.to_int(),
+            None,
+        );
+        assert!(task_repo.create_task(task).await.is_err());
+
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(None, None, None)
+            .await
+            .unwrap();
+        assert!(audit_logs.is_empty());
+    }
+
+    #[sqlx::test(fixtures("comments"))]
+    async fn test_audit_log_all_repositories(pool: SqlitePool) {
+        let context = audit_context();
+        let audit_log_repo = AuditLogRepository::new(pool.clone());
+
+        let user_repo = UserRepository::new(pool.clone()).with_audit_context(context.clone());
+        let user = User::new(
+            "audited_user".to_string(),
+            "audited@example.com".to_string(),
+            "password".to_string(),
+        )
+        .unwrap();
+        let user = user_repo.create_user(user).await.unwrap();
+
+        let project_repo = ProjectRepository::new(pool.clone()).with_audit_context(context.clone());
+        let project = project_repo
+            .create_project(Project::new("Audited Project".to_string()))
+            .await
+            .unwrap();
+
+        let comment_repo = CommentRepository::new(pool.clone()).with_audit_context(context);
+        let comment = comment_repo
+            .create_comment(Comment::new(1, 3, "Audited".to_string()))
+            .await
+            .unwrap();
+
+        for (entity_type, entity_id) in [
+            ("user", user.user_id.unwrap()),
+            ("project", project.project_id.unwrap()),
+            ("comment", comment.comment_id.unwrap()),
+        ] {
+            let filter = entity_filter(entity_type, entity_id);
+            let audit_logs = audit_log_repo
+                .get_audit_logs_by_filter(Some(&filter), None, None)
+                .await
+                .unwrap();
+            assert_eq!(audit_logs.len(), 1);
+            assert_eq!(audit_logs[0].action, "create");
+        }
+
+        // パスワードのハッシュは記録しない
+        let filter = entity_filter("user", user.user_id.unwrap());
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), None, None)
+            .await
+            .unwrap();
+        assert!(
+            !audit_logs[0]
+                .after_json
+                .as_ref()
+                .unwrap()
+                .contains("password")
+        );
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_audit_log_filter_and_pagination(pool: SqlitePool) {
+        let audit_log_repo = AuditLogRepository::new(pool.clone());
+        let project_repo = ProjectRepository::new(pool.clone()).with_audit_context(audit_context());
+        for i in 0..3 {
+            project_repo
+                .create_project(Project::new(format!("Project {}", i)))
+                .await
+                .unwrap();
+        }
+        // 操作者なし
+        ProjectRepository::new(pool)
+            .create_project(Project::new("Anonymous".to_string()))
+            .await
+            .unwrap();
+
+        let filter = AuditLogFilter {
+            actor_user_id: Some(1),
+            ..AuditLogFilter::new()
+        };
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), Some(&1), Some(&2))
+            .await
+            .unwrap();
+        assert_eq!(audit_logs.len(), 2);
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), Some(&2), Some(&2))
+            .await
+            .unwrap();
+        assert_eq!(audit_logs.len(), 1);
+
+        let filter = AuditLogFilter {
+            action: Some("update".to_string()),
+            ..AuditLogFilter::new()
+        };
+        let audit_logs = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), None, None)
+            .await
+            .unwrap();
+        assert!(audit_logs.is_empty());
+
+        let filter = AuditLogFilter {
+            entity_type: Some("unknown".to_string()),
+            ..AuditLogFilter::new()
+        };
+        let result = audit_log_repo
+            .get_audit_logs_by_filter(Some(&filter), None, None)
+            .await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+    }
+}
@@ -174,7 +174,7 @@ mod comment_repo_test {
             updated_at: None,
         };
 
-        let updated_comment = comment_repo.update_comment(comment).await.unwrap();
+        let updated_comment = comment_repo.update_comment(comment).await.unwrap().value;
         assert_eq!(updated_comment.user_id, 2);
         assert_eq!(updated_comment.task_id, 3);
         assert_eq!(updated_comment.content, "Test Comment 0 updated");
@@ -355,7 +355,9 @@ mod comment_repo_test {
     #[sqlx::test(fixtures("comments"))]
     async fn test_comment_repo_get_comment_count_with_transaction(pool: SqlitePool) {
         let mut tx = pool.begin().await.unwrap();
-        let count = get_comment_count_with_transaction(&mut tx).await.unwrap();
+        let count = get_comment_count_with_transaction(None, &mut tx)
+            .await
+            .unwrap();
         assert_eq!(count, 12);
         tx.commit().await.unwrap();
     }
@@ -363,7 +365,7 @@ mod comment_repo_test {
     #[sqlx::test(fixtures("comments"))]
     async fn test_comment_repo_get_comment_count_by_task_id_with_transaction(pool: SqlitePool) {
         let mut tx = pool.begin().await.unwrap();
-        let count = get_comment_count_by_task_id_with_transaction(3, &mut tx)
+        let count = get_comment_count_by_task_id_with_transaction(3, None, &mut tx)
             .await
             .unwrap();
         assert_eq!(count, 4);
@@ -373,7 +375,7 @@ mod comment_repo_test {
     #[sqlx::test(fixtures("comments"))]
     async fn test_comment_repo_get_comment_count_by_user_id_with_transaction(pool: SqlitePool) {
         let mut tx = pool.begin().await.unwrap();
-        let count = get_comment_count_by_user_id_with_transaction(1, &mut tx)
+        let count = get_comment_count_by_user_id_with_transaction(1, None, &mut tx)
             .await
             .unwrap();
         assert_eq!(count, 6);
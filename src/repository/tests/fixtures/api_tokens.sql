@@ -0,0 +1,7 @@
+INSERT INTO users (user_id, username, email, password_hash) VALUES (1, 'TestUser0', 'test0@example.com', 'password0');
+INSERT INTO users (user_id, username, email, password_hash) VALUES (2, 'TestUser1', 'test1@example.com', 'password1');
+
+INSERT INTO api_tokens (api_token_id, user_id, name, token_hash, created_at, expires_at, last_used_at, revoked_at) VALUES (1, 1, 'ci', 'active_api_token_hash_0', 0, NULL, NULL, NULL);
+INSERT INTO api_tokens (api_token_id, user_id, name, token_hash, created_at, expires_at, last_used_at, revoked_at) VALUES (2, 1, 'script', 'active_api_token_hash_1', 0, 4102444800, NULL, NULL);
+INSERT INTO api_tokens (api_token_id, user_id, name, token_hash, created_at, expires_at, last_used_at, revoked_at) VALUES (3, 1, 'expired', 'expired_api_token_hash', 0, 1, NULL, NULL);
+INSERT INTO api_tokens (api_token_id, user_id, name, token_hash, created_at, expires_at, last_used_at, revoked_at) VALUES (4, 2, 'revoked', 'revoked_api_token_hash', 0, NULL, NULL, 1);
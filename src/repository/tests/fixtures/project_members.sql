@@ -0,0 +1,12 @@
+INSERT INTO users (user_id, username, email, password_hash) VALUES (1, 'TestUser0', 'test0@example.com', 'password0');
+INSERT INTO users (user_id, username, email, password_hash) VALUES (2, 'TestUser1', 'test1@example.com', 'password1');
+INSERT INTO users (user_id, username, email, password_hash) VALUES (3, 'TestUser2', 'test2@example.com', 'password2');
+INSERT INTO users (user_id, username, email, password_hash) VALUES (4, 'TestUser3', 'test3@example.com', 'password3');
+
+INSERT INTO projects (project_id, name) VALUES (1, 'Test Project 0');
+INSERT INTO projects (project_id, name) VALUES (2, 'Test Project 1');
+
+INSERT INTO project_members (project_member_id, project_id, user_id, role) VALUES (1, 1, 1, 0);
+INSERT INTO project_members (project_member_id, project_id, user_id, role) VALUES (2, 1, 2, 1);
+INSERT INTO project_members (project_member_id, project_id, user_id, role) VALUES (3, 1, 3, 2);
+INSERT INTO project_members (project_member_id, project_id, user_id, role) VALUES (4, 2, 2, 0);
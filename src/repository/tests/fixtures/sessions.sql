@@ -0,0 +1,7 @@
+INSERT INTO users (user_id, username, email, password_hash) VALUES (1, 'TestUser0', 'test0@example.com', 'password0');
+INSERT INTO users (user_id, username, email, password_hash) VALUES (2, 'TestUser1', 'test1@example.com', 'password1');
+
+INSERT INTO sessions (session_id, user_id, token_hash, created_at, expires_at, revoked_at) VALUES (1, 1, 'active_token_hash_0', 0, 4102444800, NULL);
+INSERT INTO sessions (session_id, user_id, token_hash, created_at, expires_at, revoked_at) VALUES (2, 1, 'active_token_hash_1', 0, 4102444800, NULL);
+INSERT INTO sessions (session_id, user_id, token_hash, created_at, expires_at, revoked_at) VALUES (3, 1, 'expired_token_hash', 0, 1, NULL);
+INSERT INTO sessions (session_id, user_id, token_hash, created_at, expires_at, revoked_at) VALUES (4, 2, 'revoked_token_hash', 0, 4102444800, 1);
@@ -0,0 +1,161 @@
+use crate::errors::DBAccessError;
+use crate::repository::migration_repo::{MIGRATOR, MigrationRepository};
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod migration_repo_test {
+    use super::*;
+
+    fn versions() -> Vec<i64> {
+        MIGRATOR
+            .iter()
+            .filter(|migration| !migration.migration_type.is_down_migration())
+            .map(|migration| migration.version)
+            .collect()
+    }
+
+    async fn table_exists(pool: &SqlitePool, name: &str) -> bool {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = $1",
+        )
+        .bind(name)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+            > 0
+    }
+
+    async fn column_exists(pool: &SqlitePool, table: &str, column: &str) -> bool {
+        sqlx::query_scalar::<_, i64>("SELECT COUNT(*) FROM pragma_table_info($1) WHERE name = $2")
+            .bind(table)
+            .bind(column)
+            .fetch_one(pool)
+            .await
+            .unwrap()
+            > 0
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_run_and_revert_migrations(pool: SqlitePool) {
+        let migration_repo = MigrationRepository::new(pool.clone());
+        let versions = versions();
+
+        let status = migration_repo.get_migration_status().await.unwrap();
+        assert_eq!(status.len(), versions.len());
+        assert!(status.iter().all(|migration| !migration.applied));
+        assert_eq!(migration_repo.get_current_version().await.unwrap(), None);
+        // 状態の取得では管理テーブルを作成しない
+        assert!(!table_exists(&pool, "_sqlx_migrations").await);
+
+        let applied = migration_repo.run_pending_migrations().await.unwrap();
+        assert_eq!(applied, versions);
+        assert!(table_exists(&pool, "task_history").await);
+        assert!(column_exists(&pool, "tasks", "version").await);
+        assert!(column_exists(&pool, "tasks", "deleted_at").await);
+        assert!(column_exists(&pool, "users", "is_admin").await);
+        assert_eq!(
+            migration_repo.get_current_version().await.unwrap(),
+            versions.last().copied()
+        );
+
+        // 2回目は何も適用しない
+        let applied = migration_repo.run_pending_migrations().await.unwrap();
+        assert!(applied.is_empty());
+
+        // 省略時は最新の1件のみ取り消す
+        let reverted = migration_repo.revert_migrations(None).await.unwrap();
+        assert_eq!(reverted, vec![*versions.last().unwrap()]);
+        assert!(!column_exists(&pool, "users", "is_admin").await);
+        assert!(column_exists(&pool, "tasks", "deleted_at").await);
+        assert_eq!(
+            migration_repo.get_current_version().await.unwrap(),
+            Some(versions[versions.len() - 2])
+        );
+
+        let reverted = migration_repo
+            .revert_migrations(Some(versions[0]))
+            .await
+            .unwrap();
+        assert_eq!(reverted.len(), versions.len() - 2);
+        assert!(table_exists(&pool, "users").await);
+        assert!(!table_exists(&pool, "projects").await);
+
+        let reverted = migration_repo.revert_migrations(Some(0)).await.unwrap();
+        assert_eq!(reverted, vec![versions[0]]);
+        assert!(!table_exists(&pool, "users").await);
+        assert_eq!(migration_repo.get_current_version().await.unwrap(), None);
+    }
+
+    #[sqlx::test(migrations = false)]
+    async fn test_revert_soft_delete_removes_trash(pool: SqlitePool) {
+        let migration_repo = MigrationRepository::new(pool.clone());
+        migration_repo.run_pending_migrations().await.unwrap();
+
+        // ゴミ箱のタスクの子、ゴミ箱のプロジェクトのタスクはdeleted_atがなくても削除される
+        sqlx::raw_sql(
+            r#"
+                INSERT INTO users (user_id, username, email, password_hash)
+                VALUES (1, 'user', 'user@example.com', 'hash');
+                INSERT INTO projects (project_id, name, deleted_at)
+                VALUES (1, 'live', NULL), (2, 'trashed', 100);
+                INSERT INTO tasks (task_id, project_id, parent_id, level, name, status, created_at, deleted_at)
+                VALUES (1, 1, NULL, 0, 'live', 0, 0, NULL),
+                    (2, 1, NULL, 0, 'trashed', 0, 0, 100),
+                    (3, 1, 2, 1, 'child of trashed', 0, 0, NULL),
+                    (4, 2, NULL, 0, 'in trashed project', 0, 0, NULL);
+                INSERT INTO comments (comment_id, user_id, task_id, content, created_at, deleted_at)
+                VALUES (1, 1, 1, 'live', 0, NULL),
+                    (2, 1, 1, 'trashed', 0, 100),
+                    (3, 1, 3, 'on child of trashed', 0, NULL);
+                INSERT INTO user_assign (user_assign_id, user_id, task_id, deleted_at)
+                VALUES (1, 1, 1, NULL), (2, 1, 2, 100);
+                INSERT INTO audit_log (audit_log_id, entity_type, entity_id, action, created_at)
+                VALUES (1, 'task', 2, 'restore', 0), (2, 'task', 2, 'purge', 0), (3, 'task', 1, 'create', 0);
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        migration_repo
+            .revert_migrations(Some(20250606000000))
+            .await
+            .unwrap();
+        assert!(!column_exists(&pool, "tasks", "deleted_at").await);
+
+        for (table, id) in [
+            ("projects", "project_id"),
+            ("tasks", "task_id"),
+            ("comments", "comment_id"),
+            ("user_assign", "user_assign_id"),
+        ] {
+            let ids: Vec<i64> =
+                sqlx::query_scalar(&format!("SELECT {id} FROM {table} ORDER BY {id}"))
+                    .fetch_all(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(ids, vec![1], "{}", table);
+        }
+
+        let actions: Vec<String> =
+            sqlx::query_scalar("SELECT action FROM audit_log ORDER BY audit_log_id")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(actions, vec!["update", "delete", "create"]);
+    }
+
+    #[sqlx::test]
+    async fn test_revert_migrations_with_invalid_target(pool: SqlitePool) {
+        let migration_repo = MigrationRepository::new(pool);
+
+        let status = migration_repo.get_migration_status().await.unwrap();
+        assert!(status.iter().all(|migration| migration.applied));
+        assert!(status.iter().all(|migration| !migration.checksum_mismatch));
+
+        let result = migration_repo.revert_migrations(Some(1)).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+        let result = migration_repo.revert_migrations(Some(-1)).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+    }
+}
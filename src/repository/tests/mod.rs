@@ -1,10 +1,26 @@
 #[cfg(test)]
+mod api_token_test;
+#[cfg(test)]
+mod audit_log_test;
+#[cfg(test)]
 mod comment_test;
 #[cfg(test)]
+mod migration_test;
+#[cfg(test)]
+mod password_test;
+#[cfg(test)]
+mod project_member_test;
+#[cfg(test)]
 mod project_test;
 #[cfg(test)]
+mod session_test;
+#[cfg(test)]
+mod task_history_test;
+#[cfg(test)]
 mod task_test;
 #[cfg(test)]
+mod task_tree_test;
+#[cfg(test)]
 mod task_user_test;
 #[cfg(test)]
 mod user_assign_test;
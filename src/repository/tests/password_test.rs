@@ -0,0 +1,85 @@
+use crate::repository::password::{
+    hash_password, hash_password_async, is_legacy_hash, is_phc_hash, needs_rehash,
+    verify_dummy_password, verify_password, verify_password_async,
+};
+use crate::repository::validations::validate_user_password;
+use sha2::{Digest, Sha256};
+
+#[cfg(test)]
+mod password_test {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify_password() {
+        let hash = hash_password("correct horse battery staple").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(is_phc_hash(&hash));
+        assert!(!is_legacy_hash(&hash));
+        assert!(validate_user_password(&hash).is_ok());
+
+        assert!(verify_password("correct horse battery staple", &hash).unwrap());
+        assert!(!verify_password("wrong password", &hash).unwrap());
+        assert!(!needs_rehash(&hash));
+    }
+
+    #[test]
+    fn test_hash_password_is_salted() {
+        let hash1 = hash_password("password").unwrap();
+        let hash2 = hash_password("password").unwrap();
+        assert_ne!(hash1, hash2);
+        assert!(verify_password("password", &hash1).unwrap());
+        assert!(verify_password("password", &hash2).unwrap());
+    }
+
+    #[test]
+    fn test_hash_password_empty() {
+        assert!(hash_password("").is_err());
+    }
+
+    #[test]
+    fn test_verify_legacy_password() {
+        let legacy = format!("{:x}", Sha256::digest("password".as_bytes()));
+        assert!(is_legacy_hash(&legacy));
+        assert!(validate_user_password(&legacy).is_ok());
+        assert!(verify_password("password", &legacy).unwrap());
+        assert!(!verify_password("wrong password", &legacy).unwrap());
+        assert!(needs_rehash(&legacy));
+    }
+
+    #[test]
+    fn test_needs_rehash_with_old_params() {
+        // 現在の設定と異なる作業量で作成されたハッシュ
+        let hash = "$argon2id$v=19$m=8,t=1,p=1$c2FsdHNhbHQ$4FtJCzPeVeJ1+3D0CzBOuw";
+        assert!(is_phc_hash(hash));
+        assert!(needs_rehash(hash));
+    }
+
+    #[actix_web::test]
+    async fn test_hash_and_verify_password_async() {
+        let hash = hash_password_async("password".to_string()).await.unwrap();
+        assert!(
+            verify_password_async("password".to_string(), hash.clone())
+                .await
+                .unwrap()
+        );
+        assert!(
+            !verify_password_async("wrong password".to_string(), hash)
+                .await
+                .unwrap()
+        );
+        assert!(hash_password_async("".to_string()).await.is_err());
+    }
+
+    #[test]
+    fn test_verify_dummy_password() {
+        assert!(verify_dummy_password("password").is_ok());
+        assert!(verify_dummy_password("").is_ok());
+    }
+
+    #[test]
+    fn test_verify_invalid_hash() {
+        assert!(!is_phc_hash("invalid_hash"));
+        assert!(!verify_password("password", "invalid_hash").unwrap());
+        assert!(validate_user_password("invalid_hash").is_err());
+    }
+}
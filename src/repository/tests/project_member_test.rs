@@ -0,0 +1,164 @@
+use crate::enums::ProjectRole;
+use crate::errors::DBAccessError;
+use crate::models::{Project, ProjectMember};
+use crate::repository::project_member_repo::ProjectMemberRepository;
+use crate::repository::project_repo::ProjectRepository;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod project_member_repo_test {
+    use super::*;
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_member_repo_create_project_member(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        let project_member = ProjectMember::new(2, 4, ProjectRole::Viewer);
+        let created = project_member_repo
+            .create_project_member(project_member)
+            .await
+            .unwrap();
+        assert_ne!(created.project_member_id, None);
+        assert_eq!(created.project_id, 2);
+        assert_eq!(created.user_id, 4);
+        assert_eq!(created.role, ProjectRole::Viewer.to_int());
+
+        let role = project_member_repo.get_project_role(2, 4).await.unwrap();
+        assert_eq!(role, Some(ProjectRole::Viewer));
+    }
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_member_repo_create_project_member_invalid(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        // 既にメンバー
+        let result = project_member_repo
+            .create_project_member(ProjectMember::new(1, 2, ProjectRole::Viewer))
+            .await;
+        assert!(matches!(result, Err(DBAccessError::ConflictError(_))));
+
+        // 存在しないプロジェクト
+        let result = project_member_repo
+            .create_project_member(ProjectMember::new(100, 4, ProjectRole::Viewer))
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 存在しないユーザー
+        let result = project_member_repo
+            .create_project_member(ProjectMember::new(1, 100, ProjectRole::Viewer))
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 不正なロール
+        let mut project_member = ProjectMember::new(1, 4, ProjectRole::Viewer);
+        project_member.role = 3;
+        let result = project_member_repo
+            .create_project_member(project_member)
+            .await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+    }
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_member_repo_get_project_members_by_project_id(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        let project_members = project_member_repo
+            .get_project_members_by_project_id(1)
+            .await
+            .unwrap();
+        assert_eq!(project_members.len(), 3);
+        assert_eq!(project_members[0].user_id, 1);
+        assert_eq!(project_members[1].user_id, 2);
+        assert_eq!(project_members[2].user_id, 3);
+
+        assert_eq!(
+            project_member_repo.get_project_role(1, 1).await.unwrap(),
+            Some(ProjectRole::Owner)
+        );
+        assert_eq!(
+            project_member_repo.get_project_role(2, 1).await.unwrap(),
+            None
+        );
+    }
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_member_repo_update_project_member_role(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        let updated = project_member_repo
+            .update_project_member_role(1, 3, ProjectRole::Member.to_int())
+            .await
+            .unwrap();
+        assert_eq!(updated.role, ProjectRole::Member.to_int());
+
+        // 唯一のオーナーは降格できない
+        let result = project_member_repo
+            .update_project_member_role(1, 1, ProjectRole::Member.to_int())
+            .await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // オーナーが複数いれば降格できる
+        project_member_repo
+            .update_project_member_role(1, 2, ProjectRole::Owner.to_int())
+            .await
+            .unwrap();
+        project_member_repo
+            .update_project_member_role(1, 1, ProjectRole::Member.to_int())
+            .await
+            .unwrap();
+
+        let result = project_member_repo
+            .update_project_member_role(1, 4, ProjectRole::Member.to_int())
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_member_repo_delete_project_member(pool: SqlitePool) {
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        project_member_repo
+            .delete_project_member(1, 3)
+            .await
+            .unwrap();
+        assert_eq!(
+            project_member_repo.get_project_role(1, 3).await.unwrap(),
+            None
+        );
+
+        // 唯一のオーナーは削除できない
+        let result = project_member_repo.delete_project_member(1, 1).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        let result = project_member_repo.delete_project_member(1, 3).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+
+    #[sqlx::test(fixtures("project_members"))]
+    async fn test_project_repo_create_project_with_owner(pool: SqlitePool) {
+        let project_repo = ProjectRepository::new(pool.clone());
+        let project_member_repo = ProjectMemberRepository::new(pool);
+
+        let project = project_repo
+            .create_project_with_owner(Project::new("Owned Project".to_string()), 4)
+            .await
+            .unwrap();
+        let project_id = project.project_id.unwrap();
+
+        assert_eq!(
+            project_member_repo
+                .get_project_role(project_id, 4)
+                .await
+                .unwrap(),
+            Some(ProjectRole::Owner)
+        );
+
+        // オーナーの登録に失敗した場合はプロジェクトも作成されない
+        let result = project_repo
+            .create_project_with_owner(Project::new("No Owner Project".to_string()), 100)
+            .await;
+        assert!(result.is_err());
+        let result = project_repo.get_project_by_name("No Owner Project").await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+}
@@ -1,5 +1,7 @@
+use crate::enums::DeletePolicy;
 use crate::models::Project;
 use crate::repository::project_repo::ProjectRepository;
+use crate::repository::task_repo::TaskRepository;
 use chrono::Utc;
 use sqlx::sqlite::SqlitePool;
 
@@ -150,7 +152,7 @@ mod project_repo_test {
         assert!(retrieved_project.is_ok());
 
         project_repo
-            .delete_project(created_project.project_id.unwrap())
+            .delete_project(created_project.project_id.unwrap(), DeletePolicy::Restrict)
             .await
             .unwrap();
         let retrieved_project = project_repo
@@ -195,10 +197,32 @@ mod project_repo_test {
         assert!(result.is_err());
     }
 
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_project_repo_delete_and_restore_project(pool: SqlitePool) {
+        let project_repo = ProjectRepository::new(pool.clone());
+        let task_repo = TaskRepository::new(pool);
+
+        // プロジェクトのタスクも一緒にゴミ箱に移動し、一緒に戻る
+        project_repo
+            .delete_project(2, DeletePolicy::Cascade)
+            .await
+            .unwrap();
+        assert!(project_repo.get_project_by_id(2).await.is_err());
+        assert!(task_repo.get_task_by_id(4).await.is_err());
+        assert!(task_repo.get_task_by_id(1).await.is_ok());
+
+        let restored = project_repo.restore_project(2).await.unwrap().value;
+        assert_eq!(restored.project_id, Some(2));
+        assert!(task_repo.get_task_by_id(4).await.is_ok());
+        assert!(task_repo.get_task_by_id(12).await.is_ok());
+    }
+
     #[sqlx::test]
     async fn test_project_repo_delete_nonexistent_project(pool: SqlitePool) {
         let project_repo = ProjectRepository::new(pool);
-        let result = project_repo.delete_project(114514).await;
+        let result = project_repo
+            .delete_project(114514, DeletePolicy::Restrict)
+            .await;
         assert!(result.is_err());
     }
 
@@ -0,0 +1,109 @@
+use crate::errors::DBAccessError;
+use crate::models::Session;
+use crate::repository::session_repo::SessionRepository;
+use chrono::Utc;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod session_repo_test {
+    use super::*;
+
+    #[sqlx::test(fixtures("sessions"))]
+    async fn test_session_repo_create_session(pool: SqlitePool) {
+        let session_repo = SessionRepository::new(pool);
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        let session = Session::new(2, "new_token_hash".to_string(), expires_at);
+        let created = session_repo.create_session(session).await.unwrap();
+        assert_ne!(created.session_id, None);
+        assert_eq!(created.user_id, 2);
+        assert_eq!(created.expires_at, expires_at);
+        assert_eq!(created.revoked_at, None);
+
+        let retrieved = session_repo
+            .get_active_session_by_token_hash("new_token_hash")
+            .await
+            .unwrap();
+        assert_eq!(retrieved, created);
+    }
+
+    #[sqlx::test(fixtures("sessions"))]
+    async fn test_session_repo_create_session_invalid(pool: SqlitePool) {
+        let session_repo = SessionRepository::new(pool);
+        let expires_at = Utc::now().timestamp() + 3600;
+
+        // 存在しないユーザー
+        let session = Session::new(100, "token_hash".to_string(), expires_at);
+        let result = session_repo.create_session(session).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 空のトークンハッシュ
+        let session = Session::new(1, "".to_string(), expires_at);
+        let result = session_repo.create_session(session).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        // 期限切れ
+        let session = Session::new(1, "token_hash".to_string(), 1);
+        let result = session_repo.create_session(session).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+    }
+
+    #[sqlx::test(fixtures("sessions"))]
+    async fn test_session_repo_get_active_session_by_token_hash(pool: SqlitePool) {
+        let session_repo = SessionRepository::new(pool);
+
+        let session = session_repo
+            .get_active_session_by_token_hash("active_token_hash_0")
+            .await
+            .unwrap();
+        assert_eq!(session.session_id, Some(1));
+        assert_eq!(session.user_id, 1);
+
+        for token_hash in ["expired_token_hash", "revoked_token_hash", "unknown"] {
+            let result = session_repo
+                .get_active_session_by_token_hash(token_hash)
+                .await;
+            assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+        }
+    }
+
+    #[sqlx::test(fixtures("sessions"))]
+    async fn test_session_repo_revoke_session(pool: SqlitePool) {
+        let session_repo = SessionRepository::new(pool);
+
+        session_repo
+            .revoke_session("active_token_hash_0")
+            .await
+            .unwrap();
+        let result = session_repo
+            .get_active_session_by_token_hash("active_token_hash_0")
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+
+        // 他のセッションは影響を受けない
+        session_repo
+            .get_active_session_by_token_hash("active_token_hash_1")
+            .await
+            .unwrap();
+
+        // 既に無効化済み
+        let result = session_repo.revoke_session("active_token_hash_0").await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+
+    #[sqlx::test(fixtures("sessions"))]
+    async fn test_session_repo_revoke_sessions_by_user_id(pool: SqlitePool) {
+        let session_repo = SessionRepository::new(pool);
+
+        // 期限切れで未無効化のセッションも対象になる
+        let revoked = session_repo.revoke_sessions_by_user_id(1).await.unwrap();
+        assert_eq!(revoked, 3);
+
+        for token_hash in ["active_token_hash_0", "active_token_hash_1"] {
+            let result = session_repo
+                .get_active_session_by_token_hash(token_hash)
+                .await;
+            assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+        }
+    }
+}
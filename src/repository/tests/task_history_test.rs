@@ -0,0 +1,114 @@
+use crate::errors::DBAccessError;
+use crate::models::{AuditContext, Task};
+use crate::repository::task_history_repo::{TaskHistoryRepository, build_task_histories};
+use crate::repository::task_repo::TaskRepository;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod task_history_repo_test {
+    use super::*;
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_history_recorded_on_update(pool: SqlitePool) {
+        let task_repo =
+            TaskRepository::new(pool.clone()).with_audit_context(AuditContext::new(Some(1), None));
+        let task_history_repo = TaskHistoryRepository::new(pool);
+
+        let mut task = task_repo.get_task_by_id(1).await.unwrap();
+        task.name = "Renamed Task".to_string();
+        task.description = None;
+        task.deadline = None;
+        task_repo.update_task(task.clone()).await.unwrap();
+
+        // 変更のない更新では履歴は増えない
+        task_repo.update_task(task.clone()).await.unwrap();
+
+        task.status = 1;
+        task_repo.update_task(task).await.unwrap();
+
+        let histories = task_history_repo
+            .get_task_history_by_task_id(1, None, None)
+            .await
+            .unwrap();
+        assert_eq!(histories.len(), 4);
+
+        // 古い順
+        assert_eq!(histories[0].field_name, "name");
+        assert_eq!(
+            histories[0].old_value.as_deref(),
+            Some("Test PJ0 Major TASK")
+        );
+        assert_eq!(histories[0].new_value.as_deref(), Some("Renamed Task"));
+        assert_eq!(histories[1].field_name, "description");
+        assert_eq!(histories[1].new_value, None);
+        assert_eq!(histories[2].field_name, "deadline");
+        assert_eq!(histories[2].old_value.as_deref(), Some("0"));
+        assert_eq!(histories[2].new_value, None);
+        assert_eq!(histories[3].field_name, "status");
+        assert_eq!(histories[3].old_value.as_deref(), Some("0"));
+        assert_eq!(histories[3].new_value.as_deref(), Some("1"));
+        for history in histories.iter() {
+            assert_eq!(history.task_id, 1);
+            assert_eq!(history.changed_by, Some(1));
+            assert!(history.changed_at > 0);
+        }
+
+        let histories = task_history_repo
+            .get_task_history_by_task_id(1, Some(&2), Some(&3))
+            .await
+            .unwrap();
+        assert_eq!(histories.len(), 1);
+        assert_eq!(histories[0].field_name, "status");
+
+        let histories = task_history_repo
+            .get_task_history_by_task_id(2, None, None)
+            .await
+            .unwrap();
+        assert!(histories.is_empty());
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_get_task_history_with_invalid_task_id(pool: SqlitePool) {
+        let task_history_repo = TaskHistoryRepository::new(pool);
+
+        let result = task_history_repo
+            .get_task_history_by_task_id(-1, None, None)
+            .await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        let result = task_history_repo
+            .get_task_history_by_task_id(9999, None, None)
+            .await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+
+    #[test]
+    fn test_build_task_histories() {
+        let before = Task::new(
+            1,
+            Some(2),
+            1,
+            "Task".to_string(),
+            Some("Description".to_string()),
+            0,
+            None,
+        );
+        let mut after = before.clone();
+        after.task_id = Some(5);
+        after.parent_id = None;
+        after.deadline = Some(100);
+
+        let histories = build_task_histories(&before, &after, None);
+        assert_eq!(histories.len(), 2);
+        assert_eq!(histories[0].task_id, 5);
+        assert_eq!(histories[0].field_name, "parent_id");
+        assert_eq!(histories[0].old_value.as_deref(), Some("2"));
+        assert_eq!(histories[0].new_value, None);
+        assert_eq!(histories[1].field_name, "deadline");
+        assert_eq!(histories[1].old_value, None);
+        assert_eq!(histories[1].new_value.as_deref(), Some("100"));
+        assert_eq!(histories[1].changed_by, None);
+
+        assert!(build_task_histories(&before, &before, None).is_empty());
+    }
+}
@@ -1,9 +1,13 @@
+use crate::enums::AuditEntityType;
+use crate::enums::DeletePolicy;
 use crate::enums::TaskLevel;
 use crate::enums::TaskStatus;
-use crate::models::{Task, task::TaskFilter};
+use crate::errors::DBAccessError;
+use crate::models::{Task, TaskBatchOperation, task::TaskFilter};
 use crate::repository::task_repo::{
     TaskRepository, get_task_by_id_with_transaction, get_tasks_with_pagination_with_transaction,
 };
+use crate::repository::version_repo::get_version;
 use chrono::Utc;
 use sqlx::sqlite::SqlitePool;
 
@@ -159,6 +163,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), Some(&3), Some(&5))
@@ -220,6 +225,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -248,6 +254,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -274,6 +281,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
 
         let tasks = task_repo
@@ -303,6 +311,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -329,6 +338,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -358,6 +368,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -386,6 +397,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -412,6 +424,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -437,6 +450,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -466,6 +480,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -488,6 +503,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -512,6 +528,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -540,6 +557,7 @@ mod task_repo_test {
             updated_at_from: Some(999),
             updated_at_to: Some(1000),
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -562,6 +580,7 @@ mod task_repo_test {
             updated_at_from: Some(8000),
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -586,6 +605,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: Some(3000),
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -612,6 +632,7 @@ mod task_repo_test {
             updated_at_from: Some(99999),
             updated_at_to: Some(99999),
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -646,6 +667,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -671,6 +693,7 @@ mod task_repo_test {
             updated_at_from: Some(99999),
             updated_at_to: Some(99999),
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -709,7 +732,7 @@ mod task_repo_test {
             created_at: 0,
             updated_at: None,
         };
-        let updated_task = task_repo.update_task(task).await.unwrap();
+        let updated_task = task_repo.update_task(task).await.unwrap().value;
         assert_eq!(updated_task.task_id, Some(1));
         assert_eq!(updated_task.project_id, 1);
         assert_eq!(updated_task.parent_id, None);
@@ -750,8 +773,12 @@ mod task_repo_test {
         let found_task = task_repo.get_task_by_id(17).await.unwrap();
         assert_eq!(found_task.task_id, Some(17));
 
-        let result = task_repo.delete_task(17).await.unwrap();
-        assert_eq!(result, ());
+        let result = task_repo
+            .delete_task(17, DeletePolicy::Restrict)
+            .await
+            .unwrap();
+        assert_eq!(result.policy, "restrict");
+        assert_eq!(result.tasks, 1);
 
         let not_found_task = task_repo.get_task_by_id(17).await;
         assert!(not_found_task.is_err());
@@ -760,10 +787,102 @@ mod task_repo_test {
     #[sqlx::test(fixtures("tasks"))]
     async fn test_task_repo_delete_task_not_exists(pool: SqlitePool) {
         let task_repo = TaskRepository::new(pool);
-        let result = task_repo.delete_task(100).await;
+        let result = task_repo.delete_task(100, DeletePolicy::Restrict).await;
         assert!(result.is_err());
     }
 
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_repo_delete_task_with_policy(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone());
+
+        let result = task_repo.delete_task(1, DeletePolicy::Restrict).await;
+        assert!(matches!(result, Err(DBAccessError::ConflictError(_))));
+        assert!(task_repo.get_task_by_id(1).await.is_ok());
+
+        // 大タスクを削除すると、子は大タスクに、孫は中タスクになる
+        let result = task_repo
+            .delete_task(1, DeletePolicy::Reparent)
+            .await
+            .unwrap();
+        assert_eq!(result.tasks, 1);
+        assert_eq!(result.reparented_tasks, 2);
+        let minor = task_repo.get_task_by_id(2).await.unwrap();
+        assert_eq!(minor.parent_id, None);
+        assert_eq!(minor.level, TaskLevel::Major.to_int());
+        let trivial = task_repo.get_task_by_id(3).await.unwrap();
+        assert_eq!(trivial.parent_id, Some(2));
+        assert_eq!(trivial.level, TaskLevel::Minor.to_int());
+
+        // 割り当てのある小タスクは中タスクにできない
+        let result = task_repo.delete_task(5, DeletePolicy::Reparent).await;
+        assert!(matches!(result, Err(DBAccessError::ConflictError(_))));
+        assert_eq!(task_repo.get_task_by_id(15).await.unwrap().level, 2);
+
+        let result = task_repo
+            .delete_task(4, DeletePolicy::Cascade)
+            .await
+            .unwrap();
+        assert_eq!(result.tasks, 14);
+        assert_eq!(result.user_assigns, 3);
+        assert_eq!(result.reparented_tasks, 0);
+        assert!(task_repo.get_task_by_id(12).await.is_err());
+
+        // 子孫のタスクと割り当ても1行ずつ監査ログに残る
+        let deleted: Vec<(String, i64)> = sqlx::query_as(
+            "SELECT entity_type, entity_id FROM audit_log WHERE action = 'delete' ORDER BY entity_type, entity_id",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        let deleted_tasks: Vec<i64> = deleted
+            .iter()
+            .filter(|(entity_type, _)| entity_type == "task")
+            .map(|(_, entity_id)| *entity_id)
+            .collect();
+        assert_eq!(
+            deleted_tasks,
+            (1..=17)
+                .filter(|id| *id != 2 && *id != 3)
+                .collect::<Vec<i64>>()
+        );
+        let deleted_assigns = deleted
+            .iter()
+            .filter(|(entity_type, _)| entity_type == "user_assign")
+            .count();
+        assert_eq!(deleted_assigns, 3);
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_repo_restore_task(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone());
+
+        // 先にゴミ箱に移動していたタスクは、親を戻しても一緒には戻らない
+        task_repo
+            .delete_task(6, DeletePolicy::Cascade)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE tasks SET deleted_at = 1 WHERE task_id = 6")
+            .execute(&pool)
+            .await
+            .unwrap();
+        task_repo
+            .delete_task(5, DeletePolicy::Cascade)
+            .await
+            .unwrap();
+        assert!(task_repo.get_task_by_id(7).await.is_err());
+
+        let restored = task_repo.restore_task(5).await.unwrap().value;
+        assert_eq!(restored.task_id, Some(5));
+        assert!(task_repo.get_task_by_id(7).await.is_ok());
+        assert!(task_repo.get_task_by_id(6).await.is_err());
+
+        task_repo.restore_task(6).await.unwrap();
+        assert!(task_repo.get_task_by_id(6).await.is_ok());
+
+        let result = task_repo.restore_task(6).await;
+        assert!(matches!(result, Err(DBAccessError::NotFoundError(_))));
+    }
+
     #[sqlx::test(fixtures("tasks"))]
     async fn test_task_repo_create_task_with_invalid_project_id(pool: SqlitePool) {
         let task_repo = TaskRepository::new(pool);
@@ -831,6 +950,29 @@ mod task_repo_test {
         assert!(result.is_err());
     }
 
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_repo_update_task_with_changed_project_id(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool);
+        let task = Task {
+            task_id: Some(1),
+            project_id: 2, // 別のプロジェクトへは移動できない
+            parent_id: None,
+            level: TaskLevel::Major.to_int(),
+            name: "Test Task".to_string(),
+            description: Some("Test Task Description".to_string()),
+            status: TaskStatus::NotStarted.to_int(),
+            deadline: None,
+            created_at: 0,
+            updated_at: None,
+        };
+        let result = task_repo.update_task(task).await;
+        assert!(matches!(result, Err(DBAccessError::ValidationError(_))));
+
+        let task = task_repo.get_task_by_id(1).await.unwrap();
+        assert_eq!(task.project_id, 1);
+        assert_eq!(task.name, "Test PJ0 Major TASK");
+    }
+
     #[sqlx::test(fixtures("tasks"))]
     async fn test_task_repo_update_task_with_invalid_parent_level(pool: SqlitePool) {
         let task_repo = TaskRepository::new(pool);
@@ -905,6 +1047,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -930,6 +1073,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -955,6 +1099,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -980,6 +1125,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -1005,6 +1151,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -1030,6 +1177,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -1055,6 +1203,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -1080,6 +1229,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let tasks = task_repo
             .get_tasks_by_filter(Some(&filter), None, None)
@@ -1121,6 +1271,7 @@ mod task_repo_test {
             updated_at_from: None,
             updated_at_to: None,
             assignee_id: None,
+            member_id: None,
         };
         let user_ids = vec![1, 2];
         let tasks = get_tasks_with_pagination_with_transaction(
@@ -1137,4 +1288,96 @@ mod task_repo_test {
         assert_eq!(tasks[0].task_id, Some(15));
         assert_eq!(tasks[1].task_id, Some(16));
     }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_repo_update_task_with_expected_versions(pool: SqlitePool) {
+        let mut task = TaskRepository::new(pool.clone())
+            .get_task_by_id(1)
+            .await
+            .unwrap();
+        task.deadline = None;
+        assert_eq!(
+            get_version(&pool, AuditEntityType::Task, 1).await.unwrap(),
+            Some(1)
+        );
+
+        let result = TaskRepository::new(pool.clone())
+            .with_expected_versions(Some(vec![2]))
+            .update_task(task.clone())
+            .await;
+        assert!(matches!(
+            result,
+            Err(DBAccessError::PreconditionFailedError(_))
+        ));
+
+        let updated = TaskRepository::new(pool.clone())
+            .with_expected_versions(Some(vec![1]))
+            .update_task(task.clone())
+            .await
+            .unwrap();
+        assert_eq!(updated.version, 2);
+        assert_eq!(
+            get_version(&pool, AuditEntityType::Task, 1).await.unwrap(),
+            Some(2)
+        );
+
+        let result = TaskRepository::new(pool.clone())
+            .with_expected_versions(Some(vec![1]))
+            .delete_task(1, DeletePolicy::Restrict)
+            .await;
+        assert!(matches!(
+            result,
+            Err(DBAccessError::PreconditionFailedError(_))
+        ));
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_repo_run_task_batch_rollback(pool: SqlitePool) {
+        let task_repo = TaskRepository::new(pool.clone());
+        let count = task_repo.get_tasks_count().await.unwrap();
+
+        let major = Task::new(1, None, 0, "BatchMajor".to_string(), None, 0, None);
+        let minor = Task::new(1, None, 1, "BatchMinor".to_string(), None, 0, None);
+        let result = task_repo
+            .run_task_batch(vec![
+                TaskBatchOperation::Create {
+                    temp_id: Some("major".to_string()),
+                    parent_temp_id: None,
+                    task: major.clone(),
+                },
+                TaskBatchOperation::Create {
+                    temp_id: None,
+                    parent_temp_id: Some("major".to_string()),
+                    task: minor.clone(),
+                },
+                TaskBatchOperation::Delete {
+                    task_id: 100,
+                    version: None,
+                    policy: None,
+                },
+            ])
+            .await;
+        let error = result.unwrap_err();
+        assert_eq!(error.index, Some(2));
+        assert!(matches!(error.error, DBAccessError::ValidationError(_)));
+        assert_eq!(task_repo.get_tasks_count().await.unwrap(), count);
+
+        let results = task_repo
+            .run_task_batch(vec![
+                TaskBatchOperation::Create {
+                    temp_id: Some("major".to_string()),
+                    parent_temp_id: None,
+                    task: major,
+                },
+                TaskBatchOperation::Create {
+                    temp_id: None,
+                    parent_temp_id: Some("major".to_string()),
+                    task: minor,
+                },
+            ])
+            .await
+            .unwrap();
+        assert_eq!(results[1].task.parent_id, results[0].task.task_id);
+        assert_eq!(task_repo.get_tasks_count().await.unwrap(), count + 2);
+    }
 }
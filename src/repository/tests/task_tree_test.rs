@@ -0,0 +1,117 @@
+use crate::models::TaskTreeNode;
+use crate::models::repository_model::task::TaskFilter;
+use crate::repository::task_tree_repo::TaskTreeRepository;
+use sqlx::sqlite::SqlitePool;
+
+#[cfg(test)]
+mod task_tree_repo_test {
+
+    use super::*;
+
+    fn task_ids(nodes: &[TaskTreeNode]) -> Vec<i64> {
+        nodes.iter().map(|node| node.task.task_id).collect()
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_tree_repo_get_project_tree(pool: SqlitePool) {
+        let task_tree_repo = TaskTreeRepository::new(pool);
+
+        let tree = task_tree_repo
+            .get_project_tree(2, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree), [4]);
+        assert_eq!(task_ids(&tree[0].children), [5]);
+
+        // レベル2のタスクも含め、割り当てられたユーザーも返す
+        let minor = &tree[0].children[0];
+        assert_eq!(minor.depth, 1);
+        assert_eq!(task_ids(&minor.children), (6..=17).collect::<Vec<i64>>());
+        let task16 = &minor.children[10];
+        assert_eq!(task16.depth, 2);
+        assert_eq!(task16.task.users.len(), 2);
+        assert!(minor.children[0].task.users.is_empty());
+        assert!(minor.children.iter().all(|node| node.matched));
+
+        let tree = task_tree_repo
+            .get_project_tree(2, None, None, Some(1))
+            .await
+            .unwrap();
+        assert!(tree[0].children[0].children.is_empty());
+
+        let result = task_tree_repo
+            .get_project_tree(2, None, None, Some(3))
+            .await;
+        assert!(result.is_err());
+
+        let result = task_tree_repo.get_project_tree(999, None, None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_tree_repo_get_task_tree_with_filter(pool: SqlitePool) {
+        let task_tree_repo = TaskTreeRepository::new(pool);
+
+        let tree = task_tree_repo
+            .get_task_tree(1, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree), [1]);
+        assert_eq!(task_ids(&tree[0].children[0].children), [3]);
+
+        // 一致したタスクの祖先は一致しなくても残す
+        let filter = TaskFilter {
+            status: Some(1),
+            ..TaskFilter::new()
+        };
+        let tree = task_tree_repo
+            .get_task_tree(4, Some(&filter), None, None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree), [4]);
+        assert!(!tree[0].matched);
+        assert!(!tree[0].children[0].matched);
+        assert_eq!(task_ids(&tree[0].children[0].children), [7]);
+        assert!(tree[0].children[0].children[0].matched);
+
+        let tree = task_tree_repo
+            .get_task_tree(4, None, Some(&vec![2]), None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree[0].children[0].children), [16]);
+
+        let tree = task_tree_repo
+            .get_task_tree(1, Some(&filter), None, None)
+            .await
+            .unwrap();
+        assert!(tree.is_empty());
+
+        let result = task_tree_repo.get_task_tree(999, None, None, None).await;
+        assert!(result.is_err());
+    }
+
+    #[sqlx::test(fixtures("tasks"))]
+    async fn test_task_tree_repo_excludes_other_project_descendants(pool: SqlitePool) {
+        let task_tree_repo = TaskTreeRepository::new(pool.clone());
+
+        // 親と異なるプロジェクトに属する子孫は返さない
+        sqlx::query("UPDATE tasks SET project_id = 1 WHERE task_id = 5")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let tree = task_tree_repo
+            .get_task_tree(4, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree), [4]);
+        assert!(tree[0].children.is_empty());
+
+        let tree = task_tree_repo
+            .get_project_tree(2, None, None, None)
+            .await
+            .unwrap();
+        assert_eq!(task_ids(&tree), [4]);
+        assert!(tree[0].children.is_empty());
+    }
+}
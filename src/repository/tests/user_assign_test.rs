@@ -141,6 +141,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: None,
             task_id: Some(3),
+            member_id: None,
         };
 
         let user_assign = user_assign_repo
@@ -160,6 +161,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: None,
             task_id: Some(100),
+            member_id: None,
         };
 
         let user_assign = user_assign_repo
@@ -176,6 +178,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: Some(1),
             task_id: None,
+            member_id: None,
         };
 
         let user_assign = user_assign_repo
@@ -195,6 +198,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: Some(100),
             task_id: None,
+            member_id: None,
         };
 
         let user_assign = user_assign_repo
@@ -211,6 +215,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: Some(1),
             task_id: Some(3),
+            member_id: None,
         };
 
         let user_assign = user_assign_repo
@@ -231,6 +236,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: Some(1),
             task_id: Some(100),
+            member_id: None,
         };
         let user_assign = user_assign_repo
             .get_user_assigns_by_filter(Some(&filter), None, None)
@@ -246,6 +252,7 @@ mod user_assign_repo_test {
         let filter = UserAssignFilter {
             user_id: Some(1),
             task_id: None,
+            member_id: None,
         };
 
         let user_assigns = user_assign_repo
@@ -287,7 +294,8 @@ mod user_assign_repo_test {
         let updated_user_assign = user_assign_repo
             .update_user_assign(user_assign)
             .await
-            .unwrap();
+            .unwrap()
+            .value;
         assert_eq!(updated_user_assign.user_id, 2);
         assert_eq!(updated_user_assign.task_id, 11);
     }
@@ -1,3 +1,4 @@
+use crate::enums::DeletePolicy;
 use crate::models::{User, UserFilter};
 use crate::repository::user_repo::{
     UserRepository, get_user_by_id_with_transaction, get_users_with_pagination_with_transaction,
@@ -235,7 +236,7 @@ mod user_repo_test {
         assert!(retrieved_user.is_ok());
 
         user_repo
-            .delete_user(created_user.user_id.unwrap())
+            .delete_user(created_user.user_id.unwrap(), DeletePolicy::Restrict)
             .await
             .unwrap();
         let retrieved_user = user_repo
@@ -321,7 +322,7 @@ mod user_repo_test {
     #[sqlx::test]
     async fn test_user_repo_delete_nonexistent_user(pool: SqlitePool) {
         let user_repo = UserRepository::new(pool);
-        let result = user_repo.delete_user(114514).await;
+        let result = user_repo.delete_user(114514, DeletePolicy::Restrict).await;
         assert!(result.is_err());
     }
 
@@ -938,4 +939,70 @@ mod user_repo_test {
         assert_eq!(users[0].username, "TestUser0");
         assert_eq!(users[1].username, "TestUser1");
     }
+
+    #[sqlx::test]
+    async fn test_user_repo_verify_user_password(pool: SqlitePool) {
+        let user_repo = UserRepository::new(pool);
+
+        let user = User::new(
+            "verify_test".to_string(),
+            "verify_test@test.com".to_string(),
+            "verify_password".to_string(),
+        )
+        .unwrap();
+        let created_user = user_repo.create_user(user).await.unwrap();
+
+        let verified = user_repo
+            .verify_user_password("verify_test", "verify_password")
+            .await
+            .unwrap();
+        assert_eq!(verified, Some(created_user));
+
+        let verified = user_repo
+            .verify_user_password("verify_test", "wrong_password")
+            .await
+            .unwrap();
+        assert_eq!(verified, None);
+
+        let verified = user_repo
+            .verify_user_password("not_exist_user", "verify_password")
+            .await
+            .unwrap();
+        assert_eq!(verified, None);
+    }
+
+    #[sqlx::test]
+    async fn test_user_repo_verify_user_password_rehash_legacy(pool: SqlitePool) {
+        let user_repo = UserRepository::new(pool);
+
+        // 移行前のSHA-256形式で保存されたユーザー
+        let legacy_hash = format!("{:x}", Sha256::digest("legacy_password".as_bytes()));
+        let user = User {
+            user_id: None,
+            username: "legacy_test".to_string(),
+            email: "legacy_test@test.com".to_string(),
+            password_hash: legacy_hash.clone(),
+        };
+        user_repo.create_user(user).await.unwrap();
+
+        let verified = user_repo
+            .verify_user_password("legacy_test", "legacy_password")
+            .await
+            .unwrap();
+        assert!(verified.is_some());
+
+        let stored = user_repo
+            .get_user_with_password_by_name("legacy_test")
+            .await
+            .unwrap();
+        assert_ne!(stored.password_hash, legacy_hash);
+        assert!(stored.password_hash.starts_with("$argon2id$"));
+
+        // 再ハッシュ後も同じパスワードで検証できる
+        let verified = user_repo
+            .verify_user_password("legacy_test", "legacy_password")
+            .await
+            .unwrap();
+        assert!(verified.is_some());
+    }
 }
@@ -0,0 +1,838 @@
+use crate::enums::{AuditAction, AuditEntityType};
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
+use crate::models::{AuditContext, DeleteSummary, TrashFilter, TrashItem, TrashPurgeSummary};
+use crate::repository::audit_log_repo::create_audit_logs_with_transaction;
+use crate::settings::server_config;
+use chrono::Utc;
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+
+const SECONDS_PER_DAY: i64 = 60 * 60 * 24;
+
+// ゴミ箱にあるプロジェクト・タスク・コメントを1つの一覧にまとめるクエリ
+const TRASH_ITEMS_QUERY: &str = r#"
+    SELECT entity_type, id, project_id, task_id, name, deleted_at FROM (
+        SELECT 'project' AS entity_type, project_id AS id, project_id, NULL AS task_id, name, deleted_at
+        FROM projects
+        WHERE deleted_at IS NOT NULL
+        UNION ALL
+        SELECT 'task', task_id, project_id, task_id, name, deleted_at
+        FROM tasks
+        WHERE deleted_at IS NOT NULL
+        UNION ALL
+        SELECT 'comment', comments.comment_id, tasks.project_id, comments.task_id, comments.content, comments.deleted_at
+        FROM comments
+        INNER JOIN tasks ON comments.task_id = tasks.task_id
+        WHERE comments.deleted_at IS NOT NULL
+    )
+"#;
+
+enum TrashFilterValue {
+    I64(i64),
+    String(String),
+}
+
+pub struct TrashRepository {
+    pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+}
+
+impl TrashRepository {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // 新しくゴミ箱に移動した順に返す
+    pub async fn get_trash_items(
+        &self,
+        filter: Option<&TrashFilter>,
+    ) -> Result<Vec<TrashItem>, DBAccessError> {
+        let _timer = QueryTimer::start("trash", "get_trash_items");
+        let entity_type = match filter.and_then(|filter| filter.entity_type.as_deref()) {
+            Some(entity_type) => Some(validate_trash_entity_type(entity_type)?),
+            None => None,
+        };
+        let project_id = filter.and_then(|filter| filter.project_id);
+        let member_id = filter.and_then(|filter| filter.member_id);
+
+        fetch_trash_items(&self.pool, entity_type, project_id, member_id, None).await
+    }
+
+    pub async fn get_trash_item(
+        &self,
+        entity_type: AuditEntityType,
+        id: i64,
+    ) -> Result<TrashItem, DBAccessError> {
+        let _timer = QueryTimer::start("trash", "get_trash_item");
+        let result = fetch_trash_items(&self.pool, Some(entity_type), None, None, Some(id)).await?;
+
+        match result.into_iter().next() {
+            Some(item) => Ok(item),
+            None => Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::TrashItemNotFound,
+                [("entity", json!(entity_type.to_str())), ("id", json!(id))],
+            ))),
+        }
+    }
+
+    // older_than_days日より前にゴミ箱に移動したものを完全に削除する。
+    // 未指定の場合は設定の保持日数を使い、0の場合はゴミ箱のすべてを削除する
+    pub async fn purge_trash(
+        &self,
+        older_than_days: Option<i64>,
+    ) -> Result<TrashPurgeSummary, DBAccessError> {
+        let _timer = QueryTimer::start("trash", "purge_trash");
+        let days = older_than_days.unwrap_or(server_config().trash.retention_days);
+        if days < 0 {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::TrashRetentionDaysInvalid,
+                [("days", json!(days))],
+            )));
+        }
+        let deleted_before = Utc::now().timestamp() - days * SECONDS_PER_DAY;
+
+        let mut tx = self.pool.begin().await?;
+
+        // 参照する側から順に削除する
+        let comment_ids = sqlx::query_scalar!(
+            r#"
+                DELETE FROM comments
+                WHERE deleted_at IS NOT NULL AND deleted_at <= $1
+                RETURNING comment_id AS "comment_id!"
+            "#,
+            deleted_before,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(purge_error)?;
+
+        let user_assign_ids = sqlx::query_scalar!(
+            r#"
+                DELETE FROM user_assign
+                WHERE deleted_at IS NOT NULL AND deleted_at <= $1
+                RETURNING user_assign_id AS "user_assign_id!"
+            "#,
+            deleted_before,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(purge_error)?;
+
+        // 子孫のタスクは親と同時かそれより前にゴミ箱に移動しているため、まとめて削除できる
+        let task_ids = sqlx::query_scalar!(
+            r#"
+                DELETE FROM tasks
+                WHERE deleted_at IS NOT NULL AND deleted_at <= $1
+                RETURNING task_id AS "task_id!"
+            "#,
+            deleted_before,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(purge_error)?;
+
+        let project_ids = sqlx::query_scalar!(
+            r#"
+                DELETE FROM projects
+                WHERE deleted_at IS NOT NULL AND deleted_at <= $1
+                RETURNING project_id AS "project_id!"
+            "#,
+            deleted_before,
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(purge_error)?;
+
+        let purged = [
+            (AuditEntityType::Comment, &comment_ids),
+            (AuditEntityType::UserAssign, &user_assign_ids),
+            (AuditEntityType::Task, &task_ids),
+            (AuditEntityType::Project, &project_ids),
+        ];
+        for (entity_type, ids) in purged {
+            create_audit_logs_with_transaction(
+                &self.audit_context,
+                entity_type,
+                ids,
+                AuditAction::Purge,
+                &mut tx,
+            )
+            .await?;
+        }
+
+        tx.commit().await.map_err(purge_error)?;
+
+        let summary = TrashPurgeSummary {
+            deleted_before,
+            projects: project_ids.len() as i64,
+            tasks: task_ids.len() as i64,
+            user_assigns: user_assign_ids.len() as i64,
+            comments: comment_ids.len() as i64,
+        };
+        log::info!("Purged trash: {:?}", summary);
+
+        Ok(summary)
+    }
+}
+
+fn purge_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::TrashPurgeFailed,
+        e.to_string()
+    )))
+}
+
+fn trash_move_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::TrashMoveFailed,
+        e.to_string()
+    )))
+}
+
+fn trash_restore_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::TrashRestoreFailed,
+        e.to_string()
+    )))
+}
+
+// ゴミ箱の対象はプロジェクト・タスク・コメントのみ
+fn validate_trash_entity_type(entity_type: &str) -> Result<AuditEntityType, DBAccessError> {
+    match AuditEntityType::from_string(entity_type) {
+        Ok(
+            entity_type @ (AuditEntityType::Project
+            | AuditEntityType::Task
+            | AuditEntityType::Comment),
+        ) => Ok(entity_type),
+        _ => Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::TrashEntityTypeInvalid,
+            [("entity_type", json!(entity_type))],
+        ))),
+    }
+}
+
+async fn fetch_trash_items(
+    pool: &Pool<Sqlite>,
+    entity_type: Option<AuditEntityType>,
+    project_id: Option<i64>,
+    member_id: Option<i64>,
+    id: Option<i64>,
+) -> Result<Vec<TrashItem>, DBAccessError> {
+    let mut where_clauses = Vec::new();
+    let mut bind_values = Vec::new();
+
+    if let Some(entity_type) = entity_type {
+        bind_values.push(TrashFilterValue::String(entity_type.to_str().to_string()));
+        where_clauses.push(format!("entity_type = ${}", bind_values.len()));
+    }
+    if let Some(project_id) = project_id {
+        bind_values.push(TrashFilterValue::I64(project_id));
+        where_clauses.push(format!("project_id = ${}", bind_values.len()));
+    }
+    if let Some(member_id) = member_id {
+        bind_values.push(TrashFilterValue::I64(member_id));
+        where_clauses.push(format!(
+            "project_id IN (SELECT project_id FROM project_members WHERE user_id = ${})",
+            bind_values.len()
+        ));
+    }
+    if let Some(id) = id {
+        bind_values.push(TrashFilterValue::I64(id));
+        where_clauses.push(format!("id = ${}", bind_values.len()));
+    }
+
+    let mut query = TRASH_ITEMS_QUERY.to_string();
+    if !where_clauses.is_empty() {
+        query.push_str(&format!(" WHERE {}", where_clauses.join(" AND ")));
+    }
+    query.push_str(" ORDER BY deleted_at DESC, entity_type, id");
+
+    let mut query_builder = sqlx::query_as::<_, TrashItem>(&query);
+    for value in bind_values.iter() {
+        match value {
+            TrashFilterValue::I64(v) => query_builder = query_builder.bind(v),
+            TrashFilterValue::String(v) => query_builder = query_builder.bind(v),
+        }
+    }
+
+    let result = query_builder.fetch_all(pool).await.map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::TrashGetFailed,
+            e.to_string()
+        )))
+    })?;
+    log::debug!("Get trash items: {:?}", result.len());
+
+    Ok(result)
+}
+
+// ゴミ箱に移動した日時。ゴミ箱にない場合はNotFound
+pub async fn get_deleted_at_with_transaction(
+    entity_type: AuditEntityType,
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<i64, DBAccessError> {
+    let query = match entity_type {
+        AuditEntityType::Project => {
+            "SELECT deleted_at FROM projects WHERE project_id = $1 AND deleted_at IS NOT NULL"
+        }
+        AuditEntityType::Task => {
+            "SELECT deleted_at FROM tasks WHERE task_id = $1 AND deleted_at IS NOT NULL"
+        }
+        AuditEntityType::Comment => {
+            "SELECT deleted_at FROM comments WHERE comment_id = $1 AND deleted_at IS NOT NULL"
+        }
+        _ => {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::TrashEntityTypeInvalid,
+                [("entity_type", json!(entity_type.to_str()))],
+            )));
+        }
+    };
+
+    let result = sqlx::query_scalar::<_, i64>(query)
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::TrashGetFailed,
+                e.to_string()
+            )))
+        })?;
+
+    match result {
+        Some(deleted_at) => Ok(deleted_at),
+        None => Err(DBAccessError::NotFoundError(get_error_message(
+            ErrorKey::TrashItemNotFound,
+            [("entity", json!(entity_type.to_str())), ("id", json!(id))],
+        ))),
+    }
+}
+
+// タスクと子孫のタスク、それらの割り当てとコメントをゴミ箱に移動する。
+// 既にゴミ箱にある行は元の日時のまま残し、復元時に一緒に戻らないようにする。
+// 移動した行は1行ずつ監査ログに記録する。起点のタスクは呼び出し元で変更前の内容とともに記録する。
+// 移動した件数を返す
+pub async fn trash_task_subtree_with_transaction(
+    task_id: i64,
+    deleted_at: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<DeleteSummary, DBAccessError> {
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE user_assign SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE comments SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let task_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE tasks SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING task_id AS "task_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+    let descendant_ids: Vec<i64> = task_ids
+        .iter()
+        .copied()
+        .filter(|id| *id != task_id)
+        .collect();
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Task,
+        &descendant_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+
+    Ok(DeleteSummary {
+        tasks: task_ids.len() as i64,
+        user_assigns: user_assign_ids.len() as i64,
+        comments: comment_ids.len() as i64,
+        ..Default::default()
+    })
+}
+
+// trash_task_subtree_with_transactionで同時にゴミ箱に移動したものだけを戻し、
+// 起点以外の戻した行を監査ログに記録する
+pub async fn restore_task_subtree_with_transaction(
+    task_id: i64,
+    deleted_at: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    let task_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE tasks SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING task_id AS "task_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+    let descendant_ids: Vec<i64> = task_ids
+        .iter()
+        .copied()
+        .filter(|id| *id != task_id)
+        .collect();
+
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE user_assign SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE task_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE comments SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        task_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Task,
+        &descendant_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// プロジェクトと、そのタスク（別のプロジェクトにある子孫を含む）、割り当て、コメントをゴミ箱に移動する。
+// プロジェクト以外の移動した行は1行ずつ監査ログに記録する。移動した件数を返す
+pub async fn trash_project_with_transaction(
+    project_id: i64,
+    deleted_at: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<DeleteSummary, DBAccessError> {
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE user_assign SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE comments SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let task_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE tasks SET deleted_at = $2
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at IS NULL
+            RETURNING task_id AS "task_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let projects = sqlx::query!(
+        r#"
+            UPDATE projects SET deleted_at = $2
+            WHERE project_id = $1 AND deleted_at IS NULL
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(trash_move_error)?
+    .rows_affected() as i64;
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Task,
+        &task_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+
+    Ok(DeleteSummary {
+        projects,
+        tasks: task_ids.len() as i64,
+        user_assigns: user_assign_ids.len() as i64,
+        comments: comment_ids.len() as i64,
+        ..Default::default()
+    })
+}
+
+// trash_project_with_transactionで同時にゴミ箱に移動したものだけを戻し、
+// プロジェクト以外の戻した行を監査ログに記録する
+pub async fn restore_project_with_transaction(
+    project_id: i64,
+    deleted_at: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    sqlx::query!(
+        r#"
+            UPDATE projects SET deleted_at = NULL
+            WHERE project_id = $1 AND deleted_at = $2
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    let task_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE tasks SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING task_id AS "task_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE user_assign SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            WITH RECURSIVE subtree(task_id) AS (
+                SELECT task_id FROM tasks WHERE project_id = $1
+                UNION
+                SELECT tasks.task_id FROM tasks INNER JOIN subtree ON tasks.parent_id = subtree.task_id
+            )
+            UPDATE comments SET deleted_at = NULL
+            WHERE task_id IN (SELECT task_id FROM subtree) AND deleted_at = $2
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        project_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_restore_error)?;
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Task,
+        &task_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Restore,
+        tx,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// ユーザーの割り当てとコメントのうち、ゴミ箱にないものをゴミ箱に移動し、1行ずつ監査ログに記録する
+pub async fn trash_user_dependents_with_transaction(
+    user_id: i64,
+    deleted_at: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            UPDATE user_assign SET deleted_at = $2
+            WHERE user_id = $1 AND deleted_at IS NULL
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        user_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            UPDATE comments SET deleted_at = $2
+            WHERE user_id = $1 AND deleted_at IS NULL
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        user_id,
+        deleted_at,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(trash_move_error)?;
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Delete,
+        tx,
+    )
+    .await?;
+
+    Ok(())
+}
+
+// ゴミ箱にあるユーザーの割り当てとコメントを完全に削除し、1行ずつ監査ログに記録する。
+// ユーザーは完全に削除するため、参照している行をゴミ箱に残せない。削除した件数を返す
+pub async fn purge_user_dependents_with_transaction(
+    user_id: i64,
+    audit_context: &AuditContext,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<DeleteSummary, DBAccessError> {
+    let comment_ids = sqlx::query_scalar!(
+        r#"
+            DELETE FROM comments
+            WHERE user_id = $1 AND deleted_at IS NOT NULL
+            RETURNING comment_id AS "comment_id!"
+        "#,
+        user_id,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(purge_error)?;
+
+    let user_assign_ids = sqlx::query_scalar!(
+        r#"
+            DELETE FROM user_assign
+            WHERE user_id = $1 AND deleted_at IS NOT NULL
+            RETURNING user_assign_id AS "user_assign_id!"
+        "#,
+        user_id,
+    )
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(purge_error)?;
+
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::Comment,
+        &comment_ids,
+        AuditAction::Purge,
+        tx,
+    )
+    .await?;
+    create_audit_logs_with_transaction(
+        audit_context,
+        AuditEntityType::UserAssign,
+        &user_assign_ids,
+        AuditAction::Purge,
+        tx,
+    )
+    .await?;
+
+    Ok(DeleteSummary {
+        user_assigns: user_assign_ids.len() as i64,
+        comments: comment_ids.len() as i64,
+        ..Default::default()
+    })
+}
@@ -1,19 +1,28 @@
-use crate::enums::TaskLevel;
-use crate::errors::db_error::DBAccessError;
+use crate::enums::{AuditAction, AuditEntityType, TaskLevel};
+use crate::errors::db_error::{BatchError, DBAccessError};
 use crate::errors::messages::{ErrorKey, get_error_message};
-use crate::models::{UserAssign, UserAssignFilter};
+use crate::metrics::QueryTimer;
+use crate::models::{
+    AuditContext, AuditLog, UserAssign, UserAssignBatchOperation, UserAssignBatchResult,
+    UserAssignFilter,
+};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
 use crate::repository::task_repo::get_task_by_id_with_transaction;
 use crate::repository::user_repo::get_user_by_id_with_transaction;
 use crate::repository::validations::{
-    validate_pagination, validate_user_assign_id, validate_user_assign_id_is_none,
-    validate_user_assign_task_id, validate_user_assign_user_id,
+    validate_batch_operations_count, validate_pagination, validate_user_assign_id,
+    validate_user_assign_id_is_none, validate_user_assign_task_id, validate_user_assign_user_id,
 };
+use crate::repository::version_repo::{Versioned, bump_version_with_transaction};
 use anyhow::Result;
+use serde_json::json;
 use sqlx::{Pool, Sqlite, Transaction};
 use std::collections::HashMap;
 
 pub struct UserAssignRepository {
     pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+    expected_versions: Option<Vec<i64>>,
 }
 
 #[derive(Debug)]
@@ -23,7 +32,40 @@ enum FilterValue {
 
 impl UserAssignRepository {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+            expected_versions: None,
+        }
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // If-Matchで指定されたバージョン。更新・削除の前にトランザクション内で確認する
+    pub fn with_expected_versions(mut self, expected_versions: Option<Vec<i64>>) -> Self {
+        self.expected_versions = expected_versions;
+        self
+    }
+
+    fn user_assign_audit_log(
+        &self,
+        user_assign_id: i64,
+        action: AuditAction,
+        before: Option<&UserAssign>,
+        after: Option<&UserAssign>,
+    ) -> AuditLog {
+        AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::UserAssign,
+            user_assign_id,
+            action,
+            before.and_then(to_audit_json),
+            after.and_then(to_audit_json),
+        )
     }
 
     async fn validate_target_user_and_task(
@@ -45,7 +87,7 @@ impl UserAssignRepository {
         if task.level != TaskLevel::max_level() as i64 {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::UserAssignToNotMaxLevelTask,
-                format!("ID = {}", user_assign.task_id),
+                [("id", json!(user_assign.task_id))],
             )));
         }
 
@@ -63,9 +105,9 @@ impl UserAssignRepository {
             .iter()
             .any(|assign| assign.user_id == user_assign.user_id)
         {
-            return Err(DBAccessError::ValidationError(get_error_message(
+            return Err(DBAccessError::ConflictError(get_error_message(
                 ErrorKey::UserAssignSameUserAssignExists,
-                format!("ID = {}", user_assign.task_id),
+                [("id", json!(user_assign.task_id))],
             )));
         }
         Ok(())
@@ -74,21 +116,40 @@ impl UserAssignRepository {
     pub async fn create_user_assign(
         &self,
         user_assign: UserAssign,
+    ) -> Result<UserAssign, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "create_user_assign");
+        let mut tx = self.pool.begin().await?;
+
+        let user_assign = self
+            .create_user_assign_with_transaction(&user_assign, &mut tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserAssignCreateFailed,
+                e.to_string()
+            )))
+        })?;
+        Ok(user_assign)
+    }
+
+    // 呼び出し元のトランザクションで作成する。コミットは呼び出し元で行う
+    pub async fn create_user_assign_with_transaction(
+        &self,
+        user_assign: &UserAssign,
+        tx: &mut Transaction<'_, Sqlite>,
     ) -> Result<UserAssign, DBAccessError> {
         validate_user_assign_id_is_none(user_assign.user_assign_id)?;
         validate_user_assign_user_id(user_assign.user_id)?;
         validate_user_assign_task_id(user_assign.task_id)?;
 
-        let mut tx = self.pool.begin().await?;
-
-        self.validate_target_user_and_task(&user_assign, &mut tx)
+        self.validate_target_user_and_task(user_assign, tx).await?;
+        self.validate_user_assign_to_not_max_level_task(user_assign, tx)
             .await?;
-        self.validate_user_assign_to_not_max_level_task(&user_assign, &mut tx)
-            .await?;
-        self.validate_user_assign_same_user_assign_exists(&user_assign, &mut tx)
+        self.validate_user_assign_same_user_assign_exists(user_assign, tx)
             .await?;
 
-        let result = sqlx::query_as!(
+        let user_assign = sqlx::query_as!(
             UserAssign,
             r#"
                 INSERT INTO user_assign (user_id, task_id)
@@ -98,36 +159,35 @@ impl UserAssignRepository {
             user_assign.user_id,
             user_assign.task_id,
         )
-        .fetch_one(&mut *tx)
-        .await;
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserAssignCreateFailed,
+                e.to_string()
+            )))
+        })?;
 
-        match result {
-            Ok(user_assign) => {
-                log::info!("User assign created: {:?}", user_assign);
-                tx.commit().await.map_err(|e| {
-                    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
-                        ErrorKey::UserAssignCreateFailed,
-                        e.to_string()
-                    )))
-                })?;
-                Ok(user_assign)
-            }
-            Err(e) => {
-                let _ = tx.rollback().await;
-                Err(DBAccessError::QueryError(anyhow::anyhow!(
-                    get_error_message(ErrorKey::UserAssignCreateFailed, e.to_string())
-                )))
-            }
-        }
+        log::info!("User assign created: {:?}", user_assign);
+        let audit_log = self.user_assign_audit_log(
+            user_assign.user_assign_id.unwrap_or_default(),
+            AuditAction::Create,
+            None,
+            Some(&user_assign),
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        Ok(user_assign)
     }
 
     pub async fn get_user_assign_by_id(&self, id: i64) -> Result<UserAssign, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "get_user_assign_by_id");
         let result = sqlx::query_as!(
             UserAssign,
             r#"
                 SELECT user_assign_id, user_id, task_id
                 FROM user_assign
-                WHERE user_assign_id = $1
+                WHERE user_assign_id = $1 AND deleted_at IS NULL
             "#,
             id,
         )
@@ -145,17 +205,19 @@ impl UserAssignRepository {
             Some(user_assign) => Ok(user_assign),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::UserAssignGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
 
     pub async fn get_all_user_assigns(&self) -> Result<Vec<UserAssign>, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "get_all_user_assigns");
         let result = sqlx::query_as!(
             UserAssign,
             r#"
                 SELECT user_assign_id, user_id, task_id
                 FROM user_assign
+                WHERE deleted_at IS NULL
             "#,
         )
         .fetch_all(&self.pool)
@@ -172,9 +234,10 @@ impl UserAssignRepository {
     }
 
     pub async fn get_user_assigns_count(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "get_user_assigns_count");
         let result = sqlx::query_scalar!(
             r#"
-                SELECT COUNT(*) FROM user_assign
+                SELECT COUNT(*) FROM user_assign WHERE deleted_at IS NULL
             "#,
         )
         .fetch_one(&self.pool)
@@ -195,6 +258,7 @@ impl UserAssignRepository {
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<UserAssign>, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "get_user_assigns_with_pagination");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::UserAssignGetAllFailed,
@@ -218,6 +282,7 @@ impl UserAssignRepository {
             r#"
                 SELECT user_assign_id, user_id, task_id
                 FROM user_assign
+                WHERE deleted_at IS NULL
                 ORDER BY user_assign_id
                 LIMIT $1 OFFSET $2
             "#,
@@ -260,6 +325,7 @@ impl UserAssignRepository {
         page: Option<&i32>,
         page_size: Option<&i32>,
     ) -> Result<Vec<UserAssign>, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "get_user_assigns_by_filter");
         let mut query = String::from(
             r#"
             SELECT user_assign_id, user_id, task_id
@@ -286,6 +352,8 @@ impl UserAssignRepository {
 
             index = bind_values.len() + 1;
             filter_bind_values = bind_values;
+        } else {
+            query.push_str(" WHERE deleted_at IS NULL");
         }
 
         let count = get_user_assigns_count_with_transaction(&mut tx, filter).await?;
@@ -338,6 +406,48 @@ impl UserAssignRepository {
     pub async fn update_user_assign(
         &self,
         user_assign: UserAssign,
+    ) -> Result<Versioned<UserAssign>, DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "update_user_assign");
+        if user_assign.user_assign_id.is_none() {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::UserIdInvalid,
+                [("id", json!(user_assign.user_assign_id))],
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::UserAssign,
+            user_assign.user_assign_id.unwrap_or_default(),
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        let user_assign = self
+            .update_user_assign_with_transaction(&user_assign, &mut tx)
+            .await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserAssignUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!("Update user assign: {:?}", user_assign);
+        Ok(Versioned {
+            value: user_assign,
+            version: version.unwrap_or_default(),
+        })
+    }
+
+    // 呼び出し元のトランザクションで更新する。バージョンの更新とコミットは呼び出し元で行う
+    pub async fn update_user_assign_with_transaction(
+        &self,
+        user_assign: &UserAssign,
+        tx: &mut Transaction<'_, Sqlite>,
     ) -> Result<UserAssign, DBAccessError> {
         validate_user_assign_user_id(user_assign.user_id)?;
         validate_user_assign_task_id(user_assign.task_id)?;
@@ -345,32 +455,36 @@ impl UserAssignRepository {
         if user_assign.user_assign_id.is_none() {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::UserIdInvalid,
-                format!("ID = {:?}", user_assign.user_assign_id),
+                [("id", json!(user_assign.user_assign_id))],
             )));
         }
 
-        let mut tx = self.pool.begin().await?;
-
-        self.validate_target_user_and_task(&user_assign, &mut tx)
+        self.validate_target_user_and_task(user_assign, tx).await?;
+        self.validate_user_assign_to_not_max_level_task(user_assign, tx)
             .await?;
-        self.validate_user_assign_to_not_max_level_task(&user_assign, &mut tx)
-            .await?;
-        self.validate_user_assign_same_user_assign_exists(&user_assign, &mut tx)
+        self.validate_user_assign_same_user_assign_exists(user_assign, tx)
             .await?;
 
+        let before = get_user_assign_by_id_with_transaction(
+            user_assign.user_assign_id.unwrap_or_default(),
+            tx,
+        )
+        .await
+        .ok();
+
         let result = sqlx::query_as!(
             UserAssign,
             r#"
                 UPDATE user_assign
                 SET user_id = $1, task_id = $2
-                WHERE user_assign_id = $3
+                WHERE user_assign_id = $3 AND deleted_at IS NULL
                 RETURNING user_assign_id, user_id, task_id
             "#,
             user_assign.user_id,
             user_assign.task_id,
             user_assign.user_assign_id,
         )
-        .fetch_optional(&mut *tx)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
@@ -379,35 +493,72 @@ impl UserAssignRepository {
             )))
         })?;
 
+        let Some(updated) = result else {
+            return Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::UserAssignGetByIdNotFound,
+                [("id", json!(user_assign.user_assign_id))],
+            )));
+        };
+
+        let audit_log = self.user_assign_audit_log(
+            updated.user_assign_id.unwrap_or_default(),
+            AuditAction::Update,
+            before.as_ref(),
+            Some(&updated),
+        );
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        Ok(updated)
+    }
+
+    pub async fn delete_user_assign(&self, id: i64) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("user_assign", "delete_user_assign");
+        validate_user_assign_id(Some(id))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        bump_version_with_transaction(
+            AuditEntityType::UserAssign,
+            id,
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        let deleted = self
+            .delete_user_assign_with_transaction(id, &mut tx)
+            .await?;
+
         tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
-                ErrorKey::UserAssignUpdateFailed,
+                ErrorKey::UserAssignDeleteFailed,
                 e.to_string()
             )))
         })?;
 
-        log::info!("Update user assign: {:?}", result);
+        log::info!("Delete user assign: {:?}", deleted);
 
-        match result {
-            Some(user_assign) => Ok(user_assign),
-            None => Err(DBAccessError::NotFoundError(get_error_message(
-                ErrorKey::UserAssignGetByIdNotFound,
-                format!("ID = {:?}", user_assign.user_assign_id),
-            ))),
-        }
+        Ok(())
     }
 
-    pub async fn delete_user_assign(&self, id: i64) -> Result<(), DBAccessError> {
+    // 呼び出し元のトランザクションで削除し、削除した割り当てを返す。コミットは呼び出し元で行う
+    pub async fn delete_user_assign_with_transaction(
+        &self,
+        id: i64,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<UserAssign, DBAccessError> {
         validate_user_assign_id(Some(id))?;
 
-        let result = sqlx::query!(
+        let result = sqlx::query_as!(
+            UserAssign,
             r#"
                 DELETE FROM user_assign
                 WHERE user_assign_id = $1
+                RETURNING user_assign_id, user_id, task_id
             "#,
             id,
         )
-        .execute(&self.pool)
+        .fetch_optional(&mut **tx)
         .await
         .map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
@@ -416,16 +567,103 @@ impl UserAssignRepository {
             )))
         })?;
 
-        if result.rows_affected() == 0 {
-            return Err(DBAccessError::ValidationError(get_error_message(
-                ErrorKey::UserAssignDeleteFailedByIdNotFound,
-                format!("ID = {}", id),
-            )));
+        let deleted = match result {
+            Some(user_assign) => user_assign,
+            None => {
+                return Err(DBAccessError::ValidationError(get_error_message(
+                    ErrorKey::UserAssignDeleteFailedByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+
+        let audit_log = self.user_assign_audit_log(id, AuditAction::Delete, Some(&deleted), None);
+        create_audit_log_with_transaction(&audit_log, tx).await?;
+
+        Ok(deleted)
+    }
+
+    // 全ての操作を1つのトランザクションで実行する。いずれかが失敗した場合は全てロールバックする
+    pub async fn run_user_assign_batch(
+        &self,
+        operations: Vec<UserAssignBatchOperation>,
+    ) -> Result<Vec<UserAssignBatchResult>, BatchError> {
+        let _timer = QueryTimer::start("user_assign", "run_user_assign_batch");
+        validate_batch_operations_count(operations.len())?;
+
+        let mut tx = self.pool.begin().await.map_err(DBAccessError::from)?;
+
+        let mut results = Vec::new();
+        for (index, operation) in operations.into_iter().enumerate() {
+            let result = self
+                .run_user_assign_batch_operation(index, operation, &mut tx)
+                .await
+                .map_err(|e| BatchError::at(index, e))?;
+            results.push(result);
         }
 
-        log::info!("Delete user assign: {:?}", result);
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::BatchCommitFailed,
+                e.to_string()
+            )))
+        })?;
+        log::info!("User assign batch: {} operations", results.len());
+        Ok(results)
+    }
+
+    async fn run_user_assign_batch_operation(
+        &self,
+        index: usize,
+        operation: UserAssignBatchOperation,
+        tx: &mut Transaction<'_, Sqlite>,
+    ) -> Result<UserAssignBatchResult, DBAccessError> {
+        let (action, user_assign) = match operation {
+            UserAssignBatchOperation::Create { user_assign } => {
+                let user_assign = self
+                    .create_user_assign_with_transaction(&user_assign, tx)
+                    .await?;
+                (AuditAction::Create, user_assign)
+            }
+            UserAssignBatchOperation::Update {
+                version,
+                user_assign,
+            } => {
+                bump_version_with_transaction(
+                    AuditEntityType::UserAssign,
+                    user_assign.user_assign_id.unwrap_or_default(),
+                    version.as_ref().map(std::slice::from_ref),
+                    tx,
+                )
+                .await?;
+                let user_assign = self
+                    .update_user_assign_with_transaction(&user_assign, tx)
+                    .await?;
+                (AuditAction::Update, user_assign)
+            }
+            UserAssignBatchOperation::Delete {
+                user_assign_id,
+                version,
+            } => {
+                bump_version_with_transaction(
+                    AuditEntityType::UserAssign,
+                    user_assign_id,
+                    version.as_ref().map(std::slice::from_ref),
+                    tx,
+                )
+                .await?;
+                let user_assign = self
+                    .delete_user_assign_with_transaction(user_assign_id, tx)
+                    .await?;
+                (AuditAction::Delete, user_assign)
+            }
+        };
 
-        Ok(())
+        Ok(UserAssignBatchResult {
+            index,
+            op: action.to_str().to_string(),
+            user_assign,
+        })
     }
 }
 
@@ -438,7 +676,7 @@ pub async fn get_user_assign_by_id_with_transaction(
         r#"
             SELECT user_assign_id, user_id, task_id
             FROM user_assign
-            WHERE user_assign_id = $1
+            WHERE user_assign_id = $1 AND deleted_at IS NULL
         "#,
         id,
     )
@@ -456,7 +694,7 @@ pub async fn get_user_assign_by_id_with_transaction(
         Some(user_assign) => Ok(user_assign),
         None => Err(DBAccessError::NotFoundError(get_error_message(
             ErrorKey::UserAssignGetByIdNotFound,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         ))),
     }
 }
@@ -470,7 +708,7 @@ pub async fn get_user_assign_by_user_id_with_transaction(
         r#"
             SELECT user_assign_id, user_id, task_id
             FROM user_assign
-            WHERE user_id = $1
+            WHERE user_id = $1 AND deleted_at IS NULL
         "#,
         user_id,
     )
@@ -496,7 +734,7 @@ pub async fn get_user_assign_by_task_id_with_transaction(
         r#"
             SELECT user_assign_id, user_id, task_id
             FROM user_assign
-            WHERE task_id = $1
+            WHERE task_id = $1 AND deleted_at IS NULL
         "#,
         task_id,
     )
@@ -523,7 +761,7 @@ pub async fn get_user_assign_by_user_id_and_task_id_with_transaction(
         r#"
             SELECT user_assign_id, user_id, task_id
             FROM user_assign
-            WHERE user_id = $1 AND task_id = $2
+            WHERE user_id = $1 AND task_id = $2 AND deleted_at IS NULL
         "#,
         user_id,
         task_id,
@@ -545,7 +783,7 @@ pub async fn get_user_assign_by_user_id_and_task_id_with_transaction(
         Some(user_assign) => Ok(user_assign),
         None => Err(DBAccessError::NotFoundError(get_error_message(
             ErrorKey::UserAssignGetByUserIdAndTaskIdNotFound,
-            format!("User ID = {}, Task ID = {}", user_id, task_id),
+            [("user_id", json!(user_id)), ("task_id", json!(task_id))],
         ))),
     }
 }
@@ -586,6 +824,7 @@ pub async fn get_user_assigns_count_with_transaction(
             count
         }
         None => {
+            let query = format!("{} WHERE deleted_at IS NULL", query);
             let count = sqlx::query_scalar::<_, i64>(&query)
                 .fetch_one(&mut **transaction)
                 .await
@@ -605,7 +844,8 @@ pub async fn get_user_assigns_count_with_transaction(
 }
 
 fn build_where_clause(filter: &UserAssignFilter) -> (String, Vec<FilterValue>) {
-    let mut where_calses = Vec::new();
+    // ゴミ箱の割り当ては常に除外する
+    let mut where_calses = vec!["deleted_at IS NULL".to_string()];
     let mut bind_values: Vec<FilterValue> = Vec::new();
 
     let mut index = 1;
@@ -617,16 +857,26 @@ fn build_where_clause(filter: &UserAssignFilter) -> (String, Vec<FilterValue>) {
     if filter.task_id.is_some() {
         where_calses.push(format!("task_id = ${}", index));
         bind_values.push(FilterValue::I64(filter.task_id.unwrap()));
+        index += 1;
     }
-
-    if !where_calses.is_empty() {
-        (
-            format!(" WHERE {}", where_calses.join(" AND ")),
-            bind_values,
-        )
-    } else {
-        ("".to_string(), bind_values)
+    if let Some(member_id) = filter.member_id {
+        where_calses.push(format!(
+            r#"
+                task_id IN (
+                    SELECT tasks.task_id FROM tasks
+                    INNER JOIN project_members ON project_members.project_id = tasks.project_id
+                    WHERE project_members.user_id = ${}
+                )
+            "#,
+            index
+        ));
+        bind_values.push(FilterValue::I64(member_id));
     }
+
+    (
+        format!(" WHERE {}", where_calses.join(" AND ")),
+        bind_values,
+    )
 }
 
 fn validate_filter(filter: &UserAssignFilter) -> Result<()> {
@@ -652,7 +902,7 @@ pub async fn get_related_task_ids_by_user_ids(
         0 => return Ok(HashMap::new()),
         _ => format!(
             r#"
-                SELECT user_assign_id, user_id, task_id FROM user_assign WHERE user_id IN ({})
+                SELECT user_assign_id, user_id, task_id FROM user_assign WHERE user_id IN ({}) AND deleted_at IS NULL
         "#,
             placeholder.join(",")
         ),
@@ -697,7 +947,7 @@ pub async fn get_related_user_ids_by_task_ids(
         0 => return Ok(HashMap::new()),
         _ => format!(
             r#"
-            SELECT user_assign_id, task_id, user_id FROM user_assign WHERE task_id IN ({})
+            SELECT user_assign_id, task_id, user_id FROM user_assign WHERE task_id IN ({}) AND deleted_at IS NULL
         "#,
             placeholder.join(",")
         ),
@@ -1,15 +1,31 @@
+use crate::enums::{AuditAction, AuditEntityType, DeletePolicy};
 use crate::errors::db_error::DBAccessError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::metrics::QueryTimer;
 use crate::models::user::UserFilter;
-use crate::models::{User, UserNoPassword};
+use crate::models::{AuditContext, AuditLog, DeleteSummary, User, UserNoPassword, UserPatch};
+use crate::repository::audit_log_repo::{create_audit_log_with_transaction, to_audit_json};
+use crate::repository::password::{
+    hash_password_async, needs_rehash, verify_dummy_password_async, verify_password_async,
+};
+use crate::repository::trash_repo::{
+    purge_user_dependents_with_transaction, trash_user_dependents_with_transaction,
+};
 use crate::repository::validations::{
     validate_pagination, validate_user_email, validate_user_id, validate_user_id_is_none,
     validate_user_name, validate_user_password,
 };
+use crate::repository::version_repo::{
+    Versioned, bump_version_with_transaction, check_version_with_transaction,
+};
+use chrono::Utc;
+use serde_json::json;
 use sqlx::{Pool, Sqlite, Transaction};
 
 pub struct UserRepository {
     pool: Pool<Sqlite>,
+    audit_context: AuditContext,
+    expected_versions: Option<Vec<i64>>,
 }
 
 #[derive(Debug)]
@@ -37,16 +53,35 @@ fn deduplicate(users: Vec<User>) -> Vec<User> {
 
 impl UserRepository {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            audit_context: AuditContext::default(),
+            expected_versions: None,
+        }
+    }
+
+    // 監査ログに記録する操作者とリクエストIDを設定する
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    // If-Matchで指定されたバージョン。更新・削除の前にトランザクション内で確認する
+    pub fn with_expected_versions(mut self, expected_versions: Option<Vec<i64>>) -> Self {
+        self.expected_versions = expected_versions;
+        self
     }
 
     pub async fn create_user(&self, user: User) -> Result<UserNoPassword, DBAccessError> {
+        let _timer = QueryTimer::start("user", "create_user");
         validate_user_id(user.user_id)?;
         validate_user_id_is_none(user.user_id)?;
         validate_user_name(&user.username)?;
         validate_user_email(&user.email)?;
         validate_user_password(&user.password_hash)?;
 
+        let mut tx = self.pool.begin().await?;
+
         let result = sqlx::query_as!(
             User,
             r#"
@@ -58,9 +93,29 @@ impl UserRepository {
             user.email,
             user.password_hash,
         )
-        .fetch_one(&self.pool)
+        .fetch_one(&mut *tx)
         .await
         .map_err(|e| {
+            DBAccessError::from_write_error(
+                e,
+                ErrorKey::UserCreateFailed,
+                ErrorKey::UserAlreadyExists,
+            )
+        })?;
+
+        // パスワードのハッシュは監査ログに残さない
+        let created = result.to_user_no_password();
+        let audit_log = AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::User,
+            created.user_id.unwrap_or_default(),
+            AuditAction::Create,
+            None,
+            to_audit_json(&created),
+        );
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::UserCreateFailed,
                 e.to_string()
@@ -68,10 +123,11 @@ impl UserRepository {
         })?;
         log::info!("Created user: {:?}", result);
 
-        Ok(result.to_user_no_password())
+        Ok(created)
     }
 
     pub async fn get_user_by_id(&self, id: i64) -> Result<UserNoPassword, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_user_by_id");
         let result = sqlx::query_as!(
             User,
             r#"
@@ -91,16 +147,74 @@ impl UserRepository {
         })?;
         log::debug!("Get user by id: {:?}", result);
 
-        match result {
-            Some(user) => Ok(user.to_user_no_password()),
+        match result.zip(version) {
+            Some((user, version)) => Ok(Versioned {
+                value: user.to_user_no_password(),
+                version,
+            }),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::UserGetByIdNotFound,
-                format!("ID = {}", id),
+                [("id", json!(id))],
             ))),
         }
     }
 
+    // 存在しないユーザーは管理者ではないものとして扱う
+    pub async fn is_admin(&self, user_id: i64) -> Result<bool, DBAccessError> {
+        let _timer = QueryTimer::start("user", "is_admin");
+        let result = sqlx::query_scalar!(
+            r#"
+                SELECT is_admin AS "is_admin: bool"
+                FROM users
+                WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserGetAdminFailed,
+                e.to_string()
+            )))
+        })?;
+
+        Ok(result.unwrap_or(false))
+    }
+
+    pub async fn set_admin(&self, user_id: i64, is_admin: bool) -> Result<(), DBAccessError> {
+        let _timer = QueryTimer::start("user", "set_admin");
+        let result = sqlx::query!(
+            r#"
+                UPDATE users
+                SET is_admin = $1
+                WHERE user_id = $2
+            "#,
+            is_admin,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserSetAdminFailed,
+                e.to_string()
+            )))
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::UserGetByIdNotFound,
+                [("id", json!(user_id))],
+            )));
+        }
+        log::info!("Set admin: User ID = {}, Admin = {}", user_id, is_admin);
+
+        Ok(())
+    }
+
     pub async fn get_user_by_name(&self, name: &str) -> Result<UserNoPassword, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_user_by_name");
         let result = sqlx::query_as!(
             User,
             r#"
@@ -124,12 +238,87 @@ impl UserRepository {
             Some(user) => Ok(user.to_user_no_password()),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::UserGetByNameNotFound,
-                format!("Name = {}", name),
+                [("name", json!(name))],
+            ))),
+        }
+    }
+
+    // 認証用。パスワードハッシュを含むユーザーを返すため、レスポンスには使用しないこと
+    pub async fn get_user_with_password_by_name(&self, name: &str) -> Result<User, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_user_with_password_by_name");
+        let result = sqlx::query_as!(
+            User,
+            r#"
+                SELECT user_id, username, email, password_hash
+                FROM users
+                WHERE username = $1
+            "#,
+            name
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserGetByNameFailed,
+                e.to_string()
+            )))
+        })?;
+
+        match result {
+            Some(user) => Ok(user),
+            None => Err(DBAccessError::NotFoundError(get_error_message(
+                ErrorKey::UserGetByNameNotFound,
+                [("name", json!(name))],
             ))),
         }
     }
 
+    // ユーザー名とパスワードを検証する。ユーザーが存在しない場合もパスワード不一致と同じくNoneを返し、
+    // 応答時間で存在が分からないようダミーのハッシュで同じだけ検証する。
+    // 検証に成功し、保存されたハッシュが古い形式（SHA-256）または古いパラメータの場合は再ハッシュして保存する。
+    pub async fn verify_user_password(
+        &self,
+        name: &str,
+        password: &str,
+    ) -> Result<Option<UserNoPassword>, DBAccessError> {
+        let _timer = QueryTimer::start("user", "verify_user_password");
+        let user = match self.get_user_with_password_by_name(name).await {
+            Ok(user) => user,
+            Err(DBAccessError::NotFoundError(_)) => {
+                verify_dummy_password_async(password.to_string()).await?;
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if !verify_password_async(password.to_string(), user.password_hash.clone()).await? {
+            log::info!("Password verification failed: User ID = {:?}", user.user_id);
+            return Ok(None);
+        }
+
+        if needs_rehash(&user.password_hash) {
+            let password_hash = hash_password_async(password.to_string()).await?;
+            let mut tx = self.pool.begin().await?;
+            update_user_password_hash_with_transaction(
+                &user.user_id.unwrap(),
+                &password_hash,
+                &mut tx,
+            )
+            .await?;
+            tx.commit().await.map_err(|e| {
+                DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                    ErrorKey::PasswordRehashFailed,
+                    e.to_string()
+                )))
+            })?;
+            log::info!("Rehashed password: User ID = {:?}", user.user_id);
+        }
+
+        Ok(Some(user.to_user_no_password()))
+    }
+
     pub async fn get_all_users(&self) -> Result<Vec<UserNoPassword>, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_all_users");
         let result = sqlx::query_as!(
             User,
             r#"
@@ -151,6 +340,7 @@ impl UserRepository {
     }
 
     pub async fn get_users_count(&self) -> Result<i64, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_users_count");
         let result = sqlx::query_scalar!(
             r#"
                 SELECT COUNT(*) FROM users
@@ -174,6 +364,7 @@ impl UserRepository {
         page: &i32,
         page_size: &i32,
     ) -> Result<Vec<UserNoPassword>, DBAccessError> {
+        let _timer = QueryTimer::start("user", "get_users_with_pagination");
         let mut tx = self.pool.begin().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::ProjectGetAllFailed,
@@ -189,7 +380,7 @@ impl UserRepository {
         if offset as i64 >= count {
             return Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::UserGetUsersPaginationNotFound,
-                format!("Offset: {}, Count: {}", offset, count),
+                [("offset", json!(offset)), ("count", json!(count))],
             )));
         }
         log::debug!(
@@ -240,7 +431,11 @@ impl UserRepository {
         }
     }
 
-    pub async fn update_user(&self, user: User) -> Result<UserNoPassword, DBAccessError> {
+    pub async fn update_user(
+        &self,
+        user: User,
+    ) -> Result<Versioned<UserNoPassword>, DBAccessError> {
+        let _timer = QueryTimer::start("user", "update_user");
         validate_user_id(user.user_id)?;
         validate_user_name(&user.username)?;
         validate_user_email(&user.email)?;
@@ -249,12 +444,20 @@ impl UserRepository {
         if user.user_id.is_none() {
             return Err(DBAccessError::ValidationError(get_error_message(
                 ErrorKey::UserIdInvalid,
-                format!("ID = {:?}", user.user_id),
+                [("id", json!(user.user_id))],
             )));
         }
 
         let mut tx = self.pool.begin().await?;
-        let _ = get_user_by_id_with_transaction(&user.user_id.unwrap(), &mut tx).await?;
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::User,
+            user.user_id.unwrap_or_default(),
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+        let before = get_user_by_id_with_transaction(&user.user_id.unwrap(), &mut tx).await?;
 
         let result = sqlx::query_as!(
             User,
@@ -272,12 +475,25 @@ impl UserRepository {
         .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
-            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            DBAccessError::from_write_error(
+                e,
                 ErrorKey::UserUpdateFailed,
-                e.to_string()
-            )))
+                ErrorKey::UserAlreadyExists,
+            )
         })?;
 
+        if let Some(updated) = &result {
+            let audit_log = AuditLog::new(
+                &self.audit_context,
+                AuditEntityType::User,
+                before.user_id.unwrap_or_default(),
+                AuditAction::Update,
+                to_audit_json(&before),
+                to_audit_json(&updated.to_user_no_password()),
+            );
+            create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+        }
+
         tx.commit().await.map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::UserUpdateFailed,
@@ -287,25 +503,187 @@ impl UserRepository {
 
         log::info!("Updated user: {:?}", result);
 
-        match result {
-            Some(user) => Ok(user.to_user_no_password()),
+        match result.zip(version) {
+            Some((user, version)) => Ok(Versioned {
+                value: user.to_user_no_password(),
+                version,
+            }),
             None => Err(DBAccessError::NotFoundError(get_error_message(
                 ErrorKey::UserGetByIdNotFound,
-                format!("ID = {:?}", user.user_id),
+                [("id", json!(user.user_id))],
             ))),
         }
     }
 
-    pub async fn delete_user(&self, id: i64) -> Result<(), DBAccessError> {
+    // 指定されたフィールドのみ検証して更新する。passwordはハッシュ化済みの値を受け取る
+    pub async fn patch_user(
+        &self,
+        id: i64,
+        patch: UserPatch,
+    ) -> Result<Versioned<UserNoPassword>, DBAccessError> {
+        let _timer = QueryTimer::start("user", "patch_user");
         validate_user_id(Some(id))?;
-        let result = sqlx::query!(
+        if let Some(username) = &patch.username {
+            validate_user_name(username)?;
+        }
+        if let Some(email) = &patch.email {
+            validate_user_email(email)?;
+        }
+        if let Some(password_hash) = &patch.password {
+            validate_user_password(password_hash)?;
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        if patch.is_empty() {
+            let version = check_version_with_transaction(
+                AuditEntityType::User,
+                id,
+                self.expected_versions.as_deref(),
+                &mut tx,
+            )
+            .await?;
+            let before = get_user_by_id_with_transaction(&id, &mut tx).await?;
+            return Ok(Versioned {
+                value: before,
+                version: version.unwrap_or_default(),
+            });
+        }
+
+        let version = bump_version_with_transaction(
+            AuditEntityType::User,
+            id,
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+        let before = get_user_by_id_with_transaction(&id, &mut tx).await?;
+
+        let updated = sqlx::query_as!(
+            User,
+            r#"
+                UPDATE users
+                SET username = COALESCE($1, username),
+                    email = COALESCE($2, email),
+                    password_hash = COALESCE($3, password_hash)
+                WHERE user_id = $4
+                RETURNING user_id, username, email, password_hash
+            "#,
+            patch.username,
+            patch.email,
+            patch.password,
+            id,
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| {
+            DBAccessError::from_write_error(
+                e,
+                ErrorKey::UserUpdateFailed,
+                ErrorKey::UserAlreadyExists,
+            )
+        })?
+        .to_user_no_password();
+
+        let audit_log = AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::User,
+            id,
+            AuditAction::Update,
+            to_audit_json(&before),
+            to_audit_json(&updated),
+        );
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+                ErrorKey::UserUpdateFailed,
+                e.to_string()
+            )))
+        })?;
+
+        log::info!("Patched user: {:?}", updated);
+        Ok(Versioned {
+            value: updated,
+            version: version.unwrap_or_default(),
+        })
+    }
+
+    // ユーザーは完全に削除する。restrictはゴミ箱にない割り当てかコメントがあれば失敗し、
+    // cascadeはそれらも一緒に削除する。reparentは使えない。
+    // どちらの場合もゴミ箱にある割り当て・コメントとセッションは削除する
+    pub async fn delete_user(
+        &self,
+        id: i64,
+        policy: DeletePolicy,
+    ) -> Result<DeleteSummary, DBAccessError> {
+        let _timer = QueryTimer::start("user", "delete_user");
+        validate_user_id(Some(id))?;
+        if policy == DeletePolicy::Reparent {
+            return Err(DBAccessError::ValidationError(get_error_message(
+                ErrorKey::DeletePolicyNotSupported,
+                [
+                    ("entity", json!("user")),
+                    ("policy", json!(policy.to_str())),
+                ],
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        bump_version_with_transaction(
+            AuditEntityType::User,
+            id,
+            self.expected_versions.as_deref(),
+            &mut tx,
+        )
+        .await?;
+
+        if policy == DeletePolicy::Restrict {
+            // ユーザーは完全に削除するため、ゴミ箱にある割り当てとコメントも残せない
+            let dependents = count_user_dependents_with_transaction(id, &mut tx).await?;
+            if dependents.user_assigns > 0
+                || dependents.comments > 0
+                || dependents.trashed_user_assigns > 0
+                || dependents.trashed_comments > 0
+            {
+                return Err(DBAccessError::ConflictError(get_error_message(
+                    ErrorKey::DeletePolicyRestricted,
+                    [
+                        ("id", json!(id)),
+                        ("user_assigns", json!(dependents.user_assigns)),
+                        ("comments", json!(dependents.comments)),
+                        (
+                            "trashed_user_assigns",
+                            json!(dependents.trashed_user_assigns),
+                        ),
+                        ("trashed_comments", json!(dependents.trashed_comments)),
+                    ],
+                )));
+            }
+        }
+
+        // タスクやプロジェクトの削除と同じくゴミ箱に移動してから、ゴミ箱ごと完全に削除する
+        let now = Utc::now().timestamp();
+        trash_user_dependents_with_transaction(id, now, &self.audit_context, &mut tx).await?;
+        let purged =
+            purge_user_dependents_with_transaction(id, &self.audit_context, &mut tx).await?;
+        // プロジェクトのメンバーとAPIトークンは外部キーのON DELETE CASCADEで削除される
+        sqlx::query!("DELETE FROM sessions WHERE user_id = $1", id)
+            .execute(&mut *tx)
+            .await
+            .map_err(delete_dependents_error)?;
+
+        let result = sqlx::query_as!(
+            User,
             r#"
                 DELETE FROM users
                 WHERE user_id = $1
+                RETURNING user_id, username, email, password_hash
             "#,
             id
         )
-        .execute(&self.pool)
+        .fetch_optional(&mut *tx)
         .await
         .map_err(|e| {
             DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
@@ -314,19 +692,85 @@ impl UserRepository {
             )))
         })?;
 
-        if result.rows_affected() == 0 {
-            return Err(DBAccessError::NotFoundError(get_error_message(
+        let deleted = match result {
+            Some(user) => user.to_user_no_password(),
+            None => {
+                return Err(DBAccessError::NotFoundError(get_error_message(
+                    ErrorKey::UserDeleteFailedByIdNotFound,
+                    [("id", json!(id))],
+                )));
+            }
+        };
+
+        let audit_log = AuditLog::new(
+            &self.audit_context,
+            AuditEntityType::User,
+            id,
+            AuditAction::Delete,
+            to_audit_json(&deleted),
+            None,
+        );
+        create_audit_log_with_transaction(&audit_log, &mut tx).await?;
+
+        tx.commit().await.map_err(|e| {
+            DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
                 ErrorKey::UserDeleteFailedByIdNotFound,
-                format!("ID = {}", id),
-            )));
-        }
+                e.to_string()
+            )))
+        })?;
 
-        log::info!("Deleted user: {:?}", result);
+        log::info!("Deleted user: {:?}", deleted);
 
-        Ok(())
+        Ok(DeleteSummary {
+            policy: policy.to_str().to_string(),
+            users: 1,
+            user_assigns: purged.user_assigns,
+            comments: purged.comments,
+            ..Default::default()
+        })
     }
 }
 
+// ユーザーを参照している割り当て・コメントの件数。ゴミ箱にあるものは別に数える
+struct UserDependents {
+    user_assigns: i64,
+    comments: i64,
+    trashed_user_assigns: i64,
+    trashed_comments: i64,
+}
+
+async fn count_user_dependents_with_transaction(
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<UserDependents, DBAccessError> {
+    sqlx::query_as!(
+        UserDependents,
+        r#"
+            SELECT
+                (SELECT COUNT(*) FROM user_assign WHERE user_id = $1 AND deleted_at IS NULL) AS "user_assigns!: i64",
+                (SELECT COUNT(*) FROM comments WHERE user_id = $1 AND deleted_at IS NULL) AS "comments!: i64",
+                (SELECT COUNT(*) FROM user_assign WHERE user_id = $1 AND deleted_at IS NOT NULL) AS "trashed_user_assigns!: i64",
+                (SELECT COUNT(*) FROM comments WHERE user_id = $1 AND deleted_at IS NOT NULL) AS "trashed_comments!: i64"
+        "#,
+        id
+    )
+    .fetch_one(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::UserDeleteFailed,
+            e.to_string()
+        )))
+    })
+}
+
+fn delete_dependents_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::DeletePolicyDependentsFailed,
+        e.to_string()
+    )))
+}
+
 fn build_where_clause(
     filter: &UserFilter,
     task_ids: Option<&Vec<i64>>,
@@ -383,6 +827,41 @@ fn validate_filter(filter: &UserFilter) -> Result<(), DBAccessError> {
     Ok(())
 }
 
+pub async fn update_user_password_hash_with_transaction(
+    id: &i64,
+    password_hash: &str,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<(), DBAccessError> {
+    validate_user_password(password_hash)?;
+
+    let result = sqlx::query!(
+        r#"
+            UPDATE users
+            SET password_hash = $1
+            WHERE user_id = $2
+        "#,
+        password_hash,
+        id
+    )
+    .execute(&mut **tx)
+    .await
+    .map_err(|e| {
+        DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+            ErrorKey::PasswordRehashFailed,
+            e.to_string()
+        )))
+    })?;
+
+    if result.rows_affected() == 0 {
+        return Err(DBAccessError::NotFoundError(get_error_message(
+            ErrorKey::UserGetByIdNotFound,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
 pub async fn get_user_by_id_with_transaction(
     id: &i64,
     tx: &mut Transaction<'_, Sqlite>,
@@ -410,7 +889,7 @@ pub async fn get_user_by_id_with_transaction(
         Some(user) => Ok(user.to_user_no_password()),
         None => Err(DBAccessError::NotFoundError(get_error_message(
             ErrorKey::UserGetByIdNotFound,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         ))),
     }
 }
@@ -429,7 +908,7 @@ pub async fn get_users_count_with_transaction(
     if task_ids.is_some() && task_ids.unwrap().len() > 0 {
         query.push_str(
             r#"
-            INNER JOIN user_assign ON users.user_id = user_assign.user_id
+            INNER JOIN user_assign ON users.user_id = user_assign.user_id AND user_assign.deleted_at IS NULL
         "#,
         );
     }
@@ -497,7 +976,7 @@ pub async fn get_users_with_pagination_with_transaction(
     if task_ids.is_some() && task_ids.unwrap().len() > 0 {
         query.push_str(
             r#"
-            INNER JOIN user_assign ON users.user_id = user_assign.user_id
+            INNER JOIN user_assign ON users.user_id = user_assign.user_id AND user_assign.deleted_at IS NULL
         "#,
         );
     }
@@ -1,8 +1,11 @@
-use crate::enums::{TaskLevel, TaskStatus};
+use crate::enums::{ProjectRole, TaskLevel, TaskStatus};
 use crate::errors::db_error::DBAccessError;
 use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::repository::password::{is_legacy_hash, is_phc_hash};
+use crate::settings::server_config;
 use email_address::EmailAddress;
 use regex::Regex;
+use serde_json::json;
 
 pub fn validate_user_id(id: Option<i64>) -> Result<(), DBAccessError> {
     if id.is_none() {
@@ -12,7 +15,7 @@ pub fn validate_user_id(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserIdInvalid,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -23,7 +26,7 @@ pub fn validate_user_id_is_none(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserIdMustBeNone,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -32,14 +35,14 @@ pub fn validate_user_name(name: &str) -> Result<(), DBAccessError> {
     if name.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserNameEmpty,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
     if name.len() > 128 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserNameTooLong,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
@@ -47,7 +50,7 @@ pub fn validate_user_name(name: &str) -> Result<(), DBAccessError> {
     if !re.is_match(name) {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserNameContainsInvalidCharacters,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
@@ -58,21 +61,21 @@ pub fn validate_user_email(email: &str) -> Result<(), DBAccessError> {
     if email.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserEmailEmpty,
-            format!("Email = {}", email),
+            [("email", json!(email))],
         )));
     }
 
     if email.len() > 254 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserEmailTooLong,
-            format!("Email = {}", email),
+            [("email", json!(email))],
         )));
     }
 
     if !EmailAddress::is_valid(email) {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserEmailInvalid,
-            format!("Email = {}", email),
+            [("email", json!(email))],
         )));
     }
 
@@ -83,15 +86,15 @@ pub fn validate_user_password(password: &str) -> Result<(), DBAccessError> {
     if password.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserPasswordEmpty,
-            format!("Password = {}", password),
+            [("password", json!(password))],
         )));
     }
 
-    let re_sha256 = Regex::new(r"^[a-f0-9]{64}$").unwrap();
-    if !re_sha256.is_match(password) {
+    // Argon2のPHC形式に加え、移行前のSHA-256形式も受け付ける
+    if !is_phc_hash(password) && !is_legacy_hash(password) {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserPasswordInvalid,
-            format!("Password = {}", password),
+            [("password", json!(password))],
         )));
     }
 
@@ -106,7 +109,7 @@ pub fn validate_project_id(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::ProjectIdInvalid,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -115,14 +118,14 @@ pub fn validate_project_name(name: &str) -> Result<(), DBAccessError> {
     if name.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::ProjectNameEmpty,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
     if name.len() > 128 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::ProjectNameTooLong,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
@@ -137,7 +140,7 @@ pub fn validate_task_id(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskIdInvalid,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -148,7 +151,7 @@ pub fn validate_task_id_is_none(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskIdMustBeNone,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -157,7 +160,7 @@ pub fn validate_task_project_id(id: i64) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskProjectIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -172,28 +175,29 @@ pub fn validate_task_parent_id(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskParentIdInvalid,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
 
 pub fn validate_task_level(level: i64) -> Result<(), DBAccessError> {
-    TaskLevel::from_int(level).map_err(|e| {
-        DBAccessError::ValidationError(get_error_message(
-            ErrorKey::TaskLevelInvalid,
-            format!("{}", e.to_string()),
-        ))
-    })?;
+    TaskLevel::from_int(level).map_err(|e| DBAccessError::ValidationError(e.into()))?;
     Ok(())
 }
 
 pub fn validate_task_status(status: i64) -> Result<(), DBAccessError> {
-    TaskStatus::from_int(status).map_err(|e| {
-        DBAccessError::ValidationError(get_error_message(
-            ErrorKey::TaskStatusInvalid,
-            format!("{}", e.to_string()),
-        ))
-    })?;
+    TaskStatus::from_int(status).map_err(|e| DBAccessError::ValidationError(e.into()))?;
+    Ok(())
+}
+
+// ツリーの深さはタスクのレベルの数を超えない
+pub fn validate_task_tree_max_depth(max_depth: i64) -> Result<(), DBAccessError> {
+    if !(0..=TaskLevel::max_level()).contains(&max_depth) {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::TaskTreeMaxDepthInvalid,
+            [("max_depth", json!(max_depth))],
+        )));
+    }
     Ok(())
 }
 
@@ -201,14 +205,14 @@ pub fn validate_task_name(name: &str) -> Result<(), DBAccessError> {
     if name.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskNameEmpty,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
     if name.len() > 128 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskNameTooLong,
-            format!("Name = {}", name),
+            [("name", json!(name))],
         )));
     }
 
@@ -223,7 +227,7 @@ pub fn validate_task_description(description: Option<&String>) -> Result<(), DBA
     if description.unwrap().len() > 1024 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskDescriptionTooLong,
-            format!("Description = {}", description.unwrap()),
+            [("description", json!(description.unwrap()))],
         )));
     }
 
@@ -234,7 +238,7 @@ pub fn validate_task_unix_timestamp(timestamp: i64) -> Result<(), DBAccessError>
     if timestamp < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskTimestampInvalid,
-            format!("Timestamp = {}", timestamp),
+            [("timestamp", json!(timestamp))],
         )));
     }
 
@@ -249,7 +253,7 @@ pub fn validate_task_unix_timestamp_or_none(timestamp: Option<i64>) -> Result<()
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::TaskTimestampInvalid,
-            format!("Timestamp = {}", timestamp.unwrap()),
+            [("timestamp", json!(timestamp.unwrap()))],
         )));
     }
 }
@@ -262,7 +266,7 @@ pub fn validate_user_assign_id(id: Option<i64>) -> Result<(), DBAccessError> {
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserAssignIdInvalid,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -273,7 +277,7 @@ pub fn validate_user_assign_id_is_none(id: Option<i64>) -> Result<(), DBAccessEr
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserAssignIdMustBeNone,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
         )));
     }
 }
@@ -282,7 +286,7 @@ pub fn validate_user_assign_user_id(id: i64) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserAssignUserIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -293,7 +297,7 @@ pub fn validate_user_assign_task_id(id: i64) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::UserAssignTaskIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -304,7 +308,7 @@ pub fn validate_comment_user_id(id: i64) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentUserIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -315,7 +319,7 @@ pub fn validate_comment_task_id(id: i64) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentTaskIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -326,14 +330,14 @@ pub fn validate_comment_content(content: &str) -> Result<(), DBAccessError> {
     if content.is_empty() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentContentEmpty,
-            format!("Content = {}", content),
+            [("content", json!(content))],
         )));
     }
 
     if content.len() > 2024 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentContentTooLong,
-            format!("Content = {}", content),
+            [("content", json!(content))],
         )));
     }
 
@@ -349,7 +353,7 @@ pub fn validate_comment_id(id: Option<i64>) -> Result<(), DBAccessError> {
     if id < 0 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentIdInvalid,
-            format!("ID = {}", id),
+            [("id", json!(id))],
         )));
     }
 
@@ -362,9 +366,194 @@ pub fn validate_comment_id_is_none(id: Option<i64>) -> Result<(), DBAccessError>
     } else {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::CommentIdMustBeNone,
-            format!("ID = {}", id.unwrap()),
+            [("id", json!(id.unwrap()))],
+        )));
+    }
+}
+
+pub fn validate_session_id_is_none(id: Option<i64>) -> Result<(), DBAccessError> {
+    if id.is_none() {
+        return Ok(());
+    } else {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::SessionIdMustBeNone,
+            [("id", json!(id.unwrap()))],
+        )));
+    }
+}
+
+pub fn validate_session_user_id(id: i64) -> Result<(), DBAccessError> {
+    if id < 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::SessionUserIdInvalid,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_session_token_hash(token_hash: &str) -> Result<(), DBAccessError> {
+    if token_hash.is_empty() {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::SessionTokenHashEmpty,
+            "".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_session_expires_at(expires_at: i64, now: i64) -> Result<(), DBAccessError> {
+    if expires_at <= now {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::SessionExpiresAtInvalid,
+            [("expires_at", json!(expires_at)), ("now", json!(now))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_project_member_id_is_none(id: Option<i64>) -> Result<(), DBAccessError> {
+    if let Some(id) = id {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ProjectMemberIdMustBeNone,
+            [("id", json!(id))],
         )));
     }
+
+    Ok(())
+}
+
+pub fn validate_project_member_project_id(id: i64) -> Result<(), DBAccessError> {
+    if id < 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ProjectMemberProjectIdInvalid,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_project_member_user_id(id: i64) -> Result<(), DBAccessError> {
+    if id < 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ProjectMemberUserIdInvalid,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_project_role(role: i64) -> Result<(), DBAccessError> {
+    match ProjectRole::from_int(role) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(DBAccessError::ValidationError(e.into())),
+    }
+}
+
+pub fn validate_api_token_id_is_none(id: Option<i64>) -> Result<(), DBAccessError> {
+    if let Some(id) = id {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenIdMustBeNone,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_api_token_id(id: i64) -> Result<(), DBAccessError> {
+    if id < 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenIdInvalid,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_api_token_user_id(id: i64) -> Result<(), DBAccessError> {
+    if id < 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenUserIdInvalid,
+            [("id", json!(id))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_api_token_name(name: &str) -> Result<(), DBAccessError> {
+    if name.is_empty() {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenNameEmpty,
+            [("name", json!(name))],
+        )));
+    }
+
+    if name.len() > 128 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenNameTooLong,
+            [("name", json!(name))],
+        )));
+    }
+
+    Ok(())
+}
+
+pub fn validate_api_token_token_hash(token_hash: &str) -> Result<(), DBAccessError> {
+    if token_hash.is_empty() {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenTokenHashEmpty,
+            "".to_string(),
+        )));
+    }
+
+    Ok(())
+}
+
+// 有効期限は省略可能（無期限）
+pub fn validate_api_token_expires_at(
+    expires_at: Option<i64>,
+    now: i64,
+) -> Result<(), DBAccessError> {
+    if let Some(expires_at) = expires_at
+        && expires_at <= now
+    {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::ApiTokenExpiresAtInvalid,
+            [("expires_at", json!(expires_at)), ("now", json!(now))],
+        )));
+    }
+
+    Ok(())
+}
+
+// 1回のバッチで実行できる操作の上限
+pub const MAX_BATCH_OPERATIONS: usize = 100;
+
+pub fn validate_batch_operations_count(count: usize) -> Result<(), DBAccessError> {
+    if count == 0 {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::BatchOperationsEmpty,
+            [("count", json!(count))],
+        )));
+    }
+    if count > MAX_BATCH_OPERATIONS {
+        return Err(DBAccessError::ValidationError(get_error_message(
+            ErrorKey::BatchOperationsTooMany,
+            [
+                ("count", json!(count)),
+                ("max", json!(MAX_BATCH_OPERATIONS)),
+            ],
+        )));
+    }
+    Ok(())
 }
 
 pub fn validate_pagination(
@@ -375,14 +564,14 @@ pub fn validate_pagination(
     if page.is_none() && page_size.is_some() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::NoPageSpecified,
-            format!("Page = {:?}, PageSize = {:?}", page, page_size),
+            [("page", json!(page)), ("page_size", json!(page_size))],
         )));
     }
 
     if page.is_some() && page_size.is_none() {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::NoPageSizeSpecified,
-            format!("Page = {:?}, PageSize = {:?}", page, page_size),
+            [("page", json!(page)), ("page_size", json!(page_size))],
         )));
     }
 
@@ -396,14 +585,14 @@ pub fn validate_pagination(
     if **page < 1 || **page_size < 1 {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::InvalidPagination,
-            format!("Page = {}, PageSize = {}", *page, *page_size),
+            [("page", json!(*page)), ("page_size", json!(*page_size))],
         )));
     }
 
-    if **page_size > 100 {
+    if **page_size > server_config().pagination.max_page_size {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::PageSizeTooLarge,
-            format!("Page = {}, PageSize = {}", *page, *page_size),
+            [("page", json!(*page)), ("page_size", json!(*page_size))],
         )));
     }
 
@@ -411,7 +600,7 @@ pub fn validate_pagination(
     if offset as i64 > *max_count {
         return Err(DBAccessError::ValidationError(get_error_message(
             ErrorKey::InvalidPagination,
-            format!("Offset = {}, MaxCount = {}", offset, *max_count),
+            [("offset", json!(offset)), ("max_count", json!(*max_count))],
         )));
     }
 
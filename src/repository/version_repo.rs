@@ -0,0 +1,136 @@
+use crate::enums::AuditEntityType;
+use crate::errors::db_error::DBAccessError;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use serde_json::json;
+use sqlx::{Pool, Sqlite, Transaction};
+
+// エンティティごとのテーブルと、対象の行を特定する条件
+fn version_target(entity_type: &AuditEntityType) -> (&'static str, &'static str) {
+    match entity_type {
+        AuditEntityType::User => ("users", "user_id = $1"),
+        AuditEntityType::Project => ("projects", "project_id = $1 AND deleted_at IS NULL"),
+        AuditEntityType::Task => ("tasks", "task_id = $1 AND deleted_at IS NULL"),
+        AuditEntityType::UserAssign => {
+            ("user_assign", "user_assign_id = $1 AND deleted_at IS NULL")
+        }
+        AuditEntityType::Comment => ("comments", "comment_id = $1 AND deleted_at IS NULL"),
+    }
+}
+
+// エンティティごとのバージョンを取得するクエリ
+fn version_query(entity_type: &AuditEntityType) -> String {
+    let (table, condition) = version_target(entity_type);
+    format!("SELECT version FROM {} WHERE {}", table, condition)
+}
+
+fn version_get_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::VersionGetFailed,
+        e.to_string()
+    )))
+}
+
+fn version_update_error(e: sqlx::Error) -> DBAccessError {
+    DBAccessError::QueryError(anyhow::anyhow!(get_error_message(
+        ErrorKey::VersionUpdateFailed,
+        e.to_string()
+    )))
+}
+
+// 作成直後のバージョン。マイグレーションで定義したversion列の初期値と合わせる
+pub const INITIAL_VERSION: i64 = 1;
+
+// 更新後のエンティティと、その更新で付与されたバージョン
+#[derive(Debug, Clone, PartialEq)]
+pub struct Versioned<T> {
+    pub value: T,
+    pub version: i64,
+}
+
+// 存在しない場合はNone
+pub async fn get_version(
+    pool: &Pool<Sqlite>,
+    entity_type: AuditEntityType,
+    id: i64,
+) -> Result<Option<i64>, DBAccessError> {
+    sqlx::query_scalar(&version_query(&entity_type))
+        .bind(id)
+        .fetch_optional(pool)
+        .await
+        .map_err(version_get_error)
+}
+
+pub async fn get_version_with_transaction(
+    entity_type: &AuditEntityType,
+    id: i64,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Option<i64>, DBAccessError> {
+    sqlx::query_scalar(&version_query(entity_type))
+        .bind(id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(version_get_error)
+}
+
+// バージョンを1つ進め、新しいバージョンを返す。If-Matchで指定されたバージョンがある場合は
+// いずれかと一致するときだけ進めるため、確認と更新の間に別の更新が入り込むことはない。
+// エンティティが存在しない場合はNone（呼び出し元で404にする）
+pub async fn bump_version_with_transaction(
+    entity_type: AuditEntityType,
+    id: i64,
+    expected_versions: Option<&[i64]>,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Option<i64>, DBAccessError> {
+    let (table, condition) = version_target(&entity_type);
+    let mut sql = format!(
+        "UPDATE {} SET version = version + 1 WHERE {}",
+        table, condition
+    );
+    if let Some(expected_versions) = expected_versions {
+        let placeholders: Vec<String> = (0..expected_versions.len())
+            .map(|i| format!("${}", i + 2))
+            .collect();
+        sql.push_str(&format!(" AND version IN ({})", placeholders.join(", ")));
+    }
+    sql.push_str(" RETURNING version");
+
+    let mut query = sqlx::query_scalar::<_, i64>(&sql).bind(id);
+    for version in expected_versions.unwrap_or_default() {
+        query = query.bind(*version);
+    }
+    let version = query
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(version_update_error)?;
+
+    if version.is_some() || expected_versions.is_none() {
+        return Ok(version);
+    }
+
+    // 一致するバージョンがなかった場合、エンティティが存在すれば412にする
+    check_version_with_transaction(entity_type, id, expected_versions, tx).await
+}
+
+// 更新せずに、If-Matchで指定されたバージョンのいずれかと一致するか確認して現在のバージョンを返す。
+// 変更のない部分更新で使う。エンティティが存在しない場合はNone（呼び出し元で404にする）
+pub async fn check_version_with_transaction(
+    entity_type: AuditEntityType,
+    id: i64,
+    expected_versions: Option<&[i64]>,
+    tx: &mut Transaction<'_, Sqlite>,
+) -> Result<Option<i64>, DBAccessError> {
+    let version = get_version_with_transaction(&entity_type, id, tx).await?;
+    match (version, expected_versions) {
+        (Some(version), Some(expected_versions)) if !expected_versions.contains(&version) => {
+            Err(DBAccessError::PreconditionFailedError(get_error_message(
+                ErrorKey::VersionMismatch,
+                [
+                    ("entity", json!(entity_type.to_str())),
+                    ("id", json!(id)),
+                    ("version", json!(version)),
+                ],
+            )))
+        }
+        _ => Ok(version),
+    }
+}
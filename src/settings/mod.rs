@@ -0,0 +1,474 @@
+use crate::cli::ConfigArgs;
+use crate::constants::{
+    DEFAULT_BIND_ADDRESS, DEFAULT_LANGUAGE, DEFAULT_LOG_DESTINATION, DEFAULT_LOG_FORMAT,
+    DEFAULT_LOG_LEVEL, DEFAULT_LOG_MAX_FILES, DEFAULT_LOG_MAX_SIZE_BYTES, DEFAULT_MAX_CONNECTIONS,
+    DEFAULT_MAX_PAGE_SIZE, DEFAULT_PASSWORD_HASH_ITERATIONS, DEFAULT_PASSWORD_HASH_MEMORY_KIB,
+    DEFAULT_PASSWORD_HASH_PARALLELISM, DEFAULT_TRASH_RETENTION_DAYS, LOG_FORMATS,
+    SUPPORTED_LANGUAGES,
+};
+use crate::errors::ConfigError;
+use crate::errors::catalog::catalog_languages;
+use crate::errors::messages::{ErrorKey, get_error_message};
+use crate::logging::REDACTED;
+use ::config::{Config, Environment, File};
+use argon2::Params;
+use directories::ProjectDirs;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+#[cfg(test)]
+mod tests;
+
+const CONFIG_ENV_PREFIX: &str = "MENAHEL";
+const CONFIG_PATH_ENV: &str = "MENAHEL_CONFIG";
+const CONFIG_FILE_NAME: &str = "server.json5";
+
+// 優先順位: CLI引数 > 環境変数(MENAHEL_*) > 設定ファイル > デフォルト値
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct ServerConfig {
+    pub server: HttpConfig,
+    pub database: DatabaseConfig,
+    pub log: LogConfig,
+    pub default_language: String,
+    pub pagination: PaginationConfig,
+    pub trash: TrashConfig,
+    pub messages: MessagesConfig,
+    pub metrics: MetricsConfig,
+    pub password_hash: PasswordHashConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct HttpConfig {
+    pub bind_address: String,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: DEFAULT_BIND_ADDRESS.to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DatabaseConfig {
+    // 未設定の場合は環境変数DATABASE_URLを使用する
+    pub url: Option<String>,
+    pub max_connections: u32,
+    // 起動時に未適用のマイグレーションを適用する
+    pub auto_migrate: bool,
+}
+
+// 接続文字列には認証情報が含まれることがあるため、起動時のログなどには出力しない
+impl fmt::Debug for DatabaseConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DatabaseConfig")
+            .field("url", &self.url.as_ref().map(|_| REDACTED))
+            .field("max_connections", &self.max_connections)
+            .field("auto_migrate", &self.auto_migrate)
+            .finish()
+    }
+}
+
+impl Default for DatabaseConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
+            auto_migrate: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct LogConfig {
+    // ファイルパス、またはstdout/stderr
+    pub destination: String,
+    pub level: String,
+    // jsonまたはtext
+    pub format: String,
+    // ファイル出力時にこのサイズを超えるとローテーションする。0の場合はローテーションしない
+    pub max_size_bytes: u64,
+    // ローテーションで残す古いファイルの数
+    pub max_files: u32,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        Self {
+            destination: DEFAULT_LOG_DESTINATION.to_string(),
+            level: DEFAULT_LOG_LEVEL.to_string(),
+            format: DEFAULT_LOG_FORMAT.to_string(),
+            max_size_bytes: DEFAULT_LOG_MAX_SIZE_BYTES,
+            max_files: DEFAULT_LOG_MAX_FILES,
+        }
+    }
+}
+
+impl LogConfig {
+    pub fn level_filter(&self) -> Option<log::LevelFilter> {
+        log::LevelFilter::from_str(&self.level).ok()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PaginationConfig {
+    pub max_page_size: i32,
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            max_page_size: DEFAULT_MAX_PAGE_SIZE,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct TrashConfig {
+    // 完全削除で日数を指定しなかった場合、この日数より前にゴミ箱に移動したものを削除する
+    pub retention_days: i64,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_TRASH_RETENTION_DAYS,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct MessagesConfig {
+    // <言語>.json / .json5 / .yaml のメッセージカタログを置くディレクトリ。未設定の場合は組み込みのメッセージのみ使用する
+    pub catalog_dir: Option<String>,
+    // SIGHUPを受信したらカタログを再読み込みする
+    pub reload_on_sighup: bool,
+}
+
+impl Default for MessagesConfig {
+    fn default() -> Self {
+        Self {
+            catalog_dir: None,
+            reload_on_sighup: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(default)]
+pub struct MetricsConfig {
+    // 認証なしで/metricsにアクセスできるようにする。無効の場合は管理者のみアクセスできる。
+    // スクレイパーからしか到達できないアドレスでサーバーを公開している場合にのみ有効にする
+    pub public: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct PasswordHashConfig {
+    // Argon2idの作業量。変更すると、既存のハッシュは次回のパスワード検証時に再ハッシュされる
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl Default for PasswordHashConfig {
+    fn default() -> Self {
+        Self {
+            memory_kib: DEFAULT_PASSWORD_HASH_MEMORY_KIB,
+            iterations: DEFAULT_PASSWORD_HASH_ITERATIONS,
+            parallelism: DEFAULT_PASSWORD_HASH_PARALLELISM,
+        }
+    }
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            server: HttpConfig::default(),
+            database: DatabaseConfig::default(),
+            log: LogConfig::default(),
+            default_language: DEFAULT_LANGUAGE.to_string(),
+            pagination: PaginationConfig::default(),
+            trash: TrashConfig::default(),
+            messages: MessagesConfig::default(),
+            metrics: MetricsConfig::default(),
+            password_hash: PasswordHashConfig::default(),
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn load(args: &ConfigArgs) -> Result<Self, ConfigError> {
+        let load_error = |e: ::config::ConfigError| {
+            ConfigError::LoadError(
+                get_error_message(ErrorKey::ConfigLoadFailed, e.to_string()).to_string(),
+            )
+        };
+
+        let mut builder = Config::builder()
+            .add_source(Config::try_from(&ServerConfig::default()).map_err(load_error)?);
+
+        match config_file_path(args) {
+            Some(path) => {
+                if !path.exists() {
+                    return Err(ConfigError::LoadError(
+                        get_error_message(ErrorKey::ConfigFileNotFound, path.display().to_string())
+                            .to_string(),
+                    ));
+                }
+                builder = builder.add_source(File::from(path));
+            }
+            None => {
+                if let Some(path) = default_config_file_path() {
+                    builder = builder.add_source(File::from(path).required(false));
+                }
+            }
+        }
+
+        builder = builder
+            .add_source(
+                Environment::with_prefix(CONFIG_ENV_PREFIX)
+                    .prefix_separator("_")
+                    .separator("__")
+                    .try_parsing(true),
+            )
+            .set_override_option("server.bind_address", args.bind_address.clone())
+            .and_then(|b| b.set_override_option("database.url", args.database_url.clone()))
+            .and_then(|b| {
+                b.set_override_option(
+                    "database.max_connections",
+                    args.max_connections.map(i64::from),
+                )
+            })
+            .and_then(|b| b.set_override_option("database.auto_migrate", args.auto_migrate))
+            .and_then(|b| b.set_override_option("log.destination", args.log_destination.clone()))
+            .and_then(|b| b.set_override_option("log.level", args.log_level.clone()))
+            .and_then(|b| b.set_override_option("log.format", args.log_format.clone()))
+            .and_then(|b| b.set_override_option("log.max_size_bytes", args.log_max_size_bytes))
+            .and_then(|b| b.set_override_option("log.max_files", args.log_max_files.map(i64::from)))
+            .and_then(|b| b.set_override_option("default_language", args.default_language.clone()))
+            .and_then(|b| {
+                b.set_override_option(
+                    "pagination.max_page_size",
+                    args.max_page_size.map(i64::from),
+                )
+            })
+            .and_then(|b| {
+                b.set_override_option("messages.catalog_dir", args.message_catalog_dir.clone())
+            })
+            .map_err(load_error)?;
+
+        let mut config: ServerConfig = builder
+            .build()
+            .and_then(|config| config.try_deserialize())
+            .map_err(load_error)?;
+
+        if config.database.url.is_none() {
+            config.database.url = std::env::var("DATABASE_URL").ok();
+        }
+
+        config.validate()?;
+        Ok(config)
+    }
+
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut errors = Vec::new();
+
+        if !is_valid_bind_address(&self.server.bind_address) {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigBindAddressInvalid,
+                    format!("server.bind_address = {}", self.server.bind_address),
+                )
+                .to_string(),
+            );
+        }
+        if self
+            .database
+            .url
+            .as_ref()
+            .is_none_or(|url| url.trim().is_empty())
+        {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigDatabaseUrlMissing,
+                    "database.url or DATABASE_URL".to_string(),
+                )
+                .to_string(),
+            );
+        }
+        if self.database.max_connections == 0 {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigMaxConnectionsInvalid,
+                    format!(
+                        "database.max_connections = {}",
+                        self.database.max_connections
+                    ),
+                )
+                .to_string(),
+            );
+        }
+        if self.log.destination.trim().is_empty() {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigLogDestinationInvalid,
+                    "log.destination".to_string(),
+                )
+                .to_string(),
+            );
+        }
+        if self.log.level_filter().is_none() {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigLogLevelInvalid,
+                    format!("log.level = {}", self.log.level),
+                )
+                .to_string(),
+            );
+        }
+        if !LOG_FORMATS.contains(&self.log.format.as_str()) {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigLogFormatInvalid,
+                    format!(
+                        "log.format = {}, supported = {}",
+                        self.log.format,
+                        LOG_FORMATS.join(", ")
+                    ),
+                )
+                .to_string(),
+            );
+        }
+        if self.log.max_size_bytes > 0 && self.log.max_files == 0 {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigLogRotationInvalid,
+                    format!(
+                        "log.max_size_bytes = {}, log.max_files = {}",
+                        self.log.max_size_bytes, self.log.max_files
+                    ),
+                )
+                .to_string(),
+            );
+        }
+        // カタログのファイルで追加した言語もデフォルトにできる
+        let mut languages: Vec<String> =
+            SUPPORTED_LANGUAGES.iter().map(|l| l.to_string()).collect();
+        if let Some(dir) = &self.messages.catalog_dir {
+            languages.extend(catalog_languages(Path::new(dir)));
+        }
+        languages.sort();
+        languages.dedup();
+        if !languages.contains(&self.default_language) {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigLanguageInvalid,
+                    format!(
+                        "default_language = {}, supported = {}",
+                        self.default_language,
+                        languages.join(", ")
+                    ),
+                )
+                .to_string(),
+            );
+        }
+        if self.pagination.max_page_size <= 0 {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigMaxPageSizeInvalid,
+                    format!(
+                        "pagination.max_page_size = {}",
+                        self.pagination.max_page_size
+                    ),
+                )
+                .to_string(),
+            );
+        }
+        if self.trash.retention_days <= 0 {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigTrashRetentionDaysInvalid,
+                    format!("trash.retention_days = {}", self.trash.retention_days),
+                )
+                .to_string(),
+            );
+        }
+        let password_hash = &self.password_hash;
+        if let Err(e) = Params::new(
+            password_hash.memory_kib,
+            password_hash.iterations,
+            password_hash.parallelism,
+            None,
+        ) {
+            errors.push(
+                get_error_message(
+                    ErrorKey::ConfigPasswordHashInvalid,
+                    format!(
+                        "password_hash.memory_kib = {}, password_hash.iterations = {}, password_hash.parallelism = {}, reason = {}",
+                        password_hash.memory_kib,
+                        password_hash.iterations,
+                        password_hash.parallelism,
+                        e
+                    ),
+                )
+                .to_string(),
+            );
+        }
+
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(ConfigError::ValidationError(errors)),
+        }
+    }
+
+    pub fn database_url(&self) -> &str {
+        self.database.url.as_deref().unwrap_or_default()
+    }
+}
+
+fn config_file_path(args: &ConfigArgs) -> Option<PathBuf> {
+    args.config
+        .clone()
+        .or_else(|| std::env::var(CONFIG_PATH_ENV).ok().map(PathBuf::from))
+}
+
+// 例: Linuxでは ~/.config/menahel/server.json5
+pub fn default_config_file_path() -> Option<PathBuf> {
+    ProjectDirs::from("", "", "menahel").map(|dirs| dirs.config_dir().join(CONFIG_FILE_NAME))
+}
+
+fn is_valid_bind_address(bind_address: &str) -> bool {
+    match bind_address.rsplit_once(':') {
+        Some((host, port)) => !host.is_empty() && port.parse::<u16>().is_ok(),
+        None => false,
+    }
+}
+
+static SERVER_CONFIG: OnceCell<ServerConfig> = OnceCell::new();
+static DEFAULT_SERVER_CONFIG: Lazy<ServerConfig> = Lazy::new(ServerConfig::default);
+
+// 起動時に一度だけ呼び出す
+pub fn init_server_config(config: ServerConfig) -> Result<(), ConfigError> {
+    SERVER_CONFIG.set(config).map_err(|_| {
+        ConfigError::InitError(
+            get_error_message(ErrorKey::ConfigAlreadyInitialized, "".to_string()).to_string(),
+        )
+    })
+}
+
+// 初期化前（テストやクライアント）はデフォルト値を返す
+pub fn server_config() -> &'static ServerConfig {
+    SERVER_CONFIG.get().unwrap_or(&DEFAULT_SERVER_CONFIG)
+}
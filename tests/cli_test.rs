@@ -0,0 +1,95 @@
+use sqlx::sqlite::SqlitePool;
+use std::process::{Command, Output};
+
+#[cfg(test)]
+mod cli_test {
+    use super::*;
+
+    const TEST_DB_DIR: &str = "./test_db/cli_test";
+
+    #[ctor::ctor]
+    fn init() {
+        if !std::path::Path::new(TEST_DB_DIR).exists() {
+            std::fs::create_dir_all(TEST_DB_DIR).unwrap();
+        }
+
+        let files = std::fs::read_dir(TEST_DB_DIR).unwrap();
+        for file in files {
+            let path = file.unwrap().path();
+            if path.is_file() {
+                std::fs::remove_file(path).unwrap();
+            }
+        }
+    }
+
+    // サーバー設定は環境変数で渡し、サブコマンドに反映されることを確認する
+    fn run_cli(database_url: &str, args: &[&str]) -> Output {
+        let output = Command::new(env!("CARGO_BIN_EXE_menahel"))
+            .args([
+                "--database-url",
+                database_url,
+                "--log-destination",
+                "stderr",
+            ])
+            .args(args)
+            .env("MENAHEL_PASSWORD_HASH__MEMORY_KIB", "8192")
+            .env("MENAHEL_PASSWORD_HASH__ITERATIONS", "3")
+            .env("MENAHEL_PASSWORD_HASH__PARALLELISM", "2")
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+        output
+    }
+
+    async fn password_hash(database_url: &str, username: &str) -> String {
+        let pool = SqlitePool::connect(database_url).await.unwrap();
+        let password_hash =
+            sqlx::query_scalar::<_, String>("SELECT password_hash FROM users WHERE username = $1")
+                .bind(username)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        pool.close().await;
+        password_hash
+    }
+
+    #[actix_web::test]
+    async fn test_user_commands_use_configured_password_hash() {
+        let database_url = format!("sqlite://{}/test_user_commands.db", TEST_DB_DIR);
+        run_cli(&database_url, &["migrate", "up"]);
+
+        run_cli(
+            &database_url,
+            &[
+                "user",
+                "create",
+                "--username",
+                "cli_user",
+                "--email",
+                "cli_user@example.com",
+                "--password",
+                "password",
+            ],
+        );
+        let created = password_hash(&database_url, "cli_user").await;
+        assert!(created.starts_with("$argon2id$v=19$m=8192,t=3,p=2$"));
+
+        run_cli(
+            &database_url,
+            &[
+                "user",
+                "set-password",
+                "cli_user",
+                "--password",
+                "new_password",
+            ],
+        );
+        let updated = password_hash(&database_url, "cli_user").await;
+        assert_ne!(updated, created);
+        assert!(updated.starts_with("$argon2id$v=19$m=8192,t=3,p=2$"));
+    }
+}